@@ -1,25 +1,34 @@
 /// Add command implementation
 use crate::errors::ThoughtError;
+use crate::input::editor;
+use crate::models::Timezone;
 use crate::models::thought::Thought;
-use crate::services::{entity_parser, entity_resolution};
+use crate::services::thought_batch::{self, BatchMode};
+use crate::services::{date_expression, entity_parser, entity_resolution, entity_similarity};
 use crate::storage::connection::get_connection;
 use crate::storage::entities_repository::EntitiesRepository;
+use crate::storage::entity_aliases_repository::EntityAliasesRepository;
 use crate::storage::migrations::run_migrations;
 use crate::storage::thoughts_repository::ThoughtsRepository;
-use chrono::NaiveDate;
+use crate::tui::compose::{self, Composer};
+use chrono::{DateTime, Utc};
+use ratatui::{TerminalOptions, Viewport};
+use std::collections::BTreeSet;
+use std::io::{self, IsTerminal};
 use std::path::Path;
 
+/// How many recently mentioned entities the `--editor` template lists
+const TEMPLATE_ENTITY_COUNT: usize = 10;
+
 /// Execute the add command
-pub fn execute(content: String, date: Option<String>, db_path: &Path) -> Result<(), ThoughtError> {
+///
+/// A `--date` day is resolved in `timezone`, and the thought is stamped with the moment
+/// that day starts there.
+pub fn execute(content: String, date: Option<String>, timezone: Timezone, db_path: &Path) -> Result<(), ThoughtError> {
     // Create and validate thought
-    let thought = if let Some(ref date_str) = date {
-        let naive = NaiveDate::parse_from_str(date_str, "%Y-%m-%d").map_err(|_| {
-            ThoughtError::InvalidInput(format!("Invalid date format '{}'. Expected YYYY-MM-DD.", date_str))
-        })?;
-        let datetime = naive.and_hms_opt(0, 0, 0).unwrap().and_utc();
-        Thought::new_with_date(content.clone(), datetime)?
-    } else {
-        Thought::new(content.clone())?
+    let thought = match resolve_date(date.as_deref(), timezone)? {
+        Some(created_at) => Thought::new_with_date(content.clone(), created_at)?,
+        None => Thought::new(content.clone())?,
     };
 
     // Get database connection
@@ -33,6 +42,7 @@ pub fn execute(content: String, date: Option<String>, db_path: &Path) -> Result<
 
     // Extract and save entities
     let entity_names = entity_parser::extract_unique_entities(&content);
+    warn_about_near_duplicates(&conn, &entity_names)?;
     for entity_name in &entity_names {
         if let Some(entity_id) = entity_resolution::resolve_or_create_entity(&conn, entity_name)? {
             EntitiesRepository::link_to_thought(&conn, entity_id, thought_id)?;
@@ -53,3 +63,230 @@ pub fn execute(content: String, date: Option<String>, db_path: &Path) -> Result<
 
     Ok(())
 }
+
+/// Execute the add command with `--editor`
+///
+/// Opens the editor on a template whose `#` lines give the reference syntax and the
+/// recently mentioned entities; they are stripped on save. Content that fails
+/// validation (empty or too long) reopens the editor with the error in the comments
+/// and the text kept. Saving that empty-content template unchanged, or the editor
+/// exiting abnormally, adds nothing.
+pub fn execute_with_editor(date: Option<String>, timezone: Timezone, db_path: &Path) -> Result<(), ThoughtError> {
+    // Reject a bad --date before anything is typed
+    resolve_date(date.as_deref(), timezone)?;
+
+    let recent = {
+        let conn = get_connection(db_path)?;
+        run_migrations(&conn)?;
+        EntitiesRepository::recently_mentioned(&conn, TEMPLATE_ENTITY_COUNT)?
+    };
+
+    let mut draft = String::new();
+    let mut problem: Option<ThoughtError> = None;
+    loop {
+        let template = editor::with_comments(&draft, &template_comments(&recent, problem.as_ref()));
+        let edited = match editor::launch_editor(Some(&template)) {
+            Ok(edited) => edited,
+            Err(ThoughtError::EditorLaunchFailed(editor_name)) => {
+                eprintln!("Warning: Editor '{}' exited abnormally. No thought added.", editor_name);
+                return Ok(());
+            }
+            Err(e) => return Err(e),
+        };
+        if matches!(problem, Some(ThoughtError::EmptyContent)) && edited == template {
+            println!("No thought added.");
+            return Ok(());
+        }
+
+        let content = editor::strip_comments(&edited);
+        match Thought::new(content.clone()) {
+            Ok(_) => return execute(content, date, timezone, db_path),
+            Err(e @ (ThoughtError::EmptyContent | ThoughtError::ContentTooLong { .. })) => {
+                eprintln!("{e}. Reopening the editor.");
+                draft = content;
+                problem = Some(e);
+            }
+            Err(e) => return Err(e),
+        }
+    }
+}
+
+/// Execute the add command with `-i`
+///
+/// Prompts for the thought inline; typing `[` offers the known entity names and
+/// aliases, so references land on existing entities instead of misspelled new ones.
+/// Cancelling the prompt adds nothing.
+pub fn execute_interactive(date: Option<String>, timezone: Timezone, db_path: &Path) -> Result<(), ThoughtError> {
+    // Reject a bad --date before anything is typed
+    resolve_date(date.as_deref(), timezone)?;
+    if !io::stdin().is_terminal() {
+        return Err(ThoughtError::InvalidInput(
+            "-i needs a terminal; pipe content to `wet add -` instead".to_string(),
+        ));
+    }
+
+    let (entities, aliases) = {
+        let conn = get_connection(db_path)?;
+        run_migrations(&conn)?;
+        (
+            EntitiesRepository::list_all(&conn)?,
+            EntityAliasesRepository::list_all(&conn)?,
+        )
+    };
+
+    let mut terminal = ratatui::try_init_with_options(TerminalOptions {
+        viewport: Viewport::Inline(compose::HEIGHT),
+    })?;
+    let result = Composer::new(&entities, &aliases).run(&mut terminal);
+    ratatui::restore();
+
+    match result? {
+        Some(content) => execute(content, date, timezone, db_path),
+        None => {
+            println!("No thought added.");
+            Ok(())
+        }
+    }
+}
+
+/// The `#` lines of the `--editor` template, led by the error from the last attempt
+fn template_comments(recent: &[String], problem: Option<&ThoughtError>) -> Vec<String> {
+    let mut comments = Vec::new();
+    match problem {
+        Some(ThoughtError::EmptyContent) => {
+            comments.push(format!(
+                "Error: {}. Save this unchanged to cancel.",
+                ThoughtError::EmptyContent
+            ));
+            comments.push(String::new());
+        }
+        Some(e) => {
+            comments.push(format!("Error: {e}."));
+            comments.push(String::new());
+        }
+        None => {}
+    }
+    comments.push("Write the thought above. Lines starting with '#' are ignored.".to_string());
+    comments.push(String::new());
+    comments.push("Mention an entity as [Entity], or as [shown text](Entity) to word it differently.".to_string());
+    if !recent.is_empty() {
+        comments.push(format!("Recently mentioned: {}", recent.join(", ")));
+    }
+    comments
+}
+
+/// Execute the add command in batch mode
+///
+/// `input` is split into thoughts by `mode`. A thought's own `YYYY-MM-DD:` prefix wins
+/// over `--date`, which dates the rest. Every thought is validated before anything is
+/// written, and all of them are saved in one transaction.
+pub fn execute_batch(
+    input: &str,
+    mode: BatchMode,
+    date: Option<String>,
+    timezone: Timezone,
+    db_path: &Path,
+) -> Result<(), ThoughtError> {
+    let default_date = resolve_date(date.as_deref(), timezone)?;
+    let entries = thought_batch::split(input, mode)?;
+    if entries.is_empty() {
+        return Err(ThoughtError::EmptyContent);
+    }
+
+    let thoughts = entries
+        .into_iter()
+        .map(|entry| {
+            let created_at = entry.date.map(|day| timezone.start_of_day(day)).or(default_date);
+            match created_at {
+                Some(created_at) => Thought::new_with_date(entry.content, created_at),
+                None => Thought::new(entry.content),
+            }
+            .map_err(|e| ThoughtError::InvalidInput(format!("line {}: {e}", entry.line)))
+        })
+        .collect::<Result<Vec<_>, _>>()?;
+
+    let mut conn = get_connection(db_path)?;
+    run_migrations(&conn)?;
+
+    let all_names: Vec<String> = thoughts
+        .iter()
+        .flat_map(|thought| entity_parser::extract_unique_entities(&thought.content))
+        .collect::<BTreeSet<_>>()
+        .into_iter()
+        .collect();
+    warn_about_near_duplicates(&conn, &all_names)?;
+
+    let tx = conn.transaction()?;
+    let mut ids = Vec::with_capacity(thoughts.len());
+    let mut reference_count = 0;
+    for thought in &thoughts {
+        let thought_id = ThoughtsRepository::save(&tx, thought)?;
+        let entity_names = entity_parser::extract_unique_entities(&thought.content);
+        for entity_name in &entity_names {
+            if let Some(entity_id) = entity_resolution::resolve_or_create_entity(&tx, entity_name)? {
+                EntitiesRepository::link_to_thought(&tx, entity_id, thought_id)?;
+            }
+        }
+        reference_count += entity_names.len();
+        ids.push(thought_id);
+    }
+    tx.commit()?;
+
+    let id_range = match (ids.first(), ids.last()) {
+        (Some(first), Some(last)) if first != last => format!("IDs: {first}-{last}"),
+        _ => format!("ID: {}", ids[0]),
+    };
+    println!(
+        "{} thought{} added successfully ({}, {} entity reference{})",
+        ids.len(),
+        if ids.len() == 1 { "" } else { "s" },
+        id_range,
+        reference_count,
+        if reference_count == 1 { "" } else { "s" }
+    );
+
+    Ok(())
+}
+
+/// The instant a `--date` expression stands for: the start of that day in `timezone`
+fn resolve_date(date: Option<&str>, timezone: Timezone) -> Result<Option<DateTime<Utc>>, ThoughtError> {
+    let Some(date_str) = date else {
+        return Ok(None);
+    };
+    let naive = date_expression::parse(date_str, timezone.today()).ok_or_else(|| {
+        ThoughtError::InvalidInput(format!(
+            "Invalid date format '{}'. Expected {}.",
+            date_str,
+            date_expression::EXPECTED
+        ))
+    })?;
+    Ok(Some(timezone.start_of_day(naive)))
+}
+
+/// Warn on stderr about each name that is about to create a new entity while an
+/// existing entity has a suspiciously similar name (see `entity_similarity`), since
+/// that is usually a typo that would split one entity's history in two.
+fn warn_about_near_duplicates(conn: &rusqlite::Connection, entity_names: &[String]) -> Result<(), ThoughtError> {
+    let new_names: Vec<&String> = entity_names
+        .iter()
+        .filter(|name| matches!(EntitiesRepository::resolve(conn, name), Ok(None)))
+        .collect();
+    if new_names.is_empty() {
+        return Ok(());
+    }
+
+    let existing = EntitiesRepository::list_all(conn)?;
+    for name in new_names {
+        for entity in &existing {
+            if entity_similarity::are_near_duplicates(name, &entity.canonical_name) {
+                eprintln!(
+                    "Warning: new entity '{}' looks similar to existing '{}'. If they are the same, run:",
+                    name, entity.canonical_name
+                );
+                eprintln!("  wet entity merge \"{}\" --into \"{}\"", name, entity.canonical_name);
+            }
+        }
+    }
+
+    Ok(())
+}
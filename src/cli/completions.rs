@@ -0,0 +1,278 @@
+/// Completions command implementation and the dynamic values it completes
+///
+/// `wet completions <shell>` prints a script that hands every completion back to
+/// `wet` itself (clap_complete's `COMPLETE=<shell>` protocol, answered at the top of
+/// `main`). That is what lets entity names and thought IDs come from the database.
+use crate::config::{self, Config};
+use crate::errors::ThoughtError;
+use crate::storage::connection::get_connection;
+use crate::storage::entities_repository::EntitiesRepository;
+use crate::storage::entity_aliases_repository::EntityAliasesRepository;
+use crate::storage::notebooks::{list_notebooks, notebook_path};
+use crate::storage::thoughts_repository::ThoughtsRepository;
+use crate::storage::{DATA_DIR_ENV, DB_PATH_ENV, path_from_env, resolve_data_dir};
+use clap::ValueEnum;
+use clap_complete::engine::CompletionCandidate;
+use clap_complete::env::{Bash, EnvCompleter, Fish, Zsh};
+use rusqlite::Connection;
+use std::collections::HashMap;
+use std::ffi::{OsStr, OsString};
+use std::io;
+use std::path::PathBuf;
+
+/// Environment variable the generated scripts set when asking `wet` for completions
+const COMPLETE_ENV: &str = "COMPLETE";
+
+/// Shells `wet completions` can write a script for
+#[derive(Debug, Clone, Copy, PartialEq, Eq, ValueEnum)]
+pub enum CompletionShell {
+    Bash,
+    Zsh,
+    Fish,
+}
+
+/// Execute the completions command: print the registration script for `shell`
+pub fn execute(shell: CompletionShell) -> Result<(), ThoughtError> {
+    let completer: &dyn EnvCompleter = match shell {
+        CompletionShell::Bash => &Bash,
+        CompletionShell::Zsh => &Zsh,
+        CompletionShell::Fish => &Fish,
+    };
+    completer.write_registration(COMPLETE_ENV, "wet", "wet", "wet", &mut io::stdout().lock())?;
+    Ok(())
+}
+
+/// Complete an entity name: canonical names and registered aliases
+///
+/// Matched case-insensitively, so `sa` offers `Sarah` and the shell inserts the
+/// stored spelling.
+pub fn entity_names(current: &OsStr) -> Vec<CompletionCandidate> {
+    let Some(current) = current.to_str() else {
+        return Vec::new();
+    };
+    let names = open_database()
+        .and_then(|conn| entity_values(&conn).ok())
+        .unwrap_or_default();
+    candidates(current, names)
+}
+
+/// Complete a thought ID, newest first, with the start of its content as help
+pub fn thought_ids(current: &OsStr) -> Vec<CompletionCandidate> {
+    let Some(current) = current.to_str() else {
+        return Vec::new();
+    };
+    let ids = open_database()
+        .and_then(|conn| thought_values(&conn).ok())
+        .unwrap_or_default();
+    candidates(current, ids)
+}
+
+/// Complete a config key, with its default as help
+///
+/// The config file isn't read: loading it may migrate it, which is no job for a Tab press.
+pub fn config_keys(current: &OsStr) -> Vec<CompletionCandidate> {
+    let Some(current) = current.to_str() else {
+        return Vec::new();
+    };
+    let config = Config::default();
+    let keys = Config::keys()
+        .unwrap_or_default()
+        .into_iter()
+        .map(|key| {
+            let help = config.get_value(&key).ok().map(|value| format!("default: {value}"));
+            (key, help)
+        })
+        .collect();
+    candidates(current, keys)
+}
+
+/// Complete a notebook name from the databases in the data directory
+pub fn notebook_names(current: &OsStr) -> Vec<CompletionCandidate> {
+    let Some(current) = current.to_str() else {
+        return Vec::new();
+    };
+    let names = data_dir()
+        .and_then(|dir| list_notebooks(&dir).ok())
+        .unwrap_or_default()
+        .into_iter()
+        .map(|name| (name, None))
+        .collect();
+    candidates(current, names)
+}
+
+/// Canonical names, then aliases annotated with the entity they stand for
+fn entity_values(conn: &Connection) -> Result<Vec<(String, Option<String>)>, ThoughtError> {
+    let entities = EntitiesRepository::list_all(conn)?;
+    let canonical: HashMap<i64, &str> = entities
+        .iter()
+        .filter_map(|entity| Some((entity.id?, entity.canonical_name.as_str())))
+        .collect();
+
+    let mut values: Vec<(String, Option<String>)> = entities
+        .iter()
+        .map(|entity| (entity.canonical_name.clone(), entity.kind.map(|kind| kind.to_string())))
+        .collect();
+    for (entity_id, alias) in EntityAliasesRepository::list_all(conn)? {
+        let help = canonical.get(&entity_id).map(|name| format!("alias of {name}"));
+        values.push((alias, help));
+    }
+    Ok(values)
+}
+
+/// Thought IDs, newest first, each with a one-line preview of its content
+fn thought_values(conn: &Connection) -> Result<Vec<(String, Option<String>)>, ThoughtError> {
+    let mut thoughts = ThoughtsRepository::list_all(conn)?;
+    thoughts.reverse();
+    Ok(thoughts
+        .into_iter()
+        .filter_map(|thought| {
+            let preview: String = thought.content.split_whitespace().collect::<Vec<_>>().join(" ");
+            Some((thought.id?.to_string(), Some(preview.chars().take(60).collect())))
+        })
+        .collect())
+}
+
+/// The values starting with `current`, ignoring case, as completion candidates
+fn candidates(current: &str, values: Vec<(String, Option<String>)>) -> Vec<CompletionCandidate> {
+    let prefix = current.to_lowercase();
+    values
+        .into_iter()
+        .filter(|(value, _)| value.to_lowercase().starts_with(&prefix))
+        .map(|(value, help)| CompletionCandidate::new(value).help(help.map(Into::into)))
+        .collect()
+}
+
+/// The database `wet` would use, opened only if it already exists
+///
+/// Completion runs on every Tab, so it never creates the data directory or the
+/// database, and gives up quietly where the command itself would report an error.
+/// As for the command, `--notebook` wins over `WETWARE_DB`, which wins over the
+/// default notebook.
+fn open_database() -> Option<Connection> {
+    let db_path = match (notebook_from_args(std::env::args_os()), path_from_env(DB_PATH_ENV)) {
+        (Some(name), _) => notebook_path(&data_dir()?, &name).ok()?,
+        (None, Some(path)) => path,
+        (None, None) => default_db_path()?,
+    };
+    if !db_path.exists() {
+        return None;
+    }
+    get_connection(&db_path).ok()
+}
+
+/// The notebook picked with `--notebook` on the command line being completed
+///
+/// clap_complete passes the words typed so far as `wet`'s own arguments, so this
+/// looks through them rather than through parsed options.
+fn notebook_from_args(args: impl IntoIterator<Item = OsString>) -> Option<String> {
+    let mut args = args.into_iter();
+    let mut notebook = None;
+    while let Some(arg) = args.next() {
+        if arg == "--notebook" {
+            notebook = args.next().and_then(|value| value.into_string().ok());
+        } else if let Some(value) = arg.to_str().and_then(|arg| arg.strip_prefix("--notebook=")) {
+            notebook = Some(value.to_string());
+        }
+    }
+    notebook
+}
+
+/// The default notebook's database, or `None` where resolving it would fail
+///
+/// The config file is read as it is, never migrated.
+fn default_db_path() -> Option<PathBuf> {
+    let data_dir = data_dir()?;
+    let config = config::read_config(&data_dir).ok()?.with_env().ok()?;
+    notebook_path(&data_dir, &config.notebooks.default).ok()
+}
+
+/// The data directory, or `None` where resolving it would fail
+fn data_dir() -> Option<PathBuf> {
+    let data_dir_override = path_from_env(DATA_DIR_ENV);
+    // Debug builds refuse to guess the data directory (see `resolve_data_dir`)
+    if cfg!(debug_assertions) && data_dir_override.is_none() {
+        return None;
+    }
+    resolve_data_dir(data_dir_override.as_deref()).ok()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::models::Entity;
+    use crate::models::thought::Thought;
+    use crate::storage::get_memory_connection;
+    use crate::storage::migrations::run_migrations;
+
+    fn values(candidates: &[CompletionCandidate]) -> Vec<String> {
+        candidates
+            .iter()
+            .map(|candidate| candidate.get_value().to_string_lossy().into_owned())
+            .collect()
+    }
+
+    #[test]
+    fn test_candidates_match_prefix_ignoring_case() {
+        let names = vec![
+            ("Sarah".to_string(), None),
+            ("sam".to_string(), Some("alias of Samuel".to_string())),
+            ("project-alpha".to_string(), None),
+        ];
+        let matched = candidates("SA", names);
+        assert_eq!(values(&matched), ["Sarah", "sam"]);
+        assert_eq!(
+            matched[1].get_help().map(ToString::to_string).as_deref(),
+            Some("alias of Samuel")
+        );
+    }
+
+    #[test]
+    fn test_notebook_from_args_takes_the_last_flag_in_either_form() {
+        let args = |words: &[&str]| words.iter().map(OsString::from).collect::<Vec<_>>();
+        assert_eq!(
+            notebook_from_args(args(&["wet", "--", "wet", "--notebook", "work", "entity", "show", ""])),
+            Some("work".to_string())
+        );
+        assert_eq!(
+            notebook_from_args(args(&["wet", "--notebook=home", "delete", "--notebook", "work"])),
+            Some("work".to_string())
+        );
+        assert_eq!(
+            notebook_from_args(args(&["wet", "entity", "show", "--notebook=home"])),
+            Some("home".to_string())
+        );
+        assert_eq!(notebook_from_args(args(&["wet", "entity", "show", ""])), None);
+    }
+
+    #[test]
+    fn test_entity_values_include_aliases_with_their_entity() {
+        let conn = get_memory_connection().unwrap();
+        run_migrations(&conn).unwrap();
+        let sarah = EntitiesRepository::find_or_create(&conn, &Entity::new("Sarah".to_string())).unwrap();
+        EntityAliasesRepository::add_alias(&conn, sarah, "Sally").unwrap();
+
+        assert_eq!(
+            entity_values(&conn).unwrap(),
+            [
+                ("Sarah".to_string(), None),
+                ("Sally".to_string(), Some("alias of Sarah".to_string()))
+            ]
+        );
+    }
+
+    #[test]
+    fn test_thought_values_are_newest_first_with_a_preview() {
+        let conn = get_memory_connection().unwrap();
+        run_migrations(&conn).unwrap();
+        ThoughtsRepository::save(&conn, &Thought::new("First".to_string()).unwrap()).unwrap();
+        ThoughtsRepository::save(&conn, &Thought::new("Second\nline".to_string()).unwrap()).unwrap();
+
+        assert_eq!(
+            thought_values(&conn).unwrap(),
+            [
+                ("2".to_string(), Some("Second line".to_string())),
+                ("1".to_string(), Some("First".to_string()))
+            ]
+        );
+    }
+}
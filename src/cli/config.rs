@@ -1,18 +1,142 @@
 /// Config command implementation
-use crate::config;
+use crate::config::{self, Config};
 use crate::errors::ThoughtError;
+use crate::input::editor;
 use std::path::Path;
 
-/// Execute the config command
+/// Marks the lines `wet config edit` adds to explain an error; removed again on save
+const ERROR_MARKER: &str = "# wet: ";
+
+/// Execute the config command: print the effective value of `key`, or set it in the file
 pub fn execute(data_dir: &Path, key: String, value: Option<String>) -> Result<(), ThoughtError> {
     if let Some(value) = value {
-        let mut cfg = config::load_config(data_dir)?;
-        cfg.set_value(&key, &value)?;
-        config::save_config(data_dir, &cfg)?;
+        config::set_in_file(data_dir, &key, &value)?;
+        warn_if_overridden(&key);
     } else {
-        let cfg = config::load_config(data_dir)?;
+        let cfg = config::load_config(data_dir)?.with_env()?;
         let current = cfg.get_value(&key)?;
         println!("{current}");
     }
     Ok(())
 }
+
+/// Execute `wet config --list`: every setting, its effective value and its source
+pub fn execute_list(data_dir: &Path) -> Result<(), ThoughtError> {
+    let settings = config::list_settings(data_dir)?;
+    let key_width = settings.iter().map(|setting| setting.key.len()).max().unwrap_or(0);
+    let value_width = settings.iter().map(|setting| setting.value.len()).max().unwrap_or(0);
+    for setting in settings {
+        println!(
+            "{:key_width$}  {:value_width$}  ({})",
+            setting.key, setting.value, setting.source
+        );
+    }
+    Ok(())
+}
+
+/// Execute `wet config --unset <key>`: remove the key from the file
+pub fn execute_unset(data_dir: &Path, key: &str) -> Result<(), ThoughtError> {
+    if config::unset_in_file(data_dir, key)? {
+        let default = Config::default().get_value(key).ok();
+        match default {
+            Some(default) => println!("Unset {key} (now {default}, the default)"),
+            None => println!("Unset {key}"),
+        }
+        warn_if_overridden(key);
+    } else {
+        println!("{key} is not set in the config file");
+    }
+    Ok(())
+}
+
+/// Execute `wet config edit`: open `config.toml` in the editor and save it only if it is valid
+///
+/// An invalid file reopens with the error in `# wet:` lines above the text. Saving that
+/// unchanged, or the editor exiting abnormally, leaves the config file as it was.
+pub fn execute_edit(data_dir: &Path) -> Result<(), ThoughtError> {
+    let path = config::config_path(data_dir);
+    let original = std::fs::read_to_string(&path)?;
+
+    let mut text = original.clone();
+    let mut problem: Option<String> = None;
+    loop {
+        let buffer = match &problem {
+            Some(problem) => with_error(&text, problem),
+            None => text.clone(),
+        };
+        let edited = match editor::launch_editor(Some(&buffer)) {
+            Ok(edited) => edited,
+            Err(ThoughtError::EditorLaunchFailed(editor_name)) => {
+                eprintln!(
+                    "Warning: Editor '{}' exited abnormally. Config not changed.",
+                    editor_name
+                );
+                return Ok(());
+            }
+            Err(e) => return Err(e),
+        };
+        if problem.is_some() && edited == buffer {
+            println!("Config not changed.");
+            return Ok(());
+        }
+
+        text = without_error(&edited);
+        match config::validate(&text) {
+            Ok(_) if text == original => {
+                println!("Config not changed.");
+                return Ok(());
+            }
+            Ok(_) => {
+                std::fs::write(&path, &text)?;
+                println!("Config saved to {}", path.display());
+                return Ok(());
+            }
+            Err(e) => {
+                eprintln!("{e}. Reopening the editor.");
+                problem = Some(e.to_string());
+            }
+        }
+    }
+}
+
+/// `text` led by `problem` and a hint, as `# wet:` lines
+fn with_error(text: &str, problem: &str) -> String {
+    let mut buffer = String::new();
+    for line in format!("Error: {problem}").lines() {
+        buffer.push_str(&format!("{ERROR_MARKER}{line}\n"));
+    }
+    buffer.push_str(&format!(
+        "{ERROR_MARKER}Fix it and save, or save this unchanged to keep the old config.\n"
+    ));
+    buffer.push_str(text);
+    buffer
+}
+
+/// `text` without the lines [`with_error`] added
+fn without_error(text: &str) -> String {
+    text.split_inclusive('\n')
+        .filter(|line| !line.starts_with(ERROR_MARKER))
+        .collect()
+}
+
+/// Point out an environment variable that hides the value just written to the file
+fn warn_if_overridden(key: &str) {
+    let name = config::env_var(key);
+    if std::env::var_os(&name).is_some() {
+        eprintln!("Note: {name} is set and overrides {key} from the config file.");
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_error_lines_are_added_and_removed() {
+        let text = "version = 1\n# my own comment\n";
+        let buffer = with_error(text, "Unknown config key: thoughts.ordr");
+        assert!(buffer.starts_with("# wet: Error: Unknown config key: thoughts.ordr\n"));
+        assert!(buffer.ends_with(text));
+        assert_eq!(without_error(&buffer), text);
+    }
+}
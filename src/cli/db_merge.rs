@@ -0,0 +1,566 @@
+/// Database merge command implementation
+use crate::errors::ThoughtError;
+use crate::models::entity::Entity;
+use crate::models::relation_type::RelationType;
+use crate::models::timezone::Timezone;
+use crate::services::relation_types;
+use crate::storage::connection::get_connection;
+use crate::storage::entities_repository::EntitiesRepository;
+use crate::storage::entity_aliases_repository::EntityAliasesRepository;
+use crate::storage::entity_attributes_repository::EntityAttributesRepository;
+use crate::storage::entity_relations_repository::EntityRelationsRepository;
+use crate::storage::migrations::{run_migrations, run_migrations_in};
+use crate::storage::thoughts_repository::ThoughtsRepository;
+use chrono::NaiveDate;
+use clap::ValueEnum;
+use rusqlite::{Connection, OpenFlags};
+use std::collections::{HashMap, HashSet};
+use std::path::Path;
+
+/// What to do when an entity has a different description in each database
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, ValueEnum)]
+pub enum DescriptionPolicy {
+    /// Refuse to merge while any descriptions conflict
+    #[default]
+    Refuse,
+    /// Keep this database's description
+    Keep,
+    /// Take the other database's description
+    Theirs,
+    /// Keep this database's description and append the other one
+    Append,
+}
+
+/// What a database merge changed, for reporting back to the user
+#[derive(Debug, Default, PartialEq, Eq)]
+pub struct DbMergeSummary {
+    /// Thoughts copied into this database
+    pub thoughts_imported: usize,
+    /// Thoughts skipped because one with the same content and date was already here
+    pub duplicates_skipped: usize,
+    /// Other-database entities found here by name or alias
+    pub entities_matched: usize,
+    /// Entities created here because nothing matched
+    pub entities_created: usize,
+    /// Aliases registered here that weren't before
+    pub aliases_added: usize,
+    /// Parent/child and typed relations recorded here that weren't before
+    pub relations_added: usize,
+    /// Hierarchy edges left out because they would have created a self-relation or cycle
+    pub relations_dropped: usize,
+    /// Canonical names of entities whose descriptions differed, alphabetical
+    pub description_conflicts: Vec<String>,
+}
+
+/// Execute the db merge command
+///
+/// Imports everything from the wetware database at `other` into the one at
+/// `db_path`, in one transaction. `dry_run` reports the same summary and rolls back.
+/// The other database is never written: it is read through a migrated copy (see
+/// [`open_snapshot`]).
+///
+/// # Arguments
+/// * `other` - Database to import from
+/// * `policy` - How to resolve descriptions that differ between the two
+/// * `dry_run` - Only report what would happen
+/// * `timezone` - Zone the dates of duplicate thoughts are compared in
+/// * `configured` - Relation types from the config file, to tell which are undirected
+/// * `db_path` - Database to import into
+///
+/// # Returns
+/// * `Ok(())` - Databases merged, or dry run finished
+/// * `Err(ThoughtError::DescriptionConflicts)` - Descriptions differ under the `refuse` policy
+/// * `Err(ThoughtError::AmbiguousAlias)` - An entity's name is an alias of several entities here
+pub fn execute(
+    other: &Path,
+    policy: DescriptionPolicy,
+    dry_run: bool,
+    timezone: Timezone,
+    configured: &[RelationType],
+    db_path: &Path,
+) -> Result<(), ThoughtError> {
+    if !other.is_file() {
+        return Err(ThoughtError::InvalidInput(format!(
+            "Database '{}' does not exist",
+            other.display()
+        )));
+    }
+    if db_path.exists() && other.canonicalize()? == db_path.canonicalize()? {
+        return Err(ThoughtError::InvalidInput(
+            "Cannot merge a database into itself".to_string(),
+        ));
+    }
+
+    let scratch = tempfile::TempDir::new()?;
+    let source = open_snapshot(other, timezone, scratch.path())?;
+    let mut conn = get_connection(db_path)?;
+    run_migrations(&conn)?;
+
+    let tx = conn.transaction()?;
+    let summary = merge(&tx, &source, policy, timezone, configured)?;
+    if !dry_run {
+        tx.commit()?;
+    }
+
+    print_summary(&summary, policy);
+    if dry_run {
+        println!();
+        println!("Dry run: nothing was imported.");
+    }
+    Ok(())
+}
+
+/// A copy of the database at `path` in `dir`, brought up to date with this build
+///
+/// The original is opened read-only and copied with `VACUUM INTO`; only the copy is
+/// migrated, with legacy backdated thoughts dated in `timezone` as they would be here.
+/// An older schema or unnormalized timestamps in the other database are never
+/// upgraded in place, not even by a dry run.
+fn open_snapshot(path: &Path, timezone: Timezone, dir: &Path) -> Result<Connection, ThoughtError> {
+    let copy = dir.join("source.db");
+    let original = Connection::open_with_flags(path, OpenFlags::SQLITE_OPEN_READ_ONLY)?;
+    original.execute("VACUUM INTO ?1", [copy.to_string_lossy()])?;
+    drop(original);
+
+    let snapshot = get_connection(&copy)?;
+    run_migrations_in(&snapshot, timezone)?;
+    Ok(snapshot)
+}
+
+/// Print what the merge did, in the order it happened
+fn print_summary(summary: &DbMergeSummary, policy: DescriptionPolicy) {
+    println!(
+        "Imported {} thought(s); skipped {} duplicate(s).",
+        summary.thoughts_imported, summary.duplicates_skipped
+    );
+    println!(
+        "Entities: {} matched, {} created; added {} alias(es) and {} relation(s).",
+        summary.entities_matched, summary.entities_created, summary.aliases_added, summary.relations_added
+    );
+    if summary.relations_dropped > 0 {
+        println!(
+            "Dropped {} relation(s) that would have become self-relations or cycles.",
+            summary.relations_dropped
+        );
+    }
+    if !summary.description_conflicts.is_empty() {
+        let resolution = match policy {
+            DescriptionPolicy::Refuse | DescriptionPolicy::Keep => "kept this database's",
+            DescriptionPolicy::Theirs => "took the other database's",
+            DescriptionPolicy::Append => "appended the other database's",
+        };
+        println!(
+            "Descriptions differed for {} ({resolution}).",
+            summary.description_conflicts.join(", ")
+        );
+    }
+}
+
+/// Import everything in `source` into `conn`.
+///
+/// The storage-level half of [`execute`], run against `conn` as given, so the
+/// caller decides whether to commit. Each entity of `source` is matched here with
+/// `EntitiesRepository::resolve`, by its canonical name and then by each of its
+/// aliases; without a match it is created. A matched entity gains the other's name
+/// as an alias when the two differ, so text referring to it keeps resolving. Aliases,
+/// relations, and attributes missing here are added; an undirected typed relation
+/// (per `configured` and the built-ins) counts as present either way round. A thought
+/// is skipped when one with the same content on the same day (in `timezone`) is
+/// already here.
+pub fn merge(
+    conn: &Connection,
+    source: &Connection,
+    policy: DescriptionPolicy,
+    timezone: Timezone,
+    configured: &[RelationType],
+) -> Result<DbMergeSummary, ThoughtError> {
+    let mut summary = DbMergeSummary::default();
+
+    let source_aliases = group_aliases(EntityAliasesRepository::list_all(source)?);
+    let mut entity_ids: HashMap<i64, i64> = HashMap::new();
+    for entity in EntitiesRepository::list_all(source)? {
+        let source_id = entity.id.unwrap();
+        let aliases = source_aliases.get(&source_id).cloned().unwrap_or_default();
+        let (target_id, target_name) = match find_match(conn, &entity, &aliases)? {
+            Some(existing) => {
+                summary.entities_matched += 1;
+                merge_details(conn, &existing, &entity, policy, &mut summary)?;
+                (existing.id.unwrap(), existing.name)
+            }
+            None => {
+                summary.entities_created += 1;
+                let id = EntitiesRepository::find_or_create(conn, &Entity::new(entity.canonical_name.clone()))?;
+                EntitiesRepository::update_description(conn, &entity.name, entity.description.clone())?;
+                EntitiesRepository::set_kind(conn, &entity.name, entity.kind)?;
+                (id, entity.name.clone())
+            }
+        };
+        summary.aliases_added += add_aliases(conn, target_id, &target_name, &entity, &aliases)?;
+
+        let attributes = EntityAttributesRepository::list_for_entity(conn, target_id)?;
+        for (key, value) in EntityAttributesRepository::list_for_entity(source, source_id)? {
+            if !attributes.contains_key(&key) {
+                EntityAttributesRepository::set(conn, target_id, &key, &value)?;
+            }
+        }
+        entity_ids.insert(source_id, target_id);
+    }
+
+    summary.description_conflicts.sort();
+    if policy == DescriptionPolicy::Refuse && !summary.description_conflicts.is_empty() {
+        return Err(ThoughtError::DescriptionConflicts(std::mem::take(
+            &mut summary.description_conflicts,
+        )));
+    }
+
+    merge_relations(conn, source, &entity_ids, configured, &mut summary)?;
+    import_thoughts(conn, source, &entity_ids, timezone, &mut summary)?;
+
+    Ok(summary)
+}
+
+/// Aliases grouped by the entity they belong to
+fn group_aliases(aliases: Vec<(i64, String)>) -> HashMap<i64, Vec<String>> {
+    let mut grouped: HashMap<i64, Vec<String>> = HashMap::new();
+    for (entity_id, alias) in aliases {
+        grouped.entry(entity_id).or_default().push(alias);
+    }
+    grouped
+}
+
+/// The entity here that `entity` from the other database is, by its name or else any of its aliases
+fn find_match(conn: &Connection, entity: &Entity, aliases: &[String]) -> Result<Option<Entity>, ThoughtError> {
+    for name in std::iter::once(&entity.canonical_name).chain(aliases) {
+        if let Some(existing) = EntitiesRepository::resolve(conn, name)? {
+            return Ok(Some(existing));
+        }
+    }
+    Ok(None)
+}
+
+/// Fill in the description and kind of `existing` from `incoming`, recording a
+/// description conflict and resolving it by `policy`
+fn merge_details(
+    conn: &Connection,
+    existing: &Entity,
+    incoming: &Entity,
+    policy: DescriptionPolicy,
+    summary: &mut DbMergeSummary,
+) -> Result<(), ThoughtError> {
+    if existing.kind.is_none() && incoming.kind.is_some() {
+        EntitiesRepository::set_kind(conn, &existing.name, incoming.kind)?;
+    }
+
+    let ours = existing.description.as_deref().filter(|d| !d.trim().is_empty());
+    let Some(theirs) = incoming.description.as_deref().filter(|d| !d.trim().is_empty()) else {
+        return Ok(());
+    };
+    let merged = match ours {
+        None => theirs.to_string(),
+        Some(ours) if ours.trim() == theirs.trim() => return Ok(()),
+        Some(ours) => {
+            summary.description_conflicts.push(existing.canonical_name.clone());
+            match policy {
+                DescriptionPolicy::Refuse | DescriptionPolicy::Keep => return Ok(()),
+                DescriptionPolicy::Theirs => theirs.to_string(),
+                DescriptionPolicy::Append => format!("{}\n\n{}", ours.trim_end(), theirs.trim_start()),
+            }
+        }
+    };
+    EntitiesRepository::update_description(conn, &existing.name, Some(merged))
+}
+
+/// Register the other entity's name and aliases on `target_id`, returning how many were new
+///
+/// The target's own (lowercase) `target_name` is skipped, as in `entity merge`.
+fn add_aliases(
+    conn: &Connection,
+    target_id: i64,
+    target_name: &str,
+    entity: &Entity,
+    aliases: &[String],
+) -> Result<usize, ThoughtError> {
+    let existing: HashSet<String> = EntityAliasesRepository::list_for_entity(conn, target_id)?
+        .into_iter()
+        .map(|alias| alias.to_lowercase())
+        .collect();
+
+    let mut added = 0;
+    let mut seen = HashSet::new();
+    for name in std::iter::once(&entity.canonical_name).chain(aliases) {
+        let lowercase = name.trim().to_lowercase();
+        if lowercase == target_name || existing.contains(&lowercase) || !seen.insert(lowercase) {
+            continue;
+        }
+        EntityAliasesRepository::add_alias(conn, target_id, name)?;
+        added += 1;
+    }
+    Ok(added)
+}
+
+/// Copy the other database's parent/child and typed relations onto the matched entities
+///
+/// Several entities there can match one entity here, so an edge counts as added only
+/// the first time it lands.
+fn merge_relations(
+    conn: &Connection,
+    source: &Connection,
+    entity_ids: &HashMap<i64, i64>,
+    configured: &[RelationType],
+    summary: &mut DbMergeSummary,
+) -> Result<(), ThoughtError> {
+    let mut existing: HashSet<(i64, i64)> = EntityRelationsRepository::list_all_edges(conn)?.into_iter().collect();
+    for (child, parent) in EntityRelationsRepository::list_all_edges(source)? {
+        let (child, parent) = (entity_ids[&child], entity_ids[&parent]);
+        if existing.contains(&(child, parent)) {
+            continue;
+        }
+        if child == parent || EntityRelationsRepository::would_create_cycle(conn, child, parent)? {
+            summary.relations_dropped += 1;
+            continue;
+        }
+        EntityRelationsRepository::add_relation(conn, child, parent)?;
+        existing.insert((child, parent));
+        summary.relations_added += 1;
+    }
+
+    for (from, relation_type, to) in EntityRelationsRepository::list_all_typed_edges(source)? {
+        let (from, to) = (entity_ids[&from], entity_ids[&to]);
+        if from == to {
+            summary.relations_dropped += 1;
+            continue;
+        }
+        let undirected = !relation_types::describe(&relation_type, configured).directed;
+        let present = EntityRelationsRepository::has_typed_relation(conn, from, &relation_type, to)?
+            || (undirected && EntityRelationsRepository::has_typed_relation(conn, to, &relation_type, from)?);
+        if !present {
+            EntityRelationsRepository::add_typed_relation(conn, from, &relation_type, to)?;
+            summary.relations_added += 1;
+        }
+    }
+    Ok(())
+}
+
+/// Copy the other database's thoughts and their entity links, skipping duplicates
+fn import_thoughts(
+    conn: &Connection,
+    source: &Connection,
+    entity_ids: &HashMap<i64, i64>,
+    timezone: Timezone,
+    summary: &mut DbMergeSummary,
+) -> Result<(), ThoughtError> {
+    let mut links: HashMap<i64, Vec<i64>> = HashMap::new();
+    for (thought_id, entity_id) in EntitiesRepository::thought_links(source, None, None, timezone)? {
+        links.entry(thought_id).or_default().push(entity_ids[&entity_id]);
+    }
+
+    let mut known: HashSet<(String, NaiveDate)> = ThoughtsRepository::list_all(conn)?
+        .into_iter()
+        .map(|thought| (thought.content, timezone.date_of(thought.created_at)))
+        .collect();
+
+    for thought in ThoughtsRepository::list_all(source)? {
+        if !known.insert((thought.content.clone(), timezone.date_of(thought.created_at))) {
+            summary.duplicates_skipped += 1;
+            continue;
+        }
+        let id = ThoughtsRepository::save(conn, &thought)?;
+        for entity_id in links.get(&thought.id.unwrap()).into_iter().flatten() {
+            EntitiesRepository::link_to_thought(conn, *entity_id, id)?;
+        }
+        summary.thoughts_imported += 1;
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::models::thought::Thought;
+    use crate::services::entity_parser::extract_entities;
+    use crate::storage::connection::get_memory_connection;
+    use chrono::{TimeZone, Utc};
+
+    fn setup() -> Connection {
+        let conn = get_memory_connection().unwrap();
+        run_migrations(&conn).unwrap();
+        conn
+    }
+
+    fn add_thought(conn: &Connection, content: &str, day: u32) -> i64 {
+        let date = Utc.with_ymd_and_hms(2025, 3, day, 9, 0, 0).unwrap();
+        let id = ThoughtsRepository::save(conn, &Thought::new_with_date(content.to_string(), date).unwrap()).unwrap();
+        for name in extract_entities(content) {
+            let entity_id = EntitiesRepository::find_or_create(conn, &Entity::new(name)).unwrap();
+            EntitiesRepository::link_to_thought(conn, entity_id, id).unwrap();
+        }
+        id
+    }
+
+    fn entity(conn: &Connection, name: &str) -> Entity {
+        EntitiesRepository::resolve(conn, name).unwrap().unwrap()
+    }
+
+    #[test]
+    fn test_merge_imports_thoughts_and_skips_duplicates() {
+        let ours = setup();
+        let theirs = setup();
+        add_thought(&ours, "Coffee with [Sarah]", 1);
+        add_thought(&theirs, "Coffee with [Sarah]", 1);
+        add_thought(&theirs, "Coffee with [Sarah]", 2);
+        add_thought(&theirs, "Lunch with [Bob]", 3);
+
+        let summary = merge(
+            &ours,
+            &theirs,
+            DescriptionPolicy::Refuse,
+            Timezone::Named(chrono_tz::UTC),
+            &[],
+        )
+        .unwrap();
+
+        assert_eq!(summary.thoughts_imported, 2);
+        assert_eq!(summary.duplicates_skipped, 1);
+        assert_eq!(summary.entities_matched, 1);
+        assert_eq!(summary.entities_created, 1);
+        assert_eq!(ThoughtsRepository::list_all(&ours).unwrap().len(), 3);
+        assert_eq!(ThoughtsRepository::list_by_entity(&ours, "sarah").unwrap().len(), 2);
+        assert_eq!(ThoughtsRepository::list_by_entity(&ours, "bob").unwrap().len(), 1);
+    }
+
+    #[test]
+    fn test_merge_matches_by_alias_and_unions_aliases_and_relations() {
+        let ours = setup();
+        let theirs = setup();
+        add_thought(&ours, "Met [Robert] at [Acme]", 1);
+        let robert = entity(&ours, "robert").id.unwrap();
+        EntityAliasesRepository::add_alias(&ours, robert, "Bob").unwrap();
+
+        add_thought(&theirs, "[Bob] joined [Acme]", 2);
+        let bob = entity(&theirs, "bob").id.unwrap();
+        let acme = entity(&theirs, "acme").id.unwrap();
+        EntityAliasesRepository::add_alias(&theirs, bob, "Bobby").unwrap();
+        EntityRelationsRepository::add_typed_relation(&theirs, bob, "works-at", acme).unwrap();
+
+        let summary = merge(
+            &ours,
+            &theirs,
+            DescriptionPolicy::Refuse,
+            Timezone::Named(chrono_tz::UTC),
+            &[],
+        )
+        .unwrap();
+
+        assert_eq!(summary.entities_matched, 2);
+        assert_eq!(summary.entities_created, 0);
+        assert_eq!(summary.aliases_added, 1);
+        assert_eq!(summary.relations_added, 1);
+        assert_eq!(
+            EntityAliasesRepository::list_for_entity(&ours, robert).unwrap(),
+            ["Bob", "Bobby"]
+        );
+        let acme = entity(&ours, "acme").id.unwrap();
+        assert!(EntityRelationsRepository::has_typed_relation(&ours, robert, "works-at", acme).unwrap());
+        assert_eq!(ThoughtsRepository::list_by_entity(&ours, "robert").unwrap().len(), 2);
+    }
+
+    #[test]
+    fn test_merge_drops_relations_that_would_form_a_cycle() {
+        let ours = setup();
+        let theirs = setup();
+        add_thought(&ours, "[Team] in [Org]", 1);
+        let (team, org) = (entity(&ours, "team").id.unwrap(), entity(&ours, "org").id.unwrap());
+        EntityRelationsRepository::add_relation(&ours, team, org).unwrap();
+
+        add_thought(&theirs, "[Team] in [Org]", 1);
+        let (team, org) = (entity(&theirs, "team").id.unwrap(), entity(&theirs, "org").id.unwrap());
+        EntityRelationsRepository::add_relation(&theirs, org, team).unwrap();
+
+        let summary = merge(
+            &ours,
+            &theirs,
+            DescriptionPolicy::Refuse,
+            Timezone::Named(chrono_tz::UTC),
+            &[],
+        )
+        .unwrap();
+        assert_eq!(summary.relations_dropped, 1);
+        assert_eq!(summary.relations_added, 0);
+    }
+
+    #[test]
+    fn test_merge_description_policies() {
+        let conflicting = || {
+            let ours = setup();
+            let theirs = setup();
+            add_thought(&ours, "[Sarah] and [Bob]", 1);
+            add_thought(&theirs, "[Sarah] and [Bob]", 1);
+            EntitiesRepository::update_description(&ours, "sarah", Some("Designer".to_string())).unwrap();
+            EntitiesRepository::update_description(&theirs, "sarah", Some("Engineer".to_string())).unwrap();
+            EntitiesRepository::update_description(&theirs, "bob", Some("Neighbour".to_string())).unwrap();
+            (ours, theirs)
+        };
+        let description = |conn: &Connection, name: &str| entity(conn, name).description;
+        let utc = Timezone::Named(chrono_tz::UTC);
+
+        let (ours, theirs) = conflicting();
+        let err = merge(&ours, &theirs, DescriptionPolicy::Refuse, utc, &[]).unwrap_err();
+        assert!(matches!(err, ThoughtError::DescriptionConflicts(ref names) if names == &["Sarah"]));
+
+        let (ours, theirs) = conflicting();
+        let summary = merge(&ours, &theirs, DescriptionPolicy::Keep, utc, &[]).unwrap();
+        assert_eq!(summary.description_conflicts, ["Sarah"]);
+        assert_eq!(description(&ours, "sarah").as_deref(), Some("Designer"));
+        assert_eq!(description(&ours, "bob").as_deref(), Some("Neighbour"));
+
+        let (ours, theirs) = conflicting();
+        merge(&ours, &theirs, DescriptionPolicy::Theirs, utc, &[]).unwrap();
+        assert_eq!(description(&ours, "sarah").as_deref(), Some("Engineer"));
+
+        let (ours, theirs) = conflicting();
+        merge(&ours, &theirs, DescriptionPolicy::Append, utc, &[]).unwrap();
+        assert_eq!(description(&ours, "sarah").as_deref(), Some("Designer\n\nEngineer"));
+    }
+
+    #[test]
+    fn test_merge_counts_edges_once_when_entities_share_a_match() {
+        let ours = setup();
+        let theirs = setup();
+        add_thought(&ours, "[Robert] and [Sarah] at [Acme]", 1);
+        let robert = entity(&ours, "robert").id.unwrap();
+        let sarah = entity(&ours, "sarah").id.unwrap();
+        EntityAliasesRepository::add_alias(&ours, robert, "Bob").unwrap();
+        EntityRelationsRepository::add_typed_relation(&ours, sarah, "related-to", robert).unwrap();
+
+        // Bob and Robert are two entities there, one here
+        add_thought(&theirs, "[Bob], [Robert], [Sarah] and [Acme]", 2);
+        let acme = entity(&theirs, "acme").id.unwrap();
+        for name in ["bob", "robert"] {
+            let id = entity(&theirs, name).id.unwrap();
+            EntityRelationsRepository::add_relation(&theirs, id, acme).unwrap();
+            EntityRelationsRepository::add_typed_relation(&theirs, id, "works-at", acme).unwrap();
+        }
+        // Stored the other way round here, and undirected
+        let their_robert = entity(&theirs, "robert").id.unwrap();
+        let their_sarah = entity(&theirs, "sarah").id.unwrap();
+        EntityRelationsRepository::add_typed_relation(&theirs, their_robert, "related-to", their_sarah).unwrap();
+
+        let summary = merge(
+            &ours,
+            &theirs,
+            DescriptionPolicy::Refuse,
+            Timezone::Named(chrono_tz::UTC),
+            &[],
+        )
+        .unwrap();
+
+        assert_eq!(summary.relations_added, 2);
+        assert_eq!(EntityRelationsRepository::list_all_edges(&ours).unwrap().len(), 1);
+        assert_eq!(
+            EntityRelationsRepository::list_typed_relations(&ours, robert)
+                .unwrap()
+                .len(),
+            2
+        );
+    }
+}
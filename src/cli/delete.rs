@@ -1,5 +1,6 @@
 /// Delete command implementation
 use crate::errors::ThoughtError;
+use crate::models::TimestampFormat;
 use crate::storage::connection::get_connection;
 use crate::storage::migrations::run_migrations;
 use crate::storage::thoughts_repository::ThoughtsRepository;
@@ -9,7 +10,7 @@ use std::path::Path;
 ///
 /// Deletes a thought by its numeric ID. Prints the deleted thought's content
 /// as confirmation.
-pub fn execute(id: i64, db_path: &Path) -> Result<(), ThoughtError> {
+pub fn execute(id: i64, timestamps: TimestampFormat, db_path: &Path) -> Result<(), ThoughtError> {
     let conn = get_connection(db_path)?;
     run_migrations(&conn)?;
 
@@ -17,7 +18,7 @@ pub fn execute(id: i64, db_path: &Path) -> Result<(), ThoughtError> {
     let thought = ThoughtsRepository::get_by_id(&conn, id)?;
     ThoughtsRepository::delete(&conn, id)?;
 
-    let date = thought.created_at.format("%Y-%m-%d");
+    let date = timestamps.format(thought.created_at);
     println!("Deleted thought {id} ({date}): {}", thought.content);
 
     Ok(())
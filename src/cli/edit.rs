@@ -1,12 +1,12 @@
 /// Edit command implementation
 use crate::errors::ThoughtError;
 use crate::input::editor;
-use crate::services::{entity_parser, entity_resolution};
+use crate::models::Timezone;
+use crate::services::{date_expression, entity_parser, entity_resolution};
 use crate::storage::connection::get_connection;
 use crate::storage::entities_repository::EntitiesRepository;
 use crate::storage::migrations::run_migrations;
 use crate::storage::thoughts_repository::ThoughtsRepository;
-use chrono::NaiveDate;
 use std::path::Path;
 
 /// Execute the edit command
@@ -18,14 +18,16 @@ use std::path::Path;
 /// # Arguments
 /// * `id` - Numeric ID of the thought to edit (shown as `[id]` in `wet` listing)
 /// * `content` - Optional new text content for the thought
-/// * `date` - Optional new date string in YYYY-MM-DD format
+/// * `date` - Optional new date, a date expression resolved in `timezone`
 /// * `use_editor` - If true, open the thought's current content in an interactive editor
+/// * `timezone` - Zone the new date's day starts in
 /// * `db_path` - Optional path to the SQLite database file
 pub fn execute(
     id: i64,
     content: Option<String>,
     date: Option<String>,
     use_editor: bool,
+    timezone: Timezone,
     db_path: &Path,
 ) -> Result<(), ThoughtError> {
     // Validate at least one edit argument was provided
@@ -75,10 +77,14 @@ pub fn execute(
 
     // Parse new date if provided
     let new_date = if let Some(ref date_str) = date {
-        let naive = NaiveDate::parse_from_str(date_str, "%Y-%m-%d").map_err(|_| {
-            ThoughtError::InvalidInput(format!("Invalid date format '{}'. Expected YYYY-MM-DD.", date_str))
+        let naive = date_expression::parse(date_str, timezone.today()).ok_or_else(|| {
+            ThoughtError::InvalidInput(format!(
+                "Invalid date format '{}'. Expected {}.",
+                date_str,
+                date_expression::EXPECTED
+            ))
         })?;
-        Some(naive.and_hms_opt(0, 0, 0).unwrap().and_utc())
+        Some(timezone.start_of_day(naive))
     } else {
         None
     };
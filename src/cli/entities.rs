@@ -1,18 +1,26 @@
 /// Entities command implementation
+use crate::cli::entity_attribute::parse_assignment;
 use crate::errors::ThoughtError;
-use crate::services::description_formatter;
+use crate::models::EntityKind;
+use crate::services::{description_formatter, entity_tree};
 use crate::storage::connection::get_connection;
 use crate::storage::entities_repository::EntitiesRepository;
+use crate::storage::entity_attributes_repository::EntityAttributesRepository;
+use crate::storage::entity_relations_repository::EntityRelationsRepository;
 use crate::storage::migrations::run_migrations;
+use std::collections::HashMap;
 use std::path::Path;
 
 /// Execute the entities command
 ///
 /// Lists all entities in alphabetical order. If terminal width >= 60 characters,
-/// entities with descriptions show ellipsized previews on a single line.
+/// entities with descriptions show ellipsized previews on a single line. Entities
+/// with a kind show it in parentheses after the name.
 ///
 /// # Arguments
 /// * `db_path` - Optional database path
+/// * `kind` - Only list entities of this kind
+/// * `attributes` - `key=value` filters; only entities matching all of them are listed
 ///
 /// # Returns
 /// * `Ok(())` - Success
@@ -22,6 +30,7 @@ use std::path::Path;
 /// Wide terminal (>= 60 chars):
 /// ```text
 /// entity-name - Preview of description text…
+/// entity-name (person) - Preview of description text…
 /// entity-without-description
 /// ```
 ///
@@ -30,7 +39,15 @@ use std::path::Path;
 /// entity-name
 /// entity-without-description
 /// ```
-pub fn execute(db_path: &Path) -> Result<(), ThoughtError> {
+pub fn execute(db_path: &Path, kind: Option<&str>, attributes: &[String]) -> Result<(), ThoughtError> {
+    let kind_filter = kind
+        .map(|raw| raw.parse::<EntityKind>().map_err(ThoughtError::InvalidInput))
+        .transpose()?;
+    let attribute_filters = attributes
+        .iter()
+        .map(|assignment| parse_assignment(assignment))
+        .collect::<Result<Vec<_>, _>>()?;
+
     // Get database connection
     let conn = get_connection(db_path)?;
 
@@ -38,10 +55,21 @@ pub fn execute(db_path: &Path) -> Result<(), ThoughtError> {
     run_migrations(&conn)?;
 
     // Get all entities (already in alphabetical order from repository)
-    let entities = EntitiesRepository::list_all(&conn)?;
+    let mut entities = EntitiesRepository::list_all(&conn)?;
+    if let Some(kind_filter) = kind_filter {
+        entities.retain(|entity| entity.kind == Some(kind_filter));
+    }
+    for (key, value) in &attribute_filters {
+        let matching = EntityAttributesRepository::entity_ids_with(&conn, key, value)?;
+        entities.retain(|entity| entity.id.is_some_and(|id| matching.contains(&id)));
+    }
 
     if entities.is_empty() {
-        println!("No entities found.");
+        if kind_filter.is_some() || !attribute_filters.is_empty() {
+            println!("No matching entities found.");
+        } else {
+            println!("No entities found.");
+        }
         return Ok(());
     }
 
@@ -53,24 +81,80 @@ pub fn execute(db_path: &Path) -> Result<(), ThoughtError> {
     let show_previews = terminal_width >= MIN_WIDTH_FOR_PREVIEW;
 
     for entity in entities {
+        let label = match entity.kind {
+            Some(kind) => format!("{} ({})", entity.canonical_name, kind),
+            None => entity.canonical_name.clone(),
+        };
         if show_previews && entity.has_description() {
             // T055: Generate and display preview for entities with descriptions
-            let preview = description_formatter::generate_preview(
-                entity.description_or_empty(),
-                &entity.canonical_name,
-                terminal_width,
-            );
+            let preview =
+                description_formatter::generate_preview(entity.description_or_empty(), &label, terminal_width);
 
             if !preview.is_empty() {
-                println!("{} - {}", entity.canonical_name, preview);
+                println!("{} - {}", label, preview);
             } else {
                 // Preview empty (entity name too long or other issue) - show name only
-                println!("{}", entity.canonical_name);
+                println!("{}", label);
             }
         } else {
             // T056: Display entity name only (no description or narrow terminal)
-            println!("{}", entity.canonical_name);
+            println!("{}", label);
+        }
+    }
+
+    Ok(())
+}
+
+/// Execute the entities command in tree mode (`--tree`)
+///
+/// Prints the parent/child hierarchy as an indented tree, each entity followed by the
+/// number of thoughts linked to it directly. An entity with several parents is shown
+/// under each of them and marked with the other parents' names.
+///
+/// # Output Format
+/// ```text
+/// Amazon (12 thoughts)
+/// ├── AWS (5 thoughts)
+/// │   └── Kubernetes (2 thoughts) [also under: Google]
+/// └── Retail (1 thought)
+/// ```
+pub fn execute_tree(db_path: &Path) -> Result<(), ThoughtError> {
+    let conn = get_connection(db_path)?;
+    run_migrations(&conn)?;
+
+    let entities = EntitiesRepository::list_all(&conn)?;
+    if entities.is_empty() {
+        println!("No entities found.");
+        return Ok(());
+    }
+
+    let edges = EntityRelationsRepository::list_all_edges(&conn)?;
+    let counts = EntitiesRepository::thought_counts(&conn)?;
+    let by_id: HashMap<i64, _> = entities.iter().filter_map(|e| e.id.map(|id| (id, e))).collect();
+
+    for row in entity_tree::build(&entities, &edges) {
+        let entity = by_id[&row.entity_id];
+        let count = counts.get(&row.entity_id).copied().unwrap_or(0);
+        let count_label = if count == 1 {
+            "1 thought".to_string()
+        } else {
+            format!("{} thoughts", count)
+        };
+        let details = match entity.kind {
+            Some(kind) => format!("{}, {}", kind, count_label),
+            None => count_label,
+        };
+
+        let mut line = format!("{}{} ({})", row.prefix, entity.canonical_name, details);
+        if !row.other_parents.is_empty() {
+            let others: Vec<&str> = row
+                .other_parents
+                .iter()
+                .map(|id| by_id[id].canonical_name.as_str())
+                .collect();
+            line.push_str(&format!(" [also under: {}]", others.join(", ")));
         }
+        println!("{}", line);
     }
 
     Ok(())
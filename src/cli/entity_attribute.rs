@@ -0,0 +1,137 @@
+/// Entity set/unset command implementations
+use crate::errors::ThoughtError;
+use crate::storage::connection::get_connection;
+use crate::storage::entities_repository::EntitiesRepository;
+use crate::storage::entity_attributes_repository::EntityAttributesRepository;
+use crate::storage::migrations::run_migrations;
+use std::path::Path;
+
+/// Parse a `key=value` argument, splitting at the first `=`.
+///
+/// The key and value are trimmed; both must be non-empty (remove an attribute with
+/// `wet entity unset` instead of setting it to nothing).
+///
+/// # Examples
+///
+/// ```
+/// use wetware::cli::entity_attribute::parse_assignment;
+///
+/// let (key, value) = parse_assignment("url=https://example.com/?a=b").unwrap();
+/// assert_eq!(key, "url");
+/// assert_eq!(value, "https://example.com/?a=b");
+/// assert!(parse_assignment("status").is_err());
+/// ```
+pub fn parse_assignment(assignment: &str) -> Result<(String, String), ThoughtError> {
+    let Some((key, value)) = assignment.split_once('=') else {
+        return Err(ThoughtError::InvalidInput(format!(
+            "Expected key=value, got '{}'",
+            assignment
+        )));
+    };
+
+    let (key, value) = (key.trim(), value.trim());
+    if key.is_empty() {
+        return Err(ThoughtError::InvalidInput(format!(
+            "Attribute key cannot be empty in '{}'",
+            assignment
+        )));
+    }
+    if value.is_empty() {
+        return Err(ThoughtError::InvalidInput(format!(
+            "Attribute '{}' has no value. Remove it with: wet entity unset <name> {}",
+            key, key
+        )));
+    }
+
+    Ok((key.to_lowercase(), value.to_string()))
+}
+
+/// Execute the entity set command
+///
+/// Sets each `key=value` attribute on the entity, replacing existing values. All
+/// assignments are parsed before anything is written, so a malformed one changes nothing.
+///
+/// # Arguments
+/// * `entity_name` - Entity to update (case-insensitive; may be an alias)
+/// * `assignments` - One or more `key=value` pairs
+/// * `db_path` - Database path
+pub fn execute_set(entity_name: &str, assignments: &[String], db_path: &Path) -> Result<(), ThoughtError> {
+    let parsed = assignments
+        .iter()
+        .map(|assignment| parse_assignment(assignment))
+        .collect::<Result<Vec<_>, _>>()?;
+
+    let mut conn = get_connection(db_path)?;
+    run_migrations(&conn)?;
+
+    let entity = EntitiesRepository::resolve(&conn, entity_name)?
+        .ok_or_else(|| ThoughtError::EntityNotFound(entity_name.to_string()))?;
+    let entity_id = entity.id.unwrap();
+
+    let tx = conn.transaction()?;
+    for (key, value) in &parsed {
+        EntityAttributesRepository::set(&tx, entity_id, key, value)?;
+    }
+    tx.commit()?;
+
+    for (key, value) in &parsed {
+        println!("Set {} = {} on '{}'.", key, value, entity.canonical_name);
+    }
+
+    Ok(())
+}
+
+/// Execute the entity unset command
+///
+/// Removes each named attribute from the entity. Keys that aren't set are reported
+/// but not an error.
+///
+/// # Arguments
+/// * `entity_name` - Entity to update (case-insensitive; may be an alias)
+/// * `keys` - Attribute keys to remove
+/// * `db_path` - Database path
+pub fn execute_unset(entity_name: &str, keys: &[String], db_path: &Path) -> Result<(), ThoughtError> {
+    let conn = get_connection(db_path)?;
+    run_migrations(&conn)?;
+
+    let entity = EntitiesRepository::resolve(&conn, entity_name)?
+        .ok_or_else(|| ThoughtError::EntityNotFound(entity_name.to_string()))?;
+
+    for key in keys {
+        if EntityAttributesRepository::unset(&conn, entity.id.unwrap(), key)? {
+            println!(
+                "Removed {} from '{}'.",
+                key.trim().to_lowercase(),
+                entity.canonical_name
+            );
+        } else {
+            println!("'{}' has no attribute '{}'.", entity.canonical_name, key.trim());
+        }
+    }
+
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_assignment_lowercases_and_trims_key() {
+        let (key, value) = parse_assignment(" Email = sarah@example.com ").unwrap();
+        assert_eq!(key, "email");
+        assert_eq!(value, "sarah@example.com");
+    }
+
+    #[test]
+    fn test_parse_assignment_rejects_missing_parts() {
+        assert!(matches!(parse_assignment("status"), Err(ThoughtError::InvalidInput(_))));
+        assert!(matches!(
+            parse_assignment("=active"),
+            Err(ThoughtError::InvalidInput(_))
+        ));
+
+        let err = parse_assignment("status=").unwrap_err().to_string();
+        assert!(err.contains("wet entity unset"));
+    }
+}
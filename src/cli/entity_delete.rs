@@ -0,0 +1,318 @@
+/// Entity delete command implementation
+use crate::errors::ThoughtError;
+use crate::input::prompt;
+use crate::models::entity::Entity;
+use crate::models::thought::Thought;
+use crate::services::entity_parser::{extract_entities, strip_entity_references};
+use crate::storage::connection::get_connection;
+use crate::storage::entities_repository::EntitiesRepository;
+use crate::storage::entity_aliases_repository::EntityAliasesRepository;
+use crate::storage::entity_relations_repository::EntityRelationsRepository;
+use crate::storage::migrations::run_migrations;
+use crate::storage::thoughts_repository::ThoughtsRepository;
+use clap::ValueEnum;
+use rusqlite::Connection;
+use std::path::Path;
+
+/// What to do with references to the deleted entity in thought and description text
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, ValueEnum)]
+pub enum ReferencePolicy {
+    /// Refuse to delete an entity that any text still references
+    #[default]
+    Refuse,
+    /// Replace each reference with its plain display text
+    Strip,
+    /// Leave the text as it is; its references no longer point at any entity
+    Dangle,
+}
+
+/// Everything deleting an entity would touch, gathered before anything changes
+#[derive(Debug)]
+pub struct DeletePlan {
+    /// The entity to delete
+    pub entity: Entity,
+    /// Its registered aliases, removed with it
+    pub aliases: Vec<String>,
+    /// Parent/child and typed relations, removed with it
+    pub relations: usize,
+    /// Thoughts linked to it in `thought_entities`
+    pub linked_thoughts: usize,
+    /// Thoughts whose text references it by canonical name or alias
+    pub referencing_thoughts: Vec<Thought>,
+    /// Other entities whose description references it
+    pub referencing_descriptions: Vec<Entity>,
+}
+
+impl DeletePlan {
+    /// Whether any stored text still references the entity
+    pub fn is_referenced(&self) -> bool {
+        !self.referencing_thoughts.is_empty() || !self.referencing_descriptions.is_empty()
+    }
+}
+
+/// Resolve `entity_name` (alias-aware) and gather what deleting it would touch
+pub fn plan(conn: &Connection, entity_name: &str) -> Result<DeletePlan, ThoughtError> {
+    let Some(entity) = EntitiesRepository::resolve(conn, entity_name)? else {
+        return Err(ThoughtError::EntityNotFound(entity_name.to_string()));
+    };
+    let entity_id = entity.id.unwrap();
+
+    let aliases = EntityAliasesRepository::list_for_entity(conn, entity_id)?;
+    let relations = EntityRelationsRepository::list_parents(conn, entity_id)?.len()
+        + EntityRelationsRepository::list_children(conn, entity_id)?.len()
+        + EntityRelationsRepository::list_typed_relations(conn, entity_id)?.len();
+    let linked_thoughts = EntitiesRepository::thought_counts(conn)?
+        .get(&entity_id)
+        .copied()
+        .unwrap_or(0);
+
+    let mut names: Vec<String> = vec![entity.name.clone()];
+    names.extend(aliases.iter().map(|a| a.to_lowercase()));
+    let references = |text: &str| extract_entities(text).iter().any(|e| names.contains(&e.to_lowercase()));
+
+    let referencing_thoughts = ThoughtsRepository::list_all(conn)?
+        .into_iter()
+        .filter(|t| references(&t.content))
+        .collect();
+    let referencing_descriptions = EntitiesRepository::list_all(conn)?
+        .into_iter()
+        .filter(|e| e.id != entity.id && e.description.as_deref().is_some_and(references))
+        .collect();
+
+    Ok(DeletePlan {
+        entity,
+        aliases,
+        relations,
+        linked_thoughts,
+        referencing_thoughts,
+        referencing_descriptions,
+    })
+}
+
+/// Carry out a [`DeletePlan`] under `policy` within a single transaction.
+///
+/// `Refuse` fails with `EntityStillReferenced` if any text references the entity;
+/// `Strip` rewrites those references to plain text first; `Dangle` leaves the text
+/// alone. The entity row is then deleted, cascading its links, aliases and relations.
+pub fn delete(conn: &mut Connection, plan: &DeletePlan, policy: ReferencePolicy) -> Result<(), ThoughtError> {
+    if policy == ReferencePolicy::Refuse && plan.is_referenced() {
+        return Err(ThoughtError::EntityStillReferenced {
+            name: plan.entity.canonical_name.clone(),
+            thoughts: plan.referencing_thoughts.len(),
+            descriptions: plan.referencing_descriptions.len(),
+        });
+    }
+
+    let tx = conn.transaction()?;
+
+    if policy == ReferencePolicy::Strip {
+        let mut names: Vec<&str> = vec![plan.entity.name.as_str()];
+        names.extend(plan.aliases.iter().map(String::as_str));
+
+        for thought in &plan.referencing_thoughts {
+            let stripped = strip_entity_references(&thought.content, &names);
+            ThoughtsRepository::update(&tx, thought.id.unwrap(), &stripped, thought.created_at)?;
+        }
+        for other in &plan.referencing_descriptions {
+            let desc = other.description.as_deref().unwrap_or_default();
+            EntitiesRepository::update_description(&tx, &other.name, Some(strip_entity_references(desc, &names)))?;
+        }
+    }
+
+    EntitiesRepository::delete(&tx, plan.entity.id.unwrap())?;
+
+    tx.commit()?;
+    Ok(())
+}
+
+/// Print what deleting the entity would remove and how references are handled
+fn print_plan(plan: &DeletePlan, policy: ReferencePolicy) {
+    println!("Entity '{}':", plan.entity.canonical_name);
+    println!("  {} linked thought(s)", plan.linked_thoughts);
+    if plan.aliases.is_empty() {
+        println!("  0 alias(es)");
+    } else {
+        println!("  {} alias(es): {}", plan.aliases.len(), plan.aliases.join(", "));
+    }
+    println!("  {} relation(s)", plan.relations);
+
+    if plan.is_referenced() {
+        let action = match policy {
+            ReferencePolicy::Refuse => "block the deletion",
+            ReferencePolicy::Strip => "be stripped to plain text",
+            ReferencePolicy::Dangle => "be left dangling",
+        };
+        println!(
+            "  References in {} thought(s) and {} description(s) will {}.",
+            plan.referencing_thoughts.len(),
+            plan.referencing_descriptions.len(),
+            action
+        );
+    }
+}
+
+/// Execute the entity delete command
+///
+/// Deletes an entity together with its aliases, relations and thought links. Text
+/// references to it are handled by `policy`. Shows what will be removed and asks for
+/// confirmation unless `yes` is set; `dry_run` shows the same summary and stops.
+///
+/// # Arguments
+/// * `entity_name` - Entity to delete (case-insensitive, may be an alias)
+/// * `policy` - How to handle references to the entity in stored text
+/// * `dry_run` - Only report what would happen
+/// * `yes` - Skip the confirmation prompt
+/// * `db_path` - Database path
+///
+/// # Returns
+/// * `Ok(())` - Entity deleted, deletion declined, or dry run finished
+/// * `Err(ThoughtError::EntityNotFound)` - No entity with the given name exists
+/// * `Err(ThoughtError::EntityStillReferenced)` - Referenced under the `refuse` policy
+pub fn execute(
+    entity_name: &str,
+    policy: ReferencePolicy,
+    dry_run: bool,
+    yes: bool,
+    db_path: &Path,
+) -> Result<(), ThoughtError> {
+    let mut conn = get_connection(db_path)?;
+    run_migrations(&conn)?;
+
+    let plan = plan(&conn, entity_name)?;
+
+    print_plan(&plan, policy);
+
+    // Under `refuse`, a referenced entity fails here with the reason, dry run or not.
+    if policy == ReferencePolicy::Refuse && plan.is_referenced() {
+        return delete(&mut conn, &plan, policy);
+    }
+
+    if dry_run {
+        println!();
+        println!("Dry run: nothing was deleted.");
+        return Ok(());
+    }
+
+    if !yes {
+        let answer = prompt::ask(&format!("Delete entity '{}'? [y/N]: ", plan.entity.canonical_name))?;
+        if answer != "y" && answer != "yes" {
+            println!("Aborted; nothing was deleted.");
+            return Ok(());
+        }
+    }
+
+    delete(&mut conn, &plan, policy)?;
+    println!("Entity '{}' deleted.", plan.entity.canonical_name);
+
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::storage::connection::get_memory_connection;
+
+    fn setup() -> Connection {
+        let conn = get_memory_connection().unwrap();
+        run_migrations(&conn).unwrap();
+        conn
+    }
+
+    fn add_thought(conn: &Connection, content: &str) -> i64 {
+        let id = ThoughtsRepository::save(conn, &Thought::new(content.to_string()).unwrap()).unwrap();
+        for name in extract_entities(content) {
+            let entity_id = EntitiesRepository::find_or_create(conn, &Entity::new(name)).unwrap();
+            EntitiesRepository::link_to_thought(conn, entity_id, id).unwrap();
+        }
+        id
+    }
+
+    #[test]
+    fn test_plan_collects_links_aliases_relations_and_references() {
+        let conn = setup();
+        add_thought(&conn, "Coffee with [Sara]");
+        add_thought(&conn, "Team [Acme]");
+        let sara = EntitiesRepository::find_by_name(&conn, "sara").unwrap().unwrap();
+        let acme = EntitiesRepository::find_by_name(&conn, "acme").unwrap().unwrap();
+        EntityAliasesRepository::add_alias(&conn, sara.id.unwrap(), "sar").unwrap();
+        EntityRelationsRepository::add_relation(&conn, sara.id.unwrap(), acme.id.unwrap()).unwrap();
+        EntitiesRepository::update_description(&conn, "acme", Some("Where [sar] works".to_string())).unwrap();
+
+        let plan = plan(&conn, "sar").unwrap();
+
+        assert_eq!(plan.entity.canonical_name, "Sara");
+        assert_eq!(plan.aliases, vec!["sar"]);
+        assert_eq!(plan.relations, 1);
+        assert_eq!(plan.linked_thoughts, 1);
+        assert_eq!(plan.referencing_thoughts.len(), 1);
+        assert_eq!(plan.referencing_descriptions.len(), 1);
+        assert!(plan.is_referenced());
+    }
+
+    #[test]
+    fn test_delete_refuse_keeps_referenced_entity() {
+        let mut conn = setup();
+        add_thought(&conn, "Coffee with [Sara]");
+
+        let plan = plan(&conn, "sara").unwrap();
+        let result = delete(&mut conn, &plan, ReferencePolicy::Refuse);
+
+        assert!(matches!(
+            result,
+            Err(ThoughtError::EntityStillReferenced {
+                thoughts: 1,
+                descriptions: 0,
+                ..
+            })
+        ));
+        assert!(EntitiesRepository::find_by_name(&conn, "sara").unwrap().is_some());
+    }
+
+    #[test]
+    fn test_delete_refuse_allows_unreferenced_entity() {
+        let mut conn = setup();
+        EntitiesRepository::find_or_create(&conn, &Entity::new("typo".to_string())).unwrap();
+
+        let plan = plan(&conn, "typo").unwrap();
+        delete(&mut conn, &plan, ReferencePolicy::Refuse).unwrap();
+
+        assert!(EntitiesRepository::find_by_name(&conn, "typo").unwrap().is_none());
+    }
+
+    #[test]
+    fn test_delete_strip_rewrites_text_and_cascades() {
+        let mut conn = setup();
+        let id = add_thought(&conn, "Coffee with [Sara] and [Bob]");
+        let sara = EntitiesRepository::find_by_name(&conn, "sara").unwrap().unwrap();
+        EntityAliasesRepository::add_alias(&conn, sara.id.unwrap(), "sar").unwrap();
+        EntitiesRepository::update_description(&conn, "bob", Some("Knows [S](sar)".to_string())).unwrap();
+
+        let plan = plan(&conn, "sara").unwrap();
+        delete(&mut conn, &plan, ReferencePolicy::Strip).unwrap();
+
+        assert_eq!(
+            ThoughtsRepository::get_by_id(&conn, id).unwrap().content,
+            "Coffee with Sara and [Bob]"
+        );
+        let bob = EntitiesRepository::find_by_name(&conn, "bob").unwrap().unwrap();
+        assert_eq!(bob.description.as_deref(), Some("Knows S"));
+        assert!(EntitiesRepository::resolve(&conn, "sar").unwrap().is_none());
+        assert_eq!(EntitiesRepository::list_for_thought(&conn, id).unwrap().len(), 1);
+    }
+
+    #[test]
+    fn test_delete_dangle_leaves_text_untouched() {
+        let mut conn = setup();
+        let id = add_thought(&conn, "Coffee with [Sara]");
+
+        let plan = plan(&conn, "sara").unwrap();
+        delete(&mut conn, &plan, ReferencePolicy::Dangle).unwrap();
+
+        assert_eq!(
+            ThoughtsRepository::get_by_id(&conn, id).unwrap().content,
+            "Coffee with [Sara]"
+        );
+        assert!(EntitiesRepository::find_by_name(&conn, "sara").unwrap().is_none());
+        assert!(EntitiesRepository::list_for_thought(&conn, id).unwrap().is_empty());
+    }
+}
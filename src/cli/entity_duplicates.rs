@@ -0,0 +1,219 @@
+/// Entity duplicates command implementation
+use crate::errors::ThoughtError;
+use crate::models::entity::Entity;
+use crate::services::entity_similarity::find_near_duplicates;
+use crate::storage::connection::get_connection;
+use crate::storage::entities_repository::EntitiesRepository;
+use crate::storage::entity_aliases_repository::EntityAliasesRepository;
+use crate::storage::entity_attributes_repository::EntityAttributesRepository;
+use crate::storage::entity_relations_repository::EntityRelationsRepository;
+use crate::storage::migrations::run_migrations;
+use rusqlite::Connection;
+use std::cmp::Reverse;
+use std::collections::HashMap;
+use std::path::Path;
+
+/// A pair of entities whose names are probably the same thing
+#[derive(Debug)]
+pub struct DuplicateCandidate {
+    /// The entity suggested to merge away (fewer linked thoughts, or less metadata)
+    pub source: Entity,
+    /// Thoughts directly linked to `source`
+    pub source_thoughts: usize,
+    /// The entity suggested to keep
+    pub target: Entity,
+    /// Thoughts directly linked to `target`
+    pub target_thoughts: usize,
+}
+
+/// Find near-duplicate entity pairs, each oriented so the entity with fewer linked
+/// thoughts is the one suggested to merge away. Ties keep the entity with more
+/// metadata (see [`metadata_counts`]), then the alphabetically first.
+pub fn find_duplicates(conn: &Connection) -> Result<Vec<DuplicateCandidate>, ThoughtError> {
+    let entities = EntitiesRepository::list_all(conn)?;
+    let counts = EntitiesRepository::thought_counts(conn)?;
+    let metadata = metadata_counts(conn, &entities)?;
+    let count_of = |e: &Entity| counts.get(&e.id.unwrap()).copied().unwrap_or(0);
+    let keep_rank = |e: &Entity| {
+        (
+            count_of(e),
+            metadata.get(&e.id.unwrap()).copied().unwrap_or(0),
+            Reverse(e.name.clone()),
+        )
+    };
+
+    let names: Vec<&str> = entities.iter().map(|e| e.canonical_name.as_str()).collect();
+
+    let candidates = find_near_duplicates(&names)
+        .into_iter()
+        .map(|(i, j)| {
+            let (first, second) = (&entities[i], &entities[j]);
+            let (source, target) = if keep_rank(second) < keep_rank(first) {
+                (second, first)
+            } else {
+                (first, second)
+            };
+            DuplicateCandidate {
+                source_thoughts: count_of(source),
+                source: source.clone(),
+                target_thoughts: count_of(target),
+                target: target.clone(),
+            }
+        })
+        .collect();
+
+    Ok(candidates)
+}
+
+/// Entity id -> how much an entity carries besides its thoughts: one for a kind, plus
+/// one per attribute, alias, and relation (either end, parent/child or typed)
+///
+/// The entity carrying more of it is the one that has been curated, so it makes the
+/// better survivor when thought counts tie.
+fn metadata_counts(conn: &Connection, entities: &[Entity]) -> Result<HashMap<i64, usize>, ThoughtError> {
+    let mut counts: HashMap<i64, usize> = HashMap::new();
+    for entity in entities.iter().filter(|e| e.kind.is_some()) {
+        *counts.entry(entity.id.unwrap()).or_default() += 1;
+    }
+    for (entity_id, attributes) in EntityAttributesRepository::list_all(conn)? {
+        *counts.entry(entity_id).or_default() += attributes.len();
+    }
+    let aliases = EntityAliasesRepository::list_all(conn)?.into_iter().map(|(id, _)| id);
+    let edges = EntityRelationsRepository::list_all_edges(conn)?
+        .into_iter()
+        .flat_map(|(child, parent)| [child, parent]);
+    let typed_edges = EntityRelationsRepository::list_all_typed_edges(conn)?
+        .into_iter()
+        .flat_map(|(from, _, to)| [from, to]);
+    for entity_id in aliases.chain(edges).chain(typed_edges) {
+        *counts.entry(entity_id).or_default() += 1;
+    }
+    Ok(counts)
+}
+
+/// "1 thought" / "3 thoughts"
+fn thoughts_label(count: usize) -> String {
+    format!("{} thought{}", count, if count == 1 { "" } else { "s" })
+}
+
+/// Execute the entity duplicates command
+///
+/// Lists pairs of entities whose names are likely the same thing written differently,
+/// with their thought counts, the `entity merge` command that would fold one into the
+/// other, and the `entity alias` command that can follow it to keep the old spelling
+/// resolving (merge doesn't register one).
+///
+/// # Arguments
+/// * `db_path` - Database path
+pub fn execute(db_path: &Path) -> Result<(), ThoughtError> {
+    let conn = get_connection(db_path)?;
+    run_migrations(&conn)?;
+
+    let candidates = find_duplicates(&conn)?;
+
+    if candidates.is_empty() {
+        println!("No likely duplicate entities found.");
+        return Ok(());
+    }
+
+    for (i, candidate) in candidates.iter().enumerate() {
+        if i > 0 {
+            println!();
+        }
+        println!(
+            "{} ({}) ~ {} ({})",
+            candidate.source.canonical_name,
+            thoughts_label(candidate.source_thoughts),
+            candidate.target.canonical_name,
+            thoughts_label(candidate.target_thoughts)
+        );
+        println!(
+            "  wet entity merge \"{}\" --into \"{}\"",
+            candidate.source.canonical_name, candidate.target.canonical_name
+        );
+        println!(
+            "  then, to keep \"{}\" resolving: wet entity alias \"{}\" --alias \"{}\"",
+            candidate.source.canonical_name, candidate.target.canonical_name, candidate.source.canonical_name
+        );
+    }
+
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::models::EntityKind;
+    use crate::storage::connection::get_memory_connection;
+
+    fn entity_with_thoughts(conn: &Connection, name: &str, thoughts: usize) {
+        let id = EntitiesRepository::find_or_create(conn, &Entity::new(name.to_string())).unwrap();
+        for _ in 0..thoughts {
+            conn.execute(
+                "INSERT INTO thoughts (content, created_at) VALUES ('Test', datetime('now'))",
+                [],
+            )
+            .unwrap();
+            EntitiesRepository::link_to_thought(conn, id, conn.last_insert_rowid()).unwrap();
+        }
+    }
+
+    #[test]
+    fn test_find_duplicates_orients_towards_more_used_entity() {
+        let conn = get_memory_connection().unwrap();
+        run_migrations(&conn).unwrap();
+        entity_with_thoughts(&conn, "Sara", 3);
+        entity_with_thoughts(&conn, "Sarah", 1);
+        entity_with_thoughts(&conn, "rust", 2);
+
+        let candidates = find_duplicates(&conn).unwrap();
+
+        assert_eq!(candidates.len(), 1);
+        assert_eq!(candidates[0].source.canonical_name, "Sarah");
+        assert_eq!(candidates[0].source_thoughts, 1);
+        assert_eq!(candidates[0].target.canonical_name, "Sara");
+        assert_eq!(candidates[0].target_thoughts, 3);
+    }
+
+    #[test]
+    fn test_find_duplicates_tie_keeps_the_entity_with_more_metadata() {
+        let conn = get_memory_connection().unwrap();
+        run_migrations(&conn).unwrap();
+        entity_with_thoughts(&conn, "Sara", 1);
+        entity_with_thoughts(&conn, "Sarah", 1);
+        entity_with_thoughts(&conn, "Acme", 0);
+        let sarah = EntitiesRepository::find_by_name(&conn, "sarah")
+            .unwrap()
+            .unwrap()
+            .id
+            .unwrap();
+        let acme = EntitiesRepository::find_by_name(&conn, "acme")
+            .unwrap()
+            .unwrap()
+            .id
+            .unwrap();
+        EntitiesRepository::set_kind(&conn, "sarah", Some(EntityKind::Person)).unwrap();
+        EntityAttributesRepository::set(&conn, sarah, "role", "engineer").unwrap();
+        EntityRelationsRepository::add_typed_relation(&conn, sarah, "works-at", acme).unwrap();
+
+        let candidates = find_duplicates(&conn).unwrap();
+
+        assert_eq!(candidates.len(), 1);
+        assert_eq!(candidates[0].source.canonical_name, "Sara");
+        assert_eq!(candidates[0].target.canonical_name, "Sarah");
+    }
+
+    #[test]
+    fn test_find_duplicates_tie_keeps_alphabetically_first() {
+        let conn = get_memory_connection().unwrap();
+        run_migrations(&conn).unwrap();
+        entity_with_thoughts(&conn, "project alpha", 0);
+        entity_with_thoughts(&conn, "project-alpha", 0);
+
+        let candidates = find_duplicates(&conn).unwrap();
+
+        assert_eq!(candidates.len(), 1);
+        assert_eq!(candidates[0].target.canonical_name, "project alpha");
+        assert_eq!(candidates[0].source.canonical_name, "project-alpha");
+    }
+}
@@ -1,6 +1,7 @@
 /// Entity edit command implementation
 use crate::errors::ThoughtError;
 use crate::input::editor;
+use crate::models::EntityKind;
 use crate::services::{entity_parser, entity_resolution};
 use crate::storage::connection::get_connection;
 use crate::storage::entities_repository::EntitiesRepository;
@@ -14,6 +15,7 @@ use std::path::{Path, PathBuf};
 /// * `entity_name` - Name of the entity to edit (case-insensitive)
 /// * `description` - Inline description text (mutually exclusive with other methods)
 /// * `description_file` - Path to file containing description (mutually exclusive)
+/// * `kind` - New kind for the entity; `none` (or an empty value) clears it
 /// * `db_path` - Optional database path
 ///
 /// # Input Methods
@@ -21,6 +23,9 @@ use std::path::{Path, PathBuf};
 /// 2. File: `--description-file path.txt` - Read description from file
 /// 3. Interactive: No flags - Launch editor with current description
 ///
+/// `--kind` on its own only changes the kind; the description is left alone and no
+/// editor is launched.
+///
 /// # Returns
 /// * `Ok(())` - Description successfully updated or removed
 /// * `Err(ThoughtError)` - Entity not found, file errors, editor errors, etc.
@@ -28,6 +33,7 @@ pub fn execute(
     entity_name: &str,
     description: Option<String>,
     description_file: Option<PathBuf>,
+    kind: Option<String>,
     db_path: &Path,
 ) -> Result<(), ThoughtError> {
     // T031: Check mutual exclusivity of input methods (both flags cannot be used together)
@@ -60,6 +66,18 @@ pub fn execute(
     // own canonical-only lookup must use this, not the raw (possibly alias) argument.
     let resolved_name = entity.name.clone();
 
+    if let Some(raw_kind) = kind {
+        let new_kind = parse_kind_argument(&raw_kind)?;
+        EntitiesRepository::set_kind(&conn, &resolved_name, new_kind)?;
+        match new_kind {
+            Some(new_kind) => println!("Kind set to '{}' for entity '{}'", new_kind, entity_name),
+            None => println!("Kind removed for entity '{}'", entity_name),
+        }
+        if description.is_none() && description_file.is_none() {
+            return Ok(());
+        }
+    }
+
     // Get description text based on input method
     let description_text = if let Some(inline_desc) = description {
         // T025: Inline description
@@ -116,10 +134,33 @@ pub fn execute(
     Ok(())
 }
 
+/// Parse a `--kind` value: a kind name, or `none`/empty to clear the kind
+fn parse_kind_argument(raw: &str) -> Result<Option<EntityKind>, ThoughtError> {
+    let trimmed = raw.trim();
+    if trimmed.is_empty() || trimmed.eq_ignore_ascii_case("none") {
+        return Ok(None);
+    }
+    trimmed
+        .parse::<EntityKind>()
+        .map(Some)
+        .map_err(ThoughtError::InvalidInput)
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
 
+    #[test]
+    fn test_parse_kind_argument() {
+        assert_eq!(parse_kind_argument("Person").unwrap(), Some(EntityKind::Person));
+        assert_eq!(parse_kind_argument("none").unwrap(), None);
+        assert_eq!(parse_kind_argument("  ").unwrap(), None);
+        assert!(matches!(
+            parse_kind_argument("animal"),
+            Err(ThoughtError::InvalidInput(_))
+        ));
+    }
+
     // T022: Unit tests for file reading logic
     #[test]
     fn test_file_reading_success() {
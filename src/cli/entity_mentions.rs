@@ -0,0 +1,302 @@
+/// Entity mentions command implementation
+use crate::errors::ThoughtError;
+use crate::input::prompt;
+use crate::models::TimestampFormat;
+use crate::models::entity::Entity;
+use crate::services::color_mode::ColorMode;
+use crate::services::entity_parser::{extract_entities, find_plain_mentions, link_plain_mentions};
+use crate::services::entity_styler::EntityStyler;
+use crate::storage::connection::get_connection;
+use crate::storage::entities_repository::EntitiesRepository;
+use crate::storage::entity_aliases_repository::EntityAliasesRepository;
+use crate::storage::migrations::run_migrations;
+use crate::storage::thoughts_repository::ThoughtsRepository;
+use chrono::{DateTime, Utc};
+use rusqlite::Connection;
+use std::path::Path;
+
+/// Where an unlinked mention was found
+#[derive(Debug, Clone, PartialEq)]
+pub enum MentionSource {
+    /// A thought's content
+    Thought { id: i64, created_at: DateTime<Utc> },
+    /// Another entity's description, identified by that entity's canonical name
+    Description { entity_name: String },
+}
+
+/// A stored text that mentions an entity in plain text without linking it
+#[derive(Debug, Clone)]
+pub struct UnlinkedMention {
+    /// The thought or description the text belongs to
+    pub source: MentionSource,
+    /// The text with every plain mention wrapped in reference markup
+    pub linked_text: String,
+}
+
+/// Find thoughts and descriptions that mention `entity` by its canonical name or one
+/// of `aliases` as plain text, but never reference it.
+///
+/// Text that already references the entity (by canonical name or alias) is skipped:
+/// that thought is already reachable via `--on` and `entity show`, and a repeated
+/// plain mention next to a reference is ordinary prose, not a forgotten link. The
+/// entity's own description is skipped too.
+pub fn find_unlinked(
+    conn: &Connection,
+    entity: &Entity,
+    aliases: &[String],
+) -> Result<Vec<UnlinkedMention>, ThoughtError> {
+    let mut terms: Vec<&str> = vec![entity.canonical_name.as_str()];
+    terms.extend(aliases.iter().map(String::as_str));
+    let terms_lower: Vec<String> = terms.iter().map(|t| t.to_lowercase()).collect();
+    let alias_terms = &terms[1..];
+
+    let already_referenced = |text: &str| {
+        extract_entities(text)
+            .iter()
+            .any(|e| terms_lower.contains(&e.to_lowercase()))
+    };
+    let unlinked = |text: &str| !already_referenced(text) && !find_plain_mentions(text, &terms).is_empty();
+
+    let mut mentions = Vec::new();
+
+    for thought in ThoughtsRepository::list_all(conn)? {
+        if unlinked(&thought.content) {
+            mentions.push(UnlinkedMention {
+                source: MentionSource::Thought {
+                    id: thought.id.unwrap(),
+                    created_at: thought.created_at,
+                },
+                linked_text: link_plain_mentions(&thought.content, &entity.canonical_name, alias_terms),
+            });
+        }
+    }
+
+    for other in EntitiesRepository::list_all(conn)? {
+        if other.id == entity.id {
+            continue;
+        }
+        if let Some(desc) = other.description
+            && unlinked(&desc)
+        {
+            mentions.push(UnlinkedMention {
+                source: MentionSource::Description {
+                    entity_name: other.canonical_name,
+                },
+                linked_text: link_plain_mentions(&desc, &entity.canonical_name, alias_terms),
+            });
+        }
+    }
+
+    Ok(mentions)
+}
+
+/// Write the linked text of each mention back and link the affected thoughts to the
+/// entity, atomically.
+///
+/// # Returns
+/// * `Ok((thoughts, descriptions))` - How many of each were rewritten
+pub fn apply_links(
+    conn: &mut Connection,
+    entity_id: i64,
+    mentions: &[UnlinkedMention],
+) -> Result<(usize, usize), ThoughtError> {
+    let tx = conn.transaction()?;
+    let mut thoughts = 0;
+    let mut descriptions = 0;
+
+    for mention in mentions {
+        match &mention.source {
+            MentionSource::Thought { id, created_at } => {
+                ThoughtsRepository::update(&tx, *id, &mention.linked_text, *created_at)?;
+                EntitiesRepository::link_to_thought(&tx, entity_id, *id)?;
+                thoughts += 1;
+            }
+            MentionSource::Description { entity_name } => {
+                EntitiesRepository::update_description(&tx, entity_name, Some(mention.linked_text.clone()))?;
+                descriptions += 1;
+            }
+        }
+    }
+
+    tx.commit()?;
+    Ok((thoughts, descriptions))
+}
+
+/// Render one mention as a single listing line, previewing the linked text so the
+/// would-be references are highlighted.
+fn render_mention(styler: &mut EntityStyler, mention: &UnlinkedMention, timestamps: TimestampFormat) -> String {
+    let content = styler.render_content(mention.linked_text.trim());
+    match &mention.source {
+        MentionSource::Thought { id, created_at } => {
+            format!("[{}] {} - {}", id, timestamps.format(*created_at), content)
+        }
+        MentionSource::Description { entity_name } => {
+            format!("{} (description) - {}", entity_name, content.replace('\n', " "))
+        }
+    }
+}
+
+/// Execute the entity mentions command
+///
+/// Lists thoughts and descriptions that mention an entity (or one of its aliases) as
+/// plain text without bracket markup. With `link`, wraps those mentions as `[name]`
+/// or `[alias](name)` and links the thoughts to the entity, asking per text unless
+/// `yes` is set.
+///
+/// # Arguments
+/// * `entity_name` - Entity name or alias (case-insensitive)
+/// * `link` - Rewrite the mentions into references
+/// * `yes` - With `link`, link every mention without asking
+/// * `timestamps` - How thought dates are shown
+/// * `db_path` - Database path
+/// * `color_mode` - Whether to apply ANSI styling to entity references
+///
+/// # Returns
+/// * `Ok(())` - Success
+/// * `Err(ThoughtError::EntityNotFound)` - No entity with the given name exists
+pub fn execute(
+    entity_name: &str,
+    link: bool,
+    yes: bool,
+    timestamps: TimestampFormat,
+    db_path: &Path,
+    color_mode: ColorMode,
+) -> Result<(), ThoughtError> {
+    let mut conn = get_connection(db_path)?;
+    run_migrations(&conn)?;
+
+    let Some(entity) = EntitiesRepository::resolve(&conn, entity_name)? else {
+        eprintln!("Error: Entity '{}' not found", entity_name);
+        eprintln!();
+        eprintln!("Hint: Create the entity first by referencing it in a thought:");
+        eprintln!("  wet add \"Learning about [{}] today\"", entity_name);
+        return Err(ThoughtError::EntityNotFound(entity_name.to_string()));
+    };
+    let entity_id = entity.id.unwrap();
+    let aliases = EntityAliasesRepository::list_for_entity(&conn, entity_id)?;
+
+    let mentions = find_unlinked(&conn, &entity, &aliases)?;
+    if mentions.is_empty() {
+        println!("No unlinked mentions of '{}' found.", entity.canonical_name);
+        return Ok(());
+    }
+
+    let mut styler =
+        EntityStyler::new(color_mode.should_use_colors()).with_kinds(EntitiesRepository::kinds_by_name(&conn)?);
+
+    if !link {
+        for mention in &mentions {
+            println!("{}", render_mention(&mut styler, mention, timestamps));
+        }
+        println!();
+        println!(
+            "Run `wet entity mentions \"{}\" --link` to link them.",
+            entity.canonical_name
+        );
+        return Ok(());
+    }
+
+    let mut accepted = Vec::new();
+    let mut accept_rest = yes;
+    for mention in &mentions {
+        if accept_rest {
+            accepted.push(mention.clone());
+            continue;
+        }
+        println!("{}", render_mention(&mut styler, mention, timestamps));
+        match prompt::ask("Link? [y]es / [N]o / [a]ll / [q]uit: ")?.as_str() {
+            "y" | "yes" => accepted.push(mention.clone()),
+            "a" | "all" => {
+                accepted.push(mention.clone());
+                accept_rest = true;
+            }
+            "q" | "quit" => break,
+            _ => {}
+        }
+    }
+
+    let (thoughts, descriptions) = apply_links(&mut conn, entity_id, &accepted)?;
+    println!(
+        "Linked '{}' in {} thought(s) and {} description(s).",
+        entity.canonical_name, thoughts, descriptions
+    );
+
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::models::thought::Thought;
+    use crate::storage::connection::get_memory_connection;
+
+    fn setup() -> Connection {
+        let conn = get_memory_connection().unwrap();
+        run_migrations(&conn).unwrap();
+        conn
+    }
+
+    fn entity(conn: &Connection, name: &str) -> Entity {
+        EntitiesRepository::find_or_create(conn, &Entity::new(name.to_string())).unwrap();
+        EntitiesRepository::find_by_name(conn, name).unwrap().unwrap()
+    }
+
+    fn save(conn: &Connection, content: &str) -> i64 {
+        ThoughtsRepository::save(conn, &Thought::new(content.to_string()).unwrap()).unwrap()
+    }
+
+    #[test]
+    fn test_find_unlinked_skips_text_already_referencing_entity() {
+        let conn = setup();
+        let sarah = entity(&conn, "Sarah");
+        let plain = save(&conn, "Coffee with sarah");
+        save(&conn, "Met [Sarah]; Sarah was late");
+        save(&conn, "Called [S](sarah)");
+        save(&conn, "Nothing relevant");
+
+        let mentions = find_unlinked(&conn, &sarah, &[]).unwrap();
+
+        assert_eq!(mentions.len(), 1);
+        assert!(matches!(mentions[0].source, MentionSource::Thought { id, .. } if id == plain));
+        assert_eq!(mentions[0].linked_text, "Coffee with [sarah]");
+    }
+
+    #[test]
+    fn test_find_unlinked_covers_aliases_and_other_descriptions() {
+        let conn = setup();
+        let ml = entity(&conn, "machine-learning");
+        let ai = entity(&conn, "ai");
+        EntitiesRepository::update_description(&conn, "ai", Some("Broader than ML.".to_string())).unwrap();
+        EntitiesRepository::update_description(&conn, "machine-learning", Some("Also called ML".to_string())).unwrap();
+
+        let mentions = find_unlinked(&conn, &ml, &["ML".to_string()]).unwrap();
+
+        assert_eq!(mentions.len(), 1);
+        assert_eq!(
+            mentions[0].source,
+            MentionSource::Description {
+                entity_name: ai.canonical_name
+            }
+        );
+        assert_eq!(mentions[0].linked_text, "Broader than [ML](machine-learning).");
+    }
+
+    #[test]
+    fn test_apply_links_rewrites_and_links_thoughts() {
+        let mut conn = setup();
+        let rust = entity(&conn, "rust");
+        let id = save(&conn, "Learning Rust");
+
+        let mentions = find_unlinked(&conn, &rust, &[]).unwrap();
+        let counts = apply_links(&mut conn, rust.id.unwrap(), &mentions).unwrap();
+
+        assert_eq!(counts, (1, 0));
+        assert_eq!(
+            ThoughtsRepository::get_by_id(&conn, id).unwrap().content,
+            "Learning [Rust]"
+        );
+        let linked = ThoughtsRepository::list_by_entity(&conn, "rust").unwrap();
+        assert_eq!(linked.len(), 1);
+        assert!(find_unlinked(&conn, &rust, &[]).unwrap().is_empty());
+    }
+}
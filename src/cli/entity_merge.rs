@@ -4,6 +4,7 @@ use crate::services::entity_parser::redirect_entity_references;
 use crate::storage::connection::get_connection;
 use crate::storage::entities_repository::EntitiesRepository;
 use crate::storage::entity_aliases_repository::EntityAliasesRepository;
+use crate::storage::entity_attributes_repository::EntityAttributesRepository;
 use crate::storage::entity_relations_repository::EntityRelationsRepository;
 use crate::storage::migrations::run_migrations;
 use crate::storage::thoughts_repository::ThoughtsRepository;
@@ -36,9 +37,9 @@ pub struct MergeSummary {
 /// redirected at the surviving one - `thought_entities` links are re-pointed, and
 /// literal references in thought content and entity descriptions are rewritten to
 /// keep their original wording while targeting the survivor (`[Alice]` becomes
-/// `[Alice](Bob)`). The merged entity's description, known aliases and parent/child
-/// relations transfer to the survivor before its row is deleted. The whole operation
-/// is atomic.
+/// `[Alice](Bob)`). The merged entity's description, known aliases, attributes (the
+/// survivor's value wins when both have a key) and relations transfer to the survivor
+/// before its row is deleted. The whole operation is atomic.
 ///
 /// The merged-away name is deliberately *not* registered as an alias of the survivor;
 /// run `wet entity alias <survivor> --alias <old name>` afterwards to have future
@@ -151,6 +152,7 @@ pub fn merge(conn: &mut Connection, entity_name: &str, into: &str) -> Result<Mer
 
     merge_description(&tx, &source.name, &target.name)?;
     transfer_aliases(&tx, &source, &target)?;
+    EntityAttributesRepository::copy_missing(&tx, source_id, target_id)?;
     let relations_dropped = transfer_relations(&tx, source_id, target_id)?;
 
     let links_moved = EntitiesRepository::repoint_thought_links(&tx, source_id, target_id)?;
@@ -212,10 +214,10 @@ fn transfer_aliases(
     Ok(())
 }
 
-/// Re-attach the source's parent and child edges to the target.
+/// Re-attach the source's parent and child edges, and its typed relations, to the target.
 ///
-/// Edges whose other end *is* the target would become self-relations, and edges that
-/// would close a cycle once collapsed onto the target are dropped rather than erroring:
+/// Edges whose other end *is* the target would become self-relations, and hierarchy edges
+/// that would close a cycle once collapsed onto the target are dropped rather than erroring:
 /// a merge shouldn't fail because of a graph shape the user never asked for. Returns how
 /// many edges were dropped, so the caller can report the loss rather than making it
 /// silent as well as irreversible.
@@ -240,5 +242,19 @@ fn transfer_relations(tx: &Transaction, source_id: i64, target_id: i64) -> Resul
         EntityRelationsRepository::add_relation(tx, child_id, target_id)?;
     }
 
+    for relation in EntityRelationsRepository::list_typed_relations(tx, source_id)? {
+        let other_id = relation.other.id.unwrap();
+        if other_id == target_id {
+            dropped += 1;
+            continue;
+        }
+        let (from, to) = if relation.outgoing {
+            (target_id, other_id)
+        } else {
+            (other_id, target_id)
+        };
+        EntityRelationsRepository::add_typed_relation(tx, from, &relation.relation_type, to)?;
+    }
+
     Ok(dropped)
 }
@@ -1,5 +1,7 @@
 /// Entity relate/unrelate command implementations
 use crate::errors::ThoughtError;
+use crate::models::relation_type::RelationType;
+use crate::services::relation_types;
 use crate::storage::connection::get_connection;
 use crate::storage::entities_repository::EntitiesRepository;
 use crate::storage::entity_relations_repository::EntityRelationsRepository;
@@ -80,6 +82,110 @@ pub fn execute_unrelate(entity_name: &str, parent_name: &str, db_path: &Path) ->
     Ok(())
 }
 
+/// Resolve both ends of a typed relation, rejecting an entity related to itself
+fn resolve_pair(
+    conn: &Connection,
+    entity_name: &str,
+    target_name: &str,
+) -> Result<(crate::models::entity::Entity, crate::models::entity::Entity), ThoughtError> {
+    let source = resolve_entity(conn, entity_name)?;
+    let target = resolve_entity(conn, target_name)?;
+    if source.id == target.id {
+        return Err(ThoughtError::InvalidInput(format!(
+            "An entity cannot be related to itself: '{}'",
+            entity_name
+        )));
+    }
+    Ok((source, target))
+}
+
+/// Execute the entity relate command with an explicit relation type
+///
+/// Records `entity_name <relation_type> target_name`, e.g. `Alice works-at Acme`.
+/// `child-of` is the built-in hierarchy and behaves exactly like `--parent`
+/// (cycle-checked, followed when filtering); every other type is a plain edge with no
+/// transitive meaning. Undirected types are stored once per pair, whichever way round
+/// they were first given. Idempotent: a relation already stored is reported as such.
+///
+/// # Arguments
+/// * `entity_name` - Source entity (case-insensitive, may be an alias)
+/// * `relation_type` - Relation type name, built-in or declared in the config
+/// * `target_name` - Target entity (case-insensitive, may be an alias)
+/// * `configured` - Relation types declared in the config file
+/// * `db_path` - Database path
+pub fn execute_relate_as(
+    entity_name: &str,
+    relation_type: &str,
+    target_name: &str,
+    configured: &[RelationType],
+    db_path: &Path,
+) -> Result<(), ThoughtError> {
+    let relation = relation_types::find(relation_type, configured)?;
+    if relation.is_hierarchy() {
+        return execute_relate(entity_name, target_name, db_path);
+    }
+
+    let conn = get_connection(db_path)?;
+    run_migrations(&conn)?;
+
+    let (source, target) = resolve_pair(&conn, entity_name, target_name)?;
+    let (source_id, target_id) = (source.id.unwrap(), target.id.unwrap());
+
+    let stored_reversed = !relation.directed
+        && EntityRelationsRepository::has_typed_relation(&conn, target_id, &relation.name, source_id)?;
+    let added =
+        !stored_reversed && EntityRelationsRepository::add_typed_relation(&conn, source_id, &relation.name, target_id)?;
+
+    let status = if added { "Recorded" } else { "Already exists" };
+    println!(
+        "{}: {} {} {}.",
+        status, source.canonical_name, relation.name, target.canonical_name
+    );
+
+    Ok(())
+}
+
+/// Execute the entity unrelate command with an explicit relation type
+///
+/// Removes `entity_name <relation_type> target_name`; for undirected types, in
+/// whichever direction it was stored. `child-of` behaves like `--parent`.
+/// Idempotent: succeeds even if no such relation existed.
+///
+/// # Arguments
+/// * `entity_name` - Source entity (case-insensitive, may be an alias)
+/// * `relation_type` - Relation type name, built-in or declared in the config
+/// * `target_name` - Target entity (case-insensitive, may be an alias)
+/// * `configured` - Relation types declared in the config file
+/// * `db_path` - Database path
+pub fn execute_unrelate_as(
+    entity_name: &str,
+    relation_type: &str,
+    target_name: &str,
+    configured: &[RelationType],
+    db_path: &Path,
+) -> Result<(), ThoughtError> {
+    let relation = relation_types::describe(relation_type, configured);
+    if relation.is_hierarchy() {
+        return execute_unrelate(entity_name, target_name, db_path);
+    }
+
+    let conn = get_connection(db_path)?;
+    run_migrations(&conn)?;
+
+    let (source, target) = resolve_pair(&conn, entity_name, target_name)?;
+    let (source_id, target_id) = (source.id.unwrap(), target.id.unwrap());
+
+    let name = relation.name.to_lowercase();
+    EntityRelationsRepository::remove_typed_relation(&conn, source_id, &name, target_id)?;
+    if !relation.directed {
+        EntityRelationsRepository::remove_typed_relation(&conn, target_id, &name, source_id)?;
+    }
+
+    println!("Removed: {} {} {}.", source.canonical_name, name, target.canonical_name);
+
+    Ok(())
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -226,4 +332,95 @@ mod tests {
         let result = execute_unrelate("AWS", "Amazon", &db_path);
         assert!(matches!(result, Err(ThoughtError::EntityNotFound(name)) if name == "AWS"));
     }
+
+    #[test]
+    fn test_execute_relate_as_records_typed_relation() {
+        let (_temp_dir, db_path) = temp_db_with_entities(&["Alice", "Acme"]);
+
+        execute_relate_as("alice", "Works-At", "acme", &[], &db_path).unwrap();
+
+        let conn = get_connection(&db_path).unwrap();
+        let alice = EntitiesRepository::find_by_name(&conn, "alice").unwrap().unwrap();
+        let relations = EntityRelationsRepository::list_typed_relations(&conn, alice.id.unwrap()).unwrap();
+        assert_eq!(relations.len(), 1);
+        assert_eq!(relations[0].relation_type, "works-at");
+        assert!(relations[0].outgoing);
+        assert!(
+            EntityRelationsRepository::list_parents(&conn, alice.id.unwrap())
+                .unwrap()
+                .is_empty()
+        );
+    }
+
+    #[test]
+    fn test_execute_relate_as_child_of_uses_hierarchy() {
+        let (_temp_dir, db_path) = temp_db_with_entities(&["Amazon", "AWS"]);
+
+        execute_relate_as("AWS", "child-of", "Amazon", &[], &db_path).unwrap();
+        let result = execute_relate_as("Amazon", "child-of", "AWS", &[], &db_path);
+        assert!(matches!(result, Err(ThoughtError::RelationCycle { .. })));
+
+        let conn = get_connection(&db_path).unwrap();
+        let aws = EntitiesRepository::find_by_name(&conn, "aws").unwrap().unwrap();
+        assert_eq!(
+            EntityRelationsRepository::list_parents(&conn, aws.id.unwrap())
+                .unwrap()
+                .len(),
+            1
+        );
+        assert!(
+            EntityRelationsRepository::list_typed_relations(&conn, aws.id.unwrap())
+                .unwrap()
+                .is_empty()
+        );
+    }
+
+    #[test]
+    fn test_execute_relate_as_unknown_type_errors() {
+        let (_temp_dir, db_path) = temp_db_with_entities(&["Alice", "Bob"]);
+
+        let result = execute_relate_as("Alice", "mentors", "Bob", &[], &db_path);
+        assert!(matches!(result, Err(ThoughtError::InvalidInput(_))));
+
+        let configured = [RelationType {
+            name: "mentors".to_string(),
+            inverse: Some("mentored-by".to_string()),
+            directed: true,
+        }];
+        assert!(execute_relate_as("Alice", "mentors", "Bob", &configured, &db_path).is_ok());
+    }
+
+    #[test]
+    fn test_execute_relate_as_undirected_stored_once() {
+        let (_temp_dir, db_path) = temp_db_with_entities(&["Alice", "Bob"]);
+
+        execute_relate_as("Alice", "related-to", "Bob", &[], &db_path).unwrap();
+        execute_relate_as("Bob", "related-to", "Alice", &[], &db_path).unwrap();
+
+        let conn = get_connection(&db_path).unwrap();
+        let bob = EntitiesRepository::find_by_name(&conn, "bob").unwrap().unwrap();
+        assert_eq!(
+            EntityRelationsRepository::list_typed_relations(&conn, bob.id.unwrap())
+                .unwrap()
+                .len(),
+            1
+        );
+        drop(conn);
+
+        execute_unrelate_as("Bob", "related-to", "Alice", &[], &db_path).unwrap();
+        let conn = get_connection(&db_path).unwrap();
+        assert!(
+            EntityRelationsRepository::list_typed_relations(&conn, bob.id.unwrap())
+                .unwrap()
+                .is_empty()
+        );
+    }
+
+    #[test]
+    fn test_execute_relate_as_self_relation_rejected() {
+        let (_temp_dir, db_path) = temp_db_with_entities(&["Alice"]);
+
+        let result = execute_relate_as("Alice", "related-to", "alice", &[], &db_path);
+        assert!(matches!(result, Err(ThoughtError::InvalidInput(_))));
+    }
 }
@@ -0,0 +1,124 @@
+/// Entity related command implementation
+use crate::errors::ThoughtError;
+use crate::models::Timezone;
+use crate::models::entity::Entity;
+use crate::services::color_mode::ColorMode;
+use crate::services::date_expression::parse_day;
+use crate::services::entity_styler::EntityStyler;
+use crate::services::related_entities::{self, RelatedEntity, RelatednessMetric};
+use crate::storage::connection::get_connection;
+use crate::storage::entities_repository::EntitiesRepository;
+use crate::storage::migrations::run_migrations;
+use chrono::NaiveDate;
+use rusqlite::Connection;
+use std::collections::HashMap;
+use std::path::Path;
+
+/// Number of related entities listed when `--limit` isn't given
+pub const DEFAULT_LIMIT: usize = 10;
+
+/// The entities most related to `entity_id`, best first, with their scores.
+///
+/// Only thoughts written within `since`/`until` (inclusive days in `timezone`) count, for
+/// both the shared thoughts and the totals the score is normalised by.
+pub fn find_related(
+    conn: &Connection,
+    entity_id: i64,
+    metric: RelatednessMetric,
+    since: Option<NaiveDate>,
+    until: Option<NaiveDate>,
+    timezone: Timezone,
+    limit: usize,
+) -> Result<Vec<(Entity, RelatedEntity)>, ThoughtError> {
+    let links = EntitiesRepository::thought_links(conn, since, until, timezone)?;
+    let mut entities: HashMap<i64, Entity> = EntitiesRepository::list_all(conn)?
+        .into_iter()
+        .filter_map(|e| e.id.map(|id| (id, e)))
+        .collect();
+
+    Ok(related_entities::rank(entity_id, &links, metric)
+        .into_iter()
+        .filter_map(|related| entities.remove(&related.entity_id).map(|e| (e, related)))
+        .take(limit)
+        .collect())
+}
+
+/// How related entities are ranked, over which thoughts, and how many are listed
+#[derive(Debug)]
+pub struct RelatedOptions {
+    /// Jaccard (default) or PMI
+    pub metric: RelatednessMetric,
+    /// Raw inclusive `--since` bound on the thoughts counted
+    pub since: Option<String>,
+    /// Raw inclusive `--until` bound on the thoughts counted
+    pub until: Option<String>,
+    /// How many entities to list
+    pub limit: usize,
+}
+
+/// "1 shared thought" / "3 shared thoughts"
+fn shared_label(count: usize) -> String {
+    format!("{} shared thought{}", count, if count == 1 { "" } else { "s" })
+}
+
+/// Execute the entity related command
+///
+/// Lists the entities most often mentioned in the same thoughts as `entity_name`,
+/// ranked by `metric`, with the number of thoughts they share and the score.
+///
+/// # Arguments
+/// * `entity_name` - Entity to find relatives of (case-insensitive, may be an alias)
+/// * `options` - Metric, date bounds on the thoughts counted, and how many to list
+/// * `timezone` - Zone the date bounds are days in
+/// * `db_path` - Database path
+/// * `color_mode` - Whether to color entity names
+///
+/// # Returns
+/// * `Ok(())` - Success, including when nothing shares a thought with the entity
+/// * `Err(ThoughtError)` - Entity not found, an invalid date, or a storage error
+pub fn execute(
+    entity_name: &str,
+    options: RelatedOptions,
+    timezone: Timezone,
+    db_path: &Path,
+    color_mode: ColorMode,
+) -> Result<(), ThoughtError> {
+    let today = timezone.today();
+    let since = options
+        .since
+        .as_deref()
+        .map(|d| parse_day("--since", d, today))
+        .transpose()?;
+    let until = options
+        .until
+        .as_deref()
+        .map(|d| parse_day("--until", d, today))
+        .transpose()?;
+    let metric = options.metric;
+
+    let conn = get_connection(db_path)?;
+    run_migrations(&conn)?;
+
+    let entity = EntitiesRepository::resolve(&conn, entity_name)?
+        .ok_or_else(|| ThoughtError::EntityNotFound(entity_name.to_string()))?;
+
+    let related = find_related(&conn, entity.id.unwrap(), metric, since, until, timezone, options.limit)?;
+    if related.is_empty() {
+        println!("No entities share a thought with '{}'.", entity.canonical_name);
+        return Ok(());
+    }
+
+    let mut styler =
+        EntityStyler::new(color_mode.should_use_colors()).with_kinds(EntitiesRepository::kinds_by_name(&conn)?);
+    println!("Related to {} ({}):", entity.canonical_name, metric.as_str());
+    for (other, ranked) in related {
+        println!(
+            "  {} ({}, score {:.2})",
+            styler.render_entity(&other.canonical_name),
+            shared_label(ranked.shared),
+            ranked.score
+        );
+    }
+
+    Ok(())
+}
@@ -1,42 +1,108 @@
 /// Entity show command implementation
+use crate::cli::entity_related::find_related;
 use crate::errors::ThoughtError;
+use crate::models::TimestampFormat;
+use crate::models::relation_type::{RelationType, TypedRelation};
 use crate::services::color_mode::ColorMode;
+use crate::services::date_expression::parse_day;
 use crate::services::entity_styler::EntityStyler;
+use crate::services::related_entities::RelatednessMetric;
+use crate::services::relation_types;
 use crate::storage::connection::get_connection;
 use crate::storage::entities_repository::EntitiesRepository;
 use crate::storage::entity_aliases_repository::EntityAliasesRepository;
+use crate::storage::entity_attributes_repository::EntityAttributesRepository;
 use crate::storage::entity_relations_repository::EntityRelationsRepository;
 use crate::storage::migrations::run_migrations;
 use crate::storage::thoughts_repository::ThoughtsRepository;
+use std::collections::HashMap;
 use std::path::Path;
 
-/// Number of most recent thoughts to display for the entity
-const LATEST_THOUGHTS_LIMIT: usize = 5;
+/// Number of most recent thoughts to display for the entity unless `--limit` or `--all` is given
+pub const DEFAULT_LIMIT: usize = 5;
+
+/// Number of related entities to display for the entity
+const RELATED_LIMIT: usize = 5;
+
+/// Which of the entity's thoughts `wet entity show` lists, and how many
+#[derive(Debug)]
+pub struct ShowOptions {
+    /// How many of the latest thoughts to list; `None` lists them all
+    pub limit: Option<usize>,
+    /// Raw `--since` value, a date expression
+    pub since: Option<String>,
+    /// Raw `--until` value, a date expression
+    pub until: Option<String>,
+    /// Also list thoughts linked to the entity's descendants
+    pub include_descendants: bool,
+}
+
+impl Default for ShowOptions {
+    fn default() -> Self {
+        Self {
+            limit: Some(DEFAULT_LIMIT),
+            since: None,
+            until: None,
+            include_descendants: true,
+        }
+    }
+}
 
 /// Execute the entity show command
 ///
 /// Displays an entity's full description (styled consistently with thought content,
-/// with entity references colored and aliases rendered as their display text) followed
-/// by the 5 most recent thoughts linked to the entity.
+/// with entity references colored and aliases rendered as their display text), its
+/// aliases, attributes, parents, children, typed relations and the 5 entities most often
+/// mentioned alongside it (with their shared thought counts), followed by the most recent
+/// thoughts linked to the entity or its descendants. A thought reached only through a
+/// descendant is marked with `(via Name)`, and when the limit hides some thoughts a
+/// closing line says how many matched.
 ///
 /// # Arguments
 /// * `entity_name` - Name of the entity to show (case-insensitive)
+/// * `options` - Limit, date range and whether to include descendants
+/// * `timestamps` - Zone the date range is in, and how thought dates are shown
 /// * `db_path` - Database path
 /// * `color_mode` - Whether to apply ANSI styling to entity references
+/// * `configured_types` - Relation types declared in the config, for labelling edges
 ///
 /// # Returns
 /// * `Ok(())` - Success
 /// * `Err(ThoughtError::EntityNotFound)` - No entity with the given name exists
-pub fn execute(entity_name: &str, db_path: &Path, color_mode: ColorMode) -> Result<(), ThoughtError> {
+/// * `Err(ThoughtError::InvalidInput)` - `--since` or `--until` is not a valid date
+pub fn execute(
+    entity_name: &str,
+    options: ShowOptions,
+    timestamps: TimestampFormat,
+    db_path: &Path,
+    color_mode: ColorMode,
+    configured_types: &[RelationType],
+) -> Result<(), ThoughtError> {
+    let today = timestamps.timezone.today();
+    let since = options
+        .since
+        .as_deref()
+        .map(|d| parse_day("--since", d, today))
+        .transpose()?;
+    let until = options
+        .until
+        .as_deref()
+        .map(|d| parse_day("--until", d, today))
+        .transpose()?;
+
     let conn = get_connection(db_path)?;
     run_migrations(&conn)?;
 
     let entity = EntitiesRepository::resolve(&conn, entity_name)?
         .ok_or_else(|| ThoughtError::EntityNotFound(entity_name.to_string()))?;
 
-    let mut styler = EntityStyler::new(color_mode.should_use_colors());
+    let mut styler =
+        EntityStyler::new(color_mode.should_use_colors()).with_kinds(EntitiesRepository::kinds_by_name(&conn)?);
 
-    println!("{}", entity.canonical_name);
+    match entity.kind {
+        Some(kind) => println!("{} ({})", entity.canonical_name, kind),
+        None => println!("{}", entity.canonical_name),
+    }
 
     if let Some(description) = &entity.description {
         println!();
@@ -49,6 +115,15 @@ pub fn execute(entity_name: &str, db_path: &Path, color_mode: ColorMode) -> Resu
         println!("Aliases: {}", aliases.join(", "));
     }
 
+    let attributes = EntityAttributesRepository::list_for_entity(&conn, entity.id.unwrap())?;
+    if !attributes.is_empty() {
+        println!();
+        println!("Attributes:");
+        for (key, value) in &attributes {
+            println!("  {}: {}", key, value);
+        }
+    }
+
     let parents = EntityRelationsRepository::list_parents(&conn, entity.id.unwrap())?;
     let children = EntityRelationsRepository::list_children(&conn, entity.id.unwrap())?;
 
@@ -63,21 +138,80 @@ pub fn execute(entity_name: &str, db_path: &Path, color_mode: ColorMode) -> Resu
         println!("Children: {}", names.join(", "));
     }
 
+    let relation_lines = relation_lines(
+        &EntityRelationsRepository::list_typed_relations(&conn, entity.id.unwrap())?,
+        configured_types,
+    );
+    if !relation_lines.is_empty() {
+        println!();
+        println!("Relations:");
+        for line in relation_lines {
+            println!("  {}", line);
+        }
+    }
+
+    let related = find_related(
+        &conn,
+        entity.id.unwrap(),
+        RelatednessMetric::default(),
+        None,
+        None,
+        timestamps.timezone,
+        RELATED_LIMIT,
+    )?;
+    if !related.is_empty() {
+        let names: Vec<String> = related
+            .iter()
+            .map(|(other, ranked)| format!("{} ({})", other.canonical_name, ranked.shared))
+            .collect();
+        println!();
+        println!("Related: {}", names.join(", "));
+    }
+
     println!();
-    println!("Latest thoughts:");
+    println!(
+        "{}",
+        if options.limit.is_some() {
+            "Latest thoughts:"
+        } else {
+            "Thoughts:"
+        }
+    );
 
-    let thoughts = ThoughtsRepository::list_latest_by_entity(&conn, entity_name, LATEST_THOUGHTS_LIMIT)?;
+    let thoughts = ThoughtsRepository::list_by_entity_with_sources(
+        &conn,
+        entity.id.unwrap(),
+        options.include_descendants,
+        since,
+        until,
+        timestamps.timezone,
+    )?;
 
     if thoughts.is_empty() {
         println!("No thoughts found for entity: {}", entity.canonical_name);
     } else {
-        for thought in thoughts {
+        let names: HashMap<i64, String> = EntitiesRepository::list_all(&conn)?
+            .into_iter()
+            .filter_map(|e| Some((e.id?, e.canonical_name)))
+            .collect();
+        let shown = options.limit.unwrap_or(thoughts.len()).min(thoughts.len());
+        for (thought, sources) in &thoughts[..shown] {
             let styled_content = styler.render_content(thought.content.trim());
+            let via = via_label(entity.id.unwrap(), sources, &names);
             println!(
-                "[{}] {} - {}",
+                "[{}] {} - {}{}",
                 thought.id.unwrap_or(0),
-                thought.created_at.format("%Y-%m-%d"),
-                styled_content
+                timestamps.format(thought.created_at),
+                styled_content,
+                via
+            );
+        }
+        if shown < thoughts.len() {
+            println!();
+            println!(
+                "Showing {} of {} thoughts (use --limit N or --all for more)",
+                shown,
+                thoughts.len()
             );
         }
     }
@@ -85,6 +219,39 @@ pub fn execute(entity_name: &str, db_path: &Path, color_mode: ColorMode) -> Resu
     Ok(())
 }
 
+/// ` (via AWS, S3)` when a thought reached the entity only through descendants, empty when
+/// it is linked to the entity itself
+fn via_label(entity_id: i64, sources: &[i64], names: &HashMap<i64, String>) -> String {
+    if sources.contains(&entity_id) {
+        return String::new();
+    }
+    let via: Vec<&str> = sources
+        .iter()
+        .filter_map(|id| names.get(id).map(String::as_str))
+        .collect();
+    format!(" (via {})", via.join(", "))
+}
+
+/// Group typed relations into `label: A, B` lines, labelling incoming edges with the
+/// type's inverse. Keeps the listing order (by type, outgoing first); a name shows once
+/// per label even if an undirected relation was stored in both directions.
+fn relation_lines(relations: &[TypedRelation], configured_types: &[RelationType]) -> Vec<String> {
+    let mut groups: Vec<(String, Vec<&str>)> = Vec::new();
+    for relation in relations {
+        let label = relation_types::describe(&relation.relation_type, configured_types).label(relation.outgoing);
+        let name = relation.other.canonical_name.as_str();
+        match groups.iter_mut().find(|(existing, _)| *existing == label) {
+            Some((_, names)) if names.contains(&name) => {}
+            Some((_, names)) => names.push(name),
+            None => groups.push((label, vec![name])),
+        }
+    }
+    groups
+        .into_iter()
+        .map(|(label, names)| format!("{}: {}", label, names.join(", ")))
+        .collect()
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -141,7 +308,14 @@ mod tests {
         EntityRelationsRepository::add_relation(&conn, amazon.id.unwrap(), big_tech.id.unwrap()).unwrap();
         drop(conn);
 
-        let result = execute("amazon", &db_path, ColorMode::Never);
+        let result = execute(
+            "amazon",
+            ShowOptions::default(),
+            TimestampFormat::default(),
+            &db_path,
+            ColorMode::Never,
+            &[],
+        );
         assert!(result.is_ok());
     }
 
@@ -157,7 +331,14 @@ mod tests {
         setup_entity(&conn, "rust", None);
         drop(conn);
 
-        let result = execute("rust", &db_path, ColorMode::Never);
+        let result = execute(
+            "rust",
+            ShowOptions::default(),
+            TimestampFormat::default(),
+            &db_path,
+            ColorMode::Never,
+            &[],
+        );
         assert!(result.is_ok());
     }
 
@@ -175,11 +356,25 @@ mod tests {
         EntityAliasesRepository::add_alias(&conn, entity.id.unwrap(), "rustlang").unwrap();
         drop(conn);
 
-        let result = execute("rust", &db_path, ColorMode::Never);
+        let result = execute(
+            "rust",
+            ShowOptions::default(),
+            TimestampFormat::default(),
+            &db_path,
+            ColorMode::Never,
+            &[],
+        );
         assert!(result.is_ok());
 
         // Also resolvable by the alias itself.
-        let result = execute("rustlang", &db_path, ColorMode::Never);
+        let result = execute(
+            "rustlang",
+            ShowOptions::default(),
+            TimestampFormat::default(),
+            &db_path,
+            ColorMode::Never,
+            &[],
+        );
         assert!(result.is_ok());
     }
 
@@ -193,4 +388,34 @@ mod tests {
         let entity = EntitiesRepository::find_by_name(&conn, "rust").unwrap().unwrap();
         assert_eq!(entity.description.as_deref(), Some("A systems programming language."));
     }
+
+    #[test]
+    fn test_relation_lines_label_by_direction() {
+        let relation = |relation_type: &str, outgoing: bool, name: &str| TypedRelation {
+            relation_type: relation_type.to_string(),
+            outgoing,
+            other: Entity::new(name.to_string()),
+        };
+        let relations = [
+            relation("located-in", true, "Berlin"),
+            relation("related-to", true, "Initech"),
+            relation("related-to", false, "Initech"),
+            relation("works-at", false, "Alice"),
+            relation("works-at", false, "Bob"),
+        ];
+
+        assert_eq!(
+            relation_lines(&relations, &[]),
+            vec!["located-in: Berlin", "related-to: Initech", "employs: Alice, Bob"]
+        );
+    }
+
+    #[test]
+    fn test_via_label_names_descendants_only_when_not_linked_directly() {
+        let names = HashMap::from([(1, "Amazon".to_string()), (2, "AWS".to_string()), (3, "S3".to_string())]);
+
+        assert_eq!(via_label(1, &[1, 2], &names), "");
+        assert_eq!(via_label(1, &[2], &names), " (via AWS)");
+        assert_eq!(via_label(1, &[2, 3], &names), " (via AWS, S3)");
+    }
 }
@@ -0,0 +1,604 @@
+/// Entity split command implementation
+use crate::errors::ThoughtError;
+use crate::input::prompt;
+use crate::models::entity::Entity;
+use crate::models::thought::Thought;
+use crate::models::{TimestampFormat, Timezone};
+use crate::services::color_mode::ColorMode;
+use crate::services::date_expression::parse_day;
+use crate::services::entity_parser::redirect_entity_references;
+use crate::services::entity_styler::EntityStyler;
+use crate::storage::connection::get_connection;
+use crate::storage::entities_repository::EntitiesRepository;
+use crate::storage::entity_aliases_repository::EntityAliasesRepository;
+use crate::storage::entity_relations_repository::EntityRelationsRepository;
+use crate::storage::migrations::run_migrations;
+use crate::storage::thoughts_repository::ThoughtsRepository;
+use chrono::NaiveDate;
+use rusqlite::{Connection, Transaction};
+use std::path::Path;
+
+/// Non-interactive criteria narrowing which of the source's thoughts move.
+///
+/// All set criteria must hold for a thought to be selected; the date bounds are
+/// inclusive calendar days in `timezone`.
+#[derive(Debug, Default)]
+pub struct SplitSelection {
+    /// Earliest day a selected thought may have been written on
+    pub since: Option<NaiveDate>,
+    /// Latest day a selected thought may have been written on
+    pub until: Option<NaiveDate>,
+    /// Entity (name or alias) a selected thought must also be linked to
+    pub with: Option<String>,
+    /// Zone deciding which day a thought was written on
+    pub timezone: Timezone,
+}
+
+/// What a split changed, for reporting back to the user.
+#[derive(Debug, PartialEq, Eq)]
+pub struct SplitSummary {
+    /// Canonical name of the entity the thoughts were taken from
+    pub source: String,
+    /// Canonical name of the entity the thoughts now belong to
+    pub target: String,
+    /// Whether the target entity was created by the split
+    pub target_created: bool,
+    /// Thoughts re-linked from the source to the target
+    pub thoughts_moved: usize,
+    /// Source aliases moved over to the target
+    pub aliases_moved: usize,
+    /// Source parent/child and typed edges newly added to the target
+    pub relations_copied: usize,
+}
+
+/// Resolve a name that must refer to an existing entity
+fn resolve_existing(conn: &Connection, name: &str) -> Result<Entity, ThoughtError> {
+    EntitiesRepository::resolve(conn, name)?.ok_or_else(|| ThoughtError::EntityNotFound(name.to_string()))
+}
+
+/// The thoughts linked directly to `source` that satisfy `selection`, oldest first.
+///
+/// Only direct links are candidates: a thought that reaches `source` through one of its
+/// child entities is about the child, and splitting the parent doesn't move it.
+pub fn select_thoughts(
+    conn: &Connection,
+    source: &Entity,
+    selection: &SplitSelection,
+) -> Result<Vec<Thought>, ThoughtError> {
+    let with_id = match &selection.with {
+        Some(name) => Some(resolve_existing(conn, name)?.id.unwrap()),
+        None => None,
+    };
+
+    let mut selected = Vec::new();
+    for thought in ThoughtsRepository::list_directly_linked(conn, source.id.unwrap())? {
+        let day = selection.timezone.date_of(thought.created_at);
+        if selection.since.is_some_and(|since| day < since) || selection.until.is_some_and(|until| day > until) {
+            continue;
+        }
+        if let Some(with_id) = with_id {
+            let linked = EntitiesRepository::list_for_thought(conn, thought.id.unwrap())?;
+            if !linked.iter().any(|e| e.id == Some(with_id)) {
+                continue;
+            }
+        }
+        selected.push(thought);
+    }
+
+    Ok(selected)
+}
+
+/// Move the thoughts in `thought_ids` from `entity_name` to `into` within a single
+/// transaction.
+///
+/// The storage-level half of [`execute`], separated so it can be driven directly
+/// against a connection without CLI output or prompts. `into` is resolved alias-aware
+/// and created when nothing matches. Each moved thought has its references to the
+/// source - by canonical name or by any of its aliases - redirected at the target with
+/// their original wording kept (`[Alex]` becomes `[Alex](Alex Kim)`), and its link
+/// switched over. IDs not directly linked to the source are ignored.
+pub fn split(
+    conn: &mut Connection,
+    entity_name: &str,
+    into: &str,
+    thought_ids: &[i64],
+    move_aliases: bool,
+    copy_relations: bool,
+) -> Result<SplitSummary, ThoughtError> {
+    let source = resolve_existing(conn, entity_name)?;
+    let source_id = source.id.unwrap();
+
+    let existing_target = EntitiesRepository::resolve(conn, into)?;
+    if existing_target.is_none() {
+        if into.trim().is_empty() {
+            return Err(ThoughtError::InvalidInput(
+                "New entity name cannot be empty".to_string(),
+            ));
+        }
+        if into.contains(['[', ']', '(', ')']) {
+            return Err(ThoughtError::InvalidInput(
+                "New entity name cannot contain '[', ']', '(', or ')' (these are reserved for entity reference \
+                 syntax)"
+                    .to_string(),
+            ));
+        }
+    }
+
+    if let Some(target) = &existing_target {
+        if target.id == source.id {
+            return Err(ThoughtError::InvalidInput(format!(
+                "Cannot split '{}' into itself; --into must name a different entity",
+                source.canonical_name
+            )));
+        }
+        // Same constraint as `entity merge`: the target's name ends up inside `(...)`.
+        if target.canonical_name.contains(['(', ')']) {
+            return Err(ThoughtError::InvalidInput(format!(
+                "Cannot split into '{}': entity names containing '(' or ')' cannot be used as reference \
+                 targets. Rename it first: wet entity rename \"{}\" \"<new name>\"",
+                target.canonical_name, target.canonical_name
+            )));
+        }
+    }
+
+    let tx = conn.transaction()?;
+
+    let target_created = existing_target.is_none();
+    let target = match existing_target {
+        Some(target) => target,
+        None => {
+            let id = EntitiesRepository::find_or_create(&tx, &Entity::new(into.trim().to_string()))?;
+            let mut created = Entity::new(into.trim().to_string());
+            created.id = Some(id);
+            created
+        }
+    };
+    let target_id = target.id.unwrap();
+
+    let aliases = EntityAliasesRepository::list_for_entity(&tx, source_id)?;
+    let source_names: Vec<&str> = std::iter::once(source.name.as_str())
+        .chain(aliases.iter().map(String::as_str))
+        .collect();
+
+    let mut thoughts_moved = 0;
+    for thought in ThoughtsRepository::list_directly_linked(&tx, source_id)? {
+        let thought_id = thought.id.unwrap();
+        if !thought_ids.contains(&thought_id) {
+            continue;
+        }
+
+        let redirected = source_names.iter().fold(thought.content.clone(), |text, name| {
+            redirect_entity_references(&text, name, &target.canonical_name)
+        });
+        if redirected != thought.content {
+            ThoughtsRepository::update(&tx, thought_id, &redirected, thought.created_at)?;
+        }
+
+        EntitiesRepository::unlink_from_thought(&tx, source_id, thought_id)?;
+        EntitiesRepository::link_to_thought(&tx, target_id, thought_id)?;
+        thoughts_moved += 1;
+    }
+
+    let aliases_moved = if move_aliases {
+        move_source_aliases(&tx, &source, &aliases, &target)?
+    } else {
+        0
+    };
+    let relations_copied = if copy_relations {
+        copy_source_relations(&tx, source_id, target_id)?
+    } else {
+        0
+    };
+
+    tx.commit()?;
+
+    Ok(SplitSummary {
+        source: source.canonical_name,
+        target: target.canonical_name,
+        target_created,
+        thoughts_moved,
+        aliases_moved,
+        relations_copied,
+    })
+}
+
+/// Move the source's aliases to the target, skipping one equal to the target's own
+/// name. Returns how many were moved.
+///
+/// An alias left on both entities would be ambiguous for each, so it changes hands.
+/// The thoughts staying with the source have their references to a moved alias pinned
+/// to the source (`[AJ]` becomes `[AJ](Alex)`), so they keep meaning it.
+fn move_source_aliases(
+    tx: &Transaction,
+    source: &Entity,
+    aliases: &[String],
+    target: &Entity,
+) -> Result<usize, ThoughtError> {
+    let source_id = source.id.unwrap();
+    let moved: Vec<&String> = aliases
+        .iter()
+        .filter(|alias| alias.to_lowercase() != target.name)
+        .collect();
+    for alias in &moved {
+        EntityAliasesRepository::remove_alias(tx, source_id, alias)?;
+        EntityAliasesRepository::add_alias(tx, target.id.unwrap(), alias)?;
+    }
+
+    for thought in ThoughtsRepository::list_directly_linked(tx, source_id)? {
+        let pinned = moved.iter().fold(thought.content.clone(), |text, alias| {
+            redirect_entity_references(&text, alias, &source.canonical_name)
+        });
+        if pinned != thought.content {
+            ThoughtsRepository::update(tx, thought.id.unwrap(), &pinned, thought.created_at)?;
+        }
+    }
+    Ok(moved.len())
+}
+
+/// Give the target the source's parents, children and typed relations as well.
+///
+/// Unlike a merge the source keeps its edges. Edges that would relate the target to
+/// itself or close a hierarchy cycle are skipped; returns how many edges were added,
+/// not counting those the target already had.
+fn copy_source_relations(tx: &Transaction, source_id: i64, target_id: i64) -> Result<usize, ThoughtError> {
+    let mut copied = 0;
+
+    for parent in EntityRelationsRepository::list_parents(tx, source_id)? {
+        let parent_id = parent.id.unwrap();
+        if parent_id == target_id || EntityRelationsRepository::would_create_cycle(tx, target_id, parent_id)? {
+            continue;
+        }
+        if EntityRelationsRepository::add_relation(tx, target_id, parent_id)? {
+            copied += 1;
+        }
+    }
+
+    for child in EntityRelationsRepository::list_children(tx, source_id)? {
+        let child_id = child.id.unwrap();
+        if child_id == target_id || EntityRelationsRepository::would_create_cycle(tx, child_id, target_id)? {
+            continue;
+        }
+        if EntityRelationsRepository::add_relation(tx, child_id, target_id)? {
+            copied += 1;
+        }
+    }
+
+    for relation in EntityRelationsRepository::list_typed_relations(tx, source_id)? {
+        let other_id = relation.other.id.unwrap();
+        if other_id == target_id {
+            continue;
+        }
+        let (from, to) = if relation.outgoing {
+            (target_id, other_id)
+        } else {
+            (other_id, target_id)
+        };
+        if EntityRelationsRepository::add_typed_relation(tx, from, &relation.relation_type, to)? {
+            copied += 1;
+        }
+    }
+
+    Ok(copied)
+}
+
+/// Which thoughts a split selects, and what else it copies to the target entity
+#[derive(Debug, Default)]
+pub struct SplitOptions {
+    /// Raw `--since` value, a date expression
+    pub since: Option<String>,
+    /// Raw `--until` value, a date expression
+    pub until: Option<String>,
+    /// Raw `--with` value (entity name or alias)
+    pub with: Option<String>,
+    /// Ask about each candidate thought
+    pub interactive: bool,
+    /// Move the source's aliases over to the target
+    pub move_aliases: bool,
+    /// Give the target the source's parents and children as well
+    pub copy_relations: bool,
+}
+
+/// Execute the entity split command
+///
+/// The inverse of `entity merge`: moves a subset of the thoughts linked to
+/// `entity_name` over to `into`, creating it if needed. The subset is chosen by date
+/// range, by a co-mentioned entity, interactively, or by any combination (the filters
+/// narrow the candidates the interactive prompt then walks through). At least one
+/// selector is required so a bare `split` can't silently move everything.
+///
+/// # Arguments
+/// * `entity_name` - Entity to split (case-insensitive, may be an alias)
+/// * `into` - Entity receiving the thoughts (existing, or a new name)
+/// * `options` - Thought selection and what to copy across
+/// * `timestamps` - Zone the date bounds are days in, and how listed dates are shown
+/// * `db_path` - Database path
+/// * `color_mode` - Whether to apply ANSI styling when listing thoughts
+///
+/// # Returns
+/// * `Ok(())` - Split done, or nothing matched, or the prompt was quit
+/// * `Err(ThoughtError)` - No selector given, an entity not found, an invalid date or
+///   target name, or a storage error
+pub fn execute(
+    entity_name: &str,
+    into: &str,
+    options: SplitOptions,
+    timestamps: TimestampFormat,
+    db_path: &Path,
+    color_mode: ColorMode,
+) -> Result<(), ThoughtError> {
+    if options.since.is_none() && options.until.is_none() && options.with.is_none() && !options.interactive {
+        return Err(ThoughtError::InvalidInput(
+            "Choose which thoughts to move with --since/--until, --with or --interactive".to_string(),
+        ));
+    }
+
+    let today = timestamps.timezone.today();
+    let selection = SplitSelection {
+        since: options
+            .since
+            .as_deref()
+            .map(|d| parse_day("--since", d, today))
+            .transpose()?,
+        until: options
+            .until
+            .as_deref()
+            .map(|d| parse_day("--until", d, today))
+            .transpose()?,
+        with: options.with,
+        timezone: timestamps.timezone,
+    };
+
+    let mut conn = get_connection(db_path)?;
+    run_migrations(&conn)?;
+
+    let Some(source) = EntitiesRepository::resolve(&conn, entity_name)? else {
+        eprintln!("Error: Entity '{}' not found", entity_name);
+        eprintln!();
+        eprintln!("Hint: Create the entity first by referencing it in a thought:");
+        eprintln!("  wet add \"Learning about [{}] today\"", entity_name);
+        return Err(ThoughtError::EntityNotFound(entity_name.to_string()));
+    };
+
+    let candidates = select_thoughts(&conn, &source, &selection)?;
+    if candidates.is_empty() {
+        println!("No thoughts of '{}' match; nothing was moved.", source.canonical_name);
+        return Ok(());
+    }
+
+    let mut chosen = Vec::new();
+    if options.interactive {
+        let mut styler =
+            EntityStyler::new(color_mode.should_use_colors()).with_kinds(EntitiesRepository::kinds_by_name(&conn)?);
+        for thought in &candidates {
+            println!(
+                "[{}] {} - {}",
+                thought.id.unwrap(),
+                timestamps.format(thought.created_at),
+                styler.render_content(thought.content.trim())
+            );
+            match prompt::ask("Move? [y]es / [N]o / [q]uit: ")?.as_str() {
+                "y" | "yes" => chosen.push(thought.id.unwrap()),
+                "q" | "quit" => break,
+                _ => {}
+            }
+        }
+        if chosen.is_empty() {
+            println!("No thoughts chosen; nothing was moved.");
+            return Ok(());
+        }
+    } else {
+        chosen = candidates.iter().map(|t| t.id.unwrap()).collect();
+    }
+
+    let summary = split(
+        &mut conn,
+        &source.name,
+        into,
+        &chosen,
+        options.move_aliases,
+        options.copy_relations,
+    )?;
+
+    println!(
+        "Moved {} thought(s) from '{}' to {} '{}'.",
+        summary.thoughts_moved,
+        summary.source,
+        if summary.target_created { "new entity" } else { "entity" },
+        summary.target
+    );
+    if options.move_aliases {
+        println!("Moved {} alias(es).", summary.aliases_moved);
+    }
+    if options.copy_relations {
+        println!("Copied {} relation(s).", summary.relations_copied);
+    }
+
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::storage::connection::get_memory_connection;
+    use chrono::TimeZone;
+
+    fn add_thought(conn: &Connection, content: &str, day: u32, entities: &[&str]) -> i64 {
+        let created_at = chrono::Utc.with_ymd_and_hms(2026, 3, day, 12, 0, 0).unwrap();
+        let id =
+            ThoughtsRepository::save(conn, &Thought::new_with_date(content.to_string(), created_at).unwrap()).unwrap();
+        for name in entities {
+            let entity_id = EntitiesRepository::find_or_create(conn, &Entity::new(name.to_string())).unwrap();
+            EntitiesRepository::link_to_thought(conn, entity_id, id).unwrap();
+        }
+        id
+    }
+
+    fn setup() -> Connection {
+        let conn = get_memory_connection().unwrap();
+        run_migrations(&conn).unwrap();
+        conn
+    }
+
+    #[test]
+    fn test_select_thoughts_by_date_range_and_co_mention() {
+        let conn = setup();
+        let first = add_thought(&conn, "[Alex] at [Acme]", 1, &["Alex", "Acme"]);
+        let second = add_thought(&conn, "[Alex] at home", 10, &["Alex"]);
+        let third = add_thought(&conn, "[Alex] at [Acme] again", 20, &["Alex", "Acme"]);
+        let source = EntitiesRepository::resolve(&conn, "alex").unwrap().unwrap();
+
+        let by_date = SplitSelection {
+            since: NaiveDate::from_ymd_opt(2026, 3, 10),
+            until: NaiveDate::from_ymd_opt(2026, 3, 20),
+            with: None,
+            timezone: Timezone::Named(chrono_tz::Tz::UTC),
+        };
+        let ids: Vec<_> = select_thoughts(&conn, &source, &by_date)
+            .unwrap()
+            .iter()
+            .map(|t| t.id.unwrap())
+            .collect();
+        assert_eq!(ids, vec![second, third]);
+
+        let by_co_mention = SplitSelection {
+            with: Some("ACME".to_string()),
+            ..Default::default()
+        };
+        let ids: Vec<_> = select_thoughts(&conn, &source, &by_co_mention)
+            .unwrap()
+            .iter()
+            .map(|t| t.id.unwrap())
+            .collect();
+        assert_eq!(ids, vec![first, third]);
+    }
+
+    #[test]
+    fn test_select_thoughts_unknown_co_mention_errors() {
+        let conn = setup();
+        add_thought(&conn, "[Alex]", 1, &["Alex"]);
+        let source = EntitiesRepository::resolve(&conn, "alex").unwrap().unwrap();
+        let selection = SplitSelection {
+            with: Some("Nobody".to_string()),
+            ..Default::default()
+        };
+
+        assert!(matches!(
+            select_thoughts(&conn, &source, &selection),
+            Err(ThoughtError::EntityNotFound(_))
+        ));
+    }
+
+    #[test]
+    fn test_split_moves_links_and_keeps_wording() {
+        let mut conn = setup();
+        let moved = add_thought(&conn, "Coffee with [Alex] and [Al](alex)", 1, &["Alex"]);
+        let kept = add_thought(&conn, "[Alex] again", 2, &["Alex"]);
+
+        let summary = split(&mut conn, "alex", "Alex Kim", &[moved], false, false).unwrap();
+
+        assert_eq!(summary.thoughts_moved, 1);
+        assert!(summary.target_created);
+        let content = ThoughtsRepository::get_by_id(&conn, moved).unwrap().content;
+        assert_eq!(content, "Coffee with [Alex](Alex Kim) and [Al](Alex Kim)");
+        let linked = EntitiesRepository::list_for_thought(&conn, moved).unwrap();
+        assert_eq!(linked.len(), 1);
+        assert_eq!(linked[0].canonical_name, "Alex Kim");
+        let untouched = ThoughtsRepository::get_by_id(&conn, kept).unwrap().content;
+        assert_eq!(untouched, "[Alex] again");
+    }
+
+    #[test]
+    fn test_split_redirects_alias_references() {
+        let mut conn = setup();
+        let id = add_thought(&conn, "Met [AJ] today", 1, &["Alex"]);
+        let alex = EntitiesRepository::resolve(&conn, "alex").unwrap().unwrap();
+        EntityAliasesRepository::add_alias(&conn, alex.id.unwrap(), "AJ").unwrap();
+
+        split(&mut conn, "alex", "Alex Jones", &[id], false, false).unwrap();
+
+        let content = ThoughtsRepository::get_by_id(&conn, id).unwrap().content;
+        assert_eq!(content, "Met [AJ](Alex Jones) today");
+    }
+
+    #[test]
+    fn test_split_moves_aliases_and_copies_relations() {
+        let mut conn = setup();
+        let id = add_thought(&conn, "[Alex]", 1, &["Alex", "People", "Intern", "Acme"]);
+        let staying = add_thought(&conn, "Lunch with [AJ]", 2, &["Alex"]);
+        let alex = EntitiesRepository::resolve(&conn, "alex").unwrap().unwrap().id.unwrap();
+        let people = EntitiesRepository::resolve(&conn, "people")
+            .unwrap()
+            .unwrap()
+            .id
+            .unwrap();
+        let intern = EntitiesRepository::resolve(&conn, "intern")
+            .unwrap()
+            .unwrap()
+            .id
+            .unwrap();
+        let acme = EntitiesRepository::resolve(&conn, "acme").unwrap().unwrap().id.unwrap();
+        let target_id = EntitiesRepository::find_or_create(&conn, &Entity::new("Alex Jones".to_string())).unwrap();
+        EntityAliasesRepository::add_alias(&conn, alex, "AJ").unwrap();
+        EntityRelationsRepository::add_relation(&conn, alex, people).unwrap();
+        EntityRelationsRepository::add_relation(&conn, intern, alex).unwrap();
+        EntityRelationsRepository::add_typed_relation(&conn, alex, "works-at", acme).unwrap();
+        // The target already has this one
+        EntityRelationsRepository::add_typed_relation(&conn, target_id, "works-at", acme).unwrap();
+
+        let summary = split(&mut conn, "alex", "Alex Jones", &[id], true, true).unwrap();
+
+        assert_eq!(summary.aliases_moved, 1);
+        assert_eq!(summary.relations_copied, 2);
+        assert_eq!(
+            EntityAliasesRepository::list_for_entity(&conn, target_id).unwrap(),
+            vec!["AJ".to_string()]
+        );
+        assert!(
+            EntityAliasesRepository::list_for_entity(&conn, alex)
+                .unwrap()
+                .is_empty()
+        );
+        // The thought left with the source keeps meaning it
+        let content = ThoughtsRepository::get_by_id(&conn, staying).unwrap().content;
+        assert_eq!(content, "Lunch with [AJ](Alex)");
+        assert_eq!(
+            EntityRelationsRepository::list_parents(&conn, target_id).unwrap().len(),
+            1
+        );
+        assert_eq!(
+            EntityRelationsRepository::list_children(&conn, target_id)
+                .unwrap()
+                .len(),
+            1
+        );
+        // The source keeps its own edges.
+        assert_eq!(EntityRelationsRepository::list_parents(&conn, alex).unwrap().len(), 1);
+    }
+
+    #[test]
+    fn test_split_into_existing_entity_and_into_itself() {
+        let mut conn = setup();
+        let id = add_thought(&conn, "[Alex] and [Sam]", 1, &["Alex", "Sam"]);
+
+        let summary = split(&mut conn, "alex", "sam", &[id], false, false).unwrap();
+        assert!(!summary.target_created);
+        assert_eq!(summary.target, "Sam");
+
+        let err = split(&mut conn, "sam", "SAM", &[id], false, false).unwrap_err();
+        assert!(matches!(err, ThoughtError::InvalidInput(_)));
+    }
+
+    #[test]
+    fn test_split_rejects_reference_syntax_in_new_name() {
+        let mut conn = setup();
+        let id = add_thought(&conn, "[Alex]", 1, &["Alex"]);
+
+        let err = split(&mut conn, "alex", "Alex (work)", &[id], false, false).unwrap_err();
+        assert!(matches!(err, ThoughtError::InvalidInput(_)));
+        assert!(
+            EntitiesRepository::find_by_name(&conn, "alex (work)")
+                .unwrap()
+                .is_none()
+        );
+    }
+}
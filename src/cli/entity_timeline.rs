@@ -0,0 +1,111 @@
+/// Entity timeline command implementation
+use crate::errors::ThoughtError;
+use crate::models::TimestampFormat;
+use crate::services::activity_stats::{self, Period};
+use crate::services::color_mode::ColorMode;
+use crate::services::entity_styler::EntityStyler;
+use crate::storage::connection::get_connection;
+use crate::storage::entities_repository::EntitiesRepository;
+use crate::storage::migrations::run_migrations;
+use crate::storage::thoughts_repository::ThoughtsRepository;
+use chrono::NaiveDate;
+use std::path::Path;
+
+/// "1 thought" / "3 thoughts"
+fn thoughts_label(count: usize) -> String {
+    format!("{} thought{}", count, if count == 1 { "" } else { "s" })
+}
+
+/// Execute the entity timeline command
+///
+/// Prints every thought about the entity and its descendants, oldest first, grouped
+/// by month or week. A summary line gives the total and the first and last mention,
+/// and a sparkline shows the activity of every period between them, empty ones
+/// included; the listing skips empty periods.
+///
+/// # Arguments
+/// * `entity_name` - Entity name (case-insensitive, may be an alias)
+/// * `period` - Group by month or week
+/// * `db_path` - Database path
+/// * `color_mode` - Whether to apply ANSI styling to entity references
+///
+/// # Returns
+/// * `Ok(())` - Success, including when no thoughts mention the entity
+/// * `Err(ThoughtError::EntityNotFound)` - No entity with the given name exists
+///
+/// # Output Format
+/// ```text
+/// Apollo
+/// 3 thoughts, first 2026-01-10, last 2026-03-02
+///
+/// 2026-01 █ ▄ 2026-03
+///
+/// 2026-01 (2 thoughts)
+///   [1] 2026-01-10 - Alice and Bob planned Apollo
+///   [2] 2026-01-20 - Apollo kickoff
+///
+/// 2026-03 (1 thought)
+///   [4] 2026-03-02 - Apollo launched
+/// ```
+pub fn execute(
+    entity_name: &str,
+    period: Period,
+    timestamps: TimestampFormat,
+    db_path: &Path,
+    color_mode: ColorMode,
+) -> Result<(), ThoughtError> {
+    let timezone = timestamps.timezone;
+    let conn = get_connection(db_path)?;
+    run_migrations(&conn)?;
+
+    let entity = EntitiesRepository::resolve(&conn, entity_name)?
+        .ok_or_else(|| ThoughtError::EntityNotFound(entity_name.to_string()))?;
+
+    match entity.kind {
+        Some(kind) => println!("{} ({})", entity.canonical_name, kind),
+        None => println!("{}", entity.canonical_name),
+    }
+
+    let thoughts = ThoughtsRepository::list_by_entity(&conn, &entity.canonical_name)?;
+    let (Some(first), Some(last)) = (thoughts.first(), thoughts.last()) else {
+        println!("No thoughts found for entity: {}", entity.canonical_name);
+        return Ok(());
+    };
+    println!(
+        "{}, first {}, last {}",
+        thoughts_label(thoughts.len()),
+        timezone.date_of(first.created_at),
+        timezone.date_of(last.created_at)
+    );
+
+    let days: Vec<NaiveDate> = thoughts.iter().map(|t| timezone.date_of(t.created_at)).collect();
+    let counts = activity_stats::period_counts_spanning(&days, period);
+    let heights: Vec<usize> = counts.iter().map(|(_, count)| *count).collect();
+    println!();
+    println!(
+        "{} {} {}",
+        period.label(counts[0].0),
+        activity_stats::sparkline(&heights),
+        period.label(counts[counts.len() - 1].0)
+    );
+
+    let mut styler =
+        EntityStyler::new(color_mode.should_use_colors()).with_kinds(EntitiesRepository::kinds_by_name(&conn)?);
+    for (start, count) in counts.into_iter().filter(|(_, count)| *count > 0) {
+        println!();
+        println!("{} ({})", period.label(start), thoughts_label(count));
+        for thought in thoughts
+            .iter()
+            .filter(|t| period.start_of(timezone.date_of(t.created_at)) == start)
+        {
+            println!(
+                "  [{}] {} - {}",
+                thought.id.unwrap_or(0),
+                timestamps.format(thought.created_at),
+                styler.render_content(thought.content.trim())
+            );
+        }
+    }
+
+    Ok(())
+}
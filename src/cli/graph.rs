@@ -0,0 +1,105 @@
+/// Graph command implementation
+use crate::errors::ThoughtError;
+use crate::models::relation_type::RelationType;
+use crate::services::graph_export::{self, GraphFormat, GraphOptions, GraphSource};
+use crate::services::relation_types;
+use crate::storage::connection::get_connection;
+use crate::storage::entities_repository::EntitiesRepository;
+use crate::storage::entity_aliases_repository::EntityAliasesRepository;
+use crate::storage::entity_relations_repository::EntityRelationsRepository;
+use crate::storage::migrations::run_migrations;
+use rusqlite::Connection;
+use std::path::Path;
+
+/// How far from `--root` to go when `--depth` isn't given: the root and its direct neighbours
+pub const DEFAULT_DEPTH: usize = 1;
+
+/// What a graph export contains and in which format it is written
+#[derive(Debug)]
+pub struct ExportOptions {
+    /// Output format
+    pub format: GraphFormat,
+    /// Raw `--root` value (entity name or alias)
+    pub root: Option<String>,
+    /// `--depth`; [`DEFAULT_DEPTH`] if not given
+    pub depth: Option<usize>,
+    /// Add a node per alias
+    pub aliases: bool,
+    /// Add co-occurrence edges
+    pub co_occurrence: bool,
+    /// Smallest number of shared thoughts for a co-occurrence edge
+    pub min_weight: usize,
+}
+
+impl Default for ExportOptions {
+    fn default() -> Self {
+        Self {
+            format: GraphFormat::default(),
+            root: None,
+            depth: None,
+            aliases: false,
+            co_occurrence: true,
+            min_weight: 1,
+        }
+    }
+}
+
+/// Read everything the graph is built from
+pub fn load_source(conn: &Connection, configured_types: &[RelationType]) -> Result<GraphSource, ThoughtError> {
+    let relations = EntityRelationsRepository::list_all_typed_edges(conn)?
+        .into_iter()
+        .map(|(source, name, target)| (source, relation_types::describe(&name, configured_types), target))
+        .collect();
+
+    Ok(GraphSource {
+        entities: EntitiesRepository::list_all(conn)?,
+        hierarchy: EntityRelationsRepository::list_all_edges(conn)?,
+        relations,
+        aliases: EntityAliasesRepository::list_all(conn)?,
+        co_occurrences: EntitiesRepository::co_occurrence_counts(conn)?,
+    })
+}
+
+/// Execute the graph command
+///
+/// Writes the entity network to stdout: entities as nodes, hierarchy and typed
+/// relation edges, co-occurrence edges weighted by how many thoughts mention both
+/// entities, and optionally a node per alias. With `--root`, only the entities within
+/// `--depth` edges of the root are exported.
+///
+/// # Arguments
+/// * `options` - Format and what to include
+/// * `configured_types` - Relation types declared in the config, for edge direction
+/// * `db_path` - Database path
+///
+/// # Returns
+/// * `Ok(())` - Success
+/// * `Err(ThoughtError::EntityNotFound)` - The root entity doesn't exist
+pub fn execute(options: ExportOptions, configured_types: &[RelationType], db_path: &Path) -> Result<(), ThoughtError> {
+    let conn = get_connection(db_path)?;
+    run_migrations(&conn)?;
+
+    let root = match &options.root {
+        Some(name) => Some(
+            EntitiesRepository::resolve(&conn, name)?
+                .and_then(|entity| entity.id)
+                .ok_or_else(|| ThoughtError::EntityNotFound(name.clone()))?,
+        ),
+        None => None,
+    };
+
+    let source = load_source(&conn, configured_types)?;
+    let graph = graph_export::build(
+        &source,
+        &GraphOptions {
+            root,
+            depth: options.depth.unwrap_or(DEFAULT_DEPTH),
+            aliases: options.aliases,
+            co_occurrence: options.co_occurrence,
+            min_weight: options.min_weight,
+        },
+    );
+
+    print!("{}", graph_export::render(&graph, options.format));
+    Ok(())
+}
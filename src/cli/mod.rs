@@ -1,20 +1,41 @@
 /// CLI module for command-line interface
 pub mod add;
+pub mod completions;
 pub mod config;
+pub mod db_merge;
 pub mod delete;
 pub mod edit;
 pub mod entities;
 pub mod entity_alias;
+pub mod entity_attribute;
+pub mod entity_delete;
+pub mod entity_duplicates;
 pub mod entity_edit;
+pub mod entity_mentions;
 pub mod entity_merge;
 pub mod entity_relate;
+pub mod entity_related;
 pub mod entity_rename;
 pub mod entity_show;
+pub mod entity_split;
+pub mod entity_timeline;
+pub mod graph;
+pub mod notebook;
+pub mod stats;
+pub mod thought_show;
 pub mod thoughts;
 pub mod tui;
 
+use crate::cli::db_merge::DescriptionPolicy;
+use crate::cli::entity_delete::ReferencePolicy;
+use crate::services::activity_stats::Period;
 use crate::services::color_mode::ColorMode;
+use crate::services::graph_export::GraphFormat;
+use crate::services::related_entities::RelatednessMetric;
+use crate::services::thought_batch::BatchMode;
 use clap::{Parser, Subcommand};
+use clap_complete::engine::ArgValueCompleter;
+use completions::{CompletionShell, config_keys, entity_names, notebook_names, thought_ids};
 
 #[derive(Parser)]
 #[command(name = "wet")]
@@ -24,6 +45,10 @@ pub struct Cli {
     #[arg(long, value_enum, default_value_t = ColorMode::Auto, global = true)]
     pub color: ColorMode,
 
+    /// Notebook to use instead of the default one (see `wet notebook list`)
+    #[arg(long, global = true, add = ArgValueCompleter::new(notebook_names))]
+    pub notebook: Option<String>,
+
     #[command(subcommand)]
     pub command: Commands,
 }
@@ -32,59 +57,211 @@ pub struct Cli {
 pub enum Commands {
     /// Add a new thought
     Add {
-        /// Thought content
-        content: String,
-        /// Date for the thought in YYYY-MM-DD format (defaults to today)
-        #[arg(long)]
+        /// Thought content; `-` (or nothing, when piped) reads it from stdin
+        content: Option<String>,
+        /// Date for the thought: YYYY-MM-DD, today, yesterday, -3d, 2 weeks ago, mon, last friday (defaults to today)
+        #[arg(long, allow_hyphen_values = true)]
         date: Option<String>,
+        /// Add one thought per line, or per blank-line-separated block with --batch=blocks;
+        /// a leading `YYYY-MM-DD:` dates that thought
+        #[arg(long, value_enum, value_name = "MODE", num_args = 0..=1, require_equals = true, default_missing_value = "lines")]
+        batch: Option<BatchMode>,
+        /// Write the thought in $EDITOR, starting from a template of recent entities
+        #[arg(long, conflicts_with_all = ["content", "batch"])]
+        editor: bool,
+        /// Type the thought at a prompt that completes entity names after `[`
+        #[arg(long, short = 'i', conflicts_with_all = ["content", "batch", "editor"])]
+        interactive: bool,
     },
     /// List all thoughts
     Thoughts {
         /// Filter thoughts by entity name
-        #[arg(long)]
+        #[arg(long, add = ArgValueCompleter::new(entity_names))]
         on: Option<String>,
+        /// Filter with a query, e.g. "sarah AND (alpha OR beta) AND NOT draft AND after:2025-01-01"
+        #[arg(long = "where", value_name = "QUERY", conflicts_with = "on")]
+        where_query: Option<String>,
     },
     /// Edit an existing thought
     Edit {
         /// ID of the thought to edit (visible in `wet` listing output as [id])
+        #[arg(add = ArgValueCompleter::new(thought_ids))]
         id: i64,
         /// New content for the thought (mutually exclusive with --editor)
         content: Option<String>,
-        /// New date for the thought in YYYY-MM-DD format
-        #[arg(long)]
+        /// New date for the thought: YYYY-MM-DD, today, yesterday, -3d, 2 weeks ago, mon, last friday
+        #[arg(long, allow_hyphen_values = true)]
         date: Option<String>,
         /// Open the thought in an interactive editor (mutually exclusive with CONTENT)
         #[arg(long, conflicts_with = "content")]
         editor: bool,
     },
+    /// Single-thought operations
+    Thought {
+        #[command(subcommand)]
+        command: ThoughtCommands,
+    },
     /// Delete a thought by ID
     Delete {
         /// ID of the thought to delete (visible in `wet` listing output as [id])
+        #[arg(add = ArgValueCompleter::new(thought_ids))]
         id: i64,
     },
-    /// Get or set configuration values
+    /// Get, set, list or edit configuration values
+    #[command(args_conflicts_with_subcommands = true)]
     Config {
+        #[command(subcommand)]
+        command: Option<ConfigCommands>,
         /// Configuration key in section.item format (e.g. thoughts.order)
-        key: String,
+        #[arg(required_unless_present = "list", add = ArgValueCompleter::new(config_keys))]
+        key: Option<String>,
         /// Value to set (omit to read current value)
         value: Option<String>,
+        /// List every setting with its effective value and source (default, file or env)
+        #[arg(long, conflicts_with_all = ["key", "unset"])]
+        list: bool,
+        /// Remove KEY from the config file so its default applies again
+        #[arg(long, requires = "key", conflicts_with = "value")]
+        unset: bool,
     },
     /// Launch interactive TUI thought viewer
     Tui,
+    /// Print a shell completion script (e.g. `source <(wet completions bash)`)
+    Completions {
+        /// Shell to write the script for
+        #[arg(value_enum)]
+        shell: CompletionShell,
+    },
     /// List all entities
-    Entities,
+    Entities {
+        /// Only list entities of this kind (person, organization, project, place, event, concept)
+        #[arg(long)]
+        kind: Option<String>,
+        /// Only list entities with this attribute value (repeatable; all must match)
+        #[arg(long = "attr", value_name = "KEY=VALUE")]
+        attributes: Vec<String>,
+        /// Show the parent/child hierarchy as a tree, with thought counts
+        #[arg(long, conflicts_with_all = ["kind", "attributes"])]
+        tree: bool,
+    },
+    /// Export entities and their connections as a graph (DOT, Mermaid or GraphML)
+    Graph {
+        /// Output format
+        #[arg(long, value_enum, default_value_t = GraphFormat::Dot)]
+        format: GraphFormat,
+        /// Only export entities connected to this one (case-insensitive; may be an alias)
+        #[arg(long, add = ArgValueCompleter::new(entity_names))]
+        root: Option<String>,
+        /// With --root, how many edges away to go (default 1)
+        #[arg(long, requires = "root")]
+        depth: Option<usize>,
+        /// Add a node for each alias, linked to its entity
+        #[arg(long)]
+        aliases: bool,
+        /// Leave out edges between entities mentioned in the same thoughts
+        #[arg(long)]
+        no_co_occurrence: bool,
+        /// Leave out co-occurrence edges shared by fewer thoughts than this
+        #[arg(long, default_value_t = 1, conflicts_with = "no_co_occurrence")]
+        min_weight: usize,
+    },
+    /// Show activity statistics: totals, thoughts over time, streaks and entity trends
+    Stats {
+        /// Count thoughts per week or per month
+        #[arg(long, value_enum, default_value_t = Period::Week)]
+        by: Period,
+        /// How many weeks or months to show, ending with the current one
+        #[arg(long, default_value_t = 12)]
+        periods: usize,
+        /// List entities not mentioned in this many days
+        #[arg(long, default_value_t = 90)]
+        stale_days: i64,
+    },
     /// Entity operations
     Entity {
         #[command(subcommand)]
         command: EntityCommands,
     },
+    /// List, create, rename or delete notebooks
+    Notebook {
+        #[command(subcommand)]
+        command: NotebookCommands,
+    },
+    /// Whole-database operations
+    Db {
+        #[command(subcommand)]
+        command: DbCommands,
+    },
+}
+
+#[derive(Subcommand)]
+pub enum DbCommands {
+    /// Import the thoughts, entities, aliases and relations of another wetware database
+    Merge {
+        /// Database file to import from
+        other: std::path::PathBuf,
+        /// How to resolve an entity description that differs between the databases
+        #[arg(long, value_enum, default_value_t = DescriptionPolicy::Refuse)]
+        descriptions: DescriptionPolicy,
+        /// Show what would be imported without changing anything
+        #[arg(long)]
+        dry_run: bool,
+    },
+}
+
+#[derive(Subcommand)]
+pub enum NotebookCommands {
+    /// List notebooks, marking the default one with `*`
+    List,
+    /// Create an empty notebook
+    Create {
+        /// Notebook name: letters, digits, `-` and `_`
+        name: String,
+    },
+    /// Rename a notebook
+    Rename {
+        /// Current notebook name
+        #[arg(add = ArgValueCompleter::new(notebook_names))]
+        name: String,
+        /// New notebook name
+        new_name: String,
+    },
+    /// Delete a notebook with all its thoughts and entities
+    Delete {
+        /// Notebook to delete
+        #[arg(add = ArgValueCompleter::new(notebook_names))]
+        name: String,
+        /// Delete without asking for confirmation
+        #[arg(long, short = 'y')]
+        yes: bool,
+    },
+}
+
+#[derive(Subcommand)]
+pub enum ConfigCommands {
+    /// Open config.toml in $EDITOR; it is checked on save and reopened if invalid
+    Edit,
+}
+
+#[derive(Subcommand)]
+pub enum ThoughtCommands {
+    /// Show a thought in full, with its linked entities
+    Show {
+        /// ID of the thought to show (visible in `wet` listing output as [id])
+        #[arg(add = ArgValueCompleter::new(thought_ids))]
+        id: i64,
+        /// Print the thought as JSON
+        #[arg(long)]
+        json: bool,
+    },
 }
 
 #[derive(Subcommand)]
 pub enum EntityCommands {
-    /// Edit entity description
+    /// Edit entity description or kind
     Edit {
         /// Entity name (case-insensitive)
+        #[arg(add = ArgValueCompleter::new(entity_names))]
         entity_name: String,
         /// Inline description text (mutually exclusive with --description-file)
         #[arg(long)]
@@ -92,10 +269,14 @@ pub enum EntityCommands {
         /// Path to file containing description (mutually exclusive with --description)
         #[arg(long)]
         description_file: Option<std::path::PathBuf>,
+        /// Set the entity's kind (person, organization, project, place, event, concept), or `none` to clear it
+        #[arg(long)]
+        kind: Option<String>,
     },
     /// Rename an entity, rewriting all literal references to it
     Rename {
         /// Current entity name (case-insensitive)
+        #[arg(add = ArgValueCompleter::new(entity_names))]
         entity_name: String,
         /// New entity name
         new_name: String,
@@ -103,35 +284,150 @@ pub enum EntityCommands {
     /// Merge an entity into another, redirecting all references to it
     Merge {
         /// Entity to merge away (case-insensitive)
+        #[arg(add = ArgValueCompleter::new(entity_names))]
         entity_name: String,
         /// Entity to merge into (case-insensitive)
-        #[arg(long)]
+        #[arg(long, add = ArgValueCompleter::new(entity_names))]
         into: String,
     },
+    /// Move a subset of an entity's thoughts to another (possibly new) entity
+    Split {
+        /// Entity to split (case-insensitive; may be a registered alias)
+        #[arg(add = ArgValueCompleter::new(entity_names))]
+        entity_name: String,
+        /// Entity receiving the thoughts; created if it doesn't exist
+        #[arg(long, add = ArgValueCompleter::new(entity_names))]
+        into: String,
+        /// Move thoughts written on or after this date (YYYY-MM-DD or e.g. yesterday, -2w)
+        #[arg(long, allow_hyphen_values = true)]
+        since: Option<String>,
+        /// Move thoughts written on or before this date (YYYY-MM-DD or e.g. yesterday, -2w)
+        #[arg(long, allow_hyphen_values = true)]
+        until: Option<String>,
+        /// Move thoughts that also reference this entity
+        #[arg(long, add = ArgValueCompleter::new(entity_names))]
+        with: Option<String>,
+        /// Ask about each (matching) thought before moving it
+        #[arg(long, short = 'i')]
+        interactive: bool,
+        /// Move the entity's aliases to the target (an alias can't name both)
+        #[arg(long)]
+        move_aliases: bool,
+        /// Give the target the entity's parents and children too
+        #[arg(long)]
+        copy_relations: bool,
+    },
+    /// Delete an entity with its aliases, relations and thought links
+    Delete {
+        /// Entity to delete (case-insensitive; may be a registered alias)
+        #[arg(add = ArgValueCompleter::new(entity_names))]
+        entity_name: String,
+        /// How to handle references to the entity in thought and description text
+        #[arg(long, value_enum, default_value_t = ReferencePolicy::Refuse)]
+        references: ReferencePolicy,
+        /// Show what would be deleted without changing anything
+        #[arg(long)]
+        dry_run: bool,
+        /// Delete without asking for confirmation
+        #[arg(long, short = 'y')]
+        yes: bool,
+    },
     /// Show an entity's description and latest thoughts
     Show {
         /// Entity name (case-insensitive)
+        #[arg(add = ArgValueCompleter::new(entity_names))]
         entity_name: String,
+        /// How many of the latest thoughts to list
+        #[arg(long, default_value_t = crate::cli::entity_show::DEFAULT_LIMIT, conflicts_with = "all")]
+        limit: usize,
+        /// List every matching thought
+        #[arg(long)]
+        all: bool,
+        /// Only list thoughts written on or after this date (YYYY-MM-DD or e.g. yesterday, -2w)
+        #[arg(long, allow_hyphen_values = true)]
+        since: Option<String>,
+        /// Only list thoughts written on or before this date (YYYY-MM-DD or e.g. yesterday, -2w)
+        #[arg(long, allow_hyphen_values = true)]
+        until: Option<String>,
+        /// Leave out thoughts that only mention the entity's descendants
+        #[arg(long)]
+        no_descendants: bool,
     },
-    /// Mark an entity as a child of another entity
+    /// List every thought about an entity and its descendants, grouped by month or week
+    Timeline {
+        /// Entity name (case-insensitive; may be an alias)
+        #[arg(add = ArgValueCompleter::new(entity_names))]
+        entity_name: String,
+        /// Group thoughts by month or by week
+        #[arg(long, value_enum, default_value_t = Period::Month)]
+        by: Period,
+    },
+    /// Rank the entities most often mentioned in the same thoughts as this one
+    Related {
+        /// Entity name (case-insensitive; may be an alias)
+        #[arg(add = ArgValueCompleter::new(entity_names))]
+        entity_name: String,
+        /// How to score co-occurrence
+        #[arg(long, value_enum, default_value_t = RelatednessMetric::Jaccard)]
+        metric: RelatednessMetric,
+        /// Only count thoughts written on or after this date (YYYY-MM-DD or e.g. yesterday, -2w)
+        #[arg(long, allow_hyphen_values = true)]
+        since: Option<String>,
+        /// Only count thoughts written on or before this date (YYYY-MM-DD or e.g. yesterday, -2w)
+        #[arg(long, allow_hyphen_values = true)]
+        until: Option<String>,
+        /// How many entities to list
+        #[arg(long, default_value_t = crate::cli::entity_related::DEFAULT_LIMIT)]
+        limit: usize,
+    },
+    /// List entities whose names look like duplicates of each other
+    Duplicates,
+    /// Find plain-text mentions of an entity that lack bracket markup
+    Mentions {
+        /// Entity name (case-insensitive; may be a registered alias)
+        #[arg(add = ArgValueCompleter::new(entity_names))]
+        entity_name: String,
+        /// Wrap the mentions in references and link the thoughts to the entity
+        #[arg(long)]
+        link: bool,
+        /// With --link, link every mention without asking
+        #[arg(long, short = 'y', requires = "link")]
+        yes: bool,
+    },
+    /// Relate two entities: as child and parent, or with a named relation type
     Relate {
-        /// Entity to mark as a child (case-insensitive)
+        /// Entity to relate (case-insensitive)
+        #[arg(add = ArgValueCompleter::new(entity_names))]
         entity_name: String,
+        /// Entity on the other end of a typed relation (with --as)
+        #[arg(requires = "relation_type", add = ArgValueCompleter::new(entity_names))]
+        target: Option<String>,
         /// Parent entity name (case-insensitive)
-        #[arg(long)]
-        parent: String,
+        #[arg(long, required_unless_present = "relation_type", conflicts_with = "relation_type", add = ArgValueCompleter::new(entity_names))]
+        parent: Option<String>,
+        /// Relation type, e.g. works-at, located-in, part-of, related-to, child-of
+        #[arg(long = "as", value_name = "TYPE", requires = "target")]
+        relation_type: Option<String>,
     },
-    /// Remove a parent/child relation between two entities
+    /// Remove a parent/child or typed relation between two entities
     Unrelate {
-        /// Child entity name (case-insensitive)
+        /// Entity to unrelate (case-insensitive)
+        #[arg(add = ArgValueCompleter::new(entity_names))]
         entity_name: String,
+        /// Entity on the other end of a typed relation (with --as)
+        #[arg(requires = "relation_type", add = ArgValueCompleter::new(entity_names))]
+        target: Option<String>,
         /// Parent entity name (case-insensitive)
-        #[arg(long)]
-        parent: String,
+        #[arg(long, required_unless_present = "relation_type", conflicts_with = "relation_type", add = ArgValueCompleter::new(entity_names))]
+        parent: Option<String>,
+        /// Relation type to remove
+        #[arg(long = "as", value_name = "TYPE", requires = "target")]
+        relation_type: Option<String>,
     },
     /// Register an alternate name for an entity
     Alias {
         /// Entity name (case-insensitive; may itself be an existing alias)
+        #[arg(add = ArgValueCompleter::new(entity_names))]
         entity_name: String,
         /// Alias to register for this entity
         #[arg(long)]
@@ -140,9 +436,28 @@ pub enum EntityCommands {
     /// Remove a previously-registered alias from an entity
     Unalias {
         /// Entity name (case-insensitive; may itself be an existing alias)
+        #[arg(add = ArgValueCompleter::new(entity_names))]
         entity_name: String,
         /// Alias to remove
         #[arg(long)]
         alias: String,
     },
+    /// Set structured attributes on an entity (e.g. email=sarah@example.com status=active)
+    Set {
+        /// Entity name (case-insensitive; may be an alias)
+        #[arg(add = ArgValueCompleter::new(entity_names))]
+        entity_name: String,
+        /// One or more key=value pairs
+        #[arg(required = true, value_name = "KEY=VALUE")]
+        assignments: Vec<String>,
+    },
+    /// Remove attributes from an entity
+    Unset {
+        /// Entity name (case-insensitive; may be an alias)
+        #[arg(add = ArgValueCompleter::new(entity_names))]
+        entity_name: String,
+        /// One or more attribute keys
+        #[arg(required = true, value_name = "KEY")]
+        keys: Vec<String>,
+    },
 }
@@ -0,0 +1,92 @@
+/// Notebook command implementations
+use crate::config::{self, Config};
+use crate::errors::ThoughtError;
+use crate::input::prompt;
+use crate::storage::connection::get_connection;
+use crate::storage::migrations::run_migrations;
+use crate::storage::notebooks::{self, notebook_path};
+use crate::storage::thoughts_repository::ThoughtsRepository;
+use std::path::Path;
+
+/// Config key naming the default notebook
+const DEFAULT_KEY: &str = "notebooks.default";
+
+/// Execute `wet notebook list`: every notebook, the default one marked with `*`
+///
+/// The default notebook is listed even before its database exists, since the
+/// first command to use it creates it.
+pub fn execute_list(data_dir: &Path, config: &Config) -> Result<(), ThoughtError> {
+    let default = &config.notebooks.default;
+    let mut names = notebooks::list_notebooks(data_dir)?;
+    if !names.contains(default) {
+        names.push(default.clone());
+        names.sort();
+    }
+    for name in names {
+        let marker = if &name == default { '*' } else { ' ' };
+        println!("{marker} {name}");
+    }
+    Ok(())
+}
+
+/// Execute `wet notebook create <name>`
+pub fn execute_create(data_dir: &Path, name: &str) -> Result<(), ThoughtError> {
+    notebooks::create_notebook(data_dir, name)?;
+    println!("Notebook '{name}' created. Use it with `wet --notebook {name} ...`");
+    Ok(())
+}
+
+/// Execute `wet notebook rename <old> <new>`
+///
+/// Renaming the default notebook points `notebooks.default` at the new name, so
+/// commands keep using the same notes.
+pub fn execute_rename(data_dir: &Path, old: &str, new: &str, config: &Config) -> Result<(), ThoughtError> {
+    notebooks::rename_notebook(data_dir, old, new)?;
+    println!("Notebook '{old}' renamed to '{new}'.");
+    if config.notebooks.default == old {
+        config::set_in_file(data_dir, DEFAULT_KEY, new)?;
+        println!("{DEFAULT_KEY} is now '{new}'.");
+    }
+    Ok(())
+}
+
+/// Execute `wet notebook delete <name>`
+///
+/// Deletes the notebook's database with every thought and entity in it, after
+/// confirmation unless `yes` is set. The default notebook can't be deleted.
+///
+/// # Returns
+/// * `Ok(())` - Notebook deleted, or deletion declined
+/// * `Err(ThoughtError::NotebookNotFound)` - No notebook with that name exists
+/// * `Err(ThoughtError::InvalidInput)` - The notebook is the default one
+pub fn execute_delete(data_dir: &Path, name: &str, yes: bool, config: &Config) -> Result<(), ThoughtError> {
+    if config.notebooks.default == name {
+        return Err(ThoughtError::InvalidInput(format!(
+            "'{name}' is the default notebook; make another one the default first \
+             (`wet config {DEFAULT_KEY} <name>`)"
+        )));
+    }
+    let path = notebook_path(data_dir, name)?;
+    if !path.exists() {
+        return Err(ThoughtError::NotebookNotFound(name.to_string()));
+    }
+
+    if !yes {
+        let thoughts = {
+            let conn = get_connection(&path)?;
+            run_migrations(&conn)?;
+            ThoughtsRepository::list_all(&conn)?.len()
+        };
+        let answer = prompt::ask(&format!(
+            "Delete notebook '{name}' and its {thoughts} thought(s)? [y/N]: "
+        ))?;
+        if answer != "y" && answer != "yes" {
+            println!("Aborted; nothing was deleted.");
+            return Ok(());
+        }
+    }
+
+    notebooks::delete_notebook(data_dir, name)?;
+    println!("Notebook '{name}' deleted.");
+    Ok(())
+}
@@ -0,0 +1,165 @@
+/// Stats command implementation
+use crate::errors::ThoughtError;
+use crate::models::Timezone;
+use crate::models::entity::Entity;
+use crate::services::activity_stats::{self, Period};
+use crate::services::color_mode::ColorMode;
+use crate::services::entity_styler::EntityStyler;
+use crate::storage::connection::get_connection;
+use crate::storage::entities_repository::EntitiesRepository;
+use crate::storage::migrations::run_migrations;
+use crate::storage::thoughts_repository::ThoughtsRepository;
+use chrono::NaiveDate;
+use std::collections::HashMap;
+use std::path::Path;
+
+/// Entities listed under "Most mentioned" and "Fastest growing"
+const RANKING_LIMIT: usize = 5;
+
+/// Days in each of the two windows "Fastest growing" compares
+const GROWTH_WINDOW_DAYS: i64 = 30;
+
+/// Widest bar in the per-period chart
+const BAR_WIDTH: usize = 30;
+
+/// How `wet stats` buckets thoughts into periods and when an entity counts as forgotten
+#[derive(Debug)]
+pub struct StatsOptions {
+    /// Length of the periods thoughts are counted in
+    pub period: Period,
+    /// How many periods to show, ending with the current one
+    pub periods: usize,
+    /// List entities not mentioned in this many days
+    pub stale_days: i64,
+}
+
+/// "1 thought" / "3 thoughts"
+fn thoughts_label(count: usize) -> String {
+    format!("{} thought{}", count, if count == 1 { "" } else { "s" })
+}
+
+/// "1 day" / "3 days"
+fn days_label(count: usize) -> String {
+    format!("{} day{}", count, if count == 1 { "" } else { "s" })
+}
+
+/// Execute the stats command
+///
+/// Prints totals, thoughts per week or month, a heatmap of the last year, writing
+/// streaks, the most-mentioned and fastest-growing entities, and the entities not
+/// mentioned recently. Days are calendar days in `timezone`, like the dates `wet` prints.
+///
+/// # Arguments
+/// * `options` - Period length and count, and the staleness threshold
+/// * `timezone` - Zone deciding which day each thought was written on
+/// * `db_path` - Database path
+/// * `color_mode` - Whether to color the heatmap and entity names
+///
+/// # Returns
+/// * `Ok(())` - Success
+/// * `Err(ThoughtError)` - Storage error
+pub fn execute(
+    options: StatsOptions,
+    timezone: Timezone,
+    db_path: &Path,
+    color_mode: ColorMode,
+) -> Result<(), ThoughtError> {
+    let conn = get_connection(db_path)?;
+    run_migrations(&conn)?;
+
+    let today = timezone.today();
+    let use_colors = color_mode.should_use_colors();
+
+    let thoughts = ThoughtsRepository::list_all(&conn)?;
+    let entities: HashMap<i64, Entity> = EntitiesRepository::list_all(&conn)?
+        .into_iter()
+        .filter_map(|e| e.id.map(|id| (id, e)))
+        .collect();
+    let thought_days: HashMap<i64, NaiveDate> = thoughts
+        .iter()
+        .filter_map(|t| t.id.map(|id| (id, timezone.date_of(t.created_at))))
+        .collect();
+    let days: Vec<NaiveDate> = thought_days.values().copied().collect();
+    let mentions: Vec<(i64, NaiveDate)> = EntitiesRepository::thought_links(&conn, None, None, timezone)?
+        .into_iter()
+        .filter_map(|(thought_id, entity_id)| thought_days.get(&thought_id).map(|day| (entity_id, *day)))
+        .collect();
+
+    let mut styler = EntityStyler::new(use_colors).with_kinds(EntitiesRepository::kinds_by_name(&conn)?);
+    let mut name = |entity_id: i64| {
+        entities
+            .get(&entity_id)
+            .map(|e| styler.render_entity(&e.canonical_name))
+            .unwrap_or_default()
+    };
+
+    println!("Thoughts: {}", thoughts.len());
+    println!("Entities: {}", entities.len());
+
+    let counts = activity_stats::period_counts(&days, options.period, today, options.periods);
+    let max = counts.iter().map(|(_, count)| *count).max().unwrap_or(0);
+    println!();
+    println!("Thoughts per {}:", options.period.as_str());
+    for (start, count) in counts {
+        let bar = if max == 0 { 0 } else { (count * BAR_WIDTH).div_ceil(max) };
+        let line = format!(
+            "  {:<10}  {:>3} {}",
+            options.period.label(start),
+            count,
+            "█".repeat(bar)
+        );
+        println!("{}", line.trim_end());
+    }
+
+    println!();
+    println!("Activity (last year):");
+    for line in activity_stats::render_heatmap(&activity_stats::heatmap(&days, today), today, use_colors) {
+        println!("  {}", line);
+    }
+
+    let streaks = activity_stats::streaks(&days, today);
+    println!();
+    println!(
+        "Streak: {} (longest {})",
+        days_label(streaks.current),
+        days_label(streaks.longest)
+    );
+
+    let most = activity_stats::most_mentioned(&mentions, RANKING_LIMIT);
+    if !most.is_empty() {
+        println!();
+        println!("Most mentioned:");
+        for (entity_id, count) in most {
+            println!("  {} ({})", name(entity_id), thoughts_label(count));
+        }
+    }
+
+    let growing = activity_stats::fastest_growing(&mentions, today, GROWTH_WINDOW_DAYS, RANKING_LIMIT);
+    if !growing.is_empty() {
+        println!();
+        println!(
+            "Fastest growing (last {} days vs the {} before):",
+            GROWTH_WINDOW_DAYS, GROWTH_WINDOW_DAYS
+        );
+        for growth in growing {
+            println!(
+                "  {} (+{}: {}, was {})",
+                name(growth.entity_id),
+                growth.recent - growth.previous,
+                growth.recent,
+                growth.previous
+            );
+        }
+    }
+
+    let stale = activity_stats::not_mentioned_since(&mentions, today, options.stale_days);
+    if !stale.is_empty() {
+        println!();
+        println!("Not mentioned in {} days:", options.stale_days);
+        for (entity_id, last) in stale {
+            println!("  {} (last {})", name(entity_id), last.format("%Y-%m-%d"));
+        }
+    }
+
+    Ok(())
+}
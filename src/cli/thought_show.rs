@@ -0,0 +1,297 @@
+/// Thought show command implementation
+use crate::errors::ThoughtError;
+use crate::models::{EntityKind, TimestampFormat, Timezone};
+use crate::services::color_mode::ColorMode;
+use crate::services::entity_parser;
+use crate::services::entity_styler::EntityStyler;
+use crate::storage::connection::get_connection;
+use crate::storage::entities_repository::EntitiesRepository;
+use crate::storage::entity_attributes_repository::EntityAttributesRepository;
+use crate::storage::entity_relations_repository::EntityRelationsRepository;
+use crate::storage::migrations::run_migrations;
+use crate::storage::thoughts_repository::ThoughtsRepository;
+use chrono::{DateTime, Utc};
+use rusqlite::Connection;
+use serde::Serialize;
+use std::collections::BTreeMap;
+use std::path::Path;
+
+/// Everything `wet thought show` reports about a single thought
+#[derive(Debug, Serialize)]
+pub struct ThoughtDetail {
+    /// Thought ID
+    pub id: i64,
+    /// Creation timestamp in RFC3339 format, with the offset of the configured zone
+    pub created_at: String,
+    /// Raw content, including entity markup
+    pub content: String,
+    /// Entities linked to the thought, alphabetically by canonical name
+    pub entities: Vec<LinkedEntity>,
+    /// Reference targets in the content that do not resolve to a linked entity
+    /// (e.g. an alias that has since become ambiguous)
+    pub unlinked_references: Vec<String>,
+}
+
+/// An entity linked to a thought, as seen from that thought
+#[derive(Debug, Serialize)]
+pub struct LinkedEntity {
+    /// Entity ID
+    pub id: i64,
+    /// Canonical (display) name
+    pub name: String,
+    /// The entity's kind, if it has one
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub kind: Option<EntityKind>,
+    /// The entity's attributes (`wet entity set`), by key
+    #[serde(skip_serializing_if = "BTreeMap::is_empty")]
+    pub attributes: BTreeMap<String, String>,
+    /// Wordings the content uses for this entity that differ from its canonical
+    /// name: `[alias](entity)` display text and bare known aliases
+    pub written_as: Vec<String>,
+    /// Canonical names of the entity's direct parents
+    pub parents: Vec<String>,
+    /// How many references in the content resolve to this entity; 0 means the
+    /// link is stored but nothing in the text backs it (e.g. a reference edited away)
+    pub references: usize,
+}
+
+/// Gather the details of a thought
+///
+/// The linked entities come from the stored `thought_entities` links; the content is
+/// re-parsed only to recover which wording each reference used. Each reference target
+/// is resolved the same way `wet add` resolves it (canonical name first, then known
+/// aliases), so `[sarah]` and `[sar]` both map to `Sarah` when `sar` is her alias.
+/// The timestamp is given as wall-clock time in `timezone`.
+///
+/// # Returns
+/// * `Ok(ThoughtDetail)` - Success
+/// * `Err(ThoughtError::ThoughtNotFound)` - No thought with the given ID exists
+pub fn detail(conn: &Connection, id: i64, timezone: Timezone) -> Result<ThoughtDetail, ThoughtError> {
+    let thought = ThoughtsRepository::get_by_id(conn, id).map_err(|e| match e {
+        ThoughtError::StorageError(rusqlite::Error::QueryReturnedNoRows) => ThoughtError::ThoughtNotFound(id),
+        other => other,
+    })?;
+
+    let mut entities = Vec::new();
+    for entity in EntitiesRepository::list_for_thought(conn, id)? {
+        let entity_id = entity.id.unwrap();
+        let parents = EntityRelationsRepository::list_parents(conn, entity_id)?
+            .into_iter()
+            .map(|p| p.canonical_name)
+            .collect();
+        entities.push(LinkedEntity {
+            id: entity_id,
+            name: entity.canonical_name,
+            kind: entity.kind,
+            attributes: EntityAttributesRepository::list_for_entity(conn, entity_id)?,
+            written_as: Vec::new(),
+            parents,
+            references: 0,
+        });
+    }
+
+    let mut unlinked_references: Vec<String> = Vec::new();
+    for (display, target) in entity_parser::extract_references(&thought.content) {
+        // An ambiguous alias was never linked, so it is reported like any other
+        // reference that doesn't land on a linked entity.
+        let resolved = EntitiesRepository::resolve(conn, &target).ok().flatten();
+        let linked = resolved.and_then(|r| entities.iter_mut().find(|e| Some(e.id) == r.id));
+
+        match linked {
+            Some(entity) => {
+                entity.references += 1;
+                let differs = display.to_lowercase() != entity.name.to_lowercase();
+                let seen = entity
+                    .written_as
+                    .iter()
+                    .any(|w| w.to_lowercase() == display.to_lowercase());
+                if differs && !seen {
+                    entity.written_as.push(display);
+                }
+            }
+            None => {
+                if !unlinked_references
+                    .iter()
+                    .any(|r| r.to_lowercase() == target.to_lowercase())
+                {
+                    unlinked_references.push(target);
+                }
+            }
+        }
+    }
+
+    Ok(ThoughtDetail {
+        id,
+        created_at: timezone.localize(thought.created_at).to_rfc3339(),
+        content: thought.content,
+        entities,
+        unlinked_references,
+    })
+}
+
+/// Execute the thought show command
+///
+/// Prints a single thought in full: its date, its styled content, and the entities
+/// it links to, each with the wording used in the text (when it differs from the
+/// canonical name) and its parents.
+///
+/// # Arguments
+/// * `id` - Numeric ID of the thought to show (shown as `[id]` in `wet thoughts` output)
+/// * `timestamps` - Zone the timestamp is given in, and whether the text shows the time
+/// * `db_path` - Database path
+/// * `color_mode` - Whether to apply ANSI styling to entity references
+/// * `json` - Print the details as JSON instead of human-readable text
+///
+/// # Returns
+/// * `Ok(())` - Success
+/// * `Err(ThoughtError::ThoughtNotFound)` - No thought with the given ID exists
+pub fn execute(
+    id: i64,
+    timestamps: TimestampFormat,
+    db_path: &Path,
+    color_mode: ColorMode,
+    json: bool,
+) -> Result<(), ThoughtError> {
+    let conn = get_connection(db_path)?;
+    run_migrations(&conn)?;
+
+    let detail = detail(&conn, id, timestamps.timezone)?;
+
+    if json {
+        let output = serde_json::to_string_pretty(&detail)
+            .map_err(|e| ThoughtError::ParseError(format!("Failed to serialize thought: {}", e)))?;
+        println!("{}", output);
+        return Ok(());
+    }
+
+    let mut styler =
+        EntityStyler::new(color_mode.should_use_colors()).with_kinds(EntitiesRepository::kinds_by_name(&conn)?);
+
+    let created_at = DateTime::parse_from_rfc3339(&detail.created_at)
+        .map_err(|e| ThoughtError::ParseError(format!("Invalid thought timestamp: {}", e)))?;
+    println!("[{}] {}", detail.id, timestamps.format(created_at.with_timezone(&Utc)));
+    println!();
+    println!("{}", styler.render_content(detail.content.trim()));
+
+    if !detail.entities.is_empty() {
+        println!();
+        println!("Entities:");
+        for entity in &detail.entities {
+            let mut line = format!("  {}", styler.render_entity(&entity.name));
+            if let Some(kind) = entity.kind {
+                line.push_str(&format!(" [{}]", kind));
+            }
+            if !entity.written_as.is_empty() {
+                line.push_str(&format!(" (written as: {})", entity.written_as.join(", ")));
+            }
+            if !entity.parents.is_empty() {
+                line.push_str(&format!(" - parents: {}", entity.parents.join(", ")));
+            }
+            match entity.references {
+                0 => line.push_str(" - linked, not referenced in the text"),
+                1 => {}
+                n => line.push_str(&format!(" - {} references", n)),
+            }
+            println!("{}", line);
+        }
+    }
+
+    if !detail.unlinked_references.is_empty() {
+        println!();
+        println!("Unlinked references: {}", detail.unlinked_references.join(", "));
+    }
+
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::models::entity::Entity;
+    use crate::models::thought::Thought;
+    use crate::storage::connection::get_memory_connection;
+    use crate::storage::entity_aliases_repository::EntityAliasesRepository;
+
+    fn setup() -> Connection {
+        let conn = get_memory_connection().unwrap();
+        run_migrations(&conn).unwrap();
+        conn
+    }
+
+    fn add_linked_thought(conn: &Connection, content: &str, entities: &[&str]) -> i64 {
+        let thought_id = ThoughtsRepository::save(conn, &Thought::new(content.to_string()).unwrap()).unwrap();
+        for name in entities {
+            let entity_id = EntitiesRepository::find_or_create(conn, &Entity::new(name.to_string())).unwrap();
+            EntitiesRepository::link_to_thought(conn, entity_id, thought_id).unwrap();
+        }
+        thought_id
+    }
+
+    #[test]
+    fn test_detail_missing_thought_is_not_found() {
+        let conn = setup();
+
+        let result = detail(&conn, 42, Timezone::Local);
+        assert!(matches!(result, Err(ThoughtError::ThoughtNotFound(42))));
+    }
+
+    #[test]
+    fn test_detail_records_alias_wording_and_parents() {
+        let conn = setup();
+        let id = add_linked_thought(&conn, "Lunch with [Sarah], then [S](sarah) and [sar] again", &["Sarah"]);
+        let sarah = EntitiesRepository::find_by_name(&conn, "sarah").unwrap().unwrap();
+        EntityAliasesRepository::add_alias(&conn, sarah.id.unwrap(), "sar").unwrap();
+        let acme_id = EntitiesRepository::find_or_create(&conn, &Entity::new("Acme".to_string())).unwrap();
+        EntityRelationsRepository::add_relation(&conn, sarah.id.unwrap(), acme_id).unwrap();
+
+        let detail = detail(&conn, id, Timezone::Local).unwrap();
+
+        assert_eq!(detail.entities.len(), 1);
+        assert_eq!(detail.entities[0].name, "Sarah");
+        assert_eq!(detail.entities[0].written_as, vec!["S", "sar"]);
+        assert_eq!(detail.entities[0].parents, vec!["Acme"]);
+        assert_eq!(detail.entities[0].references, 3);
+        assert!(detail.unlinked_references.is_empty());
+    }
+
+    #[test]
+    fn test_detail_reports_references_without_a_link() {
+        let conn = setup();
+        let id = add_linked_thought(&conn, "[Alice] met [Bob]", &["Alice"]);
+
+        let detail = detail(&conn, id, Timezone::Local).unwrap();
+
+        assert_eq!(detail.entities.len(), 1);
+        assert_eq!(detail.unlinked_references, vec!["Bob"]);
+    }
+
+    #[test]
+    fn test_detail_counts_references_per_link() {
+        let conn = setup();
+        let id = add_linked_thought(&conn, "Call with [Alice]", &["Alice", "Bob"]);
+
+        let detail = detail(&conn, id, Timezone::Local).unwrap();
+
+        let references: Vec<(&str, usize)> = detail
+            .entities
+            .iter()
+            .map(|e| (e.name.as_str(), e.references))
+            .collect();
+        assert_eq!(references, vec![("Alice", 1), ("Bob", 0)]);
+    }
+
+    #[test]
+    fn test_execute_json_and_text_succeed() {
+        use tempfile::TempDir;
+
+        let temp_dir = TempDir::new().unwrap();
+        let db_path = temp_dir.path().join("test.db");
+        let conn = get_connection(&db_path).unwrap();
+        run_migrations(&conn).unwrap();
+        let id = add_linked_thought(&conn, "Reading about [rust]", &["rust"]);
+        drop(conn);
+
+        assert!(execute(id, TimestampFormat::default(), &db_path, ColorMode::Never, false).is_ok());
+        assert!(execute(id, TimestampFormat::default(), &db_path, ColorMode::Always, true).is_ok());
+    }
+}
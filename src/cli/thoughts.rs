@@ -1,25 +1,38 @@
 /// Thoughts command implementation
 use crate::errors::ThoughtError;
-use crate::models::SortOrder;
+use crate::models::{SortOrder, TimestampFormat};
 use crate::services::color_mode::ColorMode;
 use crate::services::entity_styler::EntityStyler;
+use crate::services::thought_query;
 use crate::storage::connection::get_connection;
+use crate::storage::entities_repository::EntitiesRepository;
 use crate::storage::migrations::run_migrations;
 use crate::storage::thoughts_repository::ThoughtsRepository;
 use std::path::Path;
 
 /// Execute the thoughts command
+///
+/// Lists every thought, or those about `entity_filter` (`--on`) and its descendants, or
+/// those matching `where_query` (`--where`, see [`thought_query`]). Dates are shown and
+/// query dates resolved in the zone `timestamps` names.
 pub fn execute(
     db_path: &Path,
     entity_filter: Option<&str>,
+    where_query: Option<&str>,
     color_mode: ColorMode,
     sort_order: SortOrder,
+    timestamps: TimestampFormat,
 ) -> Result<(), ThoughtError> {
+    let today = timestamps.timezone.today();
+    let query = where_query.map(|q| thought_query::parse(q, today)).transpose()?;
+
     let conn = get_connection(db_path)?;
     run_migrations(&conn)?;
 
     let mut thoughts = if let Some(entity_name) = entity_filter {
         ThoughtsRepository::list_by_entity(&conn, entity_name)?
+    } else if let Some(query) = &query {
+        ThoughtsRepository::list_matching(&conn, query, timestamps.timezone)?
     } else {
         ThoughtsRepository::list_all(&conn)?
     };
@@ -31,19 +44,21 @@ pub fn execute(
     if thoughts.is_empty() {
         if let Some(entity_name) = entity_filter {
             println!("No thoughts found for entity: {}", entity_name);
+        } else if let Some(where_query) = where_query {
+            println!("No thoughts match: {}", where_query);
         } else {
             println!("No thoughts found.");
         }
     } else {
         let use_colors = color_mode.should_use_colors();
-        let mut styler = EntityStyler::new(use_colors);
+        let mut styler = EntityStyler::new(use_colors).with_kinds(EntitiesRepository::kinds_by_name(&conn)?);
 
         for thought in thoughts {
             let styled_content = styler.render_content(thought.content.trim());
             println!(
                 "[{}] {} - {}",
                 thought.id.unwrap_or(0),
-                thought.created_at.format("%Y-%m-%d"),
+                timestamps.format(thought.created_at),
                 styled_content
             );
         }
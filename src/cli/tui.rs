@@ -6,26 +6,37 @@
 use std::path::Path;
 
 use crate::errors::ThoughtError;
-use crate::models::SortOrder;
+use crate::models::{SortOrder, TimestampFormat};
 use crate::storage::connection::get_connection;
 use crate::storage::entities_repository::EntitiesRepository;
+use crate::storage::entity_aliases_repository::EntityAliasesRepository;
+use crate::storage::entity_attributes_repository::EntityAttributesRepository;
 use crate::storage::entity_relations_repository::EntityRelationsRepository;
 use crate::storage::migrations::run_migrations;
 use crate::storage::thoughts_repository::ThoughtsRepository;
 use crate::tui::App;
 
 /// Launch the interactive TUI thought viewer.
-pub fn execute(db_path: &Path, sort_order: SortOrder) -> Result<(), ThoughtError> {
+pub fn execute(db_path: &Path, sort_order: SortOrder, timestamps: TimestampFormat) -> Result<(), ThoughtError> {
     let conn = get_connection(db_path)?;
     run_migrations(&conn)?;
     let thoughts = ThoughtsRepository::list_all(&conn)?;
     let entities = EntitiesRepository::list_all(&conn)?;
     let relations = EntityRelationsRepository::list_all_edges(&conn)?;
+    let entity_kinds = EntitiesRepository::kinds_by_name(&conn)?;
+    let entity_attributes = EntityAttributesRepository::list_all(&conn)?;
+    let thought_links = EntitiesRepository::thought_links(&conn, None, None, timestamps.timezone)?;
+    let entity_aliases = EntityAliasesRepository::list_all(&conn)?;
 
     let mut terminal = ratatui::init();
 
     let result = App::new(thoughts, entities, sort_order)
+        .with_timestamp_format(timestamps)
         .with_relations(relations)
+        .with_entity_kinds(entity_kinds)
+        .with_entity_attributes(entity_attributes)
+        .with_thought_links(thought_links)
+        .with_entity_aliases(entity_aliases)
         .with_db_path(db_path.to_path_buf())
         .run(&mut terminal);
 
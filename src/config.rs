@@ -1,8 +1,11 @@
 /// Configuration file management
 use crate::errors::ThoughtError;
-use crate::models::SortOrder;
-use serde::{Deserialize, Serialize};
-use std::path::Path;
+use crate::models::{RelationType, SortOrder, TimestampFormat, Timezone};
+use crate::storage::notebooks::{DEFAULT_NOTEBOOK, validate_notebook_name};
+use serde::{Deserialize, Deserializer, Serialize};
+use std::fmt;
+use std::path::{Path, PathBuf};
+use toml_edit::{DocumentMut, Item, TableLike};
 
 fn default_thoughts_order() -> SortOrder {
     SortOrder::Descending
@@ -13,95 +16,572 @@ fn default_thoughts_order() -> SortOrder {
 pub struct ThoughtsConfig {
     #[serde(default = "default_thoughts_order")]
     pub order: SortOrder,
+    /// Zone thoughts are dated and shown in; `local` follows the system
+    #[serde(default)]
+    pub timezone: Timezone,
+    /// Show the time of day next to each thought's date
+    #[serde(default)]
+    pub show_time: bool,
 }
 
 impl Default for ThoughtsConfig {
     fn default() -> Self {
         Self {
             order: default_thoughts_order(),
+            timezone: Timezone::default(),
+            show_time: false,
+        }
+    }
+}
+
+impl ThoughtsConfig {
+    /// How thought timestamps should be printed under this config.
+    pub fn timestamp_format(&self) -> TimestampFormat {
+        TimestampFormat {
+            timezone: self.timezone,
+            show_time: self.show_time,
         }
     }
 }
 
+fn default_notebook() -> String {
+    DEFAULT_NOTEBOOK.to_string()
+}
+
+fn notebook_name<'de, D: Deserializer<'de>>(deserializer: D) -> Result<String, D::Error> {
+    let name = String::deserialize(deserializer)?;
+    validate_notebook_name(&name).map_err(serde::de::Error::custom)?;
+    Ok(name)
+}
+
+/// Which notebook commands use
+#[derive(Debug, Serialize, Deserialize, PartialEq)]
+pub struct NotebooksConfig {
+    /// Notebook used when `--notebook` isn't given
+    #[serde(default = "default_notebook", deserialize_with = "notebook_name")]
+    pub default: String,
+}
+
+impl Default for NotebooksConfig {
+    fn default() -> Self {
+        Self {
+            default: default_notebook(),
+        }
+    }
+}
+
+/// User-declared relation types, on top of the built-in ones
+#[derive(Debug, Default, Serialize, Deserialize, PartialEq)]
+pub struct RelationsConfig {
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub types: Vec<RelationType>,
+}
+
+impl RelationsConfig {
+    fn is_empty(&self) -> bool {
+        self.types.is_empty()
+    }
+}
+
 /// Wetware configuration
 #[derive(Debug, Serialize, Deserialize, PartialEq)]
 pub struct Config {
-    /// Configuration format version for future migrations
+    /// Configuration file format version, see [`load_config`]; 1 when the file has none
+    #[serde(default = "first_version")]
     pub version: u32,
     #[serde(default)]
     pub thoughts: ThoughtsConfig,
+    #[serde(default)]
+    pub notebooks: NotebooksConfig,
+    #[serde(default, skip_serializing_if = "RelationsConfig::is_empty")]
+    pub relations: RelationsConfig,
+}
+
+/// The format version of a config file without a `version` key
+fn first_version() -> u32 {
+    1
 }
 
 impl Default for Config {
     fn default() -> Self {
         Self {
-            version: 1,
+            version: CURRENT_VERSION,
             thoughts: ThoughtsConfig::default(),
+            notebooks: NotebooksConfig::default(),
+            relations: RelationsConfig::default(),
+        }
+    }
+}
+
+/// Prefix of the environment variables that override config keys
+const ENV_PREFIX: &str = "WETWARE_";
+
+/// The format version, which is bookkeeping rather than a setting
+const VERSION_KEY: &str = "version";
+
+/// Where a setting's effective value comes from
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Source {
+    /// Not set anywhere; the built-in default
+    Default,
+    /// Set in `config.toml`
+    File,
+    /// Overridden by a `WETWARE_<KEY>` environment variable
+    Env,
+}
+
+impl fmt::Display for Source {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Source::Default => write!(f, "default"),
+            Source::File => write!(f, "file"),
+            Source::Env => write!(f, "env"),
         }
     }
 }
 
+/// A setting's effective value and where it comes from, as `wet config --list` shows it
+#[derive(Debug, Clone, PartialEq)]
+pub struct Setting {
+    pub key: String,
+    pub value: String,
+    pub source: Source,
+}
+
+/// The environment variable overriding `key`: `thoughts.order` is `WETWARE_THOUGHTS_ORDER`
+pub fn env_var(key: &str) -> String {
+    format!("{ENV_PREFIX}{}", key.replace('.', "_").to_uppercase())
+}
+
 impl Config {
+    /// The dotted key of every setting that can be set with `wet config <key> <value>`.
+    ///
+    /// Generated from the serialized default config, so a new field with a scalar
+    /// default is a new key without further code.
+    pub fn keys() -> Result<Vec<String>, ThoughtError> {
+        Ok(leaves(&Config::default().to_table()?)
+            .into_iter()
+            .map(|(key, _)| key)
+            .filter(|key| key != VERSION_KEY)
+            .collect())
+    }
+
     /// Get a config value by dotted key (e.g. "thoughts.order").
     pub fn get_value(&self, key: &str) -> Result<String, ThoughtError> {
-        match key {
-            "thoughts.order" => Ok(self.thoughts.order.to_string()),
-            _ => Err(ThoughtError::InvalidInput(format!("Unknown config key: {key}"))),
-        }
+        leaves(&self.to_table()?)
+            .into_iter()
+            .find(|(leaf, _)| leaf == key && leaf != VERSION_KEY)
+            .map(|(_, value)| display_value(&value))
+            .ok_or_else(|| unknown_key(key))
     }
 
     /// Set a config value by dotted key.
+    ///
+    /// The value is read as the type of the key's default and the whole config is
+    /// checked again, so invalid values are rejected with the field's own message.
     pub fn set_value(&mut self, key: &str, value: &str) -> Result<(), ThoughtError> {
-        match key {
-            "thoughts.order" => {
-                self.thoughts.order = value.parse().map_err(|_| {
-                    ThoughtError::InvalidInput(format!(
-                        "Invalid value '{value}' for thoughts.order. Valid values: ascending, descending"
-                    ))
+        let mut table = self.to_table()?;
+        *self = assign(&mut table, key, value)?;
+        Ok(())
+    }
+
+    /// This config with every `WETWARE_<KEY>` environment variable applied on top.
+    pub fn with_env(mut self) -> Result<Self, ThoughtError> {
+        self.apply_overrides(|name| std::env::var(name).ok())?;
+        Ok(self)
+    }
+
+    /// Apply the override `lookup` finds for each key's environment variable,
+    /// returning the keys that were overridden
+    fn apply_overrides(&mut self, lookup: impl Fn(&str) -> Option<String>) -> Result<Vec<String>, ThoughtError> {
+        let mut overridden = Vec::new();
+        for key in Config::keys()? {
+            let name = env_var(&key);
+            if let Some(value) = lookup(&name) {
+                self.set_value(&key, &value).map_err(|e| match e {
+                    ThoughtError::InvalidInput(message) => ThoughtError::InvalidInput(format!("{name}: {message}")),
+                    other => other,
                 })?;
-                Ok(())
+                overridden.push(key);
+            }
+        }
+        Ok(overridden)
+    }
+
+    fn to_table(&self) -> Result<toml::Table, ThoughtError> {
+        match toml::Value::try_from(self) {
+            Ok(toml::Value::Table(table)) => Ok(table),
+            Ok(_) => Err(ThoughtError::InvalidInput(
+                "Config did not serialize to a table".to_string(),
+            )),
+            Err(e) => Err(ThoughtError::InvalidInput(format!("Failed to serialize config: {e}"))),
+        }
+    }
+}
+
+/// Every non-table value in `table` under its dotted key; a list is one value
+fn leaves(table: &toml::Table) -> Vec<(String, toml::Value)> {
+    let mut found = Vec::new();
+    for (name, value) in table {
+        match value {
+            toml::Value::Table(inner) => {
+                for (key, leaf) in leaves(inner) {
+                    found.push((format!("{name}.{key}"), leaf));
+                }
             }
-            _ => Err(ThoughtError::InvalidInput(format!("Unknown config key: {key}"))),
+            _ => found.push((name.clone(), value.clone())),
+        }
+    }
+    found
+}
+
+/// Strings as they are, everything else as TOML
+fn display_value(value: &toml::Value) -> String {
+    match value {
+        toml::Value::String(text) => text.clone(),
+        other => other.to_string(),
+    }
+}
+
+fn unknown_key(key: &str) -> ThoughtError {
+    ThoughtError::InvalidInput(format!("Unknown config key: {key}"))
+}
+
+/// Set `key` in `table` to `value`, read as the type of the key's default, and
+/// return the config the result describes
+fn assign(table: &mut toml::Table, key: &str, value: &str) -> Result<Config, ThoughtError> {
+    let invalid = |detail: &str| ThoughtError::InvalidInput(format!("Invalid value '{value}' for {key}. {detail}"));
+
+    let default = leaves(&Config::default().to_table()?)
+        .into_iter()
+        .find(|(leaf, _)| leaf == key && leaf != VERSION_KEY)
+        .map(|(_, default)| default);
+    let parsed = match default {
+        Some(toml::Value::String(_)) => toml::Value::String(value.to_string()),
+        Some(toml::Value::Boolean(_)) => {
+            toml::Value::Boolean(value.parse().map_err(|_| invalid("Valid values: true, false"))?)
+        }
+        Some(toml::Value::Integer(_)) => {
+            toml::Value::Integer(value.parse().map_err(|_| invalid("Expected a whole number"))?)
+        }
+        _ if key != VERSION_KEY && leaves(table).iter().any(|(leaf, _)| leaf == key) => {
+            return Err(ThoughtError::InvalidInput(format!(
+                "{key} can't be set from the command line; use `wet config edit`"
+            )));
         }
+        _ => return Err(unknown_key(key)),
+    };
+
+    let mut parent = &mut *table;
+    let mut path: Vec<&str> = key.split('.').collect();
+    let name = path.pop().unwrap_or(key);
+    for section in path {
+        let entry = parent
+            .entry(section)
+            .or_insert_with(|| toml::Value::Table(toml::Table::new()));
+        parent = match entry {
+            toml::Value::Table(inner) => inner,
+            _ => return Err(unknown_key(key)),
+        };
     }
+    parent.insert(name.to_string(), parsed);
+
+    toml::Value::Table(table.clone())
+        .try_into()
+        .map_err(|e: toml::de::Error| invalid(e.message()))
 }
 
 const CONFIG_FILENAME: &str = "config.toml";
 
+/// A step of the config file format: `MIGRATIONS[i]` turns a version `i + 1` file into version `i + 2`.
+///
+/// Migrations edit the document in place, so comments and unknown keys survive. To change the
+/// format, append one here; the version written to new files follows.
+type Migration = fn(&mut DocumentMut);
+
+const MIGRATIONS: &[Migration] = &[];
+
+/// The config file format this build reads and writes
+pub const CURRENT_VERSION: u32 = MIGRATIONS.len() as u32 + 1;
+
+/// Path of the config file in the data directory.
+pub fn config_path(data_dir: &Path) -> PathBuf {
+    data_dir.join(CONFIG_FILENAME)
+}
+
 /// Load configuration from the data directory.
 ///
 /// Returns default config if the file doesn't exist.
-/// Returns an error if the file exists but is malformed.
+/// Returns an error if the file exists but is malformed, or is from a newer version of wet.
+/// A file in an older format is upgraded in place, after copying it to `config.toml.v<N>.bak`.
 pub fn load_config(data_dir: &Path) -> Result<Config, ThoughtError> {
-    let config_path = data_dir.join(CONFIG_FILENAME);
+    load_with(data_dir, MIGRATIONS)
+}
+
+/// Read configuration from the data directory without ever writing to it.
+///
+/// Unlike [`load_config`], an older file is read as it is rather than upgraded, for
+/// callers that mustn't touch the disk, like shell completion.
+pub fn read_config(data_dir: &Path) -> Result<Config, ThoughtError> {
+    toml::Value::Table(table_of(&read_document(data_dir)?)?)
+        .try_into()
+        .map_err(|e: toml::de::Error| ThoughtError::InvalidInput(format!("Malformed config file: {e}")))
+}
+
+/// [`load_config`] with the migrations given
+fn load_with(data_dir: &Path, migrations: &[Migration]) -> Result<Config, ThoughtError> {
+    let config_path = config_path(data_dir);
 
     if !config_path.exists() {
         return Ok(Config::default());
     }
 
-    let contents = std::fs::read_to_string(&config_path)?;
-    toml::from_str(&contents).map_err(|e| ThoughtError::InvalidInput(format!("Malformed config file: {e}")))
+    let mut document = read_document(data_dir)?;
+    if let Some(from) = migrate(&mut document, migrations)? {
+        let backup = data_dir.join(format!("{CONFIG_FILENAME}.v{from}.bak"));
+        std::fs::copy(&config_path, &backup)?;
+        write_document(data_dir, &document)?;
+        eprintln!(
+            "Upgraded {CONFIG_FILENAME} from version {from} to {} (previous file saved as {})",
+            migrations.len() + 1,
+            backup.display()
+        );
+    }
+
+    toml::from_str(&document.to_string()).map_err(|e| ThoughtError::InvalidInput(format!("Malformed config file: {e}")))
+}
+
+/// Bring `document` up to the version after the last of `migrations`.
+///
+/// Returns the version it started at if anything ran. A file without a `version` is
+/// treated as version 1.
+fn migrate(document: &mut DocumentMut, migrations: &[Migration]) -> Result<Option<u32>, ThoughtError> {
+    let current = migrations.len() as u32 + 1;
+    let from = file_version(document)?;
+    if from > current {
+        return Err(ThoughtError::InvalidInput(format!(
+            "{CONFIG_FILENAME} is version {from}, but this wet only understands up to version {current}. \
+             Upgrade wet, or restore a backup of the file"
+        )));
+    }
+    if from == current {
+        return Ok(None);
+    }
+
+    for migration in &migrations[from as usize - 1..] {
+        migration(document);
+    }
+    document[VERSION_KEY] = toml_edit::value(i64::from(current));
+    Ok(Some(from))
+}
+
+/// The `version` of a config document, 1 if it has none
+fn file_version(document: &DocumentMut) -> Result<u32, ThoughtError> {
+    match document.get(VERSION_KEY) {
+        None => Ok(1),
+        Some(item) => item
+            .as_integer()
+            .and_then(|version| u32::try_from(version).ok())
+            .filter(|&version| version >= 1)
+            .ok_or_else(|| {
+                ThoughtError::InvalidInput("Malformed config file: version must be a positive whole number".to_string())
+            }),
+    }
+}
+
+/// Check the text of a config file before it replaces `config.toml`.
+///
+/// Stricter than [`load_config`], which only warns about keys it doesn't know: here an
+/// unknown key is an error, so a misspelled setting is caught instead of silently doing
+/// nothing. An older file is checked as [`load_config`] will read it, after migrating.
+pub fn validate(contents: &str) -> Result<Config, ThoughtError> {
+    validate_with(contents, MIGRATIONS)
+}
+
+/// [`validate`] with the migrations given
+fn validate_with(contents: &str, migrations: &[Migration]) -> Result<Config, ThoughtError> {
+    let mut document: DocumentMut = contents
+        .parse()
+        .map_err(|e| ThoughtError::InvalidInput(format!("Malformed config file: {e}")))?;
+    migrate(&mut document, migrations)?;
+    let config: Config = toml::from_str(&document.to_string())
+        .map_err(|e| ThoughtError::InvalidInput(format!("Malformed config file: {e}")))?;
+
+    if let Some(key) = unknown_keys(&document)?.first() {
+        return Err(unknown_key(key));
+    }
+    Ok(config)
+}
+
+/// Keys in `document` that no setting reads, in file order
+///
+/// These are the keys serde skips while reading the file into [`Config`], so they
+/// follow the struct's fields, whatever values (or empty lists) the file holds.
+fn unknown_keys(document: &DocumentMut) -> Result<Vec<String>, ThoughtError> {
+    let mut unknown = Vec::new();
+    let _: Config = serde_ignored::deserialize(toml::Value::Table(table_of(document)?), |path| {
+        unknown.push(path.to_string())
+    })
+    .map_err(|e: toml::de::Error| ThoughtError::InvalidInput(format!("Malformed config file: {e}")))?;
+    Ok(unknown)
 }
 
 /// Save configuration to the data directory.
+///
+/// Only values that changed are written, into the existing file, so its comments and any
+/// keys this version doesn't know are kept.
 pub fn save_config(data_dir: &Path, config: &Config) -> Result<(), ThoughtError> {
-    let config_path = data_dir.join(CONFIG_FILENAME);
-    let contents = toml::to_string_pretty(config)
-        .map_err(|e| ThoughtError::InvalidInput(format!("Failed to serialize config: {e}")))?;
-    std::fs::write(&config_path, contents)?;
-    Ok(())
+    let mut document = read_document(data_dir)?;
+    let current: Config = toml::Value::Table(table_of(&document)?)
+        .try_into()
+        .map_err(|e: toml::de::Error| ThoughtError::InvalidInput(format!("Malformed config file: {e}")))?;
+    let current = leaves(&current.to_table()?);
+    for (key, value) in leaves(&config.to_table()?) {
+        if !current.contains(&(key.clone(), value.clone())) {
+            set_in_document(&mut document, &key, &value)?;
+        }
+    }
+    write_document(data_dir, &document)
 }
 
-/// Ensure a config file exists in the data directory, creating a default one if missing.
+/// Ensure a config file exists in the data directory, creating one if missing.
+///
+/// A new file holds only the format version; every setting keeps its default until set.
+/// An existing file is never rewritten here, except by a format migration, and each key
+/// in it that no setting reads is reported as a warning.
 pub fn ensure_config(data_dir: &Path) -> Result<Config, ThoughtError> {
+    if !config_path(data_dir).exists() {
+        write_document(data_dir, &read_document(data_dir)?)?;
+    }
     let config = load_config(data_dir)?;
-    let config_path = data_dir.join(CONFIG_FILENAME);
-    if !config_path.exists() {
-        save_config(data_dir, &config)?;
+    for key in unknown_keys(&read_document(data_dir)?)? {
+        eprintln!("Warning: Unknown key '{key}' in {CONFIG_FILENAME} is ignored");
     }
     Ok(config)
 }
 
+/// Every setting with its effective value and where that value comes from.
+pub fn list_settings(data_dir: &Path) -> Result<Vec<Setting>, ThoughtError> {
+    let in_file: Vec<String> = leaves(&table_of(&read_document(data_dir)?)?)
+        .into_iter()
+        .map(|(key, _)| key)
+        .collect();
+    let mut config = load_config(data_dir)?;
+    let from_env = config.apply_overrides(|name| std::env::var(name).ok())?;
+
+    Ok(leaves(&config.to_table()?)
+        .into_iter()
+        .filter(|(key, _)| key != VERSION_KEY)
+        .map(|(key, value)| {
+            let source = if from_env.contains(&key) {
+                Source::Env
+            } else if in_file.contains(&key) {
+                Source::File
+            } else {
+                Source::Default
+            };
+            Setting {
+                value: display_value(&value),
+                key,
+                source,
+            }
+        })
+        .collect())
+}
+
+/// Set `key` in the config file, leaving the rest of the file, comments included, as it is.
+pub fn set_in_file(data_dir: &Path, key: &str, value: &str) -> Result<(), ThoughtError> {
+    let mut document = read_document(data_dir)?;
+    let mut table = table_of(&document)?;
+    assign(&mut table, key, value)?;
+    if let Some((_, parsed)) = leaves(&table).into_iter().find(|(leaf, _)| leaf == key) {
+        set_in_document(&mut document, key, &parsed)?;
+    }
+    write_document(data_dir, &document)
+}
+
+/// Remove `key` from the config file so its default applies again.
+///
+/// Returns whether the file had set it.
+pub fn unset_in_file(data_dir: &Path, key: &str) -> Result<bool, ThoughtError> {
+    let mut document = read_document(data_dir)?;
+    let known = Config::keys()?.iter().any(|known| known == key)
+        || (key != VERSION_KEY && leaves(&table_of(&document)?).iter().any(|(leaf, _)| leaf == key));
+    if !known {
+        return Err(unknown_key(key));
+    }
+    let removed = remove_from_document(document.as_table_mut(), key);
+    if removed {
+        write_document(data_dir, &document)?;
+    }
+    Ok(removed)
+}
+
+/// Write `value` at the dotted `key`, creating sections as needed and keeping the
+/// comment of a value it replaces
+fn set_in_document(document: &mut DocumentMut, key: &str, value: &toml::Value) -> Result<(), ThoughtError> {
+    let mut replacement: toml_edit::Value = value
+        .to_string()
+        .parse()
+        .map_err(|e| ThoughtError::InvalidInput(format!("Failed to serialize config: {e}")))?;
+
+    let mut path: Vec<&str> = key.split('.').collect();
+    let name = path.pop().unwrap_or(key);
+    let mut table: &mut dyn TableLike = document.as_table_mut();
+    for section in path {
+        table = table
+            .entry(section)
+            .or_insert(toml_edit::table())
+            .as_table_like_mut()
+            .ok_or_else(|| unknown_key(key))?;
+    }
+    if let Some(existing) = table.get_mut(name).and_then(Item::as_value_mut) {
+        *replacement.decor_mut() = existing.decor().clone();
+    }
+    table.insert(name, Item::Value(replacement));
+    Ok(())
+}
+
+/// Remove the dotted `key`, dropping sections left empty. Returns whether it was there.
+fn remove_from_document(table: &mut dyn TableLike, key: &str) -> bool {
+    match key.split_once('.') {
+        None => table.remove(key).is_some(),
+        Some((section, rest)) => {
+            let Some(inner) = table.get_mut(section).and_then(Item::as_table_like_mut) else {
+                return false;
+            };
+            let removed = remove_from_document(inner, rest);
+            if inner.is_empty() {
+                table.remove(section);
+            }
+            removed
+        }
+    }
+}
+
+/// The config file as an editable document; a missing file is just the current version
+fn read_document(data_dir: &Path) -> Result<DocumentMut, ThoughtError> {
+    let config_path = config_path(data_dir);
+    let contents = if config_path.exists() {
+        std::fs::read_to_string(&config_path)?
+    } else {
+        format!("{VERSION_KEY} = {CURRENT_VERSION}\n")
+    };
+    contents
+        .parse()
+        .map_err(|e| ThoughtError::InvalidInput(format!("Malformed config file: {e}")))
+}
+
+fn write_document(data_dir: &Path, document: &DocumentMut) -> Result<(), ThoughtError> {
+    std::fs::write(config_path(data_dir), document.to_string())?;
+    Ok(())
+}
+
+/// The plain values of a document
+fn table_of(document: &DocumentMut) -> Result<toml::Table, ThoughtError> {
+    toml::from_str(&document.to_string()).map_err(|e| ThoughtError::InvalidInput(format!("Malformed config file: {e}")))
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -154,9 +634,15 @@ mod tests {
     #[test]
     fn test_ensure_config_preserves_existing() {
         let temp = TempDir::new().unwrap();
-        let mut custom = Config::default();
-        custom.version = 5;
-        custom.thoughts.order = SortOrder::Ascending;
+        let custom = Config {
+            version: CURRENT_VERSION,
+            thoughts: ThoughtsConfig {
+                order: SortOrder::Ascending,
+                ..ThoughtsConfig::default()
+            },
+            notebooks: NotebooksConfig::default(),
+            relations: RelationsConfig::default(),
+        };
         save_config(temp.path(), &custom).unwrap();
         let config = ensure_config(temp.path()).unwrap();
         assert_eq!(config, custom);
@@ -178,11 +664,42 @@ mod tests {
         assert_eq!(config.thoughts.order, SortOrder::Descending);
     }
 
+    #[test]
+    fn test_load_config_without_version_is_version_one() {
+        let temp = TempDir::new().unwrap();
+        std::fs::write(temp.path().join("config.toml"), "[thoughts]\norder = \"ascending\"\n").unwrap();
+        let config = load_config(temp.path()).unwrap();
+        assert_eq!(config.version, 1);
+        assert_eq!(config.thoughts.order, SortOrder::Ascending);
+        assert!(validate("[thoughts]\norder = \"ascending\"\n").is_ok());
+    }
+
     #[test]
     fn test_backward_compat_no_order_in_thoughts() {
         let toml_str = "version = 1\n\n[thoughts]\n";
         let config: Config = toml::from_str(toml_str).unwrap();
         assert_eq!(config.thoughts.order, SortOrder::Descending);
+        assert_eq!(config.thoughts.timezone, Timezone::Local);
+        assert!(!config.thoughts.show_time);
+    }
+
+    #[test]
+    fn test_relation_types_from_toml() {
+        let toml_str = "version = 1\n\n[[relations.types]]\nname = \"mentors\"\ninverse = \"mentored-by\"\n\n\
+                        [[relations.types]]\nname = \"knows\"\ndirected = false\n";
+        let config: Config = toml::from_str(toml_str).unwrap();
+        assert_eq!(config.relations.types.len(), 2);
+        assert_eq!(config.relations.types[0].inverse.as_deref(), Some("mentored-by"));
+        assert!(!config.relations.types[1].directed);
+
+        let serialized = toml::to_string_pretty(&config).unwrap();
+        assert_eq!(toml::from_str::<Config>(&serialized).unwrap(), config);
+    }
+
+    #[test]
+    fn test_default_config_omits_relations_table() {
+        let serialized = toml::to_string_pretty(&Config::default()).unwrap();
+        assert!(!serialized.contains("relations"));
     }
 
     #[test]
@@ -216,6 +733,35 @@ mod tests {
         assert!(err.contains("ascending, descending"));
     }
 
+    #[test]
+    fn test_set_value_thoughts_timezone_and_show_time() {
+        let mut config = Config::default();
+        assert_eq!(config.get_value("thoughts.timezone").unwrap(), "local");
+        config.set_value("thoughts.timezone", "Europe/Prague").unwrap();
+        config.set_value("thoughts.show_time", "true").unwrap();
+        assert_eq!(config.get_value("thoughts.timezone").unwrap(), "Europe/Prague");
+        assert_eq!(config.get_value("thoughts.show_time").unwrap(), "true");
+
+        let err = config.set_value("thoughts.timezone", "Prague").unwrap_err().to_string();
+        assert!(err.contains("Invalid time zone: 'Prague'"));
+        let err = config.set_value("thoughts.show_time", "yes").unwrap_err().to_string();
+        assert!(err.contains("Valid values: true, false"));
+    }
+
+    #[test]
+    fn test_set_value_notebooks_default() {
+        let mut config = Config::default();
+        assert_eq!(config.get_value("notebooks.default").unwrap(), "default");
+        config.set_value("notebooks.default", "work").unwrap();
+        assert_eq!(config.notebooks.default, "work");
+
+        let err = config
+            .set_value("notebooks.default", "../work")
+            .unwrap_err()
+            .to_string();
+        assert!(err.contains("Invalid notebook name: '../work'"), "{err}");
+    }
+
     #[test]
     fn test_set_value_unknown_key() {
         let mut config = Config::default();
@@ -223,4 +769,210 @@ mod tests {
         assert!(result.is_err());
         assert!(result.unwrap_err().to_string().contains("Unknown config key"));
     }
+
+    #[test]
+    fn test_keys_come_from_the_struct() {
+        assert_eq!(
+            Config::keys().unwrap(),
+            [
+                "notebooks.default",
+                "thoughts.order",
+                "thoughts.show_time",
+                "thoughts.timezone"
+            ]
+        );
+        assert_eq!(env_var("thoughts.show_time"), "WETWARE_THOUGHTS_SHOW_TIME");
+    }
+
+    #[test]
+    fn test_overrides_apply_by_env_var_name() {
+        let mut config = Config::default();
+        let overridden = config
+            .apply_overrides(|name| (name == "WETWARE_THOUGHTS_ORDER").then(|| "ascending".to_string()))
+            .unwrap();
+        assert_eq!(overridden, ["thoughts.order"]);
+        assert_eq!(config.thoughts.order, SortOrder::Ascending);
+
+        let err = Config::default()
+            .apply_overrides(|name| (name == "WETWARE_THOUGHTS_SHOW_TIME").then(|| "maybe".to_string()))
+            .unwrap_err()
+            .to_string();
+        assert!(
+            err.contains("WETWARE_THOUGHTS_SHOW_TIME: Invalid value 'maybe'"),
+            "{err}"
+        );
+    }
+
+    #[test]
+    fn test_set_and_unset_in_file_touch_only_that_key() {
+        let temp = TempDir::new().unwrap();
+        ensure_config(temp.path()).unwrap();
+        set_in_file(temp.path(), "thoughts.order", "ascending").unwrap();
+        set_in_file(temp.path(), "thoughts.show_time", "true").unwrap();
+
+        let settings = list_settings(temp.path()).unwrap();
+        let source = |key: &str| settings.iter().find(|s| s.key == key).unwrap().source;
+        assert_eq!(source("thoughts.order"), Source::File);
+        assert_eq!(source("thoughts.timezone"), Source::Default);
+
+        assert!(unset_in_file(temp.path(), "thoughts.order").unwrap());
+        assert!(!unset_in_file(temp.path(), "thoughts.order").unwrap());
+        let contents = std::fs::read_to_string(temp.path().join("config.toml")).unwrap();
+        assert_eq!(contents, "version = 1\n\n[thoughts]\nshow_time = true\n");
+
+        assert!(unset_in_file(temp.path(), "version").is_err());
+    }
+
+    #[test]
+    fn test_list_lists_relation_types_from_the_file() {
+        let temp = TempDir::new().unwrap();
+        std::fs::write(
+            temp.path().join("config.toml"),
+            "version = 1\n\n[[relations.types]]\nname = \"knows\"\ndirected = false\n",
+        )
+        .unwrap();
+        let settings = list_settings(temp.path()).unwrap();
+        let types = settings.iter().find(|s| s.key == "relations.types").unwrap();
+        assert_eq!(types.source, Source::File);
+        assert!(types.value.contains("knows"));
+
+        let err = set_in_file(temp.path(), "relations.types", "x")
+            .unwrap_err()
+            .to_string();
+        assert!(err.contains("use `wet config edit`"), "{err}");
+    }
+
+    #[test]
+    fn test_validate_rejects_unknown_keys_and_bad_values() {
+        assert!(validate("version = 1\n# comment\n[thoughts]\norder = \"ascending\"\n").is_ok());
+
+        let err = validate("version = 1\n[thoughts]\nordr = \"ascending\"\n")
+            .unwrap_err()
+            .to_string();
+        assert!(err.contains("Unknown config key: thoughts.ordr"), "{err}");
+
+        let err = validate("version = 1\n[thoughts]\norder = \"up\"\n")
+            .unwrap_err()
+            .to_string();
+        assert!(err.contains("Valid values: ascending, descending"), "{err}");
+
+        let err = validate("[thoughts\n").unwrap_err().to_string();
+        assert!(err.contains("Malformed config file"), "{err}");
+    }
+
+    #[test]
+    fn test_unknown_keys_follow_the_fields_not_the_values() {
+        // An empty list is skipped when serializing, but is still a known key
+        assert!(validate("version = 1\n[relations]\ntypes = []\n").is_ok());
+
+        let document: DocumentMut = "version = 1\n[relations]\ntypes = []\n[[relations.extra]]\nname = \"x\"\n"
+            .parse()
+            .unwrap();
+        assert_eq!(unknown_keys(&document).unwrap(), ["relations.extra"]);
+
+        let document: DocumentMut = "version = 1\n[[relations.types]]\nname = \"knows\"\ncolour = \"red\"\n"
+            .parse()
+            .unwrap();
+        assert_eq!(unknown_keys(&document).unwrap(), ["relations.types.0.colour"]);
+    }
+
+    /// Version 1 spelled the sort order `thoughts.sort`
+    fn rename_sort(document: &mut DocumentMut) {
+        if let Some(thoughts) = document.get_mut("thoughts").and_then(Item::as_table_like_mut)
+            && let Some(sort) = thoughts.remove("sort")
+        {
+            thoughts.insert("order", sort);
+        }
+    }
+
+    fn mark_step_two(document: &mut DocumentMut) {
+        document["step_two"] = toml_edit::value(true);
+    }
+
+    #[test]
+    fn test_migrate_runs_pending_steps_and_keeps_comments() {
+        let mut document: DocumentMut =
+            "# my settings\nversion = 1\n\n[thoughts]\nsort = \"ascending\" # oldest first\n"
+                .parse()
+                .unwrap();
+        assert_eq!(migrate(&mut document, &[rename_sort, mark_step_two]).unwrap(), Some(1));
+        let text = document.to_string();
+        assert!(text.starts_with("# my settings\nversion = 3\n"), "{text}");
+        assert!(text.contains("order = \"ascending\""), "{text}");
+        assert!(!text.contains("sort"), "{text}");
+        assert!(text.contains("step_two = true"), "{text}");
+
+        // Already at version 2: only the second step runs
+        let mut document: DocumentMut = "version = 2\n[thoughts]\nsort = \"x\"\n".parse().unwrap();
+        assert_eq!(migrate(&mut document, &[rename_sort, mark_step_two]).unwrap(), Some(2));
+        assert!(document.to_string().contains("sort = \"x\""));
+
+        let mut document: DocumentMut = "version = 3\n".parse().unwrap();
+        assert_eq!(migrate(&mut document, &[rename_sort, mark_step_two]).unwrap(), None);
+    }
+
+    #[test]
+    fn test_load_backs_up_before_upgrading_in_place() {
+        let temp = TempDir::new().unwrap();
+        let old = "version = 1\n\n# keep this\n[thoughts]\nsort = \"ascending\"\n";
+        std::fs::write(temp.path().join("config.toml"), old).unwrap();
+
+        let config = load_with(temp.path(), &[rename_sort]).unwrap();
+        assert_eq!(config.version, 2);
+        assert_eq!(config.thoughts.order, SortOrder::Ascending);
+        assert_eq!(
+            std::fs::read_to_string(temp.path().join("config.toml.v1.bak")).unwrap(),
+            old
+        );
+        let upgraded = std::fs::read_to_string(temp.path().join("config.toml")).unwrap();
+        assert_eq!(
+            upgraded,
+            "version = 2\n\n# keep this\n[thoughts]\norder = \"ascending\"\n"
+        );
+    }
+
+    #[test]
+    fn test_validate_checks_the_migrated_document() {
+        let config = validate_with("version = 1\n[thoughts]\nsort = \"ascending\"\n", &[rename_sort]).unwrap();
+        assert_eq!(config.version, 2);
+        assert_eq!(config.thoughts.order, SortOrder::Ascending);
+
+        let err = validate_with("version = 1\n[thoughts]\nsort = \"up\"\n", &[rename_sort])
+            .unwrap_err()
+            .to_string();
+        assert!(err.contains("Valid values: ascending, descending"), "{err}");
+    }
+
+    #[test]
+    fn test_load_rejects_a_newer_version() {
+        let temp = TempDir::new().unwrap();
+        let newer = CURRENT_VERSION + 1;
+        std::fs::write(temp.path().join("config.toml"), format!("version = {newer}\n")).unwrap();
+        let err = load_config(temp.path()).unwrap_err().to_string();
+        assert!(err.contains(&format!("version {newer}")), "{err}");
+        assert!(err.contains("Upgrade wet"), "{err}");
+    }
+
+    #[test]
+    fn test_save_config_keeps_comments_and_unknown_keys() {
+        let temp = TempDir::new().unwrap();
+        std::fs::write(
+            temp.path().join("config.toml"),
+            "# personal setup\nversion = 1\n\n[thoughts]\nordr = \"ascending\" # typo\nshow_time = true\n",
+        )
+        .unwrap();
+        assert_eq!(
+            unknown_keys(&read_document(temp.path()).unwrap()).unwrap(),
+            ["thoughts.ordr"]
+        );
+
+        let mut config = ensure_config(temp.path()).unwrap();
+        config.thoughts.order = SortOrder::Ascending;
+        save_config(temp.path(), &config).unwrap();
+        let saved = std::fs::read_to_string(temp.path().join("config.toml")).unwrap();
+        assert!(saved.starts_with("# personal setup\n"), "{saved}");
+        assert!(saved.contains("ordr = \"ascending\" # typo"), "{saved}");
+        assert!(saved.contains("order = \"ascending\""), "{saved}");
+        assert!(!saved.contains("timezone"), "Unchanged defaults stay out: {saved}");
+    }
 }
@@ -27,6 +27,12 @@ pub enum ThoughtError {
     #[error("Entity '{0}' already exists")]
     EntityAlreadyExists(String),
 
+    #[error("Notebook '{0}' not found; create it with `wet notebook create {0}`")]
+    NotebookNotFound(String),
+
+    #[error("Notebook '{0}' already exists")]
+    NotebookAlreadyExists(String),
+
     #[error("Thought with ID {0} not found")]
     ThoughtNotFound(i64),
 
@@ -54,8 +60,28 @@ pub enum ThoughtError {
         existing_entity: String,
     },
 
+    #[error("Invalid query: {0}")]
+    InvalidQuery(String),
+
     #[error("Cannot merge entity '{0}' into itself")]
     SelfMerge(String),
+
+    #[error(
+        "Entity '{name}' is still referenced by {thoughts} thought(s) and {descriptions} description(s); \
+         use --references strip or --references dangle to delete it anyway"
+    )]
+    EntityStillReferenced {
+        name: String,
+        thoughts: usize,
+        descriptions: usize,
+    },
+
+    #[error(
+        "Descriptions differ between the databases for {}; \
+         use --descriptions keep, theirs or append to merge anyway",
+        .0.join(", ")
+    )]
+    DescriptionConflicts(Vec<String>),
 }
 
 #[cfg(test)]
@@ -92,6 +118,20 @@ mod tests {
         assert_eq!(err.to_string(), "Thought with ID 42 not found");
     }
 
+    #[test]
+    fn test_entity_still_referenced_error_message() {
+        let err = ThoughtError::EntityStillReferenced {
+            name: "Sara".to_string(),
+            thoughts: 2,
+            descriptions: 1,
+        };
+        assert_eq!(
+            err.to_string(),
+            "Entity 'Sara' is still referenced by 2 thought(s) and 1 description(s); \
+             use --references strip or --references dangle to delete it anyway"
+        );
+    }
+
     #[test]
     fn test_thought_not_found_preserves_id() {
         let err = ThoughtError::ThoughtNotFound(999);
@@ -48,6 +48,33 @@ pub fn launch_editor(initial_content: Option<&str>) -> Result<String, ThoughtErr
     Ok(content)
 }
 
+/// Build an editor buffer: `body`, a blank line, then `comments` as `#` lines
+///
+/// Paired with [`strip_comments`], which removes the comment lines again once the
+/// user has saved, the way git handles a commit message template.
+pub fn with_comments(body: &str, comments: &[String]) -> String {
+    let mut buffer = body.to_string();
+    buffer.push_str("\n\n");
+    for comment in comments {
+        if comment.is_empty() {
+            buffer.push_str("#\n");
+        } else {
+            buffer.push_str(&format!("# {}\n", comment));
+        }
+    }
+    buffer
+}
+
+/// Remove every line starting with `#` and trim the rest
+pub fn strip_comments(text: &str) -> String {
+    text.lines()
+        .filter(|line| !line.starts_with('#'))
+        .collect::<Vec<_>>()
+        .join("\n")
+        .trim()
+        .to_string()
+}
+
 /// Find available editor from fallback chain
 fn which_editor() -> Option<String> {
     for editor in ["vim", "nano", "vi"] {
@@ -79,6 +106,22 @@ mod tests {
         }
     }
 
+    #[test]
+    fn test_with_comments_and_strip_comments_round_trip() {
+        let comments = vec!["Error: too long".to_string(), String::new(), "Syntax help".to_string()];
+        let buffer = with_comments("First line\n  indented # not a comment", &comments);
+        assert_eq!(
+            buffer,
+            "First line\n  indented # not a comment\n\n# Error: too long\n#\n# Syntax help\n"
+        );
+        assert_eq!(strip_comments(&buffer), "First line\n  indented # not a comment");
+    }
+
+    #[test]
+    fn test_strip_comments_of_untouched_template_is_empty() {
+        assert_eq!(strip_comments(&with_comments("", &["Help".to_string()])), "");
+    }
+
     #[test]
     fn test_launch_editor_with_mock_editor() {
         // Use 'cat' as a mock editor that just outputs the file
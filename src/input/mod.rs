@@ -1,2 +1,4 @@
 // Input validation and handling module
 pub mod editor;
+pub mod prompt;
+pub mod stdin;
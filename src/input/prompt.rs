@@ -0,0 +1,47 @@
+// Line-based prompts on stdin for interactive CLI confirmation
+// Used where a command needs a decision per item before changing stored data
+
+use crate::errors::ThoughtError;
+use std::io::{self, BufRead, Write};
+
+/// Print `question` to stdout and read a single line of response from stdin
+///
+/// # Arguments
+/// * `question` - Prompt text, printed without a trailing newline
+///
+/// # Returns
+/// * `Ok(String)` - The answer, trimmed and lowercased (empty on EOF)
+/// * `Err(ThoughtError)` - Reading stdin or flushing stdout failed
+pub fn ask(question: &str) -> Result<String, ThoughtError> {
+    print!("{}", question);
+    io::stdout().flush()?;
+    read_answer(&mut io::stdin().lock())
+}
+
+/// Read one answer line from `reader`, trimmed and lowercased
+///
+/// End of input yields an empty answer, which callers treat as their default.
+fn read_answer<R: BufRead>(reader: &mut R) -> Result<String, ThoughtError> {
+    let mut line = String::new();
+    reader.read_line(&mut line)?;
+    Ok(line.trim().to_lowercase())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::io::Cursor;
+
+    #[test]
+    fn test_read_answer_trims_and_lowercases() {
+        let mut input = Cursor::new("  Yes \nno\n");
+        assert_eq!(read_answer(&mut input).unwrap(), "yes");
+        assert_eq!(read_answer(&mut input).unwrap(), "no");
+    }
+
+    #[test]
+    fn test_read_answer_eof_is_empty() {
+        let mut input = Cursor::new("");
+        assert_eq!(read_answer(&mut input).unwrap(), "");
+    }
+}
@@ -0,0 +1,80 @@
+// Content read from stdin for commands that accept piped text
+// `-` asks for stdin explicitly; a missing argument falls back to it when stdin isn't a terminal
+
+use crate::errors::ThoughtError;
+use std::io::{self, IsTerminal, Read};
+
+/// The content a command was given: the argument itself, or all of stdin
+///
+/// # Arguments
+/// * `content` - The positional argument; `-` or `None` reads stdin
+///
+/// # Returns
+/// * `Ok(String)` - The argument as given, or stdin with surrounding whitespace trimmed
+/// * `Err(ThoughtError)` - No argument and stdin is a terminal, or reading stdin failed
+pub fn read_content(content: Option<String>) -> Result<String, ThoughtError> {
+    let stdin = io::stdin();
+    let piped = !stdin.is_terminal();
+    resolve(content, piped, &mut stdin.lock(), true)
+}
+
+/// Like [`read_content`], but stdin is returned exactly as read, so line numbers in
+/// it stay those of the input (`wet add --batch` reports errors by line)
+pub fn read_raw_content(content: Option<String>) -> Result<String, ThoughtError> {
+    let stdin = io::stdin();
+    let piped = !stdin.is_terminal();
+    resolve(content, piped, &mut stdin.lock(), false)
+}
+
+/// Resolve `content` against `reader`, which stands in for stdin, trimming what
+/// is read if `trim`
+fn resolve<R: Read>(content: Option<String>, piped: bool, reader: &mut R, trim: bool) -> Result<String, ThoughtError> {
+    match content.as_deref() {
+        Some("-") => {}
+        Some(_) => return Ok(content.unwrap_or_default()),
+        None if piped => {}
+        None => {
+            return Err(ThoughtError::InvalidInput(
+                "No content given. Pass it as an argument, or pipe it in (e.g. `... | wet add -`)".to_string(),
+            ));
+        }
+    }
+    let mut input = String::new();
+    reader.read_to_string(&mut input)?;
+    Ok(if trim { input.trim().to_string() } else { input })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::io::Cursor;
+
+    #[test]
+    fn test_argument_is_used_as_given() {
+        let mut stdin = Cursor::new("ignored");
+        let content = resolve(Some(" note ".to_string()), true, &mut stdin, true).unwrap();
+        assert_eq!(content, " note ");
+    }
+
+    #[test]
+    fn test_dash_or_piped_stdin_is_read_and_trimmed() {
+        let content = resolve(Some("-".to_string()), false, &mut Cursor::new("\nfrom dash\n"), true).unwrap();
+        assert_eq!(content, "from dash");
+        let content = resolve(None, true, &mut Cursor::new("line one\nline two\n"), true).unwrap();
+        assert_eq!(content, "line one\nline two");
+    }
+
+    #[test]
+    fn test_raw_stdin_keeps_leading_blank_lines() {
+        let content = resolve(None, true, &mut Cursor::new("\n\nfirst\n"), false).unwrap();
+        assert_eq!(content, "\n\nfirst\n");
+    }
+
+    #[test]
+    fn test_missing_argument_on_a_terminal_is_an_error() {
+        let err = resolve(None, false, &mut Cursor::new(""), true)
+            .unwrap_err()
+            .to_string();
+        assert!(err.contains("No content given"));
+    }
+}
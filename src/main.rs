@@ -1,20 +1,21 @@
-use clap::Parser;
-use std::env;
-use std::path::PathBuf;
+use clap::{CommandFactory, Parser};
+use clap_complete::CompleteEnv;
 use std::process;
-use wetware::cli::{Cli, Commands, EntityCommands};
+use wetware::cli::{Cli, Commands, ConfigCommands, DbCommands, EntityCommands, NotebookCommands, ThoughtCommands};
 use wetware::config;
-use wetware::storage::{default_db_path_in, ensure_data_dir, resolve_data_dir};
+use wetware::storage::{
+    DATA_DIR_ENV, DB_PATH_ENV, ensure_data_dir, get_connection, path_from_env, resolve_data_dir, resolve_db_path,
+    run_migrations_in,
+};
 
 fn main() {
+    // Answer a shell's completion request (COMPLETE=<shell>) before anything else
+    CompleteEnv::with_factory(Cli::command).complete();
+
     let cli = Cli::parse();
 
     // Resolve data directory: WETWARE_DATA_DIR env var > XDG default (release only)
-    let data_dir_override = env::var("WETWARE_DATA_DIR")
-        .ok()
-        .filter(|v| !v.is_empty())
-        .map(PathBuf::from);
-    let data_dir = match resolve_data_dir(data_dir_override.as_deref()) {
+    let data_dir = match resolve_data_dir(path_from_env(DATA_DIR_ENV).as_deref()) {
         Ok(dir) => dir,
         Err(e) => {
             eprintln!("Error: {e}");
@@ -27,63 +28,307 @@ fn main() {
         eprintln!("Error creating data directory: {e}");
         process::exit(1);
     }
-    let config = match config::ensure_config(&data_dir) {
+    // `config edit` and `config --unset` are how an invalid config file gets fixed, so
+    // they run on the defaults when it doesn't load
+    let repairs_config = matches!(
+        cli.command,
+        Commands::Config {
+            command: Some(ConfigCommands::Edit),
+            ..
+        } | Commands::Config { unset: true, .. }
+    );
+    let config = match config::ensure_config(&data_dir).and_then(config::Config::with_env) {
         Ok(c) => c,
+        Err(e) if repairs_config => {
+            eprintln!("Warning: {e}. Using the default settings until the config file is fixed.");
+            config::Config::default()
+        }
         Err(e) => {
             eprintln!("Error initializing config: {e}");
             process::exit(1);
         }
     };
 
-    // Database path: WETWARE_DB env var > <data_dir>/default.db
-    let db_path = env::var("WETWARE_DB")
-        .ok()
-        .filter(|v| !v.is_empty())
-        .map(PathBuf::from)
-        .unwrap_or_else(|| default_db_path_in(&data_dir));
+    // Database path: --notebook > WETWARE_DB env var > <data_dir>/<notebooks.default>.db
+    let db_path = match resolve_db_path(
+        &data_dir,
+        cli.notebook.as_deref(),
+        path_from_env(DB_PATH_ENV),
+        &config.notebooks.default,
+    ) {
+        Ok(path) => path,
+        Err(e) => {
+            eprintln!("Error: {e}");
+            process::exit(1);
+        }
+    };
+
+    let timestamps = config.thoughts.timestamp_format();
+    let timezone = timestamps.timezone;
+
+    // Migrate an existing database before any command opens it, so legacy backdated
+    // thoughts are dated in the configured zone rather than the system one (`config`
+    // never opens it, and may be running on the defaults)
+    if !matches!(cli.command, Commands::Config { .. })
+        && db_path.exists()
+        && let Err(e) = get_connection(&db_path).and_then(|conn| run_migrations_in(&conn, timezone))
+    {
+        eprintln!("Error migrating database: {e}");
+        process::exit(1);
+    }
 
     let result = match cli.command {
-        Commands::Config { key, value } => wetware::cli::config::execute(&data_dir, key, value),
-        Commands::Delete { id } => wetware::cli::delete::execute(id, &db_path),
-        Commands::Tui => wetware::cli::tui::execute(&db_path, config.thoughts.order),
-        Commands::Add { content, date } => wetware::cli::add::execute(content, date, &db_path),
+        Commands::Config {
+            command,
+            key,
+            value,
+            list,
+            unset,
+        } => match (command, key) {
+            (Some(ConfigCommands::Edit), _) => wetware::cli::config::execute_edit(&data_dir),
+            (None, Some(key)) if unset => wetware::cli::config::execute_unset(&data_dir, &key),
+            (None, Some(key)) if !list => wetware::cli::config::execute(&data_dir, key, value),
+            (None, _) => wetware::cli::config::execute_list(&data_dir),
+        },
+        Commands::Delete { id } => wetware::cli::delete::execute(id, timestamps, &db_path),
+        Commands::Tui => wetware::cli::tui::execute(&db_path, config.thoughts.order, timestamps),
+        Commands::Completions { shell } => wetware::cli::completions::execute(shell),
+        Commands::Add {
+            content,
+            date,
+            batch,
+            editor,
+            interactive,
+        } => {
+            if editor {
+                wetware::cli::add::execute_with_editor(date, timezone, &db_path)
+            } else if interactive {
+                wetware::cli::add::execute_interactive(date, timezone, &db_path)
+            } else {
+                match batch {
+                    Some(mode) => wetware::input::stdin::read_raw_content(content)
+                        .and_then(|content| wetware::cli::add::execute_batch(&content, mode, date, timezone, &db_path)),
+                    None => wetware::input::stdin::read_content(content)
+                        .and_then(|content| wetware::cli::add::execute(content, date, timezone, &db_path)),
+                }
+            }
+        }
         Commands::Edit {
             id,
             content,
             date,
             editor,
-        } => wetware::cli::edit::execute(id, content, date, editor, &db_path),
-        Commands::Thoughts { on } => {
-            wetware::cli::thoughts::execute(&db_path, on.as_deref(), cli.color, config.thoughts.order)
+        } => wetware::cli::edit::execute(id, content, date, editor, timezone, &db_path),
+        Commands::Thoughts { on, where_query } => wetware::cli::thoughts::execute(
+            &db_path,
+            on.as_deref(),
+            where_query.as_deref(),
+            cli.color,
+            config.thoughts.order,
+            timestamps,
+        ),
+        Commands::Thought { command } => match command {
+            ThoughtCommands::Show { id, json } => {
+                wetware::cli::thought_show::execute(id, timestamps, &db_path, cli.color, json)
+            }
+        },
+        Commands::Entities { kind, attributes, tree } => {
+            if tree {
+                wetware::cli::entities::execute_tree(&db_path)
+            } else {
+                wetware::cli::entities::execute(&db_path, kind.as_deref(), &attributes)
+            }
         }
-        Commands::Entities => wetware::cli::entities::execute(&db_path),
+        Commands::Graph {
+            format,
+            root,
+            depth,
+            aliases,
+            no_co_occurrence,
+            min_weight,
+        } => wetware::cli::graph::execute(
+            wetware::cli::graph::ExportOptions {
+                format,
+                root,
+                depth,
+                aliases,
+                co_occurrence: !no_co_occurrence,
+                min_weight,
+            },
+            &config.relations.types,
+            &db_path,
+        ),
+        Commands::Stats {
+            by,
+            periods,
+            stale_days,
+        } => wetware::cli::stats::execute(
+            wetware::cli::stats::StatsOptions {
+                period: by,
+                periods,
+                stale_days,
+            },
+            timezone,
+            &db_path,
+            cli.color,
+        ),
+        Commands::Notebook { command } => match command {
+            NotebookCommands::List => wetware::cli::notebook::execute_list(&data_dir, &config),
+            NotebookCommands::Create { name } => wetware::cli::notebook::execute_create(&data_dir, &name),
+            NotebookCommands::Rename { name, new_name } => {
+                wetware::cli::notebook::execute_rename(&data_dir, &name, &new_name, &config)
+            }
+            NotebookCommands::Delete { name, yes } => {
+                wetware::cli::notebook::execute_delete(&data_dir, &name, yes, &config)
+            }
+        },
+        Commands::Db { command } => match command {
+            DbCommands::Merge {
+                other,
+                descriptions,
+                dry_run,
+            } => wetware::cli::db_merge::execute(
+                &other,
+                descriptions,
+                dry_run,
+                timezone,
+                &config.relations.types,
+                &db_path,
+            ),
+        },
         Commands::Entity { command } => match command {
             EntityCommands::Edit {
                 entity_name,
                 description,
                 description_file,
-            } => wetware::cli::entity_edit::execute(&entity_name, description, description_file, &db_path),
+                kind,
+            } => wetware::cli::entity_edit::execute(&entity_name, description, description_file, kind, &db_path),
             EntityCommands::Rename { entity_name, new_name } => {
                 wetware::cli::entity_rename::execute(&entity_name, &new_name, &db_path)
             }
             EntityCommands::Merge { entity_name, into } => {
                 wetware::cli::entity_merge::execute(&entity_name, &into, &db_path)
             }
-            EntityCommands::Show { entity_name } => {
-                wetware::cli::entity_show::execute(&entity_name, &db_path, cli.color)
-            }
-            EntityCommands::Relate { entity_name, parent } => {
-                wetware::cli::entity_relate::execute_relate(&entity_name, &parent, &db_path)
+            EntityCommands::Split {
+                entity_name,
+                into,
+                since,
+                until,
+                with,
+                interactive,
+                move_aliases,
+                copy_relations,
+            } => wetware::cli::entity_split::execute(
+                &entity_name,
+                &into,
+                wetware::cli::entity_split::SplitOptions {
+                    since,
+                    until,
+                    with,
+                    interactive,
+                    move_aliases,
+                    copy_relations,
+                },
+                timestamps,
+                &db_path,
+                cli.color,
+            ),
+            EntityCommands::Show {
+                entity_name,
+                limit,
+                all,
+                since,
+                until,
+                no_descendants,
+            } => wetware::cli::entity_show::execute(
+                &entity_name,
+                wetware::cli::entity_show::ShowOptions {
+                    limit: (!all).then_some(limit),
+                    since,
+                    until,
+                    include_descendants: !no_descendants,
+                },
+                timestamps,
+                &db_path,
+                cli.color,
+                &config.relations.types,
+            ),
+            EntityCommands::Delete {
+                entity_name,
+                references,
+                dry_run,
+                yes,
+            } => wetware::cli::entity_delete::execute(&entity_name, references, dry_run, yes, &db_path),
+            EntityCommands::Timeline { entity_name, by } => {
+                wetware::cli::entity_timeline::execute(&entity_name, by, timestamps, &db_path, cli.color)
             }
-            EntityCommands::Unrelate { entity_name, parent } => {
-                wetware::cli::entity_relate::execute_unrelate(&entity_name, &parent, &db_path)
+            EntityCommands::Related {
+                entity_name,
+                metric,
+                since,
+                until,
+                limit,
+            } => wetware::cli::entity_related::execute(
+                &entity_name,
+                wetware::cli::entity_related::RelatedOptions {
+                    metric,
+                    since,
+                    until,
+                    limit,
+                },
+                timezone,
+                &db_path,
+                cli.color,
+            ),
+            EntityCommands::Duplicates => wetware::cli::entity_duplicates::execute(&db_path),
+            EntityCommands::Mentions { entity_name, link, yes } => {
+                wetware::cli::entity_mentions::execute(&entity_name, link, yes, timestamps, &db_path, cli.color)
             }
+            EntityCommands::Relate {
+                entity_name,
+                target,
+                parent,
+                relation_type,
+            } => match (parent, relation_type, target) {
+                (Some(parent), _, _) => wetware::cli::entity_relate::execute_relate(&entity_name, &parent, &db_path),
+                (None, Some(relation_type), Some(target)) => wetware::cli::entity_relate::execute_relate_as(
+                    &entity_name,
+                    &relation_type,
+                    &target,
+                    &config.relations.types,
+                    &db_path,
+                ),
+                _ => unreachable!("clap requires --parent or --as with a target"),
+            },
+            EntityCommands::Unrelate {
+                entity_name,
+                target,
+                parent,
+                relation_type,
+            } => match (parent, relation_type, target) {
+                (Some(parent), _, _) => wetware::cli::entity_relate::execute_unrelate(&entity_name, &parent, &db_path),
+                (None, Some(relation_type), Some(target)) => wetware::cli::entity_relate::execute_unrelate_as(
+                    &entity_name,
+                    &relation_type,
+                    &target,
+                    &config.relations.types,
+                    &db_path,
+                ),
+                _ => unreachable!("clap requires --parent or --as with a target"),
+            },
             EntityCommands::Alias { entity_name, alias } => {
                 wetware::cli::entity_alias::execute_alias(&entity_name, &alias, &db_path)
             }
             EntityCommands::Unalias { entity_name, alias } => {
                 wetware::cli::entity_alias::execute_unalias(&entity_name, &alias, &db_path)
             }
+            EntityCommands::Set {
+                entity_name,
+                assignments,
+            } => wetware::cli::entity_attribute::execute_set(&entity_name, &assignments, &db_path),
+            EntityCommands::Unset { entity_name, keys } => {
+                wetware::cli::entity_attribute::execute_unset(&entity_name, &keys, &db_path)
+            }
         },
     };
 
@@ -1,3 +1,5 @@
+use crate::models::entity_kind::EntityKind;
+
 /// Entity domain model
 #[derive(Debug, Clone, PartialEq)]
 pub struct Entity {
@@ -5,6 +7,7 @@ pub struct Entity {
     pub name: String,                // Lowercase for case-insensitive lookups
     pub canonical_name: String,      // Original capitalization for display
     pub description: Option<String>, // Optional multi-paragraph description
+    pub kind: Option<EntityKind>,    // Optional classification (person, place, ...)
 }
 
 impl Entity {
@@ -15,6 +18,7 @@ impl Entity {
             name: name.to_lowercase(), // Normalize for case-insensitive matching
             canonical_name: name,      // Preserve original capitalization
             description: None,
+            kind: None,
         }
     }
 
@@ -25,6 +29,7 @@ impl Entity {
             name: name.to_lowercase(),
             canonical_name: name,
             description,
+            kind: None,
         }
     }
 
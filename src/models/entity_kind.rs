@@ -0,0 +1,91 @@
+use serde::{Deserialize, Serialize};
+use std::fmt;
+use std::str::FromStr;
+
+/// What sort of thing an entity is. Optional on every entity; unset means "not
+/// classified yet", not a kind of its own.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, PartialOrd, Ord, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum EntityKind {
+    /// A person
+    Person,
+    /// A company, team or other group of people
+    Organization,
+    /// A project or piece of work
+    Project,
+    /// A location
+    Place,
+    /// Something that happened at a particular time
+    Event,
+    /// An idea, topic or technology
+    Concept,
+}
+
+impl EntityKind {
+    /// Every kind, in display order
+    pub const ALL: [EntityKind; 6] = [
+        EntityKind::Person,
+        EntityKind::Organization,
+        EntityKind::Project,
+        EntityKind::Place,
+        EntityKind::Event,
+        EntityKind::Concept,
+    ];
+
+    /// The lowercase name stored in the database and accepted on the command line
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            EntityKind::Person => "person",
+            EntityKind::Organization => "organization",
+            EntityKind::Project => "project",
+            EntityKind::Place => "place",
+            EntityKind::Event => "event",
+            EntityKind::Concept => "concept",
+        }
+    }
+}
+
+impl fmt::Display for EntityKind {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(self.as_str())
+    }
+}
+
+impl FromStr for EntityKind {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let wanted = s.trim().to_lowercase();
+        EntityKind::ALL
+            .into_iter()
+            .find(|kind| kind.as_str() == wanted)
+            .ok_or_else(|| {
+                let names: Vec<&str> = EntityKind::ALL.iter().map(|k| k.as_str()).collect();
+                format!("Invalid entity kind: '{s}'. Valid values: {}", names.join(", "))
+            })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_round_trips_through_string() {
+        for kind in EntityKind::ALL {
+            assert_eq!(kind.to_string().parse::<EntityKind>().unwrap(), kind);
+        }
+    }
+
+    #[test]
+    fn test_from_str_is_case_insensitive() {
+        assert_eq!("Person".parse::<EntityKind>().unwrap(), EntityKind::Person);
+    }
+
+    #[test]
+    fn test_from_str_invalid_lists_valid_values() {
+        let err = "animal".parse::<EntityKind>().unwrap_err();
+        assert!(err.contains("Invalid entity kind"));
+        assert!(err.contains("person, organization, project, place, event, concept"));
+    }
+}
@@ -1,7 +1,13 @@
 pub mod entity;
+pub mod entity_kind;
+pub mod relation_type;
 pub mod sort_order;
 pub mod thought;
+pub mod timezone;
 
 pub use entity::Entity;
+pub use entity_kind::EntityKind;
+pub use relation_type::RelationType;
 pub use sort_order::SortOrder;
 pub use thought::Thought;
+pub use timezone::{TimestampFormat, Timezone};
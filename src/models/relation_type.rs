@@ -0,0 +1,125 @@
+use crate::models::entity::Entity;
+use serde::{Deserialize, Serialize};
+
+/// Name of the built-in hierarchy relation, stored in `entity_relations` rather than
+/// alongside the other types: `A child-of B` is the same edge as `wet entity relate A
+/// --parent B`.
+pub const HIERARCHY: &str = "child-of";
+
+fn default_directed() -> bool {
+    true
+}
+
+/// A named kind of relation between two entities ("works-at", "located-in", ...).
+///
+/// Built-in types come from [`RelationType::builtin`]; more can be declared in the
+/// config file under `[[relations.types]]`.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct RelationType {
+    /// Name used on the command line and stored with each edge, e.g. `works-at`
+    pub name: String,
+    /// How the relation reads from the target's side (`employs` for `works-at`).
+    /// Unused for undirected types.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub inverse: Option<String>,
+    /// Whether `A rel B` means something different from `B rel A`
+    #[serde(default = "default_directed")]
+    pub directed: bool,
+}
+
+impl RelationType {
+    fn directed(name: &str, inverse: &str) -> Self {
+        Self {
+            name: name.to_string(),
+            inverse: Some(inverse.to_string()),
+            directed: true,
+        }
+    }
+
+    /// The types every database understands, hierarchy first.
+    pub fn builtin() -> Vec<RelationType> {
+        vec![
+            Self::directed(HIERARCHY, "parent-of"),
+            Self::directed("works-at", "employs"),
+            Self::directed("located-in", "location-of"),
+            Self::directed("part-of", "has-part"),
+            Self {
+                name: "related-to".to_string(),
+                inverse: None,
+                directed: false,
+            },
+        ]
+    }
+
+    /// Whether this is the built-in parent/child hierarchy
+    pub fn is_hierarchy(&self) -> bool {
+        self.name == HIERARCHY
+    }
+
+    /// How an edge of this type reads from one of its ends: the type's own name from
+    /// the source, and its inverse (or `<name> (from)` if none is declared) from the
+    /// target. Undirected types read the same both ways.
+    pub fn label(&self, outgoing: bool) -> String {
+        if outgoing || !self.directed {
+            return self.name.clone();
+        }
+        match &self.inverse {
+            Some(inverse) => inverse.clone(),
+            None => format!("{} (from)", self.name),
+        }
+    }
+}
+
+/// One typed relation edge as seen from a given entity
+#[derive(Debug, Clone, PartialEq)]
+pub struct TypedRelation {
+    /// Stored relation type name
+    pub relation_type: String,
+    /// True when the entity is the edge's source (`entity rel other`)
+    pub outgoing: bool,
+    /// The entity at the other end
+    pub other: Entity,
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_builtin_starts_with_hierarchy() {
+        let builtin = RelationType::builtin();
+        assert!(builtin[0].is_hierarchy());
+        assert!(builtin.iter().any(|t| t.name == "works-at"));
+    }
+
+    #[test]
+    fn test_label_uses_inverse_for_incoming_edges() {
+        let works_at = RelationType::directed("works-at", "employs");
+        assert_eq!(works_at.label(true), "works-at");
+        assert_eq!(works_at.label(false), "employs");
+    }
+
+    #[test]
+    fn test_label_without_inverse() {
+        let mentors = RelationType {
+            name: "mentors".to_string(),
+            inverse: None,
+            directed: true,
+        };
+        assert_eq!(mentors.label(false), "mentors (from)");
+
+        let related = RelationType {
+            name: "related-to".to_string(),
+            inverse: None,
+            directed: false,
+        };
+        assert_eq!(related.label(false), "related-to");
+    }
+
+    #[test]
+    fn test_deserialize_defaults_to_directed() {
+        let parsed: RelationType = toml::from_str("name = \"mentors\"\ninverse = \"mentored-by\"\n").unwrap();
+        assert!(parsed.directed);
+        assert_eq!(parsed.inverse.as_deref(), Some("mentored-by"));
+    }
+}
@@ -0,0 +1,201 @@
+use chrono::{DateTime, Duration, FixedOffset, Local, NaiveDate, NaiveTime, TimeZone, Utc};
+use chrono_tz::Tz;
+use serde::{Deserialize, Deserializer, Serialize, Serializer};
+use std::fmt;
+use std::str::FromStr;
+
+/// The time zone thoughts are dated in.
+///
+/// Timestamps are stored as UTC instants; this zone decides which calendar day a
+/// thought falls on, where a day passed as `--date` or `--since` starts, and how
+/// times are shown.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum Timezone {
+    /// The system's local time zone
+    #[default]
+    Local,
+    /// A named IANA time zone, e.g. `Europe/Prague`
+    Named(Tz),
+}
+
+impl Timezone {
+    /// The instant as a wall-clock time with its UTC offset in this zone.
+    pub fn localize(&self, instant: DateTime<Utc>) -> DateTime<FixedOffset> {
+        match self {
+            Timezone::Local => instant.with_timezone(&Local).fixed_offset(),
+            Timezone::Named(tz) => instant.with_timezone(tz).fixed_offset(),
+        }
+    }
+
+    /// The calendar day the instant falls on in this zone.
+    pub fn date_of(&self, instant: DateTime<Utc>) -> NaiveDate {
+        self.localize(instant).date_naive()
+    }
+
+    /// Today's date in this zone.
+    pub fn today(&self) -> NaiveDate {
+        self.date_of(Utc::now())
+    }
+
+    /// The instant `day` begins in this zone: local midnight, or the end of the gap
+    /// when a DST change skips midnight.
+    pub fn start_of_day(&self, day: NaiveDate) -> DateTime<Utc> {
+        match self {
+            Timezone::Local => start_of_day_in(&Local, day),
+            Timezone::Named(tz) => start_of_day_in(tz, day),
+        }
+    }
+}
+
+fn start_of_day_in<Z: TimeZone>(zone: &Z, day: NaiveDate) -> DateTime<Utc> {
+    let midnight = day.and_time(NaiveTime::MIN);
+    (0..=3)
+        .find_map(|hours| {
+            zone.from_local_datetime(&(midnight + Duration::hours(hours)))
+                .earliest()
+        })
+        .map(|start| start.with_timezone(&Utc))
+        .unwrap_or_else(|| midnight.and_utc())
+}
+
+impl fmt::Display for Timezone {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Timezone::Local => write!(f, "local"),
+            Timezone::Named(tz) => write!(f, "{}", tz.name()),
+        }
+    }
+}
+
+impl FromStr for Timezone {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        if s == "local" {
+            return Ok(Timezone::Local);
+        }
+        s.parse::<Tz>()
+            .map(Timezone::Named)
+            .map_err(|_| format!("Invalid time zone: '{s}'. Use 'local' or an IANA name such as Europe/Prague"))
+    }
+}
+
+impl Serialize for Timezone {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        serializer.collect_str(self)
+    }
+}
+
+impl<'de> Deserialize<'de> for Timezone {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        String::deserialize(deserializer)?
+            .parse()
+            .map_err(serde::de::Error::custom)
+    }
+}
+
+/// How thought timestamps are printed: in which zone, and whether with the time of day.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub struct TimestampFormat {
+    pub timezone: Timezone,
+    /// Append `HH:MM` to the date
+    pub show_time: bool,
+}
+
+impl TimestampFormat {
+    /// `YYYY-MM-DD`, or `YYYY-MM-DD HH:MM` with `show_time`, in the configured zone.
+    pub fn format(&self, instant: DateTime<Utc>) -> String {
+        let local = self.timezone.localize(instant);
+        if self.show_time {
+            local.format("%Y-%m-%d %H:%M").to_string()
+        } else {
+            local.format("%Y-%m-%d").to_string()
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn prague() -> Timezone {
+        "Europe/Prague".parse().unwrap()
+    }
+
+    fn utc(s: &str) -> DateTime<Utc> {
+        DateTime::parse_from_rfc3339(s).unwrap().with_timezone(&Utc)
+    }
+
+    fn day(s: &str) -> NaiveDate {
+        NaiveDate::parse_from_str(s, "%Y-%m-%d").unwrap()
+    }
+
+    #[test]
+    fn test_date_of_uses_the_zone() {
+        // 23:30 in Prague (CEST) is still 21:30 UTC
+        let instant = utc("2026-06-10T21:30:00+00:00");
+        assert_eq!(prague().date_of(instant), day("2026-06-10"));
+        // ... but 00:30 in Prague is the previous day in UTC
+        let instant = utc("2026-06-10T22:30:00+00:00");
+        assert_eq!(prague().date_of(instant), day("2026-06-11"));
+        assert_eq!(Timezone::Named(Tz::UTC).date_of(instant), day("2026-06-10"));
+    }
+
+    #[test]
+    fn test_start_of_day_is_local_midnight() {
+        assert_eq!(
+            prague().start_of_day(day("2026-06-10")),
+            utc("2026-06-09T22:00:00+00:00")
+        );
+        assert_eq!(
+            prague().start_of_day(day("2026-01-10")),
+            utc("2026-01-09T23:00:00+00:00")
+        );
+    }
+
+    #[test]
+    fn test_start_of_day_skips_a_dst_gap_at_midnight() {
+        // Chile springs forward at midnight: 2026-09-06 starts at 01:00 local
+        let santiago: Timezone = "America/Santiago".parse().unwrap();
+        assert_eq!(
+            santiago.start_of_day(day("2026-09-06")),
+            utc("2026-09-06T04:00:00+00:00")
+        );
+    }
+
+    #[test]
+    fn test_format_with_and_without_time() {
+        let instant = utc("2026-06-10T21:30:00+00:00");
+        let date_only = TimestampFormat {
+            timezone: prague(),
+            show_time: false,
+        };
+        assert_eq!(date_only.format(instant), "2026-06-10");
+        let with_time = TimestampFormat {
+            show_time: true,
+            ..date_only
+        };
+        assert_eq!(with_time.format(instant), "2026-06-10 23:30");
+    }
+
+    #[test]
+    fn test_from_str_and_display_roundtrip() {
+        assert_eq!("local".parse::<Timezone>().unwrap(), Timezone::Local);
+        assert_eq!(prague().to_string(), "Europe/Prague");
+        assert_eq!(Timezone::Local.to_string(), "local");
+        let err = "Mars/Olympus".parse::<Timezone>().unwrap_err();
+        assert!(err.contains("Invalid time zone"));
+    }
+
+    #[test]
+    fn test_serde_roundtrip() {
+        #[derive(Serialize, Deserialize, PartialEq, Debug)]
+        struct Wrapper {
+            timezone: Timezone,
+        }
+        let w = Wrapper { timezone: prague() };
+        let serialized = toml::to_string(&w).unwrap();
+        assert_eq!(serialized.trim(), "timezone = \"Europe/Prague\"");
+        assert_eq!(toml::from_str::<Wrapper>(&serialized).unwrap(), w);
+    }
+}
@@ -0,0 +1,485 @@
+//! Activity statistics service - counts thoughts and entity mentions over time for
+//! `wet stats`: per-period totals, a year-long daily heatmap, writing streaks, and
+//! entity rankings.
+
+use chrono::{Datelike, Duration, NaiveDate};
+use clap::ValueEnum;
+use owo_colors::OwoColorize;
+use std::collections::{HashMap, HashSet};
+
+/// Length of the periods thoughts are counted in
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, ValueEnum)]
+pub enum Period {
+    /// Weeks starting on Monday
+    #[default]
+    Week,
+    /// Calendar months
+    Month,
+}
+
+impl Period {
+    /// First day of the period containing `day`
+    pub fn start_of(&self, day: NaiveDate) -> NaiveDate {
+        match self {
+            Period::Week => day - Duration::days(day.weekday().num_days_from_monday() as i64),
+            Period::Month => day.with_day(1).unwrap(),
+        }
+    }
+
+    /// First day of the period before the one starting on `start`
+    fn previous(&self, start: NaiveDate) -> NaiveDate {
+        match self {
+            Period::Week => start - Duration::days(7),
+            Period::Month => self.start_of(start - Duration::days(1)),
+        }
+    }
+
+    /// First day of the period after the one starting on `start`
+    fn next(&self, start: NaiveDate) -> NaiveDate {
+        match self {
+            Period::Week => start + Duration::days(7),
+            Period::Month => self.start_of(start + Duration::days(31)),
+        }
+    }
+
+    /// The period's name as the `--by` flag spells it
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            Period::Week => "week",
+            Period::Month => "month",
+        }
+    }
+
+    /// How the period starting on `start` is printed: `2026-03-09` for a week, `2026-03` for a month
+    pub fn label(&self, start: NaiveDate) -> String {
+        match self {
+            Period::Week => start.format("%Y-%m-%d").to_string(),
+            Period::Month => start.format("%Y-%m").to_string(),
+        }
+    }
+}
+
+/// Number of `days` falling in each period
+fn totals_by_period(days: &[NaiveDate], period: Period) -> HashMap<NaiveDate, usize> {
+    let mut totals: HashMap<NaiveDate, usize> = HashMap::new();
+    for day in days {
+        *totals.entry(period.start_of(*day)).or_default() += 1;
+    }
+    totals
+}
+
+/// Thoughts written in each of the last `count` periods, oldest first, as
+/// `(first day of the period, thoughts)`. The last period is the one containing `today`.
+///
+/// # Examples
+///
+/// ```
+/// use chrono::NaiveDate;
+/// use wetware::services::activity_stats::{period_counts, Period};
+///
+/// let day = |d| NaiveDate::from_ymd_opt(2026, 3, d).unwrap();
+/// let counts = period_counts(&[day(2), day(3), day(10)], Period::Week, day(11), 2);
+/// assert_eq!(counts, vec![(day(2), 2), (day(9), 1)]);
+/// ```
+pub fn period_counts(days: &[NaiveDate], period: Period, today: NaiveDate, count: usize) -> Vec<(NaiveDate, usize)> {
+    let mut starts = Vec::with_capacity(count);
+    let mut start = period.start_of(today);
+    for _ in 0..count {
+        starts.push(start);
+        start = period.previous(start);
+    }
+    starts.reverse();
+
+    let totals = totals_by_period(days, period);
+    starts
+        .into_iter()
+        .map(|start| (start, totals.get(&start).copied().unwrap_or(0)))
+        .collect()
+}
+
+/// Thoughts written in every period from the one containing the earliest of `days` to
+/// the one containing the latest, empty periods included, oldest first. Empty if
+/// `days` is.
+///
+/// # Examples
+///
+/// ```
+/// use chrono::NaiveDate;
+/// use wetware::services::activity_stats::{period_counts_spanning, Period};
+///
+/// let day = |m, d| NaiveDate::from_ymd_opt(2026, m, d).unwrap();
+/// let counts = period_counts_spanning(&[day(3, 20), day(1, 5), day(3, 2)], Period::Month);
+/// assert_eq!(counts, vec![(day(1, 1), 1), (day(2, 1), 0), (day(3, 1), 2)]);
+/// ```
+pub fn period_counts_spanning(days: &[NaiveDate], period: Period) -> Vec<(NaiveDate, usize)> {
+    let (Some(first), Some(last)) = (days.iter().min(), days.iter().max()) else {
+        return Vec::new();
+    };
+
+    let totals = totals_by_period(days, period);
+    let mut counts = Vec::new();
+    let mut start = period.start_of(*first);
+    while start <= *last {
+        counts.push((start, totals.get(&start).copied().unwrap_or(0)));
+        start = period.next(start);
+    }
+    counts
+}
+
+/// Bar glyphs for [`sparkline`], from the quietest non-empty period to the busiest
+const SPARK_GLYPHS: [char; 8] = ['▁', '▂', '▃', '▄', '▅', '▆', '▇', '█'];
+
+/// One character per count, its height relative to the largest count; zero is a space
+///
+/// # Examples
+///
+/// ```
+/// use wetware::services::activity_stats::sparkline;
+///
+/// assert_eq!(sparkline(&[1, 0, 4, 8]), "▁ ▄█");
+/// ```
+pub fn sparkline(counts: &[usize]) -> String {
+    let max = counts.iter().copied().max().unwrap_or(0);
+    counts
+        .iter()
+        .map(|&count| {
+            if count == 0 {
+                ' '
+            } else {
+                SPARK_GLYPHS[((count * SPARK_GLYPHS.len()).div_ceil(max) - 1).min(SPARK_GLYPHS.len() - 1)]
+            }
+        })
+        .collect()
+}
+
+/// Number of week columns in the heatmap: a year, plus the partial week at each end
+pub const HEATMAP_WEEKS: usize = 53;
+
+/// Thoughts per day over the last [`HEATMAP_WEEKS`] weeks, as columns of Monday-to-Sunday
+/// counts, oldest week first. Days after `today` in the last column are always 0.
+pub fn heatmap(days: &[NaiveDate], today: NaiveDate) -> Vec<[usize; 7]> {
+    let first = Period::Week.start_of(today) - Duration::weeks(HEATMAP_WEEKS as i64 - 1);
+    let mut grid = vec![[0; 7]; HEATMAP_WEEKS];
+    for day in days {
+        if *day < first || *day > today {
+            continue;
+        }
+        let offset = (*day - first).num_days() as usize;
+        grid[offset / 7][offset % 7] += 1;
+    }
+    grid
+}
+
+/// Glyphs for the heatmap's five levels, from no thoughts to the busiest days
+const HEATMAP_GLYPHS: [char; 5] = ['·', '░', '▒', '▓', '█'];
+
+/// Intensity level 0-4 of a day's count relative to the busiest day
+fn heatmap_level(count: usize, max: usize) -> usize {
+    if count == 0 || max == 0 {
+        0
+    } else {
+        (count * 4).div_ceil(max).clamp(1, 4)
+    }
+}
+
+/// Render a [`heatmap`] grid as lines: a month header, then one row per weekday.
+///
+/// Days with thoughts are shaded by how busy they were relative to the busiest day;
+/// with `use_colors` they're also colored green, brighter for busier days. Days after
+/// `today` are left blank.
+pub fn render_heatmap(grid: &[[usize; 7]], today: NaiveDate, use_colors: bool) -> Vec<String> {
+    let max = grid.iter().flatten().copied().max().unwrap_or(0);
+    let first = Period::Week.start_of(today) - Duration::weeks(grid.len() as i64 - 1);
+    const LABEL_WIDTH: usize = 4;
+
+    // Month names over the column in which each month starts, where there's room
+    let mut header = " ".repeat(LABEL_WIDTH);
+    let mut previous_month = None;
+    for column in 0..grid.len() {
+        let week_start = first + Duration::weeks(column as i64);
+        let month = (week_start + Duration::days(6)).month();
+        if previous_month.is_some_and(|m| m != month) && header.chars().count() <= LABEL_WIDTH + column {
+            header.push_str(&" ".repeat(LABEL_WIDTH + column - header.chars().count()));
+            header.push_str(&(week_start + Duration::days(6)).format("%b").to_string());
+        }
+        previous_month = Some(month);
+    }
+    let mut lines = vec![header.trim_end().to_string()];
+
+    for (weekday, label) in ["Mon", "", "Wed", "", "Fri", "", ""].iter().enumerate() {
+        let mut line = format!("{:<width$}", label, width = LABEL_WIDTH);
+        for (column, week) in grid.iter().enumerate() {
+            let day = first + Duration::days((column * 7 + weekday) as i64);
+            if day > today {
+                line.push(' ');
+                continue;
+            }
+            let level = heatmap_level(week[weekday], max);
+            let glyph = HEATMAP_GLYPHS[level];
+            match (use_colors, level) {
+                (false, _) | (true, 0) => line.push(glyph),
+                (true, 1 | 2) => line.push_str(&glyph.green().to_string()),
+                (true, _) => line.push_str(&glyph.bright_green().to_string()),
+            }
+        }
+        lines.push(line.trim_end().to_string());
+    }
+
+    lines
+}
+
+/// Consecutive days with at least one thought
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub struct Streaks {
+    /// Run of days ending today, or yesterday if nothing has been written yet today
+    pub current: usize,
+    /// Longest run ever
+    pub longest: usize,
+}
+
+/// Current and longest writing streaks
+///
+/// # Examples
+///
+/// ```
+/// use chrono::NaiveDate;
+/// use wetware::services::activity_stats::streaks;
+///
+/// let day = |d| NaiveDate::from_ymd_opt(2026, 3, d).unwrap();
+/// let result = streaks(&[day(1), day(2), day(3), day(9), day(10)], day(11));
+/// assert_eq!((result.current, result.longest), (2, 3));
+/// ```
+pub fn streaks(days: &[NaiveDate], today: NaiveDate) -> Streaks {
+    let written: HashSet<NaiveDate> = days.iter().copied().collect();
+
+    let mut sorted: Vec<NaiveDate> = written.iter().copied().collect();
+    sorted.sort();
+    let mut longest = 0;
+    let mut run = 0;
+    let mut previous: Option<NaiveDate> = None;
+    for day in sorted {
+        run = if previous == Some(day - Duration::days(1)) {
+            run + 1
+        } else {
+            1
+        };
+        longest = longest.max(run);
+        previous = Some(day);
+    }
+
+    let mut day = if written.contains(&today) {
+        today
+    } else {
+        today - Duration::days(1)
+    };
+    let mut current = 0;
+    while written.contains(&day) {
+        current += 1;
+        day -= Duration::days(1);
+    }
+
+    Streaks { current, longest }
+}
+
+/// Entities by number of thoughts mentioning them, most first (ties by ID), at most `limit`
+///
+/// `mentions` holds one `(entity_id, day)` pair per thought/entity link.
+pub fn most_mentioned(mentions: &[(i64, NaiveDate)], limit: usize) -> Vec<(i64, usize)> {
+    let mut counts: HashMap<i64, usize> = HashMap::new();
+    for (entity_id, _) in mentions {
+        *counts.entry(*entity_id).or_default() += 1;
+    }
+    let mut ranked: Vec<(i64, usize)> = counts.into_iter().collect();
+    ranked.sort_by(|a, b| b.1.cmp(&a.1).then(a.0.cmp(&b.0)));
+    ranked.truncate(limit);
+    ranked
+}
+
+/// An entity mentioned more in the latest window than in the one before
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Growth {
+    /// The entity
+    pub entity_id: i64,
+    /// Thoughts mentioning it in the last `window_days` days, today included
+    pub recent: usize,
+    /// Thoughts mentioning it in the `window_days` days before that
+    pub previous: usize,
+}
+
+/// Entities whose mentions grew the most between the previous `window_days` days and
+/// the last `window_days` days, largest increase first (ties by more recent mentions,
+/// then ID). Entities that didn't grow are left out.
+pub fn fastest_growing(mentions: &[(i64, NaiveDate)], today: NaiveDate, window_days: i64, limit: usize) -> Vec<Growth> {
+    let recent_start = today - Duration::days(window_days - 1);
+    let previous_start = recent_start - Duration::days(window_days);
+
+    let mut counts: HashMap<i64, (usize, usize)> = HashMap::new();
+    for (entity_id, day) in mentions {
+        if *day >= recent_start && *day <= today {
+            counts.entry(*entity_id).or_default().0 += 1;
+        } else if *day >= previous_start && *day < recent_start {
+            counts.entry(*entity_id).or_default().1 += 1;
+        }
+    }
+
+    let mut growing: Vec<Growth> = counts
+        .into_iter()
+        .filter(|(_, (recent, previous))| recent > previous)
+        .map(|(entity_id, (recent, previous))| Growth {
+            entity_id,
+            recent,
+            previous,
+        })
+        .collect();
+    growing.sort_by(|a, b| {
+        (b.recent - b.previous)
+            .cmp(&(a.recent - a.previous))
+            .then(b.recent.cmp(&a.recent))
+            .then(a.entity_id.cmp(&b.entity_id))
+    });
+    growing.truncate(limit);
+    growing
+}
+
+/// Entities last mentioned more than `days` days before `today`, with the day of their
+/// last mention, longest-forgotten first (ties by ID). Entities never mentioned are
+/// not included.
+pub fn not_mentioned_since(mentions: &[(i64, NaiveDate)], today: NaiveDate, days: i64) -> Vec<(i64, NaiveDate)> {
+    let mut last: HashMap<i64, NaiveDate> = HashMap::new();
+    for (entity_id, day) in mentions {
+        let entry = last.entry(*entity_id).or_insert(*day);
+        *entry = (*entry).max(*day);
+    }
+
+    let cutoff = today - Duration::days(days);
+    let mut stale: Vec<(i64, NaiveDate)> = last.into_iter().filter(|(_, day)| *day < cutoff).collect();
+    stale.sort_by(|a, b| a.1.cmp(&b.1).then(a.0.cmp(&b.0)));
+    stale
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn day(month: u32, day: u32) -> NaiveDate {
+        NaiveDate::from_ymd_opt(2026, month, day).unwrap()
+    }
+
+    #[test]
+    fn test_period_counts_by_month_includes_empty_months() {
+        let days = [day(1, 5), day(3, 1), day(3, 31)];
+        let counts = period_counts(&days, Period::Month, day(3, 31), 3);
+        assert_eq!(counts, vec![(day(1, 1), 1), (day(2, 1), 0), (day(3, 1), 2)]);
+    }
+
+    #[test]
+    fn test_period_counts_spanning_weeks_crosses_month_boundaries() {
+        // Monday 2026-03-30 to Sunday 2026-04-12
+        let counts = period_counts_spanning(&[day(4, 12), day(3, 30)], Period::Week);
+        assert_eq!(counts, vec![(day(3, 30), 1), (day(4, 6), 1)]);
+        assert!(period_counts_spanning(&[], Period::Week).is_empty());
+    }
+
+    #[test]
+    fn test_sparkline_scales_to_the_largest_count() {
+        assert_eq!(sparkline(&[2, 2]), "██");
+        assert_eq!(sparkline(&[1, 2, 3, 4, 5, 6, 7, 8, 0]), "▁▂▃▄▅▆▇█ ");
+        assert_eq!(sparkline(&[]), "");
+    }
+
+    #[test]
+    fn test_heatmap_places_days_by_week_and_weekday() {
+        // 2026-03-11 is a Wednesday
+        let today = day(3, 11);
+        let grid = heatmap(
+            &[
+                day(3, 9),
+                day(3, 11),
+                day(3, 11),
+                day(3, 2),
+                day(1, 1).with_year(2024).unwrap(),
+            ],
+            today,
+        );
+
+        assert_eq!(grid.len(), HEATMAP_WEEKS);
+        assert_eq!(grid[HEATMAP_WEEKS - 1], [1, 0, 2, 0, 0, 0, 0]);
+        assert_eq!(grid[HEATMAP_WEEKS - 2], [1, 0, 0, 0, 0, 0, 0]);
+        assert_eq!(grid.iter().flatten().sum::<usize>(), 4);
+    }
+
+    #[test]
+    fn test_render_heatmap_shades_by_level_and_blanks_future_days() {
+        let today = day(3, 11);
+        let grid = heatmap(&[day(3, 9), day(3, 11), day(3, 11), day(3, 11), day(3, 11)], today);
+        let lines = render_heatmap(&grid, today, false);
+
+        assert_eq!(lines.len(), 8);
+        assert!(lines[1].starts_with("Mon "));
+        assert!(lines[1].ends_with("·░"), "Got: {:?}", lines[1]);
+        assert!(lines[3].ends_with("·█"), "Got: {:?}", lines[3]);
+        // Thursday onwards of the current week is still to come
+        assert!(lines[4].ends_with('·'), "Got: {:?}", lines[4]);
+        assert_eq!(lines[4].chars().count(), 4 + HEATMAP_WEEKS - 1);
+        assert!(lines[0].contains("Mar"), "Got: {:?}", lines[0]);
+    }
+
+    #[test]
+    fn test_streaks_current_counts_from_yesterday_when_nothing_today() {
+        let days = [day(3, 8), day(3, 9), day(3, 10)];
+        assert_eq!(streaks(&days, day(3, 11)), Streaks { current: 3, longest: 3 });
+        assert_eq!(streaks(&days, day(3, 12)), Streaks { current: 0, longest: 3 });
+        assert_eq!(streaks(&[], day(3, 12)), Streaks::default());
+    }
+
+    #[test]
+    fn test_most_mentioned_orders_by_count() {
+        let mentions = [(2, day(3, 1)), (1, day(3, 1)), (2, day(3, 2)), (3, day(3, 2))];
+        assert_eq!(most_mentioned(&mentions, 2), vec![(2, 2), (1, 1)]);
+    }
+
+    #[test]
+    fn test_fastest_growing_compares_adjacent_windows() {
+        let today = day(3, 31);
+        let mentions = [
+            // Entity 1: 1 before, 3 recently
+            (1, day(2, 20)),
+            (1, day(3, 20)),
+            (1, day(3, 25)),
+            (1, day(3, 31)),
+            // Entity 2: 2 before, 1 recently - shrinking
+            (2, day(2, 10)),
+            (2, day(2, 12)),
+            (2, day(3, 15)),
+            // Entity 3: 0 before, 1 recently; older mentions are outside both windows
+            (3, day(1, 1)),
+            (3, day(3, 30)),
+        ];
+
+        let growing = fastest_growing(&mentions, today, 30, 5);
+        assert_eq!(
+            growing,
+            vec![
+                Growth {
+                    entity_id: 1,
+                    recent: 3,
+                    previous: 1
+                },
+                Growth {
+                    entity_id: 3,
+                    recent: 1,
+                    previous: 0
+                },
+            ]
+        );
+    }
+
+    #[test]
+    fn test_not_mentioned_since_uses_the_last_mention() {
+        let mentions = [(1, day(1, 1)), (1, day(3, 1)), (2, day(1, 15)), (3, day(1, 2))];
+        assert_eq!(
+            not_mentioned_since(&mentions, day(3, 31), 30),
+            vec![(3, day(1, 2)), (2, day(1, 15))]
+        );
+    }
+}
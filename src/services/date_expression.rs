@@ -0,0 +1,156 @@
+//! Date expressions - the dates `--date`, `--since`/`--until` and query date terms accept:
+//! an ISO date or a day relative to today.
+
+use crate::errors::ThoughtError;
+use chrono::{Datelike, Duration, Months, NaiveDate, Weekday};
+
+/// The forms [`parse`] accepts, for error messages
+pub const EXPECTED: &str = "YYYY-MM-DD, today, yesterday, -3d, 2 weeks ago, mon or last friday";
+
+/// Resolve a date expression against `today` (case-insensitive).
+///
+/// Accepts:
+/// * `YYYY-MM-DD`
+/// * `today`, `yesterday`
+/// * `-3d`, `-2w`, `-1m`, `-1y` - that many days, weeks, months or years back
+/// * `3 days ago`, `2 weeks ago`, `1 month ago`, `1 year ago`
+/// * `mon`, `friday` - the most recent such day, today included
+/// * `last mon`, `last friday` - the most recent such day before today
+///
+/// Returns `None` for anything else.
+///
+/// # Examples
+///
+/// ```
+/// use chrono::NaiveDate;
+/// use wetware::services::date_expression::parse;
+///
+/// // A Wednesday
+/// let today = NaiveDate::from_ymd_opt(2026, 10, 14).unwrap();
+/// assert_eq!(parse("yesterday", today), NaiveDate::from_ymd_opt(2026, 10, 13));
+/// assert_eq!(parse("2 weeks ago", today), NaiveDate::from_ymd_opt(2026, 9, 30));
+/// assert_eq!(parse("last friday", today), NaiveDate::from_ymd_opt(2026, 10, 9));
+/// ```
+pub fn parse(input: &str, today: NaiveDate) -> Option<NaiveDate> {
+    let input = input.trim().to_lowercase();
+    if let Ok(date) = NaiveDate::parse_from_str(&input, "%Y-%m-%d") {
+        return Some(date);
+    }
+
+    let words: Vec<&str> = input.split_whitespace().collect();
+    match words.as_slice() {
+        ["today"] => Some(today),
+        ["yesterday"] => today.pred_opt(),
+        [offset] if offset.starts_with('-') => {
+            let offset = &offset[1..];
+            let split = offset.find(|c: char| !c.is_ascii_digit())?;
+            let (count, unit) = offset.split_at(split);
+            back(today, count.parse().ok()?, unit)
+        }
+        [count, unit, "ago"] => back(today, count.parse().ok()?, unit),
+        [day] => {
+            let weekday: Weekday = day.parse().ok()?;
+            Some(today - Duration::days(days_since(today, weekday)))
+        }
+        ["last", day] => {
+            let weekday: Weekday = day.parse().ok()?;
+            let days = match days_since(today, weekday) {
+                0 => 7,
+                days => days,
+            };
+            Some(today - Duration::days(days))
+        }
+        _ => None,
+    }
+}
+
+/// `count` units before `today`; `None` for an unknown unit or a date out of range
+fn back(today: NaiveDate, count: u32, unit: &str) -> Option<NaiveDate> {
+    match unit {
+        "d" | "day" | "days" => today.checked_sub_signed(Duration::days(count.into())),
+        "w" | "week" | "weeks" => today.checked_sub_signed(Duration::weeks(count.into())),
+        "m" | "month" | "months" => today.checked_sub_months(Months::new(count)),
+        "y" | "year" | "years" => today.checked_sub_months(Months::new(count.checked_mul(12)?)),
+        _ => None,
+    }
+}
+
+/// Days since the most recent `weekday` on or before `today` (0 to 6)
+fn days_since(today: NaiveDate, weekday: Weekday) -> i64 {
+    let today = today.weekday().num_days_from_monday() as i64;
+    (today - weekday.num_days_from_monday() as i64).rem_euclid(7)
+}
+
+/// Parse an inclusive `--since`/`--until` bound, a date expression resolved against
+/// `today`; a value [`parse`] rejects is an `InvalidInput` error naming `flag`.
+pub fn parse_day(flag: &str, value: &str, today: NaiveDate) -> Result<NaiveDate, ThoughtError> {
+    parse(value, today)
+        .ok_or_else(|| ThoughtError::InvalidInput(format!("Invalid {} date '{}'. Expected {}.", flag, value, EXPECTED)))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// Wednesday 2026-10-14
+    fn today() -> NaiveDate {
+        NaiveDate::from_ymd_opt(2026, 10, 14).unwrap()
+    }
+
+    fn day(s: &str) -> Option<NaiveDate> {
+        Some(NaiveDate::parse_from_str(s, "%Y-%m-%d").unwrap())
+    }
+
+    #[test]
+    fn test_parse_iso_dates_and_named_days() {
+        assert_eq!(parse("2025-01-31", today()), day("2025-01-31"));
+        assert_eq!(parse("today", today()), day("2026-10-14"));
+        assert_eq!(parse(" Yesterday ", today()), day("2026-10-13"));
+    }
+
+    #[test]
+    fn test_parse_offsets_and_ago() {
+        assert_eq!(parse("-3d", today()), day("2026-10-11"));
+        assert_eq!(parse("-2w", today()), day("2026-09-30"));
+        assert_eq!(parse("-1m", today()), day("2026-09-14"));
+        assert_eq!(parse("-1y", today()), day("2025-10-14"));
+        assert_eq!(parse("3 days ago", today()), day("2026-10-11"));
+        assert_eq!(parse("1 week ago", today()), day("2026-10-07"));
+        assert_eq!(parse("2 months ago", today()), day("2026-08-14"));
+        assert_eq!(parse("0 days ago", today()), day("2026-10-14"));
+    }
+
+    #[test]
+    fn test_parse_weekdays() {
+        assert_eq!(parse("wed", today()), day("2026-10-14"));
+        assert_eq!(parse("mon", today()), day("2026-10-12"));
+        assert_eq!(parse("Friday", today()), day("2026-10-09"));
+        assert_eq!(parse("last wednesday", today()), day("2026-10-07"));
+        assert_eq!(parse("last fri", today()), day("2026-10-09"));
+        assert_eq!(parse("last mon", today()), day("2026-10-12"));
+    }
+
+    #[test]
+    fn test_parse_day_names_the_flag() {
+        assert_eq!(parse_day("--since", "-1d", today()).ok(), day("2026-10-13"));
+        let err = parse_day("--until", "someday", today()).unwrap_err().to_string();
+        assert!(err.contains("Invalid --until date 'someday'"), "{err}");
+    }
+
+    #[test]
+    fn test_parse_rejects_anything_else() {
+        for input in [
+            "",
+            "tomorrow",
+            "-3",
+            "-d",
+            "-3x",
+            "3 days",
+            "two weeks ago",
+            "next friday",
+            "2025-02-30",
+        ] {
+            assert_eq!(parse(input, today()), None, "{input:?} should not parse");
+        }
+    }
+}
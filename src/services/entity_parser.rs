@@ -1,5 +1,6 @@
 /// Entity parser service - extracts entity references from note text
 use regex::{Captures, Regex};
+use std::ops::Range;
 use std::sync::LazyLock;
 
 /// Regex pattern for entity syntax: [entity] or [alias](entity)
@@ -75,6 +76,42 @@ pub fn extract_unique_entities(text: &str) -> Vec<String> {
     unique
 }
 
+/// Extract entity references from text as `(display text, target entity)` pairs
+///
+/// Unlike [`extract_entities`], the written display text is kept alongside the target,
+/// so callers can tell which wording a reference used. For traditional `[entity]`
+/// syntax both halves are the same.
+///
+/// # Examples
+///
+/// ```
+/// use wetware::services::entity_parser::extract_references;
+///
+/// let refs = extract_references("[Sarah] took the [ML](machine-learning) course");
+/// assert_eq!(
+///     refs,
+///     vec![
+///         ("Sarah".to_string(), "Sarah".to_string()),
+///         ("ML".to_string(), "machine-learning".to_string()),
+///     ]
+/// );
+/// ```
+pub fn extract_references(text: &str) -> Vec<(String, String)> {
+    ENTITY_PATTERN
+        .captures_iter(text)
+        .filter_map(|cap| {
+            let display = cap[1].trim();
+            let target = cap.get(2).map(|m| m.as_str().trim());
+
+            match target {
+                Some(t) if !t.is_empty() => Some((display.to_string(), t.to_string())),
+                None if !display.is_empty() => Some((display.to_string(), display.to_string())),
+                _ => None,
+            }
+        })
+        .collect()
+}
+
 /// Rewrite literal references to `old_name` (case-insensitive) into `new_name`.
 ///
 /// Used when renaming an entity, to keep stored thought/description text in sync
@@ -176,6 +213,135 @@ pub fn redirect_entity_references(text: &str, old_name: &str, new_target: &str)
         .into_owned()
 }
 
+/// Strip the markup from every reference whose target is one of `names`
+/// (case-insensitive), leaving its display text as plain text.
+///
+/// Used when deleting an entity, so thoughts that mentioned it keep reading the same
+/// without pointing at an entity that no longer exists. References to other entities
+/// are left untouched.
+///
+/// # Examples
+///
+/// ```
+/// use wetware::services::entity_parser::strip_entity_references;
+///
+/// let text = strip_entity_references("Met [Sara] and [S](sara) with [Bob]", &["sara"]);
+/// assert_eq!(text, "Met Sara and S with [Bob]");
+/// ```
+pub fn strip_entity_references(text: &str, names: &[&str]) -> String {
+    let names: Vec<String> = names.iter().map(|n| n.trim().to_lowercase()).collect();
+
+    ENTITY_PATTERN
+        .replace_all(text, |caps: &Captures| {
+            let display = caps[1].trim();
+            let target = caps.get(2).map(|m| m.as_str().trim()).unwrap_or(display);
+
+            if names.contains(&target.to_lowercase()) {
+                display.to_string()
+            } else {
+                caps[0].to_string()
+            }
+        })
+        .into_owned()
+}
+
+/// Find plain-text mentions of any of `terms` (case-insensitive) that are not inside
+/// entity reference markup, returning their byte ranges in `text` in order.
+///
+/// A mention must stand as a whole word: the characters on either side may not be
+/// alphanumeric, `-` or `_`, so `rust` is not found inside `rustacean` or `rust-lang`.
+/// Where terms overlap, the longest one that stands as a whole word wins: `project
+/// alpha` over `alpha`, but `sarah` in `sarah connors` when `sarah connor` would end
+/// mid-word. Empty terms are ignored.
+///
+/// # Examples
+///
+/// ```
+/// use wetware::services::entity_parser::find_plain_mentions;
+///
+/// let text = "Asked Sarah, then [Sarah] again, not sarahs";
+/// assert_eq!(find_plain_mentions(text, &["sarah"]), vec![6..11]);
+/// ```
+pub fn find_plain_mentions(text: &str, terms: &[&str]) -> Vec<Range<usize>> {
+    let mut terms: Vec<&str> = terms.iter().map(|t| t.trim()).filter(|t| !t.is_empty()).collect();
+    if terms.is_empty() {
+        return Vec::new();
+    }
+    terms.sort_by_key(|t| std::cmp::Reverse(t.len()));
+
+    // One anchored pattern per term, longest first, so a rejected long match at a
+    // position falls back to the next-longest term starting there
+    let patterns: Vec<Regex> = terms
+        .iter()
+        .map(|t| Regex::new(&format!("(?i)^(?:{})", regex::escape(t))).unwrap())
+        .collect();
+
+    let markup: Vec<Range<usize>> = ENTITY_PATTERN.find_iter(text).map(|m| m.range()).collect();
+    let is_word_char = |c: char| c.is_alphanumeric() || c == '-' || c == '_';
+    let acceptable = |r: &Range<usize>| {
+        let before = text[..r.start].chars().next_back();
+        let after = text[r.end..].chars().next();
+        !markup.iter().any(|mk| r.start < mk.end && mk.start < r.end)
+            && !before.is_some_and(is_word_char)
+            && !after.is_some_and(is_word_char)
+    };
+
+    let mut mentions = Vec::new();
+    let mut pos = 0;
+    while let Some(c) = text[pos..].chars().next() {
+        let found = patterns
+            .iter()
+            .filter_map(|p| p.find(&text[pos..]))
+            .map(|m| pos..pos + m.end())
+            .find(|r| acceptable(r));
+        match found {
+            Some(range) => {
+                pos = range.end;
+                mentions.push(range);
+            }
+            None => pos += c.len_utf8(),
+        }
+    }
+    mentions
+}
+
+/// Wrap every plain-text mention of an entity (see [`find_plain_mentions`]) in
+/// reference markup pointing at it, keeping the wording that was written.
+///
+/// - A mention of the canonical name becomes `[Mention]`
+/// - A mention of one of `aliases` becomes `[Mention](canonical_name)`
+///
+/// # Examples
+///
+/// ```
+/// use wetware::services::entity_parser::link_plain_mentions;
+///
+/// let text = link_plain_mentions("Lunch with sarah and sar", "Sarah", &["sar"]);
+/// assert_eq!(text, "Lunch with [sarah] and [sar](Sarah)");
+/// ```
+pub fn link_plain_mentions(text: &str, canonical_name: &str, aliases: &[&str]) -> String {
+    let mut terms = vec![canonical_name];
+    terms.extend_from_slice(aliases);
+
+    let canonical_lower = canonical_name.trim().to_lowercase();
+    let mut result = String::new();
+    let mut last_end = 0;
+
+    for range in find_plain_mentions(text, &terms) {
+        let mention = &text[range.clone()];
+        result.push_str(&text[last_end..range.start]);
+        if mention.to_lowercase() == canonical_lower {
+            result.push_str(&format!("[{}]", mention));
+        } else {
+            result.push_str(&format!("[{}]({})", mention, canonical_name));
+        }
+        last_end = range.end;
+    }
+    result.push_str(&text[last_end..]);
+
+    result
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -430,4 +596,57 @@ mod tests {
         let text = redirect_entity_references("No entities here at all", "alice", "Bob");
         assert_eq!(text, "No entities here at all");
     }
+
+    #[test]
+    fn test_find_plain_mentions_skips_markup_and_partial_words() {
+        let text = "Sarah met [Sarah] and [S](sarah); sarah-k and Sarahs did not";
+        assert_eq!(find_plain_mentions(text, &["Sarah"]), vec![0..5]);
+    }
+
+    #[test]
+    fn test_find_plain_mentions_prefers_longest_term() {
+        let text = "Kicked off Project Alpha today";
+        let found = find_plain_mentions(text, &["alpha", "project alpha"]);
+        assert_eq!(found, vec![11..24]);
+    }
+
+    #[test]
+    fn test_find_plain_mentions_falls_back_to_shorter_term() {
+        let text = "Met Sarah Connors today";
+        assert_eq!(find_plain_mentions(text, &["Sarah Connor", "Sarah"]), vec![4..9]);
+    }
+
+    #[test]
+    fn test_find_plain_mentions_ignores_empty_terms() {
+        assert!(find_plain_mentions("anything", &["", "  "]).is_empty());
+    }
+
+    #[test]
+    fn test_find_plain_mentions_escapes_regex_metacharacters() {
+        assert_eq!(find_plain_mentions("Wrote some C++ today", &["c++"]), vec![11..14]);
+    }
+
+    #[test]
+    fn test_link_plain_mentions_wraps_canonical_and_alias() {
+        let text = link_plain_mentions(
+            "ML and machine-learning, see [ML](machine-learning)",
+            "machine-learning",
+            &["ML"],
+        );
+        assert_eq!(
+            text,
+            "[ML](machine-learning) and [machine-learning], see [ML](machine-learning)"
+        );
+    }
+
+    #[test]
+    fn test_link_plain_mentions_no_mentions_is_unchanged() {
+        assert_eq!(link_plain_mentions("Nothing here", "rust", &[]), "Nothing here");
+    }
+
+    #[test]
+    fn test_strip_entity_references_matches_any_name_case_insensitively() {
+        let text = strip_entity_references("[SAR] and [Sara](sara) but [sara](bob)", &["Sara", "sar"]);
+        assert_eq!(text, "SAR and Sara but [sara](bob)");
+    }
 }
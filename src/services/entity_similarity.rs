@@ -0,0 +1,139 @@
+//! Entity similarity service - spots entity names that are probably the same thing
+//! written differently ("Sarah"/"Sara", "project-alpha"/"project alpha").
+
+/// Normalize a name for comparison: lowercase, with runs of whitespace, `-` and `_`
+/// collapsed to a single space.
+///
+/// # Examples
+///
+/// ```
+/// use wetware::services::entity_similarity::normalize;
+///
+/// assert_eq!(normalize("Project-Alpha"), "project alpha");
+/// assert_eq!(normalize("  sarah__k "), "sarah k");
+/// ```
+pub fn normalize(name: &str) -> String {
+    name.to_lowercase()
+        .split(|c: char| c.is_whitespace() || c == '-' || c == '_')
+        .filter(|part| !part.is_empty())
+        .collect::<Vec<_>>()
+        .join(" ")
+}
+
+/// Levenshtein distance between two strings, counted in characters
+pub fn edit_distance(a: &str, b: &str) -> usize {
+    let b: Vec<char> = b.chars().collect();
+    let mut previous: Vec<usize> = (0..=b.len()).collect();
+
+    for (i, ca) in a.chars().enumerate() {
+        let mut current = vec![i + 1];
+        for (j, cb) in b.iter().enumerate() {
+            let substitution = previous[j] + usize::from(ca != *cb);
+            current.push(substitution.min(previous[j + 1] + 1).min(current[j] + 1));
+        }
+        previous = current;
+    }
+
+    previous[b.len()]
+}
+
+/// Whether two entity names are likely the same thing
+///
+/// After [`normalize`], two names are near-duplicates when they are equal, when the
+/// shorter one is the whole first word of the longer (`sarah` / `sarah k`), or when
+/// they are within a small edit distance that grows with the name length: none
+/// below four characters, one up to seven, two beyond. Short names are held to exact
+/// matches because one edit between `go` and `js` is a different name, not a typo.
+///
+/// # Examples
+///
+/// ```
+/// use wetware::services::entity_similarity::are_near_duplicates;
+///
+/// assert!(are_near_duplicates("Sarah", "Sara"));
+/// assert!(are_near_duplicates("project-alpha", "project alpha"));
+/// assert!(are_near_duplicates("sarah-k", "Sarah"));
+/// assert!(!are_near_duplicates("rust", "ruby"));
+/// ```
+pub fn are_near_duplicates(a: &str, b: &str) -> bool {
+    let a = normalize(a);
+    let b = normalize(b);
+    if a.is_empty() || b.is_empty() {
+        return false;
+    }
+    if a == b {
+        return true;
+    }
+
+    let (shorter, longer) = if a.chars().count() <= b.chars().count() {
+        (&a, &b)
+    } else {
+        (&b, &a)
+    };
+    let shorter_len = shorter.chars().count();
+
+    if shorter_len >= 4 && longer.split(' ').next() == Some(shorter.as_str()) {
+        return true;
+    }
+
+    let max_distance = match shorter_len {
+        0..=3 => 0,
+        4..=7 => 1,
+        _ => 2,
+    };
+    max_distance > 0 && edit_distance(&a, &b) <= max_distance
+}
+
+/// Find every pair of near-duplicate names, as index pairs `(i, j)` with `i < j`,
+/// in input order.
+pub fn find_near_duplicates(names: &[&str]) -> Vec<(usize, usize)> {
+    let mut pairs = Vec::new();
+    for i in 0..names.len() {
+        for j in (i + 1)..names.len() {
+            if are_near_duplicates(names[i], names[j]) {
+                pairs.push((i, j));
+            }
+        }
+    }
+    pairs
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_edit_distance() {
+        assert_eq!(edit_distance("", ""), 0);
+        assert_eq!(edit_distance("sarah", "sara"), 1);
+        assert_eq!(edit_distance("kitten", "sitting"), 3);
+        assert_eq!(edit_distance("abc", ""), 3);
+        assert_eq!(edit_distance("čaj", "caj"), 1);
+    }
+
+    #[test]
+    fn test_short_names_need_exact_normalized_match() {
+        assert!(!are_near_duplicates("go", "js"));
+        assert!(!are_near_duplicates("ai", "al"));
+        assert!(are_near_duplicates("AI", "ai"));
+    }
+
+    #[test]
+    fn test_longer_names_allow_more_edits() {
+        assert!(are_near_duplicates("kubernetes", "kubernets"));
+        assert!(are_near_duplicates("kubernetes", "kuberentes"));
+        assert!(!are_near_duplicates("rust", "rest api"));
+    }
+
+    #[test]
+    fn test_prefix_word_requires_whole_word() {
+        assert!(are_near_duplicates("sarah", "Sarah Smith"));
+        assert!(!are_near_duplicates("sara", "saratoga trip"));
+    }
+
+    #[test]
+    fn test_find_near_duplicates_returns_index_pairs() {
+        let names = ["Sarah", "rust", "Sara", "sarah-k"];
+        assert_eq!(find_near_duplicates(&names), vec![(0, 2), (0, 3)]);
+    }
+}
@@ -8,6 +8,7 @@ use owo_colors::{AnsiColors, OwoColorize};
 use std::collections::HashMap;
 
 use super::entity_parser::ENTITY_PATTERN;
+use crate::models::EntityKind;
 
 /// Available colors for entity styling.
 ///
@@ -28,12 +29,36 @@ const ENTITY_COLORS: [AnsiColors; 12] = [
     AnsiColors::BrightRed,
 ];
 
+/// The fixed color for entities of a given kind, so every person (say) reads the
+/// same color wherever it appears. Entities without a kind use the rotating palette.
+///
+/// # Examples
+///
+/// ```
+/// use owo_colors::AnsiColors;
+/// use wetware::models::EntityKind;
+/// use wetware::services::entity_styler::kind_color;
+///
+/// assert_eq!(kind_color(EntityKind::Person), AnsiColors::Magenta);
+/// ```
+pub fn kind_color(kind: EntityKind) -> AnsiColors {
+    match kind {
+        EntityKind::Person => AnsiColors::Magenta,
+        EntityKind::Organization => AnsiColors::Blue,
+        EntityKind::Project => AnsiColors::Green,
+        EntityKind::Place => AnsiColors::Yellow,
+        EntityKind::Event => AnsiColors::Red,
+        EntityKind::Concept => AnsiColors::Cyan,
+    }
+}
+
 /// Manages consistent color assignment for entities within a single execution.
 ///
 /// The styler ensures that:
 /// - Each unique entity gets a consistent color throughout the output
 /// - Entity names are matched case-insensitively
 /// - Colors cycle when there are more entities than available colors
+/// - Entities with a known kind (see [`EntityStyler::with_kinds`]) use their kind's color
 /// - Bracket markup is removed from entity display
 ///
 /// # Examples
@@ -52,6 +77,8 @@ pub struct EntityStyler {
     next_color: usize,
     /// Whether to apply styling (bold + colors)
     use_colors: bool,
+    /// Maps lowercase entity names and aliases to their kinds
+    kinds: HashMap<String, EntityKind>,
 }
 
 impl EntityStyler {
@@ -78,12 +105,33 @@ impl EntityStyler {
             color_map: HashMap::new(),
             next_color: 0,
             use_colors,
+            kinds: HashMap::new(),
         }
     }
 
+    /// Color entities by kind, given a map from lowercase names (and aliases) to
+    /// kinds such as `EntitiesRepository::kinds_by_name` returns.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use std::collections::HashMap;
+    /// use wetware::models::EntityKind;
+    /// use wetware::services::entity_styler::EntityStyler;
+    ///
+    /// let kinds = HashMap::from([("sarah".to_string(), EntityKind::Person)]);
+    /// let mut styler = EntityStyler::new(false).with_kinds(kinds);
+    /// assert_eq!(styler.render_content("Met [Sarah]"), "Met Sarah");
+    /// ```
+    pub fn with_kinds(mut self, kinds: HashMap<String, EntityKind>) -> Self {
+        self.kinds = kinds;
+        self
+    }
+
     /// Get or assign a color for the given entity.
     ///
-    /// Colors are assigned on first encounter and cached for consistency.
+    /// Entities with a known kind get their kind's color. Others are assigned a
+    /// palette color on first encounter, cached for consistency.
     /// Entity names are matched case-insensitively.
     ///
     /// # Arguments
@@ -95,6 +143,9 @@ impl EntityStyler {
     /// The ANSI color assigned to this entity
     fn get_color(&mut self, entity: &str) -> AnsiColors {
         let key = entity.to_lowercase();
+        if let Some(&kind) = self.kinds.get(&key) {
+            return kind_color(kind);
+        }
         if let Some(&color) = self.color_map.get(&key) {
             return color;
         }
@@ -173,6 +224,28 @@ impl EntityStyler {
 
         result
     }
+
+    /// Render a bare entity name (no markup) with the same styling it gets in content.
+    ///
+    /// Useful for listing entities alongside rendered content, so a name in a list
+    /// shares the color of its references in the text.
+    ///
+    /// # Examples
+    ///
+    /// ```
+    /// use wetware::services::entity_styler::EntityStyler;
+    ///
+    /// let mut styler = EntityStyler::new(false);
+    /// assert_eq!(styler.render_entity("Sarah"), "Sarah");
+    /// ```
+    pub fn render_entity(&mut self, name: &str) -> String {
+        if self.use_colors {
+            let color = self.get_color(name);
+            name.bold().color(color).to_string()
+        } else {
+            name.to_string()
+        }
+    }
 }
 
 #[cfg(test)]
@@ -396,4 +469,27 @@ mod tests {
         assert!(!output.contains('['));
         assert!(!output.contains(']'));
     }
+
+    #[test]
+    fn test_render_entity_shares_color_with_content_references() {
+        let mut styler = EntityStyler::new(true);
+        styler.render_content("Met [Sarah]");
+        let styled = styler.render_entity("sarah");
+
+        let expected = "sarah"
+            .bold()
+            .color(*styler.color_map.get("sarah").unwrap())
+            .to_string();
+        assert_eq!(styled, expected);
+        assert_eq!(styler.color_map.len(), 1);
+    }
+
+    #[test]
+    fn test_kind_color_overrides_palette() {
+        let kinds = HashMap::from([("sarah".to_string(), EntityKind::Person)]);
+        let mut styler = EntityStyler::new(true).with_kinds(kinds);
+        assert_eq!(styler.get_color("SARAH"), kind_color(EntityKind::Person));
+        // Kinded entities don't use up palette slots
+        assert_eq!(styler.get_color("John"), ENTITY_COLORS[0]);
+    }
 }
@@ -0,0 +1,206 @@
+//! Entity tree service - flattens the parent/child relation graph into rows that can
+//! be printed as an indented tree, one row per (entity, parent) placement.
+
+use crate::models::entity::Entity;
+use std::collections::HashMap;
+
+/// One line of the entity tree
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct TreeRow {
+    /// ID of the entity on this row
+    pub entity_id: i64,
+    /// Nesting level; roots are 0
+    pub depth: usize,
+    /// Tree guides to print before the name, e.g. `"│   └── "`
+    pub prefix: String,
+    /// IDs of the entity's parents other than the one it is shown under on this row.
+    /// Non-empty means the entity has several parents and appears once under each.
+    pub other_parents: Vec<i64>,
+    /// Whether the entity has children of its own
+    pub has_children: bool,
+}
+
+/// Flatten the hierarchy into tree rows, depth first.
+///
+/// Roots are the entities without parents, and siblings are ordered by canonical name
+/// (case-insensitive). An entity with several parents appears under each of them, so
+/// its subtree is repeated. Entities with no relations at all are roots without
+/// children.
+///
+/// # Arguments
+/// * `entities` - Every entity to place in the tree
+/// * `edges` - `(child_id, parent_id)` pairs, as `EntityRelationsRepository::list_all_edges` returns
+///
+/// # Examples
+///
+/// ```
+/// use wetware::models::entity::Entity;
+/// use wetware::services::entity_tree::build;
+///
+/// let mut amazon = Entity::new("Amazon".to_string());
+/// amazon.id = Some(1);
+/// let mut aws = Entity::new("AWS".to_string());
+/// aws.id = Some(2);
+///
+/// let rows = build(&[amazon, aws], &[(2, 1)]);
+/// let lines: Vec<String> = rows.iter().map(|r| format!("{}{}", r.prefix, r.entity_id)).collect();
+/// assert_eq!(lines, ["1", "└── 2"]);
+/// ```
+pub fn build(entities: &[Entity], edges: &[(i64, i64)]) -> Vec<TreeRow> {
+    let names: HashMap<i64, String> = entities
+        .iter()
+        .filter_map(|e| e.id.map(|id| (id, e.canonical_name.to_lowercase())))
+        .collect();
+
+    let mut children: HashMap<i64, Vec<i64>> = HashMap::new();
+    let mut parents: HashMap<i64, Vec<i64>> = HashMap::new();
+    for &(child_id, parent_id) in edges {
+        if names.contains_key(&child_id) && names.contains_key(&parent_id) {
+            children.entry(parent_id).or_default().push(child_id);
+            parents.entry(child_id).or_default().push(parent_id);
+        }
+    }
+    let by_name = |ids: &mut Vec<i64>| ids.sort_by(|a, b| names[a].cmp(&names[b]).then(a.cmp(b)));
+    children.values_mut().for_each(by_name);
+    parents.values_mut().for_each(by_name);
+
+    let mut roots: Vec<i64> = names.keys().copied().filter(|id| !parents.contains_key(id)).collect();
+    by_name(&mut roots);
+
+    let mut rows = Vec::new();
+    let mut path = Vec::new();
+    for root in roots {
+        push_subtree(
+            &Placement {
+                entity_id: root,
+                parent_id: None,
+                depth: 0,
+            },
+            "",
+            "",
+            &children,
+            &parents,
+            &mut path,
+            &mut rows,
+        );
+    }
+    rows
+}
+
+/// Where a subtree is being placed in the tree
+struct Placement {
+    entity_id: i64,
+    parent_id: Option<i64>,
+    depth: usize,
+}
+
+fn push_subtree(
+    placement: &Placement,
+    prefix: &str,
+    continuation: &str,
+    children: &HashMap<i64, Vec<i64>>,
+    parents: &HashMap<i64, Vec<i64>>,
+    path: &mut Vec<i64>,
+    rows: &mut Vec<TreeRow>,
+) {
+    let id = placement.entity_id;
+    // Relations are kept acyclic on write; this only guards against a hand-edited database.
+    if path.contains(&id) {
+        return;
+    }
+
+    let own_children = children.get(&id).map(Vec::as_slice).unwrap_or_default();
+    let other_parents = parents
+        .get(&id)
+        .map(|all| {
+            all.iter()
+                .copied()
+                .filter(|p| Some(*p) != placement.parent_id)
+                .collect()
+        })
+        .unwrap_or_default();
+    rows.push(TreeRow {
+        entity_id: id,
+        depth: placement.depth,
+        prefix: prefix.to_string(),
+        other_parents,
+        has_children: !own_children.is_empty(),
+    });
+
+    path.push(id);
+    for (i, &child) in own_children.iter().enumerate() {
+        let last = i + 1 == own_children.len();
+        let child_prefix = format!("{}{}", continuation, if last { "└── " } else { "├── " });
+        let child_continuation = format!("{}{}", continuation, if last { "    " } else { "│   " });
+        push_subtree(
+            &Placement {
+                entity_id: child,
+                parent_id: Some(id),
+                depth: placement.depth + 1,
+            },
+            &child_prefix,
+            &child_continuation,
+            children,
+            parents,
+            path,
+            rows,
+        );
+    }
+    path.pop();
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn entity(id: i64, name: &str) -> Entity {
+        let mut entity = Entity::new(name.to_string());
+        entity.id = Some(id);
+        entity
+    }
+
+    fn render(rows: &[TreeRow]) -> Vec<String> {
+        rows.iter().map(|r| format!("{}{}", r.prefix, r.entity_id)).collect()
+    }
+
+    #[test]
+    fn test_build_nests_children_alphabetically_with_guides() {
+        let entities = [
+            entity(1, "Amazon"),
+            entity(2, "Retail"),
+            entity(3, "AWS"),
+            entity(4, "EC2"),
+        ];
+        let rows = build(&entities, &[(2, 1), (3, 1), (4, 3)]);
+
+        assert_eq!(render(&rows), ["1", "├── 3", "│   └── 4", "└── 2"]);
+        assert_eq!(rows.iter().map(|r| r.depth).collect::<Vec<_>>(), [0, 1, 2, 1]);
+        assert!(rows[0].has_children && !rows[2].has_children);
+    }
+
+    #[test]
+    fn test_build_repeats_multi_parent_entity_under_each_parent() {
+        let entities = [entity(1, "Amazon"), entity(2, "Google"), entity(3, "Kubernetes")];
+        let rows = build(&entities, &[(3, 1), (3, 2)]);
+
+        assert_eq!(render(&rows), ["1", "└── 3", "2", "└── 3"]);
+        assert_eq!(rows[1].other_parents, [2]);
+        assert_eq!(rows[3].other_parents, [1]);
+    }
+
+    #[test]
+    fn test_build_includes_unrelated_entities_as_roots() {
+        let entities = [entity(1, "Zed"), entity(2, "alpha")];
+        let rows = build(&entities, &[]);
+
+        assert_eq!(render(&rows), ["2", "1"]);
+    }
+
+    #[test]
+    fn test_build_ignores_cycles() {
+        let entities = [entity(1, "Root"), entity(2, "A"), entity(3, "B")];
+        let rows = build(&entities, &[(2, 1), (3, 2), (2, 3)]);
+
+        assert_eq!(render(&rows), ["1", "└── 2", "    └── 3"]);
+    }
+}
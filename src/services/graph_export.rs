@@ -0,0 +1,655 @@
+//! Graph export service - assembles entities, their relations, aliases and
+//! co-occurrences into one graph and writes it as Graphviz DOT, Mermaid or GraphML.
+
+use crate::models::entity::Entity;
+use crate::models::entity_kind::EntityKind;
+use crate::models::relation_type::RelationType;
+use clap::ValueEnum;
+use std::collections::{HashMap, HashSet, VecDeque};
+
+/// Output format for `wet graph`
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, ValueEnum)]
+pub enum GraphFormat {
+    /// Graphviz DOT, for `dot -Tsvg`
+    #[default]
+    Dot,
+    /// Mermaid flowchart, for Markdown documents
+    Mermaid,
+    /// GraphML, for Gephi, yEd and other graph tools
+    Graphml,
+}
+
+/// Everything the graph is built from, as the storage layer returns it
+#[derive(Debug, Default)]
+pub struct GraphSource {
+    /// Every entity; only those with an ID become nodes
+    pub entities: Vec<Entity>,
+    /// `(child_id, parent_id)` hierarchy edges
+    pub hierarchy: Vec<(i64, i64)>,
+    /// `(source_id, type, target_id)` typed relation edges
+    pub relations: Vec<(i64, RelationType, i64)>,
+    /// `(entity_id, alias)` registered aliases
+    pub aliases: Vec<(i64, String)>,
+    /// `(first_id, second_id, thoughts)` pairs of entities mentioned in the same thoughts
+    pub co_occurrences: Vec<(i64, i64, usize)>,
+}
+
+/// What to include in the exported graph
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct GraphOptions {
+    /// Only export entities within `depth` edges of this entity
+    pub root: Option<i64>,
+    /// How many edges away from `root` to go; ignored without a root
+    pub depth: usize,
+    /// Add a node for each alias, linked to its entity
+    pub aliases: bool,
+    /// Add co-occurrence edges
+    pub co_occurrence: bool,
+    /// Leave out co-occurrence edges shared by fewer thoughts than this
+    pub min_weight: usize,
+}
+
+impl Default for GraphOptions {
+    fn default() -> Self {
+        Self {
+            root: None,
+            depth: 1,
+            aliases: false,
+            co_occurrence: true,
+            min_weight: 1,
+        }
+    }
+}
+
+/// What a node stands for
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum NodeKind {
+    /// An entity, with its kind if set
+    Entity(Option<EntityKind>),
+    /// A registered alias of an entity
+    Alias,
+}
+
+/// A graph node
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Node {
+    /// Identifier used in the output: `e<entity id>` or `a<n>`
+    pub id: String,
+    /// Entity canonical name or alias text
+    pub label: String,
+    /// What the node stands for
+    pub kind: NodeKind,
+}
+
+/// What an edge stands for
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum EdgeKind {
+    /// Parent to child in the hierarchy
+    Hierarchy,
+    /// A typed relation, from source to target
+    Relation {
+        /// Relation type name, e.g. `works-at`
+        name: String,
+        /// Whether the type is directed
+        directed: bool,
+    },
+    /// The two entities are mentioned together in this many thoughts
+    CoOccurrence(usize),
+    /// Alias to the entity it names
+    Alias,
+}
+
+/// A graph edge
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Edge {
+    /// ID of the node the edge starts at
+    pub source: String,
+    /// ID of the node the edge ends at
+    pub target: String,
+    /// What the edge stands for
+    pub kind: EdgeKind,
+}
+
+impl Edge {
+    /// The edge's label: relation name, thought count, or nothing for aliases
+    fn label(&self) -> Option<String> {
+        match &self.kind {
+            EdgeKind::Hierarchy => Some("parent-of".to_string()),
+            EdgeKind::Relation { name, .. } => Some(name.clone()),
+            EdgeKind::CoOccurrence(weight) => Some(weight.to_string()),
+            EdgeKind::Alias => None,
+        }
+    }
+
+    /// Name of the edge type, as GraphML's `relation` data records it
+    fn relation(&self) -> &str {
+        match &self.kind {
+            EdgeKind::Hierarchy => "parent-of",
+            EdgeKind::Relation { name, .. } => name,
+            EdgeKind::CoOccurrence(_) => "co-occurrence",
+            EdgeKind::Alias => "alias",
+        }
+    }
+
+    fn directed(&self) -> bool {
+        match &self.kind {
+            EdgeKind::Hierarchy => true,
+            EdgeKind::Relation { directed, .. } => *directed,
+            EdgeKind::CoOccurrence(_) | EdgeKind::Alias => false,
+        }
+    }
+}
+
+/// An entity graph ready to be written out
+#[derive(Debug, Clone, PartialEq, Eq, Default)]
+pub struct Graph {
+    /// Entity nodes by name, then alias nodes
+    pub nodes: Vec<Node>,
+    /// Hierarchy, relation, co-occurrence and alias edges, in that order
+    pub edges: Vec<Edge>,
+}
+
+fn entity_node_id(entity_id: i64) -> String {
+    format!("e{}", entity_id)
+}
+
+/// Build the graph to export from `source`.
+///
+/// With a root, only entities reachable from it in at most `depth` steps are kept,
+/// following hierarchy, relation and (if included) co-occurrence edges in either
+/// direction. Aliases never extend the reach; they hang off the entities kept.
+///
+/// # Examples
+///
+/// ```
+/// use wetware::models::entity::Entity;
+/// use wetware::services::graph_export::{build, GraphOptions, GraphSource};
+///
+/// let mut amazon = Entity::new("Amazon".to_string());
+/// amazon.id = Some(1);
+/// let mut aws = Entity::new("AWS".to_string());
+/// aws.id = Some(2);
+///
+/// let source = GraphSource {
+///     entities: vec![amazon, aws],
+///     hierarchy: vec![(2, 1)],
+///     ..Default::default()
+/// };
+/// let graph = build(&source, &GraphOptions::default());
+/// assert_eq!(graph.nodes.len(), 2);
+/// assert_eq!((graph.edges[0].source.as_str(), graph.edges[0].target.as_str()), ("e1", "e2"));
+/// ```
+pub fn build(source: &GraphSource, options: &GraphOptions) -> Graph {
+    let entities: HashMap<i64, &Entity> = source.entities.iter().filter_map(|e| e.id.map(|id| (id, e))).collect();
+    let known = |id: &i64| entities.contains_key(id);
+
+    let hierarchy: Vec<(i64, i64)> = source
+        .hierarchy
+        .iter()
+        .copied()
+        .filter(|(child, parent)| known(child) && known(parent))
+        .collect();
+    let relations: Vec<&(i64, RelationType, i64)> = source
+        .relations
+        .iter()
+        .filter(|(from, _, to)| known(from) && known(to))
+        .collect();
+    let co_occurrences: Vec<(i64, i64, usize)> = if options.co_occurrence {
+        source
+            .co_occurrences
+            .iter()
+            .copied()
+            .filter(|(a, b, weight)| known(a) && known(b) && *weight >= options.min_weight.max(1))
+            .collect()
+    } else {
+        Vec::new()
+    };
+
+    let kept: HashSet<i64> = match options.root {
+        Some(root) if known(&root) => {
+            let mut neighbours: HashMap<i64, Vec<i64>> = HashMap::new();
+            let pairs = hierarchy
+                .iter()
+                .copied()
+                .chain(relations.iter().map(|(from, _, to)| (*from, *to)))
+                .chain(co_occurrences.iter().map(|(a, b, _)| (*a, *b)));
+            for (a, b) in pairs {
+                neighbours.entry(a).or_default().push(b);
+                neighbours.entry(b).or_default().push(a);
+            }
+            reachable(root, options.depth, &neighbours)
+        }
+        Some(_) => HashSet::new(),
+        None => entities.keys().copied().collect(),
+    };
+
+    let name_of = |id: i64| entities[&id].canonical_name.to_lowercase();
+
+    let mut entity_ids: Vec<i64> = kept.iter().copied().collect();
+    entity_ids.sort_by_key(|id| (name_of(*id), *id));
+
+    let mut nodes: Vec<Node> = entity_ids
+        .iter()
+        .map(|id| Node {
+            id: entity_node_id(*id),
+            label: entities[id].canonical_name.clone(),
+            kind: NodeKind::Entity(entities[id].kind),
+        })
+        .collect();
+
+    let by_names = |a: i64, b: i64| (name_of(a), name_of(b));
+    let mut edges = Vec::new();
+
+    let mut hierarchy: Vec<(i64, i64)> = hierarchy
+        .into_iter()
+        .filter(|(child, parent)| kept.contains(child) && kept.contains(parent))
+        .collect();
+    hierarchy.sort_by_key(|(child, parent)| by_names(*parent, *child));
+    edges.extend(hierarchy.into_iter().map(|(child, parent)| Edge {
+        source: entity_node_id(parent),
+        target: entity_node_id(child),
+        kind: EdgeKind::Hierarchy,
+    }));
+
+    let mut relations: Vec<&(i64, RelationType, i64)> = relations
+        .into_iter()
+        .filter(|(from, _, to)| kept.contains(from) && kept.contains(to))
+        .collect();
+    relations.sort_by_key(|(from, relation, to)| (relation.name.clone(), by_names(*from, *to)));
+    edges.extend(relations.into_iter().map(|(from, relation, to)| Edge {
+        source: entity_node_id(*from),
+        target: entity_node_id(*to),
+        kind: EdgeKind::Relation {
+            name: relation.name.clone(),
+            directed: relation.directed,
+        },
+    }));
+
+    let mut co_occurrences: Vec<(i64, i64, usize)> = co_occurrences
+        .into_iter()
+        .filter(|(a, b, _)| kept.contains(a) && kept.contains(b))
+        .map(|(a, b, weight)| {
+            if name_of(b) < name_of(a) {
+                (b, a, weight)
+            } else {
+                (a, b, weight)
+            }
+        })
+        .collect();
+    co_occurrences.sort_by_key(|(a, b, _)| by_names(*a, *b));
+    edges.extend(co_occurrences.into_iter().map(|(a, b, weight)| Edge {
+        source: entity_node_id(a),
+        target: entity_node_id(b),
+        kind: EdgeKind::CoOccurrence(weight),
+    }));
+
+    if options.aliases {
+        let mut aliases: Vec<&(i64, String)> = source.aliases.iter().filter(|(id, _)| kept.contains(id)).collect();
+        aliases.sort_by_key(|(id, alias)| (name_of(*id), alias.to_lowercase()));
+        for (n, (entity_id, alias)) in aliases.into_iter().enumerate() {
+            let id = format!("a{}", n + 1);
+            edges.push(Edge {
+                source: id.clone(),
+                target: entity_node_id(*entity_id),
+                kind: EdgeKind::Alias,
+            });
+            nodes.push(Node {
+                id,
+                label: alias.clone(),
+                kind: NodeKind::Alias,
+            });
+        }
+    }
+
+    Graph { nodes, edges }
+}
+
+/// Entities at most `depth` steps from `root`, breadth first
+fn reachable(root: i64, depth: usize, neighbours: &HashMap<i64, Vec<i64>>) -> HashSet<i64> {
+    let mut seen = HashSet::from([root]);
+    let mut queue = VecDeque::from([(root, 0)]);
+    while let Some((id, distance)) = queue.pop_front() {
+        if distance == depth {
+            continue;
+        }
+        for next in neighbours.get(&id).into_iter().flatten() {
+            if seen.insert(*next) {
+                queue.push_back((*next, distance + 1));
+            }
+        }
+    }
+    seen
+}
+
+/// Color for an entity kind, matching the terminal colors `wet` uses for it
+fn kind_color_name(kind: EntityKind) -> &'static str {
+    match kind {
+        EntityKind::Person => "magenta",
+        EntityKind::Organization => "blue",
+        EntityKind::Project => "green",
+        EntityKind::Place => "gold",
+        EntityKind::Event => "red",
+        EntityKind::Concept => "cyan",
+    }
+}
+
+/// Write `graph` in the given format
+pub fn render(graph: &Graph, format: GraphFormat) -> String {
+    match format {
+        GraphFormat::Dot => to_dot(graph),
+        GraphFormat::Mermaid => to_mermaid(graph),
+        GraphFormat::Graphml => to_graphml(graph),
+    }
+}
+
+fn dot_string(text: &str) -> String {
+    format!("\"{}\"", text.replace('\\', "\\\\").replace('"', "\\\""))
+}
+
+/// Graphviz DOT: a digraph with undirected edges drawn without arrowheads
+pub fn to_dot(graph: &Graph) -> String {
+    let mut out = String::from("digraph wetware {\n    node [shape=box];\n");
+
+    for node in &graph.nodes {
+        let mut attrs = vec![format!("label={}", dot_string(&node.label))];
+        match node.kind {
+            NodeKind::Entity(Some(kind)) => attrs.push(format!("color={}", kind_color_name(kind))),
+            NodeKind::Entity(None) => {}
+            NodeKind::Alias => attrs.push("shape=ellipse, style=dashed".to_string()),
+        }
+        out.push_str(&format!("    {} [{}];\n", node.id, attrs.join(", ")));
+    }
+
+    for edge in &graph.edges {
+        let mut attrs = Vec::new();
+        if let Some(label) = edge.label() {
+            attrs.push(format!("label={}", dot_string(&label)));
+        }
+        if !edge.directed() {
+            attrs.push("dir=none".to_string());
+        }
+        match edge.kind {
+            EdgeKind::CoOccurrence(weight) => attrs.push(format!("style=dashed, weight={}", weight)),
+            EdgeKind::Alias => attrs.push("style=dotted".to_string()),
+            _ => {}
+        }
+        out.push_str(&format!(
+            "    {} -> {} [{}];\n",
+            edge.source,
+            edge.target,
+            attrs.join(", ")
+        ));
+    }
+
+    out.push_str("}\n");
+    out
+}
+
+fn mermaid_text(text: &str) -> String {
+    text.replace('"', "#quot;").replace('|', "#124;")
+}
+
+/// Mermaid flowchart: solid arrows for relations, dotted links for co-occurrence
+/// and aliases, and one class per entity kind
+pub fn to_mermaid(graph: &Graph) -> String {
+    let mut out = String::from("flowchart TD\n");
+
+    for node in &graph.nodes {
+        let label = mermaid_text(&node.label);
+        match node.kind {
+            NodeKind::Entity(_) => out.push_str(&format!("    {}[\"{}\"]\n", node.id, label)),
+            NodeKind::Alias => out.push_str(&format!("    {}([\"{}\"])\n", node.id, label)),
+        }
+    }
+
+    for edge in &graph.edges {
+        let link = match (&edge.kind, edge.directed()) {
+            (EdgeKind::CoOccurrence(_) | EdgeKind::Alias, _) => "-.-",
+            (_, true) => "-->",
+            (_, false) => "---",
+        };
+        match edge.label() {
+            Some(label) => out.push_str(&format!(
+                "    {} {}|\"{}\"| {}\n",
+                edge.source,
+                link,
+                mermaid_text(&label),
+                edge.target
+            )),
+            None => out.push_str(&format!("    {} {} {}\n", edge.source, link, edge.target)),
+        }
+    }
+
+    for kind in EntityKind::ALL {
+        let ids: Vec<&str> = graph
+            .nodes
+            .iter()
+            .filter(|n| n.kind == NodeKind::Entity(Some(kind)))
+            .map(|n| n.id.as_str())
+            .collect();
+        if !ids.is_empty() {
+            out.push_str(&format!("    classDef {} stroke:{}\n", kind, kind_color_name(kind)));
+            out.push_str(&format!("    class {} {}\n", ids.join(","), kind));
+        }
+    }
+
+    out
+}
+
+fn xml_text(text: &str) -> String {
+    text.replace('&', "&amp;")
+        .replace('<', "&lt;")
+        .replace('>', "&gt;")
+        .replace('"', "&quot;")
+}
+
+/// GraphML with `label`, `type` and `kind` node data and `relation` and `weight`
+/// edge data; undirected edges carry `directed="false"`
+pub fn to_graphml(graph: &Graph) -> String {
+    let mut out = String::from(concat!(
+        "<?xml version=\"1.0\" encoding=\"UTF-8\"?>\n",
+        "<graphml xmlns=\"http://graphml.graphdrawing.org/xmlns\">\n",
+        "  <key id=\"label\" for=\"node\" attr.name=\"label\" attr.type=\"string\"/>\n",
+        "  <key id=\"type\" for=\"node\" attr.name=\"type\" attr.type=\"string\"/>\n",
+        "  <key id=\"kind\" for=\"node\" attr.name=\"kind\" attr.type=\"string\"/>\n",
+        "  <key id=\"relation\" for=\"edge\" attr.name=\"relation\" attr.type=\"string\"/>\n",
+        "  <key id=\"weight\" for=\"edge\" attr.name=\"weight\" attr.type=\"int\"/>\n",
+        "  <graph id=\"wetware\" edgedefault=\"directed\">\n",
+    ));
+
+    for node in &graph.nodes {
+        out.push_str(&format!("    <node id=\"{}\">\n", node.id));
+        out.push_str(&format!("      <data key=\"label\">{}</data>\n", xml_text(&node.label)));
+        match node.kind {
+            NodeKind::Entity(kind) => {
+                out.push_str("      <data key=\"type\">entity</data>\n");
+                if let Some(kind) = kind {
+                    out.push_str(&format!("      <data key=\"kind\">{}</data>\n", kind));
+                }
+            }
+            NodeKind::Alias => out.push_str("      <data key=\"type\">alias</data>\n"),
+        }
+        out.push_str("    </node>\n");
+    }
+
+    for edge in &graph.edges {
+        let directed = if edge.directed() { "" } else { " directed=\"false\"" };
+        out.push_str(&format!(
+            "    <edge source=\"{}\" target=\"{}\"{}>\n",
+            edge.source, edge.target, directed
+        ));
+        out.push_str(&format!(
+            "      <data key=\"relation\">{}</data>\n",
+            xml_text(edge.relation())
+        ));
+        if let EdgeKind::CoOccurrence(weight) = edge.kind {
+            out.push_str(&format!("      <data key=\"weight\">{}</data>\n", weight));
+        }
+        out.push_str("    </edge>\n");
+    }
+
+    out.push_str("  </graph>\n</graphml>\n");
+    out
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn entity(id: i64, name: &str) -> Entity {
+        let mut entity = Entity::new(name.to_string());
+        entity.id = Some(id);
+        entity
+    }
+
+    fn works_at() -> RelationType {
+        RelationType::builtin()
+            .into_iter()
+            .find(|t| t.name == "works-at")
+            .unwrap()
+    }
+
+    /// Amazon <- AWS <- Lambda, Alice works-at Amazon, Alice and Lambda in 2 thoughts, Zed alone
+    fn source() -> GraphSource {
+        GraphSource {
+            entities: vec![
+                entity(1, "Amazon"),
+                entity(2, "AWS"),
+                entity(3, "Lambda"),
+                entity(4, "Alice"),
+                entity(5, "Zed"),
+            ],
+            hierarchy: vec![(2, 1), (3, 2)],
+            relations: vec![(4, works_at(), 1)],
+            aliases: vec![(2, "Amazon Web Services".to_string())],
+            co_occurrences: vec![(3, 4, 2), (1, 5, 1)],
+        }
+    }
+
+    fn labels(graph: &Graph) -> Vec<&str> {
+        graph.nodes.iter().map(|n| n.label.as_str()).collect()
+    }
+
+    #[test]
+    fn test_build_includes_every_entity_and_edge_kind() {
+        let graph = build(
+            &source(),
+            &GraphOptions {
+                aliases: true,
+                ..Default::default()
+            },
+        );
+
+        assert_eq!(
+            labels(&graph),
+            ["Alice", "Amazon", "AWS", "Lambda", "Zed", "Amazon Web Services"]
+        );
+        let kinds: Vec<&EdgeKind> = graph.edges.iter().map(|e| &e.kind).collect();
+        assert_eq!(
+            kinds,
+            [
+                &EdgeKind::Hierarchy,
+                &EdgeKind::Hierarchy,
+                &EdgeKind::Relation {
+                    name: "works-at".to_string(),
+                    directed: true
+                },
+                &EdgeKind::CoOccurrence(2),
+                &EdgeKind::CoOccurrence(1),
+                &EdgeKind::Alias,
+            ]
+        );
+    }
+
+    #[test]
+    fn test_build_root_and_depth_limit_the_subgraph() {
+        let options = GraphOptions {
+            root: Some(2),
+            depth: 1,
+            ..Default::default()
+        };
+        assert_eq!(labels(&build(&source(), &options)), ["Amazon", "AWS", "Lambda"]);
+
+        let options = GraphOptions { depth: 2, ..options };
+        assert_eq!(
+            labels(&build(&source(), &options)),
+            ["Alice", "Amazon", "AWS", "Lambda", "Zed"]
+        );
+    }
+
+    #[test]
+    fn test_build_without_co_occurrence_does_not_reach_through_it() {
+        let options = GraphOptions {
+            root: Some(5),
+            depth: 3,
+            co_occurrence: false,
+            ..Default::default()
+        };
+        let graph = build(&source(), &options);
+        assert_eq!(labels(&graph), ["Zed"]);
+        assert!(graph.edges.is_empty());
+    }
+
+    #[test]
+    fn test_build_min_weight_drops_weak_co_occurrences() {
+        let options = GraphOptions {
+            min_weight: 2,
+            ..Default::default()
+        };
+        let graph = build(&source(), &options);
+        let weights: Vec<usize> = graph
+            .edges
+            .iter()
+            .filter_map(|e| match e.kind {
+                EdgeKind::CoOccurrence(weight) => Some(weight),
+                _ => None,
+            })
+            .collect();
+        assert_eq!(weights, [2]);
+    }
+
+    #[test]
+    fn test_to_dot_escapes_labels_and_marks_undirected_edges() {
+        let mut source = source();
+        source.entities[4].canonical_name = "Zed \"Z\"".to_string();
+        let dot = to_dot(&build(&source, &GraphOptions::default()));
+
+        assert!(dot.starts_with("digraph wetware {\n"));
+        assert!(dot.contains("    e5 [label=\"Zed \\\"Z\\\"\"];\n"));
+        assert!(dot.contains("    e1 -> e2 [label=\"parent-of\"];\n"));
+        assert!(dot.contains("    e1 -> e5 [label=\"1\", dir=none, style=dashed, weight=1];\n"));
+    }
+
+    #[test]
+    fn test_to_mermaid_uses_dotted_links_and_kind_classes() {
+        let mut source = source();
+        source.entities[3].kind = Some(EntityKind::Person);
+        let mermaid = to_mermaid(&build(
+            &source,
+            &GraphOptions {
+                aliases: true,
+                ..Default::default()
+            },
+        ));
+
+        assert!(mermaid.starts_with("flowchart TD\n"));
+        assert!(mermaid.contains("    e4 -->|\"works-at\"| e1\n"));
+        assert!(mermaid.contains("    e4 -.-|\"2\"| e3\n"));
+        assert!(mermaid.contains("    a1([\"Amazon Web Services\"])\n"));
+        assert!(mermaid.contains("    a1 -.- e2\n"));
+        assert!(mermaid.contains("    class e4 person\n"));
+    }
+
+    #[test]
+    fn test_to_graphml_records_weights_and_escapes_text() {
+        let mut source = source();
+        source.entities[0].canonical_name = "A&B".to_string();
+        let graphml = to_graphml(&build(&source, &GraphOptions::default()));
+
+        assert!(graphml.contains("<data key=\"label\">A&amp;B</data>"));
+        assert!(graphml.contains(
+            "    <edge source=\"e4\" target=\"e3\" directed=\"false\">\n      <data key=\"relation\">co-occurrence</data>\n      <data key=\"weight\">2</data>\n"
+        ));
+        assert!(graphml.ends_with("  </graph>\n</graphml>\n"));
+    }
+}
@@ -1,5 +1,14 @@
+pub mod activity_stats;
 pub mod color_mode;
+pub mod date_expression;
 pub mod description_formatter;
 pub mod entity_parser;
 pub mod entity_resolution;
+pub mod entity_similarity;
 pub mod entity_styler;
+pub mod entity_tree;
+pub mod graph_export;
+pub mod related_entities;
+pub mod relation_types;
+pub mod thought_batch;
+pub mod thought_query;
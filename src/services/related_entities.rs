@@ -0,0 +1,149 @@
+//! Related entities service - ranks entities by how often they're mentioned in the
+//! same thoughts as a given one.
+
+use clap::ValueEnum;
+use std::collections::{HashMap, HashSet};
+
+/// How co-occurrence is normalised into a relatedness score
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, ValueEnum)]
+pub enum RelatednessMetric {
+    /// Shared thoughts over thoughts mentioning either entity (0 to 1)
+    #[default]
+    Jaccard,
+    /// Pointwise mutual information: log2 of how much more often the pair appears
+    /// together than chance would predict. Favours rarer entities.
+    Pmi,
+}
+
+impl RelatednessMetric {
+    /// The metric's name as the `--metric` flag spells it
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            RelatednessMetric::Jaccard => "jaccard",
+            RelatednessMetric::Pmi => "pmi",
+        }
+    }
+}
+
+/// An entity ranked against the one asked about
+#[derive(Debug, Clone, PartialEq)]
+pub struct RelatedEntity {
+    /// ID of the related entity
+    pub entity_id: i64,
+    /// Thoughts linked to both entities
+    pub shared: usize,
+    /// Relatedness under the chosen metric; higher is more related
+    pub score: f64,
+}
+
+/// Rank every entity that shares a thought with `entity_id`.
+///
+/// Sorted by score, then by shared thoughts, highest first, then by entity ID.
+/// Entities that never appear alongside `entity_id` are left out.
+///
+/// # Arguments
+/// * `entity_id` - The entity to find relatives of
+/// * `links` - `(thought_id, entity_id)` pairs, as `EntitiesRepository::thought_links` returns;
+///   the thoughts in it are the population the scores are computed over
+/// * `metric` - How to score each pair
+///
+/// # Examples
+///
+/// ```
+/// use wetware::services::related_entities::{rank, RelatednessMetric};
+///
+/// // Thought 1 mentions entities 1 and 2, thought 2 mentions 1 and 3, thought 3 mentions 2
+/// let links = [(1, 1), (1, 2), (2, 1), (2, 3), (3, 2)];
+/// let related = rank(1, &links, RelatednessMetric::Jaccard);
+/// assert_eq!(related[0].entity_id, 3);
+/// assert_eq!(related[0].score, 0.5);
+/// ```
+pub fn rank(entity_id: i64, links: &[(i64, i64)], metric: RelatednessMetric) -> Vec<RelatedEntity> {
+    let mut thoughts_of: HashMap<i64, HashSet<i64>> = HashMap::new();
+    for &(thought_id, linked_id) in links {
+        thoughts_of.entry(linked_id).or_default().insert(thought_id);
+    }
+    let Some(own) = thoughts_of.get(&entity_id) else {
+        return Vec::new();
+    };
+    let total = links
+        .iter()
+        .map(|(thought_id, _)| *thought_id)
+        .collect::<HashSet<_>>()
+        .len() as f64;
+
+    let mut related: Vec<RelatedEntity> = thoughts_of
+        .iter()
+        .filter(|(other_id, _)| **other_id != entity_id)
+        .filter_map(|(other_id, theirs)| {
+            let shared = own.intersection(theirs).count();
+            if shared == 0 {
+                return None;
+            }
+            let score = match metric {
+                RelatednessMetric::Jaccard => shared as f64 / (own.len() + theirs.len() - shared) as f64,
+                RelatednessMetric::Pmi => (shared as f64 * total / (own.len() * theirs.len()) as f64).log2(),
+            };
+            Some(RelatedEntity {
+                entity_id: *other_id,
+                shared,
+                score,
+            })
+        })
+        .collect();
+
+    related.sort_by(|a, b| {
+        b.score
+            .total_cmp(&a.score)
+            .then(b.shared.cmp(&a.shared))
+            .then(a.entity_id.cmp(&b.entity_id))
+    });
+    related
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// Alice (1) and Bob (2) share thoughts 1 and 2; Alice and Carol (3) share thought 2;
+    /// Bob also appears in thoughts 3 and 4; Dave (4) only in thought 5
+    fn links() -> Vec<(i64, i64)> {
+        vec![(1, 1), (1, 2), (2, 1), (2, 2), (2, 3), (3, 2), (4, 2), (5, 4)]
+    }
+
+    fn ids(related: &[RelatedEntity]) -> Vec<i64> {
+        related.iter().map(|r| r.entity_id).collect()
+    }
+
+    #[test]
+    fn test_rank_jaccard_scores_and_orders_by_overlap() {
+        let related = rank(1, &links(), RelatednessMetric::Jaccard);
+
+        // Bob: 2 shared of 4 thoughts; Carol: 1 shared of 2. Equal scores, more shared first.
+        assert_eq!(ids(&related), [2, 3]);
+        assert_eq!((related[0].shared, related[0].score), (2, 0.5));
+        assert_eq!((related[1].shared, related[1].score), (1, 0.5));
+    }
+
+    #[test]
+    fn test_rank_pmi_favours_the_rarer_entity() {
+        let related = rank(1, &links(), RelatednessMetric::Pmi);
+
+        assert_eq!(ids(&related), [3, 2]);
+        // 1 shared * 5 thoughts / (2 * 1)
+        assert!((related[0].score - 2.5f64.log2()).abs() < 1e-9);
+        // 2 shared * 5 thoughts / (2 * 4)
+        assert!((related[1].score - 1.25f64.log2()).abs() < 1e-9);
+    }
+
+    #[test]
+    fn test_rank_leaves_out_entities_never_seen_together() {
+        let related = rank(4, &links(), RelatednessMetric::Jaccard);
+        assert!(related.is_empty());
+    }
+
+    #[test]
+    fn test_rank_unlinked_entity_has_no_relatives() {
+        assert!(rank(99, &links(), RelatednessMetric::Jaccard).is_empty());
+    }
+}
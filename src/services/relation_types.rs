@@ -0,0 +1,96 @@
+//! Relation type registry - combines the built-in relation types with those declared
+//! in the config file and looks them up by name.
+
+use crate::errors::ThoughtError;
+use crate::models::relation_type::RelationType;
+
+/// Every usable relation type: the built-ins, followed by configured types in the
+/// order declared. A configured type reusing a built-in name is ignored, so the
+/// built-in semantics (notably the hierarchy's cycle checks) can't be redefined.
+pub fn available(configured: &[RelationType]) -> Vec<RelationType> {
+    let mut types = RelationType::builtin();
+    for custom in configured {
+        let name = custom.name.trim().to_lowercase();
+        if name.is_empty() || types.iter().any(|t| t.name == name) {
+            continue;
+        }
+        types.push(RelationType { name, ..custom.clone() });
+    }
+    types
+}
+
+/// Find a relation type by name (case-insensitive)
+///
+/// # Examples
+///
+/// ```
+/// use wetware::services::relation_types::find;
+///
+/// assert_eq!(find("Works-At", &[]).unwrap().inverse.as_deref(), Some("employs"));
+/// assert!(find("admires", &[]).is_err());
+/// ```
+pub fn find(name: &str, configured: &[RelationType]) -> Result<RelationType, ThoughtError> {
+    let wanted = name.trim().to_lowercase();
+    let types = available(configured);
+    match types.iter().find(|t| t.name == wanted) {
+        Some(found) => Ok(found.clone()),
+        None => {
+            let names: Vec<&str> = types.iter().map(|t| t.name.as_str()).collect();
+            Err(ThoughtError::InvalidInput(format!(
+                "Unknown relation type '{}'. Known types: {}. Declare new ones under [[relations.types]] \
+                 in the config file.",
+                name,
+                names.join(", ")
+            )))
+        }
+    }
+}
+
+/// The type to render a stored edge with. Edges whose type has since been removed
+/// from the config read as a directed type of that name with no inverse rather than failing.
+pub fn describe(name: &str, configured: &[RelationType]) -> RelationType {
+    find(name, configured).unwrap_or_else(|_| RelationType {
+        name: name.to_string(),
+        inverse: None,
+        directed: true,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn custom(name: &str) -> RelationType {
+        RelationType {
+            name: name.to_string(),
+            inverse: Some("mentored-by".to_string()),
+            directed: true,
+        }
+    }
+
+    #[test]
+    fn test_available_appends_configured_types() {
+        let types = available(&[custom("Mentors")]);
+        assert_eq!(types.last().unwrap().name, "mentors");
+        assert_eq!(types.len(), RelationType::builtin().len() + 1);
+    }
+
+    #[test]
+    fn test_configured_type_cannot_shadow_builtin() {
+        let types = available(&[custom("child-of"), custom("  ")]);
+        assert_eq!(types, RelationType::builtin());
+    }
+
+    #[test]
+    fn test_find_lists_known_types_on_error() {
+        let err = find("admires", &[custom("mentors")]).unwrap_err().to_string();
+        assert!(err.contains("works-at"));
+        assert!(err.contains("mentors"));
+    }
+
+    #[test]
+    fn test_describe_falls_back_for_undeclared_type() {
+        let described = describe("mentors", &[]);
+        assert_eq!(described.label(false), "mentors (from)");
+    }
+}
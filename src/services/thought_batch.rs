@@ -0,0 +1,180 @@
+//! Thought batches - splitting text piped into `wet add --batch` into separate thoughts,
+//! each optionally dated with a leading `YYYY-MM-DD:`.
+
+use crate::errors::ThoughtError;
+use chrono::NaiveDate;
+use clap::ValueEnum;
+
+/// How a batch is divided into thoughts
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, ValueEnum)]
+pub enum BatchMode {
+    /// Every non-blank line is a thought
+    #[default]
+    Lines,
+    /// Every run of lines between blank lines is a thought
+    Blocks,
+}
+
+/// One thought of a batch, before validation
+#[derive(Debug, Clone, PartialEq)]
+pub struct BatchEntry {
+    /// 1-based line the entry starts on, for error messages
+    pub line: usize,
+    /// Day from a leading `YYYY-MM-DD:`, if there was one
+    pub date: Option<NaiveDate>,
+    pub content: String,
+}
+
+/// Split `input` into entries according to `mode`.
+///
+/// Surrounding whitespace is trimmed from every entry and blank lines never become
+/// thoughts. A leading `YYYY-MM-DD:` is taken as the entry's date and removed from its
+/// content.
+///
+/// # Returns
+/// * `Ok(Vec<BatchEntry>)` - The entries in input order (empty for blank input)
+/// * `Err(ThoughtError::InvalidInput)` - An entry starts with a date prefix that isn't a real day
+///
+/// # Examples
+///
+/// ```
+/// use chrono::NaiveDate;
+/// use wetware::services::thought_batch::{split, BatchMode};
+///
+/// let entries = split("2026-03-10: Met [Sarah]\n\nCall [John]\n", BatchMode::Lines).unwrap();
+/// assert_eq!(entries.len(), 2);
+/// assert_eq!(entries[0].date, NaiveDate::from_ymd_opt(2026, 3, 10));
+/// assert_eq!(entries[0].content, "Met [Sarah]");
+/// assert_eq!(entries[1].line, 3);
+/// ```
+pub fn split(input: &str, mode: BatchMode) -> Result<Vec<BatchEntry>, ThoughtError> {
+    let mut raw: Vec<(usize, String)> = Vec::new();
+    match mode {
+        BatchMode::Lines => {
+            for (index, line) in input.lines().enumerate() {
+                if !line.trim().is_empty() {
+                    raw.push((index + 1, line.to_string()));
+                }
+            }
+        }
+        BatchMode::Blocks => {
+            let mut block: Option<(usize, Vec<&str>)> = None;
+            for (index, line) in input.lines().enumerate() {
+                if line.trim().is_empty() {
+                    if let Some((start, lines)) = block.take() {
+                        raw.push((start, lines.join("\n")));
+                    }
+                } else {
+                    block.get_or_insert_with(|| (index + 1, Vec::new())).1.push(line);
+                }
+            }
+            if let Some((start, lines)) = block {
+                raw.push((start, lines.join("\n")));
+            }
+        }
+    }
+
+    raw.into_iter()
+        .map(|(line, text)| {
+            let (date, content) = take_date_prefix(text.trim())
+                .map_err(|prefix| ThoughtError::InvalidInput(format!("line {line}: '{prefix}' is not a valid date")))?;
+            Ok(BatchEntry {
+                line,
+                date,
+                content: content.trim().to_string(),
+            })
+        })
+        .collect()
+}
+
+/// Split a leading `YYYY-MM-DD:` off `text`.
+///
+/// Text that doesn't start with something shaped like a date is returned whole; a
+/// prefix shaped like one that isn't a real day (`2026-02-30:`) is an error.
+fn take_date_prefix(text: &str) -> Result<(Option<NaiveDate>, &str), &str> {
+    let Some(prefix) = text.get(..10) else {
+        return Ok((None, text));
+    };
+    let shaped_like_date = prefix
+        .char_indices()
+        .all(|(i, c)| if i == 4 || i == 7 { c == '-' } else { c.is_ascii_digit() });
+    if !shaped_like_date || !text[10..].starts_with(':') {
+        return Ok((None, text));
+    }
+    let date = NaiveDate::parse_from_str(prefix, "%Y-%m-%d").map_err(|_| prefix)?;
+    Ok((Some(date), &text[11..]))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn day(y: i32, m: u32, d: u32) -> Option<NaiveDate> {
+        NaiveDate::from_ymd_opt(y, m, d)
+    }
+
+    #[test]
+    fn test_lines_skip_blank_lines_and_keep_line_numbers() {
+        let entries = split("  first  \n\n   \nsecond\n", BatchMode::Lines).unwrap();
+        assert_eq!(
+            entries,
+            [
+                BatchEntry {
+                    line: 1,
+                    date: None,
+                    content: "first".to_string()
+                },
+                BatchEntry {
+                    line: 4,
+                    date: None,
+                    content: "second".to_string()
+                },
+            ]
+        );
+    }
+
+    #[test]
+    fn test_blocks_join_lines_between_blank_lines() {
+        let input = "\nStandup notes\n- [Sarah] on leave\n\n\n2026-03-10: Retro\nwent well\n";
+        let entries = split(input, BatchMode::Blocks).unwrap();
+        assert_eq!(entries.len(), 2);
+        assert_eq!(entries[0].line, 2);
+        assert_eq!(entries[0].content, "Standup notes\n- [Sarah] on leave");
+        assert_eq!(entries[1].line, 6);
+        assert_eq!(entries[1].date, day(2026, 3, 10));
+        assert_eq!(entries[1].content, "Retro\nwent well");
+    }
+
+    #[test]
+    fn test_date_prefix_is_optional_and_strict() {
+        let entries = split(
+            "2026-03-10:tight\n2026-03-10 no colon\nAt 2026-03-10: not leading\n2026-3-1: short\n",
+            BatchMode::Lines,
+        )
+        .unwrap();
+        assert_eq!(entries[0].date, day(2026, 3, 10));
+        assert_eq!(entries[0].content, "tight");
+        assert!(entries[1..].iter().all(|entry| entry.date.is_none()));
+        assert_eq!(entries[3].content, "2026-3-1: short");
+    }
+
+    #[test]
+    fn test_impossible_date_prefix_is_an_error() {
+        let err = split("ok\n2026-02-30: nope\n", BatchMode::Lines)
+            .unwrap_err()
+            .to_string();
+        assert!(err.contains("line 2: '2026-02-30' is not a valid date"));
+    }
+
+    #[test]
+    fn test_date_prefix_alone_leaves_empty_content() {
+        let entries = split("2026-03-10:", BatchMode::Lines).unwrap();
+        assert_eq!(entries[0].content, "");
+    }
+
+    #[test]
+    fn test_blank_input_has_no_entries() {
+        assert!(split(" \n\n", BatchMode::Blocks).unwrap().is_empty());
+        assert!(split("", BatchMode::Lines).unwrap().is_empty());
+    }
+}
@@ -0,0 +1,401 @@
+//! Thought query language - boolean expressions over entities and dates, as
+//! `wet thoughts --where` and the TUI filter accept them.
+//!
+//! ```text
+//! query := or
+//! or    := and ("OR" and)*
+//! and   := unary ("AND" unary)*
+//! unary := "NOT" unary | "(" or ")" | term
+//! term  := "after:" DATE | "before:" DATE | NAME
+//! DATE  := "quoted expression" | word
+//! NAME  := "quoted name" | word+
+//! ```
+//!
+//! Keywords are upper case; any other run of bare words is one entity name, so
+//! `machine learning AND NOT draft` needs no quotes. `after:` is inclusive and `before:`
+//! exclusive, so `after:2025-01-01 AND before:2025-02-01` is January. A date may be any
+//! [`date_expression`], quoted if it has spaces: `after:"2 weeks ago"`.
+
+use crate::errors::ThoughtError;
+use crate::services::date_expression;
+use chrono::NaiveDate;
+
+/// A parsed query
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Query {
+    /// Thoughts linked to the entity (by name or alias) or any of its descendants
+    Entity(String),
+    /// Thoughts written on or after the date
+    After(NaiveDate),
+    /// Thoughts written before the date
+    Before(NaiveDate),
+    /// Thoughts the inner query doesn't match
+    Not(Box<Query>),
+    /// Thoughts both queries match
+    And(Box<Query>, Box<Query>),
+    /// Thoughts either query matches
+    Or(Box<Query>, Box<Query>),
+}
+
+impl Query {
+    /// Entity names the query mentions, in order of first appearance, without repeats
+    pub fn entity_names(&self) -> Vec<&str> {
+        let mut names = Vec::new();
+        self.collect_entity_names(&mut names);
+        names
+    }
+
+    fn collect_entity_names<'a>(&'a self, names: &mut Vec<&'a str>) {
+        match self {
+            Query::Entity(name) => {
+                if !names.iter().any(|n| n.eq_ignore_ascii_case(name)) {
+                    names.push(name);
+                }
+            }
+            Query::After(_) | Query::Before(_) => {}
+            Query::Not(inner) => inner.collect_entity_names(names),
+            Query::And(a, b) | Query::Or(a, b) => {
+                a.collect_entity_names(names);
+                b.collect_entity_names(names);
+            }
+        }
+    }
+
+    /// Evaluate the query against one thought.
+    ///
+    /// # Arguments
+    /// * `date` - The thought's date
+    /// * `has_entity` - Whether the thought is linked to the named entity (or one of its
+    ///   descendants); called with names as written in the query
+    pub fn matches(&self, date: NaiveDate, has_entity: &impl Fn(&str) -> bool) -> bool {
+        match self {
+            Query::Entity(name) => has_entity(name),
+            Query::After(day) => date >= *day,
+            Query::Before(day) => date < *day,
+            Query::Not(inner) => !inner.matches(date, has_entity),
+            Query::And(a, b) => a.matches(date, has_entity) && b.matches(date, has_entity),
+            Query::Or(a, b) => a.matches(date, has_entity) || b.matches(date, has_entity),
+        }
+    }
+}
+
+/// Whether `input` uses any query syntax (keywords, parentheses, quotes or date terms),
+/// as opposed to being a plain entity name. Input that fails to tokenize counts as a
+/// query, so parsing it reports the mistake.
+pub fn looks_like_query(input: &str) -> bool {
+    !tokenize(input).is_ok_and(|tokens| tokens.iter().all(|token| matches!(token, Token::Word(_))))
+}
+
+/// Parse a query, resolving relative dates against `today`.
+///
+/// # Returns
+/// * `Ok(Query)` - The parsed query
+/// * `Err(ThoughtError::InvalidQuery)` - The input is empty, unbalanced or has a bad date
+///
+/// # Examples
+///
+/// ```
+/// use chrono::NaiveDate;
+/// use wetware::services::thought_query::{parse, Query};
+///
+/// let today = NaiveDate::from_ymd_opt(2026, 10, 14).unwrap();
+/// let query = parse("sarah AND NOT draft", today).unwrap();
+/// assert_eq!(query.entity_names(), ["sarah", "draft"]);
+/// assert!(matches!(query, Query::And(..)));
+/// ```
+pub fn parse(input: &str, today: NaiveDate) -> Result<Query, ThoughtError> {
+    let tokens = tokenize(input)?;
+    if tokens.is_empty() {
+        return Err(ThoughtError::InvalidQuery("the query is empty".to_string()));
+    }
+    let mut parser = Parser { tokens, pos: 0, today };
+    let query = parser.or()?;
+    match parser.tokens.get(parser.pos) {
+        None => Ok(query),
+        Some(token) => Err(ThoughtError::InvalidQuery(format!("unexpected {}", token.describe()))),
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+enum Token {
+    And,
+    Or,
+    Not,
+    Open,
+    Close,
+    After(String),
+    Before(String),
+    Quoted(String),
+    Word(String),
+}
+
+impl Token {
+    fn describe(&self) -> String {
+        match self {
+            Token::And => "'AND'".to_string(),
+            Token::Or => "'OR'".to_string(),
+            Token::Not => "'NOT'".to_string(),
+            Token::Open => "'('".to_string(),
+            Token::Close => "')'".to_string(),
+            Token::After(value) => format!("'after:{}'", value),
+            Token::Before(value) => format!("'before:{}'", value),
+            Token::Quoted(name) => format!("\"{}\"", name),
+            Token::Word(word) => format!("'{}'", word),
+        }
+    }
+}
+
+fn tokenize(input: &str) -> Result<Vec<Token>, ThoughtError> {
+    let mut tokens = Vec::new();
+    let mut chars = input.chars().peekable();
+
+    while let Some(&c) = chars.peek() {
+        if c.is_whitespace() {
+            chars.next();
+        } else if c == '(' || c == ')' {
+            chars.next();
+            tokens.push(if c == '(' { Token::Open } else { Token::Close });
+        } else if c == '"' {
+            chars.next();
+            tokens.push(Token::Quoted(quoted(&mut chars)?));
+        } else {
+            let mut word = String::new();
+            while let Some(&c) = chars.peek() {
+                if c.is_whitespace() || c == '(' || c == ')' || c == '"' {
+                    break;
+                }
+                word.push(c);
+                chars.next();
+            }
+            // `after:"2 weeks ago"` - the date is the quoted string that follows
+            if chars.peek() == Some(&'"')
+                && (word.eq_ignore_ascii_case("after:") || word.eq_ignore_ascii_case("before:"))
+            {
+                chars.next();
+                word.push_str(&quoted(&mut chars)?);
+            }
+            tokens.push(word_token(word));
+        }
+    }
+
+    Ok(tokens)
+}
+
+/// Read up to the closing quote (the opening one already consumed), trimmed
+fn quoted(chars: &mut impl Iterator<Item = char>) -> Result<String, ThoughtError> {
+    let mut text = String::new();
+    loop {
+        match chars.next() {
+            Some('"') => return Ok(text.trim().to_string()),
+            Some(c) => text.push(c),
+            None => return Err(ThoughtError::InvalidQuery("unterminated quote".to_string())),
+        }
+    }
+}
+
+fn word_token(word: String) -> Token {
+    match word.as_str() {
+        "AND" => Token::And,
+        "OR" => Token::Or,
+        "NOT" => Token::Not,
+        _ => match word.split_once(':') {
+            Some((prefix, value)) if prefix.eq_ignore_ascii_case("after") => Token::After(value.to_string()),
+            Some((prefix, value)) if prefix.eq_ignore_ascii_case("before") => Token::Before(value.to_string()),
+            _ => Token::Word(word),
+        },
+    }
+}
+
+struct Parser {
+    tokens: Vec<Token>,
+    pos: usize,
+    today: NaiveDate,
+}
+
+impl Parser {
+    fn date(&self, prefix: &str, value: &str) -> Result<NaiveDate, ThoughtError> {
+        date_expression::parse(value, self.today).ok_or_else(|| {
+            ThoughtError::InvalidQuery(format!(
+                "invalid date '{}' in {}; expected {}",
+                value,
+                prefix,
+                date_expression::EXPECTED
+            ))
+        })
+    }
+
+    fn eat(&mut self, token: &Token) -> bool {
+        if self.tokens.get(self.pos) == Some(token) {
+            self.pos += 1;
+            true
+        } else {
+            false
+        }
+    }
+
+    fn or(&mut self) -> Result<Query, ThoughtError> {
+        let mut query = self.and()?;
+        while self.eat(&Token::Or) {
+            query = Query::Or(Box::new(query), Box::new(self.and()?));
+        }
+        Ok(query)
+    }
+
+    fn and(&mut self) -> Result<Query, ThoughtError> {
+        let mut query = self.unary()?;
+        while self.eat(&Token::And) {
+            query = Query::And(Box::new(query), Box::new(self.unary()?));
+        }
+        Ok(query)
+    }
+
+    fn unary(&mut self) -> Result<Query, ThoughtError> {
+        let Some(token) = self.tokens.get(self.pos).cloned() else {
+            return Err(ThoughtError::InvalidQuery("the query ends too early".to_string()));
+        };
+        self.pos += 1;
+        match token {
+            Token::Not => Ok(Query::Not(Box::new(self.unary()?))),
+            Token::Open => {
+                let query = self.or()?;
+                if !self.eat(&Token::Close) {
+                    return Err(ThoughtError::InvalidQuery("missing ')'".to_string()));
+                }
+                Ok(query)
+            }
+            Token::After(value) => Ok(Query::After(self.date("after:", &value)?)),
+            Token::Before(value) => Ok(Query::Before(self.date("before:", &value)?)),
+            Token::Quoted(name) if !name.is_empty() => Ok(Query::Entity(name)),
+            Token::Word(first) => {
+                let mut words = vec![first];
+                while let Some(Token::Word(word)) = self.tokens.get(self.pos) {
+                    words.push(word.clone());
+                    self.pos += 1;
+                }
+                Ok(Query::Entity(words.join(" ")))
+            }
+            other => Err(ThoughtError::InvalidQuery(format!("unexpected {}", other.describe()))),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn entity(name: &str) -> Box<Query> {
+        Box::new(Query::Entity(name.to_string()))
+    }
+
+    /// Wednesday 2026-10-14
+    fn today() -> NaiveDate {
+        day("2026-10-14")
+    }
+
+    fn day(s: &str) -> NaiveDate {
+        NaiveDate::parse_from_str(s, "%Y-%m-%d").unwrap()
+    }
+
+    #[test]
+    fn test_parse_and_binds_tighter_than_or() {
+        assert_eq!(
+            parse("a OR b AND c", today()).unwrap(),
+            Query::Or(entity("a"), Box::new(Query::And(entity("b"), entity("c"))))
+        );
+    }
+
+    #[test]
+    fn test_parse_the_full_example() {
+        let query = parse("sarah AND (alpha OR beta) AND NOT draft AND after:2025-01-01", today()).unwrap();
+        let expected = Query::And(
+            Box::new(Query::And(
+                Box::new(Query::And(
+                    entity("sarah"),
+                    Box::new(Query::Or(entity("alpha"), entity("beta"))),
+                )),
+                Box::new(Query::Not(entity("draft"))),
+            )),
+            Box::new(Query::After(day("2025-01-01"))),
+        );
+        assert_eq!(query, expected);
+    }
+
+    #[test]
+    fn test_parse_joins_bare_words_and_reads_quotes() {
+        assert_eq!(parse("machine learning", today()).unwrap(), *entity("machine learning"));
+        assert_eq!(parse("\"rock AND roll\"", today()).unwrap(), *entity("rock AND roll"));
+        // Lower-case keywords are just words
+        assert_eq!(parse("salt and pepper", today()).unwrap(), *entity("salt and pepper"));
+    }
+
+    #[test]
+    fn test_parse_rejects_malformed_queries() {
+        for input in [
+            "",
+            "   ",
+            "(a AND b",
+            "a AND",
+            "a b)",
+            "NOT",
+            "\"open",
+            "after:soon",
+            "OR a",
+            "\"\"",
+        ] {
+            assert!(
+                matches!(parse(input, today()), Err(ThoughtError::InvalidQuery(_))),
+                "{input:?} should not parse"
+            );
+        }
+    }
+
+    #[test]
+    fn test_parse_relative_and_quoted_dates() {
+        assert_eq!(
+            parse("after:yesterday", today()).unwrap(),
+            Query::After(day("2026-10-13"))
+        );
+        assert_eq!(parse("before:-1w", today()).unwrap(), Query::Before(day("2026-10-07")));
+        assert_eq!(
+            parse("after:\"last friday\" AND sarah", today()).unwrap(),
+            Query::And(Box::new(Query::After(day("2026-10-09"))), entity("sarah"))
+        );
+        assert!(matches!(
+            parse("after:\"next week\"", today()),
+            Err(ThoughtError::InvalidQuery(message)) if message.contains("'next week'")
+        ));
+    }
+
+    #[test]
+    fn test_matches_evaluates_entities_and_half_open_dates() {
+        let query = parse(
+            "(alpha OR beta) AND NOT draft AND after:2025-01-01 AND before:2025-02-01",
+            today(),
+        )
+        .unwrap();
+        let linked = |names: &'static [&'static str]| move |name: &str| names.contains(&name);
+
+        assert!(query.matches(day("2025-01-01"), &linked(&["alpha"])));
+        assert!(query.matches(day("2025-01-31"), &linked(&["beta"])));
+        assert!(!query.matches(day("2025-02-01"), &linked(&["alpha"])));
+        assert!(!query.matches(day("2024-12-31"), &linked(&["alpha"])));
+        assert!(!query.matches(day("2025-01-15"), &linked(&["alpha", "draft"])));
+        assert!(!query.matches(day("2025-01-15"), &linked(&["gamma"])));
+    }
+
+    #[test]
+    fn test_entity_names_are_deduplicated_case_insensitively() {
+        let query = parse("Sarah OR (sarah AND bob) OR NOT after:2025-01-01", today()).unwrap();
+        assert_eq!(query.entity_names(), ["Sarah", "bob"]);
+    }
+
+    #[test]
+    fn test_looks_like_query() {
+        assert!(!looks_like_query("machine learning"));
+        assert!(looks_like_query("sarah AND bob"));
+        assert!(looks_like_query("NOT draft"));
+        assert!(looks_like_query("(sarah)"));
+        assert!(looks_like_query("after:2025-01-01"));
+        assert!(looks_like_query("after:soon"));
+    }
+}
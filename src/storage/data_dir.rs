@@ -2,6 +2,17 @@
 use crate::errors::ThoughtError;
 use std::path::{Path, PathBuf};
 
+/// Environment variable overriding the data directory
+pub const DATA_DIR_ENV: &str = "WETWARE_DATA_DIR";
+
+/// Environment variable overriding the database path
+pub const DB_PATH_ENV: &str = "WETWARE_DB";
+
+/// The path in environment variable `name`, if it is set and not empty
+pub fn path_from_env(name: &str) -> Option<PathBuf> {
+    std::env::var_os(name).filter(|v| !v.is_empty()).map(PathBuf::from)
+}
+
 /// Resolve the wetware data directory.
 ///
 /// If `override_path` is provided, uses that directly.
@@ -1,7 +1,21 @@
 /// Repository for entities persistence
 use crate::errors::ThoughtError;
 use crate::models::entity::Entity;
-use rusqlite::{Connection, OptionalExtension};
+use crate::models::entity_kind::EntityKind;
+use crate::models::timezone::Timezone;
+use chrono::NaiveDate;
+use rusqlite::{Connection, OptionalExtension, Row};
+use std::collections::HashMap;
+
+/// Read an optional entity kind column
+///
+/// A value this version doesn't know (from a newer version or a hand-edited
+/// database) reads as no kind rather than failing the row, which would break every
+/// command that lists entities.
+pub(crate) fn kind_from_column(row: &Row, index: usize) -> rusqlite::Result<Option<EntityKind>> {
+    let raw: Option<String> = row.get(index)?;
+    Ok(raw.and_then(|value| value.parse::<EntityKind>().ok()))
+}
 
 /// Entities repository for database operations
 pub struct EntitiesRepository;
@@ -37,10 +51,20 @@ impl EntitiesRepository {
         Ok(())
     }
 
+    /// Remove a single entity's link to a thought. A no-op if they aren't linked.
+    pub fn unlink_from_thought(conn: &Connection, entity_id: i64, thought_id: i64) -> Result<(), ThoughtError> {
+        conn.execute(
+            "DELETE FROM thought_entities WHERE thought_id = ?1 AND entity_id = ?2",
+            (thought_id, entity_id),
+        )?;
+        Ok(())
+    }
+
     /// Find an entity by name (case-insensitive)
     pub fn find_by_name(conn: &Connection, name: &str) -> Result<Option<Entity>, ThoughtError> {
         let lowercase_name = name.to_lowercase();
-        let mut stmt = conn.prepare("SELECT id, name, canonical_name, description FROM entities WHERE name = ?1")?;
+        let mut stmt =
+            conn.prepare("SELECT id, name, canonical_name, description, kind FROM entities WHERE name = ?1")?;
 
         let entity = stmt
             .query_row([lowercase_name], |row| {
@@ -49,6 +73,7 @@ impl EntitiesRepository {
                     name: row.get(1)?,
                     canonical_name: row.get(2)?,
                     description: row.get(3)?,
+                    kind: kind_from_column(row, 4)?,
                 })
             })
             .optional()?;
@@ -90,8 +115,8 @@ impl EntitiesRepository {
 
     /// List all entities in alphabetical order
     pub fn list_all(conn: &Connection) -> Result<Vec<Entity>, ThoughtError> {
-        let mut stmt =
-            conn.prepare("SELECT id, name, canonical_name, description FROM entities ORDER BY canonical_name ASC")?;
+        let mut stmt = conn
+            .prepare("SELECT id, name, canonical_name, description, kind FROM entities ORDER BY canonical_name ASC")?;
 
         let entities = stmt
             .query_map([], |row| {
@@ -100,6 +125,35 @@ impl EntitiesRepository {
                     name: row.get(1)?,
                     canonical_name: row.get(2)?,
                     description: row.get(3)?,
+                    kind: kind_from_column(row, 4)?,
+                })
+            })?
+            .collect::<Result<Vec<_>, _>>()?;
+
+        Ok(entities)
+    }
+
+    /// List the entities linked to a thought in alphabetical order
+    ///
+    /// Reads the `thought_entities` links directly; a thought with no links (or a
+    /// nonexistent thought ID) returns an empty list.
+    pub fn list_for_thought(conn: &Connection, thought_id: i64) -> Result<Vec<Entity>, ThoughtError> {
+        let mut stmt = conn.prepare(
+            "SELECT e.id, e.name, e.canonical_name, e.description, e.kind
+             FROM entities e
+             INNER JOIN thought_entities te ON e.id = te.entity_id
+             WHERE te.thought_id = ?1
+             ORDER BY e.canonical_name ASC",
+        )?;
+
+        let entities = stmt
+            .query_map([thought_id], |row| {
+                Ok(Entity {
+                    id: Some(row.get(0)?),
+                    name: row.get(1)?,
+                    canonical_name: row.get(2)?,
+                    description: row.get(3)?,
+                    kind: kind_from_column(row, 4)?,
                 })
             })?
             .collect::<Result<Vec<_>, _>>()?;
@@ -107,6 +161,89 @@ impl EntitiesRepository {
         Ok(entities)
     }
 
+    /// Canonical names of the `limit` entities mentioned most recently, latest first
+    ///
+    /// An entity's recency is its newest linked thought's `created_at`; entities never
+    /// mentioned are left out.
+    pub fn recently_mentioned(conn: &Connection, limit: usize) -> Result<Vec<String>, ThoughtError> {
+        let mut stmt = conn.prepare(
+            "SELECT e.canonical_name
+             FROM entities e
+             INNER JOIN thought_entities te ON te.entity_id = e.id
+             INNER JOIN thoughts t ON t.id = te.thought_id
+             GROUP BY e.id
+             ORDER BY MAX(t.created_at) DESC, MAX(t.id) DESC
+             LIMIT ?1",
+        )?;
+
+        let names = stmt
+            .query_map([limit as i64], |row| row.get(0))?
+            .collect::<Result<Vec<_>, _>>()?;
+
+        Ok(names)
+    }
+
+    /// Count the thoughts directly linked to each entity, keyed by entity ID
+    ///
+    /// Entities with no linked thoughts are absent from the map. Links via descendant
+    /// entities are not counted.
+    pub fn thought_counts(conn: &Connection) -> Result<HashMap<i64, usize>, ThoughtError> {
+        let mut stmt = conn.prepare("SELECT entity_id, COUNT(*) FROM thought_entities GROUP BY entity_id")?;
+
+        let counts = stmt
+            .query_map([], |row| Ok((row.get::<_, i64>(0)?, row.get::<_, i64>(1)? as usize)))?
+            .collect::<Result<HashMap<_, _>, _>>()?;
+
+        Ok(counts)
+    }
+
+    /// Count the thoughts linking each pair of entities, as `(first_id, second_id, count)`
+    /// with `first_id < second_id`
+    ///
+    /// Pairs that never appear in the same thought are absent.
+    pub fn co_occurrence_counts(conn: &Connection) -> Result<Vec<(i64, i64, usize)>, ThoughtError> {
+        let mut stmt = conn.prepare(
+            "SELECT a.entity_id, b.entity_id, COUNT(*)
+             FROM thought_entities a
+             INNER JOIN thought_entities b ON b.thought_id = a.thought_id AND b.entity_id > a.entity_id
+             GROUP BY a.entity_id, b.entity_id
+             ORDER BY a.entity_id ASC, b.entity_id ASC",
+        )?;
+
+        let counts = stmt
+            .query_map([], |row| Ok((row.get(0)?, row.get(1)?, row.get::<_, i64>(2)? as usize)))?
+            .collect::<Result<Vec<_>, _>>()?;
+
+        Ok(counts)
+    }
+
+    /// Every thought/entity link as `(thought_id, entity_id)`, limited to thoughts
+    /// written within the inclusive `since`/`until` days (in `timezone`) when given
+    pub fn thought_links(
+        conn: &Connection,
+        since: Option<NaiveDate>,
+        until: Option<NaiveDate>,
+        timezone: Timezone,
+    ) -> Result<Vec<(i64, i64)>, ThoughtError> {
+        let mut stmt = conn.prepare(
+            "SELECT te.thought_id, te.entity_id
+             FROM thought_entities te
+             INNER JOIN thoughts t ON t.id = te.thought_id
+             WHERE (?1 IS NULL OR julianday(t.created_at) >= julianday(?1))
+               AND (?2 IS NULL OR julianday(t.created_at) < julianday(?2))
+             ORDER BY te.thought_id ASC, te.entity_id ASC",
+        )?;
+
+        let start = |d: Option<NaiveDate>| d.map(|d| timezone.start_of_day(d).to_rfc3339());
+        let links = stmt
+            .query_map((start(since), start(until.and_then(|d| d.succ_opt()))), |row| {
+                Ok((row.get(0)?, row.get(1)?))
+            })?
+            .collect::<Result<Vec<_>, _>>()?;
+
+        Ok(links)
+    }
+
     /// Remove all entity associations for a thought
     ///
     /// Deletes all rows from `thought_entities` where `thought_id` matches.
@@ -150,6 +287,42 @@ impl EntitiesRepository {
         Ok(())
     }
 
+    /// Set or clear an entity's kind
+    ///
+    /// Returns error if entity doesn't exist
+    pub fn set_kind(conn: &Connection, entity_name: &str, kind: Option<EntityKind>) -> Result<(), ThoughtError> {
+        let updated = conn.execute(
+            "UPDATE entities SET kind = ?1 WHERE name = ?2",
+            (kind.map(|k| k.as_str()), entity_name.to_lowercase()),
+        )?;
+        if updated == 0 {
+            return Err(ThoughtError::EntityNotFound(entity_name.to_string()));
+        }
+        Ok(())
+    }
+
+    /// Map every canonical name and alias (lowercased) to its entity's kind, for
+    /// entities that have one. Used to style inline `[entity]` references by kind.
+    pub fn kinds_by_name(conn: &Connection) -> Result<HashMap<String, EntityKind>, ThoughtError> {
+        let mut stmt = conn.prepare(
+            "SELECT e.name, e.kind FROM entities e WHERE e.kind IS NOT NULL
+             UNION ALL
+             SELECT lower(a.alias), e.kind FROM entity_aliases a
+             JOIN entities e ON e.id = a.entity_id
+             WHERE e.kind IS NOT NULL",
+        )?;
+        let rows = stmt.query_map([], |row| Ok((row.get::<_, String>(0)?, kind_from_column(row, 1)?)))?;
+
+        let mut kinds = HashMap::new();
+        for row in rows {
+            let (name, kind) = row?;
+            if let Some(kind) = kind {
+                kinds.entry(name).or_insert(kind);
+            }
+        }
+        Ok(kinds)
+    }
+
     /// Rename an entity, updating its `name` and `canonical_name`.
     ///
     /// Returns the entity's ID on success. Fails with `EntityNotFound` if `old_name`
@@ -371,6 +544,140 @@ mod tests {
         assert_eq!(count_after, 0);
     }
 
+    #[test]
+    fn test_list_for_thought_returns_linked_entities_alphabetically() {
+        let conn = get_memory_connection().unwrap();
+        run_migrations(&conn).unwrap();
+
+        conn.execute(
+            "INSERT INTO thoughts (content, created_at) VALUES ('Test', datetime('now'))",
+            [],
+        )
+        .unwrap();
+        let thought_id = conn.last_insert_rowid();
+
+        let bob_id = EntitiesRepository::find_or_create(&conn, &Entity::new("Bob".to_string())).unwrap();
+        let alice_id = EntitiesRepository::find_or_create(&conn, &Entity::new("Alice".to_string())).unwrap();
+        EntitiesRepository::find_or_create(&conn, &Entity::new("Carol".to_string())).unwrap();
+        EntitiesRepository::link_to_thought(&conn, bob_id, thought_id).unwrap();
+        EntitiesRepository::link_to_thought(&conn, alice_id, thought_id).unwrap();
+
+        let linked = EntitiesRepository::list_for_thought(&conn, thought_id).unwrap();
+        let names: Vec<_> = linked.iter().map(|e| e.canonical_name.as_str()).collect();
+        assert_eq!(names, vec!["Alice", "Bob"]);
+
+        assert!(EntitiesRepository::list_for_thought(&conn, 9999).unwrap().is_empty());
+    }
+
+    #[test]
+    fn test_thought_counts_counts_direct_links() {
+        let conn = get_memory_connection().unwrap();
+        run_migrations(&conn).unwrap();
+
+        let alice_id = EntitiesRepository::find_or_create(&conn, &Entity::new("Alice".to_string())).unwrap();
+        let bob_id = EntitiesRepository::find_or_create(&conn, &Entity::new("Bob".to_string())).unwrap();
+        for _ in 0..2 {
+            conn.execute(
+                "INSERT INTO thoughts (content, created_at) VALUES ('Test', datetime('now'))",
+                [],
+            )
+            .unwrap();
+            EntitiesRepository::link_to_thought(&conn, alice_id, conn.last_insert_rowid()).unwrap();
+        }
+
+        let counts = EntitiesRepository::thought_counts(&conn).unwrap();
+        assert_eq!(counts.get(&alice_id), Some(&2));
+        assert_eq!(counts.get(&bob_id), None);
+    }
+
+    #[test]
+    fn test_co_occurrence_counts_pairs_entities_per_shared_thought() {
+        let conn = get_memory_connection().unwrap();
+        run_migrations(&conn).unwrap();
+
+        let alice_id = EntitiesRepository::find_or_create(&conn, &Entity::new("Alice".to_string())).unwrap();
+        let bob_id = EntitiesRepository::find_or_create(&conn, &Entity::new("Bob".to_string())).unwrap();
+        let carol_id = EntitiesRepository::find_or_create(&conn, &Entity::new("Carol".to_string())).unwrap();
+        for linked in [vec![alice_id, bob_id], vec![alice_id, bob_id, carol_id], vec![carol_id]] {
+            conn.execute(
+                "INSERT INTO thoughts (content, created_at) VALUES ('Test', datetime('now'))",
+                [],
+            )
+            .unwrap();
+            let thought_id = conn.last_insert_rowid();
+            for entity_id in linked {
+                EntitiesRepository::link_to_thought(&conn, entity_id, thought_id).unwrap();
+            }
+        }
+
+        let counts = EntitiesRepository::co_occurrence_counts(&conn).unwrap();
+        assert_eq!(
+            counts,
+            vec![(alice_id, bob_id, 2), (alice_id, carol_id, 1), (bob_id, carol_id, 1)]
+        );
+    }
+
+    #[test]
+    fn test_thought_links_limits_to_the_date_window() {
+        let conn = get_memory_connection().unwrap();
+        run_migrations(&conn).unwrap();
+
+        let alice_id = EntitiesRepository::find_or_create(&conn, &Entity::new("Alice".to_string())).unwrap();
+        for created_at in ["2026-03-01T09:00:00+00:00", "2026-03-15T09:00:00+00:00"] {
+            conn.execute(
+                "INSERT INTO thoughts (content, created_at) VALUES ('Test', ?1)",
+                [created_at],
+            )
+            .unwrap();
+            EntitiesRepository::link_to_thought(&conn, alice_id, conn.last_insert_rowid()).unwrap();
+        }
+
+        let utc = Timezone::Named(chrono_tz::Tz::UTC);
+        let all = EntitiesRepository::thought_links(&conn, None, None, utc).unwrap();
+        assert_eq!(all.len(), 2);
+
+        let since = NaiveDate::from_ymd_opt(2026, 3, 15);
+        let windowed = EntitiesRepository::thought_links(&conn, since, since, utc).unwrap();
+        assert_eq!(windowed, vec![(2, alice_id)]);
+
+        // 09:00 UTC on the 15th is already the 15th in Tokyo, and so is 23:30 UTC on the 14th
+        conn.execute(
+            "INSERT INTO thoughts (content, created_at) VALUES ('Late', '2026-03-14T23:30:00+00:00')",
+            [],
+        )
+        .unwrap();
+        EntitiesRepository::link_to_thought(&conn, alice_id, conn.last_insert_rowid()).unwrap();
+        let tokyo = "Asia/Tokyo".parse().unwrap();
+        let windowed = EntitiesRepository::thought_links(&conn, since, since, tokyo).unwrap();
+        assert_eq!(windowed, vec![(2, alice_id), (3, alice_id)]);
+        let windowed = EntitiesRepository::thought_links(&conn, since, since, utc).unwrap();
+        assert_eq!(windowed, vec![(2, alice_id)]);
+    }
+
+    #[test]
+    fn test_unlink_from_thought_removes_only_that_link() {
+        let conn = get_memory_connection().unwrap();
+        run_migrations(&conn).unwrap();
+
+        conn.execute(
+            "INSERT INTO thoughts (content, created_at) VALUES ('Test', datetime('now'))",
+            [],
+        )
+        .unwrap();
+        let thought_id = conn.last_insert_rowid();
+        let alice_id = EntitiesRepository::find_or_create(&conn, &Entity::new("Alice".to_string())).unwrap();
+        let bob_id = EntitiesRepository::find_or_create(&conn, &Entity::new("Bob".to_string())).unwrap();
+        EntitiesRepository::link_to_thought(&conn, alice_id, thought_id).unwrap();
+        EntitiesRepository::link_to_thought(&conn, bob_id, thought_id).unwrap();
+
+        EntitiesRepository::unlink_from_thought(&conn, alice_id, thought_id).unwrap();
+        EntitiesRepository::unlink_from_thought(&conn, alice_id, thought_id).unwrap();
+
+        let linked = EntitiesRepository::list_for_thought(&conn, thought_id).unwrap();
+        assert_eq!(linked.len(), 1);
+        assert_eq!(linked[0].canonical_name, "Bob");
+    }
+
     #[test]
     fn test_unlink_all_from_thought_no_links_is_noop() {
         let conn = get_memory_connection().unwrap();
@@ -394,6 +701,59 @@ mod tests {
         }
     }
 
+    #[test]
+    fn test_set_kind_and_clear() {
+        let conn = get_memory_connection().unwrap();
+        run_migrations(&conn).unwrap();
+        EntitiesRepository::find_or_create(&conn, &Entity::new("Sarah".to_string())).unwrap();
+
+        EntitiesRepository::set_kind(&conn, "SARAH", Some(EntityKind::Person)).unwrap();
+        let entity = EntitiesRepository::find_by_name(&conn, "sarah").unwrap().unwrap();
+        assert_eq!(entity.kind, Some(EntityKind::Person));
+
+        EntitiesRepository::set_kind(&conn, "sarah", None).unwrap();
+        let entity = EntitiesRepository::find_by_name(&conn, "sarah").unwrap().unwrap();
+        assert_eq!(entity.kind, None);
+    }
+
+    #[test]
+    fn test_set_kind_nonexistent_entity() {
+        let conn = get_memory_connection().unwrap();
+        run_migrations(&conn).unwrap();
+
+        let result = EntitiesRepository::set_kind(&conn, "ghost", Some(EntityKind::Place));
+        assert!(matches!(result, Err(ThoughtError::EntityNotFound(name)) if name == "ghost"));
+    }
+
+    #[test]
+    fn test_unknown_stored_kind_reads_as_none() {
+        let conn = get_memory_connection().unwrap();
+        run_migrations(&conn).unwrap();
+        EntitiesRepository::find_or_create(&conn, &Entity::new("Sarah".to_string())).unwrap();
+        conn.execute("UPDATE entities SET kind = 'animal'", []).unwrap();
+
+        let sarah = EntitiesRepository::find_by_name(&conn, "sarah").unwrap().unwrap();
+        assert_eq!(sarah.kind, None);
+        let all = EntitiesRepository::list_all(&conn).unwrap();
+        assert_eq!(all.len(), 1);
+        assert_eq!(all[0].kind, None);
+    }
+
+    #[test]
+    fn test_kinds_by_name_includes_aliases() {
+        let conn = get_memory_connection().unwrap();
+        run_migrations(&conn).unwrap();
+        let sarah = EntitiesRepository::find_or_create(&conn, &Entity::new("Sarah".to_string())).unwrap();
+        EntitiesRepository::find_or_create(&conn, &Entity::new("Rust".to_string())).unwrap();
+        EntityAliasesRepository::add_alias(&conn, sarah, "Sar").unwrap();
+        EntitiesRepository::set_kind(&conn, "sarah", Some(EntityKind::Person)).unwrap();
+
+        let kinds = EntitiesRepository::kinds_by_name(&conn).unwrap();
+        assert_eq!(kinds.get("sarah"), Some(&EntityKind::Person));
+        assert_eq!(kinds.get("sar"), Some(&EntityKind::Person));
+        assert!(!kinds.contains_key("rust"));
+    }
+
     #[test]
     fn test_rename_success() {
         let conn = get_memory_connection().unwrap();
@@ -568,6 +928,35 @@ mod tests {
             .unwrap()
     }
 
+    #[test]
+    fn test_recently_mentioned_orders_by_latest_thought() {
+        use crate::models::thought::Thought;
+        use crate::storage::thoughts_repository::ThoughtsRepository;
+
+        let conn = get_memory_connection().unwrap();
+        run_migrations(&conn).unwrap();
+        let alice = EntitiesRepository::find_or_create(&conn, &Entity::new("Alice".to_string())).unwrap();
+        let bob = EntitiesRepository::find_or_create(&conn, &Entity::new("Bob".to_string())).unwrap();
+        let carol = EntitiesRepository::find_or_create(&conn, &Entity::new("Carol".to_string())).unwrap();
+        EntitiesRepository::find_or_create(&conn, &Entity::new("Unmentioned".to_string())).unwrap();
+        for (day, entity_id) in [(3, alice), (1, bob), (2, carol), (4, bob)] {
+            let created_at = chrono::NaiveDate::from_ymd_opt(2026, 5, day)
+                .unwrap()
+                .and_hms_opt(12, 0, 0)
+                .unwrap()
+                .and_utc();
+            let thought = Thought::new_with_date("Mention".to_string(), created_at).unwrap();
+            let thought_id = ThoughtsRepository::save(&conn, &thought).unwrap();
+            EntitiesRepository::link_to_thought(&conn, entity_id, thought_id).unwrap();
+        }
+
+        assert_eq!(
+            EntitiesRepository::recently_mentioned(&conn, 10).unwrap(),
+            ["Bob", "Alice", "Carol"]
+        );
+        assert_eq!(EntitiesRepository::recently_mentioned(&conn, 1).unwrap(), ["Bob"]);
+    }
+
     #[test]
     fn test_repoint_thought_links_moves_links_to_target() {
         let conn = get_memory_connection().unwrap();
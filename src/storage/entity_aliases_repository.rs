@@ -1,6 +1,7 @@
 /// Repository for entity aliases persistence
 use crate::errors::ThoughtError;
 use crate::models::entity::Entity;
+use crate::storage::entities_repository::kind_from_column;
 use rusqlite::Connection;
 
 /// Entity aliases repository for database operations
@@ -52,6 +53,17 @@ impl EntityAliasesRepository {
         Ok(aliases)
     }
 
+    /// Every registered alias as `(entity_id, alias)`, ordered by entity then alias.
+    pub fn list_all(conn: &Connection) -> Result<Vec<(i64, String)>, ThoughtError> {
+        let mut stmt = conn.prepare("SELECT entity_id, alias FROM entity_aliases ORDER BY entity_id ASC, alias ASC")?;
+
+        let aliases = stmt
+            .query_map([], |row| Ok((row.get(0)?, row.get(1)?)))?
+            .collect::<Result<Vec<_>, _>>()?;
+
+        Ok(aliases)
+    }
+
     /// All distinct entities that have `alias` registered (case-insensitive).
     ///
     /// Empty vec means the alias isn't registered for any entity. More than one
@@ -59,7 +71,7 @@ impl EntityAliasesRepository {
     /// callers must treat that as ambiguous rather than picking one.
     pub fn find_entities_by_alias(conn: &Connection, alias: &str) -> Result<Vec<Entity>, ThoughtError> {
         let mut stmt = conn.prepare(
-            "SELECT e.id, e.name, e.canonical_name, e.description
+            "SELECT e.id, e.name, e.canonical_name, e.description, e.kind
              FROM entities e
              INNER JOIN entity_aliases ea ON ea.entity_id = e.id
              WHERE ea.alias = ?1
@@ -73,6 +85,7 @@ impl EntityAliasesRepository {
                     name: row.get(1)?,
                     canonical_name: row.get(2)?,
                     description: row.get(3)?,
+                    kind: kind_from_column(row, 4)?,
                 })
             })?
             .collect::<Result<Vec<_>, _>>()?;
@@ -203,6 +216,28 @@ mod tests {
         assert_eq!(aliases, vec!["alpha", "zeta"]);
     }
 
+    #[test]
+    fn test_list_all_returns_every_entitys_aliases() {
+        let conn = get_memory_connection().unwrap();
+        run_migrations(&conn).unwrap();
+
+        let sarah = make_entity(&conn, "Sarah");
+        let john = make_entity(&conn, "John");
+        EntityAliasesRepository::add_alias(&conn, john, "jo").unwrap();
+        EntityAliasesRepository::add_alias(&conn, sarah, "sar").unwrap();
+        EntityAliasesRepository::add_alias(&conn, sarah, "boss").unwrap();
+
+        let all = EntityAliasesRepository::list_all(&conn).unwrap();
+        assert_eq!(
+            all,
+            vec![
+                (sarah, "boss".to_string()),
+                (sarah, "sar".to_string()),
+                (john, "jo".to_string())
+            ]
+        );
+    }
+
     #[test]
     fn test_deleting_entity_cascades_alias_removal() {
         let conn = get_memory_connection().unwrap();
@@ -0,0 +1,188 @@
+/// Repository for entity attributes persistence
+use crate::errors::ThoughtError;
+use rusqlite::Connection;
+use std::collections::{BTreeMap, HashMap, HashSet};
+
+/// Entity attributes repository for database operations
+///
+/// Each entity has at most one value per key. Keys are stored lowercased and
+/// trimmed, so `Email` and `email` are the same attribute; values are stored as given
+/// (trimmed).
+pub struct EntityAttributesRepository;
+
+impl EntityAttributesRepository {
+    /// Set `key` to `value` on `entity_id`, replacing any existing value.
+    pub fn set(conn: &Connection, entity_id: i64, key: &str, value: &str) -> Result<(), ThoughtError> {
+        let key = key.trim().to_lowercase();
+        if key.is_empty() {
+            return Err(ThoughtError::InvalidInput("Attribute key cannot be empty".to_string()));
+        }
+
+        conn.execute(
+            "INSERT INTO entity_attributes (entity_id, key, value) VALUES (?1, ?2, ?3)
+             ON CONFLICT(entity_id, key) DO UPDATE SET value = excluded.value",
+            (entity_id, key, value.trim()),
+        )?;
+        Ok(())
+    }
+
+    /// Remove `key` from `entity_id`. Returns whether the attribute was set.
+    pub fn unset(conn: &Connection, entity_id: i64, key: &str) -> Result<bool, ThoughtError> {
+        let removed = conn.execute(
+            "DELETE FROM entity_attributes WHERE entity_id = ?1 AND key = ?2",
+            (entity_id, key.trim()),
+        )?;
+        Ok(removed > 0)
+    }
+
+    /// All attributes of `entity_id`, keyed by (lowercase) key.
+    pub fn list_for_entity(conn: &Connection, entity_id: i64) -> Result<BTreeMap<String, String>, ThoughtError> {
+        let mut stmt = conn.prepare("SELECT key, value FROM entity_attributes WHERE entity_id = ?1")?;
+
+        let attributes = stmt
+            .query_map([entity_id], |row| Ok((row.get(0)?, row.get(1)?)))?
+            .collect::<Result<BTreeMap<_, _>, _>>()?;
+
+        Ok(attributes)
+    }
+
+    /// Every entity's attributes, keyed by entity ID. Entities without attributes are absent.
+    pub fn list_all(conn: &Connection) -> Result<HashMap<i64, BTreeMap<String, String>>, ThoughtError> {
+        let mut stmt = conn.prepare("SELECT entity_id, key, value FROM entity_attributes")?;
+        let rows = stmt.query_map([], |row| {
+            Ok((
+                row.get::<_, i64>(0)?,
+                row.get::<_, String>(1)?,
+                row.get::<_, String>(2)?,
+            ))
+        })?;
+
+        let mut attributes: HashMap<i64, BTreeMap<String, String>> = HashMap::new();
+        for row in rows {
+            let (entity_id, key, value) = row?;
+            attributes.entry(entity_id).or_default().insert(key, value);
+        }
+        Ok(attributes)
+    }
+
+    /// IDs of the entities whose `key` attribute equals `value` (both case-insensitive).
+    pub fn entity_ids_with(conn: &Connection, key: &str, value: &str) -> Result<HashSet<i64>, ThoughtError> {
+        let mut stmt =
+            conn.prepare("SELECT entity_id FROM entity_attributes WHERE key = ?1 AND value = ?2 COLLATE NOCASE")?;
+
+        let ids = stmt
+            .query_map((key.trim(), value.trim()), |row| row.get(0))?
+            .collect::<Result<HashSet<_>, _>>()?;
+
+        Ok(ids)
+    }
+
+    /// Copy `source_id`'s attributes onto `target_id`, keeping the target's value for
+    /// any key both have. Returns the number of attributes copied.
+    pub fn copy_missing(conn: &Connection, source_id: i64, target_id: i64) -> Result<usize, ThoughtError> {
+        let copied = conn.execute(
+            "INSERT OR IGNORE INTO entity_attributes (entity_id, key, value)
+             SELECT ?2, key, value FROM entity_attributes WHERE entity_id = ?1",
+            (source_id, target_id),
+        )?;
+        Ok(copied)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::models::entity::Entity;
+    use crate::storage::connection::get_memory_connection;
+    use crate::storage::entities_repository::EntitiesRepository;
+    use crate::storage::migrations::run_migrations;
+
+    fn setup() -> Connection {
+        let conn = get_memory_connection().unwrap();
+        run_migrations(&conn).unwrap();
+        conn
+    }
+
+    fn make_entity(conn: &Connection, name: &str) -> i64 {
+        EntitiesRepository::find_or_create(conn, &Entity::new(name.to_string())).unwrap()
+    }
+
+    #[test]
+    fn test_set_replaces_value_and_normalizes_key() {
+        let conn = setup();
+        let sarah = make_entity(&conn, "Sarah");
+
+        EntityAttributesRepository::set(&conn, sarah, "Status", "active").unwrap();
+        EntityAttributesRepository::set(&conn, sarah, "status ", " away ").unwrap();
+
+        let attributes = EntityAttributesRepository::list_for_entity(&conn, sarah).unwrap();
+        assert_eq!(attributes.len(), 1);
+        assert_eq!(attributes["status"], "away");
+    }
+
+    #[test]
+    fn test_set_rejects_empty_key() {
+        let conn = setup();
+        let sarah = make_entity(&conn, "Sarah");
+
+        let result = EntityAttributesRepository::set(&conn, sarah, "  ", "x");
+        assert!(matches!(result, Err(ThoughtError::InvalidInput(_))));
+    }
+
+    #[test]
+    fn test_unset_reports_whether_removed() {
+        let conn = setup();
+        let sarah = make_entity(&conn, "Sarah");
+        EntityAttributesRepository::set(&conn, sarah, "email", "s@example.com").unwrap();
+
+        assert!(EntityAttributesRepository::unset(&conn, sarah, "EMAIL").unwrap());
+        assert!(!EntityAttributesRepository::unset(&conn, sarah, "email").unwrap());
+        assert!(
+            EntityAttributesRepository::list_for_entity(&conn, sarah)
+                .unwrap()
+                .is_empty()
+        );
+    }
+
+    #[test]
+    fn test_entity_ids_with_matches_case_insensitively() {
+        let conn = setup();
+        let sarah = make_entity(&conn, "Sarah");
+        let john = make_entity(&conn, "John");
+        EntityAttributesRepository::set(&conn, sarah, "status", "Active").unwrap();
+        EntityAttributesRepository::set(&conn, john, "status", "retired").unwrap();
+
+        let ids = EntityAttributesRepository::entity_ids_with(&conn, "Status", "active").unwrap();
+        assert_eq!(ids, HashSet::from([sarah]));
+    }
+
+    #[test]
+    fn test_copy_missing_keeps_target_values() {
+        let conn = setup();
+        let source = make_entity(&conn, "Sarah K");
+        let target = make_entity(&conn, "Sarah");
+        EntityAttributesRepository::set(&conn, source, "email", "old@example.com").unwrap();
+        EntityAttributesRepository::set(&conn, source, "url", "https://example.com").unwrap();
+        EntityAttributesRepository::set(&conn, target, "email", "new@example.com").unwrap();
+
+        assert_eq!(
+            EntityAttributesRepository::copy_missing(&conn, source, target).unwrap(),
+            1
+        );
+
+        let attributes = EntityAttributesRepository::list_for_entity(&conn, target).unwrap();
+        assert_eq!(attributes["email"], "new@example.com");
+        assert_eq!(attributes["url"], "https://example.com");
+    }
+
+    #[test]
+    fn test_attributes_removed_with_entity() {
+        let conn = setup();
+        let sarah = make_entity(&conn, "Sarah");
+        EntityAttributesRepository::set(&conn, sarah, "email", "s@example.com").unwrap();
+
+        EntitiesRepository::delete(&conn, sarah).unwrap();
+
+        assert!(EntityAttributesRepository::list_all(&conn).unwrap().is_empty());
+    }
+}
@@ -1,6 +1,9 @@
-/// Repository for directed entity relations (parent/child) persistence
+/// Repository for entity relations persistence: the parent/child hierarchy
+/// (`entity_relations`) and typed relations (`entity_typed_relations`)
 use crate::errors::ThoughtError;
 use crate::models::entity::Entity;
+use crate::models::relation_type::TypedRelation;
+use crate::storage::entities_repository::kind_from_column;
 use rusqlite::Connection;
 
 /// Recursive CTE computing all entity ids reachable as descendants of `?1`
@@ -17,14 +20,14 @@ const DESCENDANTS_CTE: &str = "
 pub struct EntityRelationsRepository;
 
 impl EntityRelationsRepository {
-    /// Mark `child_id` as a child of `parent_id`. Idempotent - a no-op if the
-    /// relation already exists.
-    pub fn add_relation(conn: &Connection, child_id: i64, parent_id: i64) -> Result<(), ThoughtError> {
-        conn.execute(
+    /// Mark `child_id` as a child of `parent_id`; returns whether the relation was
+    /// added. Idempotent - a no-op if the relation already exists.
+    pub fn add_relation(conn: &Connection, child_id: i64, parent_id: i64) -> Result<bool, ThoughtError> {
+        let added = conn.execute(
             "INSERT OR IGNORE INTO entity_relations (child_id, parent_id) VALUES (?1, ?2)",
             (child_id, parent_id),
         )?;
-        Ok(())
+        Ok(added > 0)
     }
 
     /// Remove the child/parent relation if present. Safe to call even if no such
@@ -49,7 +52,7 @@ impl EntityRelationsRepository {
     /// Direct (non-transitive) parents of the given entity.
     pub fn list_parents(conn: &Connection, entity_id: i64) -> Result<Vec<Entity>, ThoughtError> {
         let mut stmt = conn.prepare(
-            "SELECT e.id, e.name, e.canonical_name, e.description
+            "SELECT e.id, e.name, e.canonical_name, e.description, e.kind
              FROM entities e
              INNER JOIN entity_relations er ON er.parent_id = e.id
              WHERE er.child_id = ?1
@@ -66,7 +69,7 @@ impl EntityRelationsRepository {
     /// Direct (non-transitive) children of the given entity.
     pub fn list_children(conn: &Connection, entity_id: i64) -> Result<Vec<Entity>, ThoughtError> {
         let mut stmt = conn.prepare(
-            "SELECT e.id, e.name, e.canonical_name, e.description
+            "SELECT e.id, e.name, e.canonical_name, e.description, e.kind
              FROM entities e
              INNER JOIN entity_relations er ON er.child_id = e.id
              WHERE er.parent_id = ?1
@@ -91,12 +94,101 @@ impl EntityRelationsRepository {
         Ok(edges)
     }
 
+    /// All typed relation edges as `(source_id, relation_type, target_id)` triples.
+    pub fn list_all_typed_edges(conn: &Connection) -> Result<Vec<(i64, String, i64)>, ThoughtError> {
+        let mut stmt = conn.prepare("SELECT source_id, relation_type, target_id FROM entity_typed_relations")?;
+
+        let edges = stmt
+            .query_map([], |row| Ok((row.get(0)?, row.get(1)?, row.get(2)?)))?
+            .collect::<Result<Vec<_>, _>>()?;
+
+        Ok(edges)
+    }
+
+    /// Record `source_id <relation_type> target_id`; returns whether it was added.
+    /// Idempotent - a no-op if that exact edge already exists. The type name is stored
+    /// as given; validating it against the known types is the caller's job.
+    pub fn add_typed_relation(
+        conn: &Connection,
+        source_id: i64,
+        relation_type: &str,
+        target_id: i64,
+    ) -> Result<bool, ThoughtError> {
+        let added = conn.execute(
+            "INSERT OR IGNORE INTO entity_typed_relations (source_id, target_id, relation_type) VALUES (?1, ?2, ?3)",
+            (source_id, target_id, relation_type),
+        )?;
+        Ok(added > 0)
+    }
+
+    /// Remove a typed edge if present; returns whether one was removed.
+    pub fn remove_typed_relation(
+        conn: &Connection,
+        source_id: i64,
+        relation_type: &str,
+        target_id: i64,
+    ) -> Result<bool, ThoughtError> {
+        let removed = conn.execute(
+            "DELETE FROM entity_typed_relations WHERE source_id = ?1 AND target_id = ?2 AND relation_type = ?3",
+            (source_id, target_id, relation_type),
+        )?;
+        Ok(removed > 0)
+    }
+
+    /// Whether the exact typed edge `source_id <relation_type> target_id` exists.
+    pub fn has_typed_relation(
+        conn: &Connection,
+        source_id: i64,
+        relation_type: &str,
+        target_id: i64,
+    ) -> Result<bool, ThoughtError> {
+        let exists: bool = conn.query_row(
+            "SELECT EXISTS(SELECT 1 FROM entity_typed_relations
+                           WHERE source_id = ?1 AND target_id = ?2 AND relation_type = ?3)",
+            (source_id, target_id, relation_type),
+            |row| row.get(0),
+        )?;
+        Ok(exists)
+    }
+
+    /// Every typed edge touching the given entity, in either direction, ordered by
+    /// relation type, then outgoing before incoming, then the other entity's name.
+    pub fn list_typed_relations(conn: &Connection, entity_id: i64) -> Result<Vec<TypedRelation>, ThoughtError> {
+        let mut stmt = conn.prepare(
+            "SELECT r.relation_type, r.source_id = ?1 AS outgoing,
+                    e.id, e.name, e.canonical_name, e.description, e.kind
+             FROM entity_typed_relations r
+             INNER JOIN entities e ON e.id = CASE WHEN r.source_id = ?1 THEN r.target_id ELSE r.source_id END
+             WHERE r.source_id = ?1 OR r.target_id = ?1
+             ORDER BY r.relation_type ASC, outgoing DESC, e.canonical_name ASC",
+        )?;
+
+        let relations = stmt
+            .query_map([entity_id], |row| {
+                Ok(TypedRelation {
+                    relation_type: row.get(0)?,
+                    outgoing: row.get(1)?,
+                    other: Entity {
+                        id: Some(row.get(2)?),
+                        name: row.get(3)?,
+                        canonical_name: row.get(4)?,
+                        description: row.get(5)?,
+                        kind: kind_from_column(row, 6)?,
+                    },
+                })
+            })?
+            .collect::<Result<Vec<_>, _>>()?;
+
+        Ok(relations)
+    }
+
     fn row_to_entity(row: &rusqlite::Row) -> rusqlite::Result<Entity> {
         Ok(Entity {
             id: Some(row.get(0)?),
             name: row.get(1)?,
             canonical_name: row.get(2)?,
             description: row.get(3)?,
+            kind: kind_from_column(row, 4)?,
         })
     }
 }
@@ -254,6 +346,20 @@ mod tests {
         assert_eq!(edges, vec![(aws, amazon)]);
     }
 
+    #[test]
+    fn test_list_all_typed_edges() {
+        let conn = get_memory_connection().unwrap();
+        run_migrations(&conn).unwrap();
+
+        let alice = make_entity(&conn, "Alice");
+        let acme = make_entity(&conn, "Acme");
+
+        EntityRelationsRepository::add_typed_relation(&conn, alice, "works-at", acme).unwrap();
+
+        let edges = EntityRelationsRepository::list_all_typed_edges(&conn).unwrap();
+        assert_eq!(edges, vec![(alice, "works-at".to_string(), acme)]);
+    }
+
     #[test]
     fn test_list_parents_and_children_empty_when_no_relations() {
         let conn = get_memory_connection().unwrap();
@@ -264,4 +370,75 @@ mod tests {
         assert!(EntityRelationsRepository::list_parents(&conn, aws).unwrap().is_empty());
         assert!(EntityRelationsRepository::list_children(&conn, aws).unwrap().is_empty());
     }
+
+    #[test]
+    fn test_typed_relation_add_is_idempotent_and_listed_from_both_ends() {
+        let conn = get_memory_connection().unwrap();
+        run_migrations(&conn).unwrap();
+        let alice = make_entity(&conn, "Alice");
+        let acme = make_entity(&conn, "Acme");
+
+        assert!(EntityRelationsRepository::add_typed_relation(&conn, alice, "works-at", acme).unwrap());
+        assert!(!EntityRelationsRepository::add_typed_relation(&conn, alice, "works-at", acme).unwrap());
+
+        let from_alice = EntityRelationsRepository::list_typed_relations(&conn, alice).unwrap();
+        assert_eq!(from_alice.len(), 1);
+        assert!(from_alice[0].outgoing);
+        assert_eq!(from_alice[0].relation_type, "works-at");
+        assert_eq!(from_alice[0].other.canonical_name, "Acme");
+
+        let from_acme = EntityRelationsRepository::list_typed_relations(&conn, acme).unwrap();
+        assert_eq!(from_acme.len(), 1);
+        assert!(!from_acme[0].outgoing);
+        assert_eq!(from_acme[0].other.canonical_name, "Alice");
+    }
+
+    #[test]
+    fn test_typed_relations_are_distinct_from_hierarchy() {
+        let conn = get_memory_connection().unwrap();
+        run_migrations(&conn).unwrap();
+        let acme = make_entity(&conn, "Acme");
+        let berlin = make_entity(&conn, "Berlin");
+
+        EntityRelationsRepository::add_typed_relation(&conn, acme, "located-in", berlin).unwrap();
+
+        assert!(EntityRelationsRepository::list_parents(&conn, acme).unwrap().is_empty());
+        assert!(!EntityRelationsRepository::would_create_cycle(&conn, berlin, acme).unwrap());
+        assert!(EntityRelationsRepository::has_typed_relation(&conn, acme, "located-in", berlin).unwrap());
+        assert!(!EntityRelationsRepository::has_typed_relation(&conn, berlin, "located-in", acme).unwrap());
+    }
+
+    #[test]
+    fn test_remove_typed_relation_reports_whether_removed() {
+        let conn = get_memory_connection().unwrap();
+        run_migrations(&conn).unwrap();
+        let alice = make_entity(&conn, "Alice");
+        let bob = make_entity(&conn, "Bob");
+        EntityRelationsRepository::add_typed_relation(&conn, alice, "related-to", bob).unwrap();
+
+        assert!(!EntityRelationsRepository::remove_typed_relation(&conn, alice, "works-at", bob).unwrap());
+        assert!(EntityRelationsRepository::remove_typed_relation(&conn, alice, "related-to", bob).unwrap());
+        assert!(
+            EntityRelationsRepository::list_typed_relations(&conn, alice)
+                .unwrap()
+                .is_empty()
+        );
+    }
+
+    #[test]
+    fn test_typed_relations_cascade_on_entity_delete() {
+        let conn = get_memory_connection().unwrap();
+        run_migrations(&conn).unwrap();
+        let alice = make_entity(&conn, "Alice");
+        let acme = make_entity(&conn, "Acme");
+        EntityRelationsRepository::add_typed_relation(&conn, alice, "works-at", acme).unwrap();
+
+        EntitiesRepository::delete(&conn, acme).unwrap();
+
+        assert!(
+            EntityRelationsRepository::list_typed_relations(&conn, alice)
+                .unwrap()
+                .is_empty()
+        );
+    }
 }
@@ -0,0 +1,53 @@
+// Migration: Add kind column to entities table
+// This migration adds an optional classification (person, place, ...) to entities
+
+use crate::errors::ThoughtError;
+use rusqlite::Connection;
+
+/// Add kind TEXT column to entities table (idempotent)
+pub fn migrate_add_entity_kind(conn: &Connection) -> Result<(), ThoughtError> {
+    // Check if column already exists
+    let column_exists: i64 = conn.query_row(
+        "SELECT COUNT(*) FROM pragma_table_info('entities') WHERE name='kind'",
+        [],
+        |row| row.get(0),
+    )?;
+
+    // Only add column if it doesn't exist
+    if column_exists == 0 {
+        conn.execute_batch("ALTER TABLE entities ADD COLUMN kind TEXT;")?;
+    }
+
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_migration_adds_kind_column_once() {
+        let conn = Connection::open_in_memory().unwrap();
+        conn.execute_batch(
+            "CREATE TABLE entities (
+                id INTEGER PRIMARY KEY AUTOINCREMENT,
+                name TEXT NOT NULL UNIQUE COLLATE NOCASE,
+                canonical_name TEXT NOT NULL,
+                description TEXT
+            );",
+        )
+        .unwrap();
+
+        migrate_add_entity_kind(&conn).unwrap();
+        migrate_add_entity_kind(&conn).unwrap();
+
+        let column_count: i64 = conn
+            .query_row(
+                "SELECT COUNT(*) FROM pragma_table_info('entities') WHERE name='kind'",
+                [],
+                |row| row.get(0),
+            )
+            .unwrap();
+        assert_eq!(column_count, 1);
+    }
+}
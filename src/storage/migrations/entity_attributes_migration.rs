@@ -0,0 +1,53 @@
+/// Database migration for entity attributes (structured key/value data such as email or status)
+/// Creates table: entity_attributes
+use rusqlite::{Connection, Result};
+
+pub fn migrate(conn: &Connection) -> Result<()> {
+    conn.execute(
+        "CREATE TABLE IF NOT EXISTS entity_attributes (
+            entity_id INTEGER NOT NULL,
+            key TEXT NOT NULL COLLATE NOCASE CHECK(length(trim(key)) > 0),
+            value TEXT NOT NULL,
+            PRIMARY KEY (entity_id, key),
+            FOREIGN KEY (entity_id) REFERENCES entities(id) ON DELETE CASCADE
+        )",
+        [],
+    )?;
+
+    conn.execute(
+        "CREATE INDEX IF NOT EXISTS idx_entity_attributes_key_value ON entity_attributes(key, value)",
+        [],
+    )?;
+
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_migration_creates_table_and_index() {
+        let conn = Connection::open_in_memory().unwrap();
+        migrate(&conn).unwrap();
+
+        let names: Vec<String> = conn
+            .prepare("SELECT name FROM sqlite_master WHERE type IN ('table', 'index') ORDER BY name")
+            .unwrap()
+            .query_map([], |row| row.get(0))
+            .unwrap()
+            .collect::<Result<Vec<_>, _>>()
+            .unwrap();
+
+        assert!(names.contains(&"entity_attributes".to_string()));
+        assert!(names.contains(&"idx_entity_attributes_key_value".to_string()));
+    }
+
+    #[test]
+    fn test_migration_idempotent() {
+        let conn = Connection::open_in_memory().unwrap();
+
+        migrate(&conn).unwrap();
+        migrate(&conn).unwrap();
+    }
+}
@@ -1,14 +1,43 @@
 /// Database migrations module
 pub mod add_entity_descriptions_migration;
+pub mod add_entity_kind_migration;
 pub mod entity_aliases_migration;
+pub mod entity_attributes_migration;
 pub mod entity_relations_migration;
 pub mod networked_notes_migration;
+pub mod normalize_timestamps_migration;
+pub mod typed_relations_migration;
 
 use crate::errors::ThoughtError;
+use crate::models::Timezone;
 use rusqlite::Connection;
 
 /// Run all database migrations
+///
+/// Migration 008 dates legacy backdated thoughts in a time zone, which only the
+/// caller knows. Here it runs only on a database without thoughts, where there is
+/// nothing to re-date; a database with thoughts waits for [`run_migrations_in`],
+/// which `main` calls with the configured zone before any command opens it.
 pub fn run_migrations(conn: &Connection) -> Result<(), ThoughtError> {
+    run_schema_migrations(conn)?;
+
+    // Run migration 008 on an empty database: only marks it as done
+    normalize_timestamps_migration::mark_if_empty(conn)
+}
+
+/// Run all database migrations, dating legacy backdated thoughts in `timezone`
+///
+/// The zone in effect when a legacy database is first opened is the one its
+/// backdated thoughts are anchored in; changing `thoughts.timezone` later doesn't
+/// re-date them.
+pub fn run_migrations_in(conn: &Connection, timezone: Timezone) -> Result<(), ThoughtError> {
+    run_schema_migrations(conn)?;
+
+    // Run migration 008: thought timestamps (backdated days anchored in `timezone`)
+    normalize_timestamps_migration::migrate(conn, timezone)
+}
+
+fn run_schema_migrations(conn: &Connection) -> Result<(), ThoughtError> {
     // Run migration 001: networked notes
     networked_notes_migration::migrate(conn)?;
 
@@ -21,6 +50,15 @@ pub fn run_migrations(conn: &Connection) -> Result<(), ThoughtError> {
     // Run migration 004: entity aliases
     entity_aliases_migration::migrate(conn)?;
 
+    // Run migration 005: typed entity relations
+    typed_relations_migration::migrate(conn)?;
+
+    // Run migration 006: entity kinds
+    add_entity_kind_migration::migrate_add_entity_kind(conn)?;
+
+    // Run migration 007: entity attributes
+    entity_attributes_migration::migrate(conn)?;
+
     Ok(())
 }
 
@@ -47,6 +85,8 @@ mod tests {
         assert!(tables.contains(&"entities".to_string()));
         assert!(tables.contains(&"thought_entities".to_string()));
         assert!(tables.contains(&"entity_aliases".to_string()));
+        assert!(tables.contains(&"entity_typed_relations".to_string()));
+        assert!(tables.contains(&"entity_attributes".to_string()));
 
         // Verify description column exists in entities table
         let description_col_exists: bool = conn
@@ -62,6 +102,54 @@ mod tests {
         );
     }
 
+    fn legacy_database() -> Connection {
+        let conn = Connection::open_in_memory().unwrap();
+        run_schema_migrations(&conn).unwrap();
+        conn.execute(
+            "INSERT INTO thoughts (content, created_at) VALUES ('Backdated', '2025-03-10T00:00:00+00:00')",
+            [],
+        )
+        .unwrap();
+        conn
+    }
+
+    fn created_at(conn: &Connection) -> String {
+        conn.query_row("SELECT created_at FROM thoughts", [], |row| row.get(0))
+            .unwrap()
+    }
+
+    #[test]
+    fn test_run_migrations_leaves_legacy_thoughts_for_the_configured_zone() {
+        let conn = legacy_database();
+
+        run_migrations(&conn).unwrap();
+        assert_eq!(created_at(&conn), "2025-03-10T00:00:00+00:00");
+
+        // Configured zone far from the system one: the day must hold in the configured zone
+        let los_angeles: Timezone = "America/Los_Angeles".parse().unwrap();
+        run_migrations_in(&conn, los_angeles).unwrap();
+        assert_eq!(created_at(&conn), "2025-03-10T07:00:00+00:00");
+
+        let auckland: Timezone = "Pacific/Auckland".parse().unwrap();
+        run_migrations_in(&conn, auckland).unwrap();
+        assert_eq!(created_at(&conn), "2025-03-10T07:00:00+00:00");
+    }
+
+    #[test]
+    fn test_run_migrations_marks_an_empty_database() {
+        let conn = Connection::open_in_memory().unwrap();
+        run_migrations(&conn).unwrap();
+        conn.execute(
+            "INSERT INTO thoughts (content, created_at) VALUES ('New', '2025-03-10T00:00:00+00:00')",
+            [],
+        )
+        .unwrap();
+
+        let los_angeles: Timezone = "America/Los_Angeles".parse().unwrap();
+        run_migrations_in(&conn, los_angeles).unwrap();
+        assert_eq!(created_at(&conn), "2025-03-10T00:00:00+00:00");
+    }
+
     #[test]
     fn test_run_migrations_idempotent() {
         let conn = Connection::open_in_memory().unwrap();
@@ -0,0 +1,142 @@
+// Migration: Normalize thought timestamps
+// Before thoughts were dated in a time zone, `--date` stamped a thought with UTC
+// midnight of the given day, so anywhere west of UTC it showed up a day early. This
+// migration re-anchors those rows at the start of the day in the configured zone and
+// rewrites every timestamp as RFC 3339 UTC.
+
+use crate::errors::ThoughtError;
+use crate::models::Timezone;
+use chrono::{DateTime, NaiveDateTime, NaiveTime, Utc};
+use rusqlite::Connection;
+
+/// `PRAGMA user_version` once timestamps are normalized
+const NORMALIZED_VERSION: i64 = 1;
+
+/// Normalize `thoughts.created_at` (runs once per database, tracked by `PRAGMA user_version`)
+///
+/// Unlike the schema migrations this one rewrites data, so it can't tell from the rows
+/// whether it already ran: a thought backdated today in a UTC zone looks exactly like a
+/// legacy one. A timestamp that doesn't parse is left as it is.
+pub fn migrate(conn: &Connection, timezone: Timezone) -> Result<(), ThoughtError> {
+    let version: i64 = conn.query_row("PRAGMA user_version", [], |row| row.get(0))?;
+    if version >= NORMALIZED_VERSION {
+        return Ok(());
+    }
+
+    conn.execute_batch("SAVEPOINT normalize_timestamps")?;
+    let rows: Vec<(i64, String)> = conn
+        .prepare("SELECT id, created_at FROM thoughts")?
+        .query_map([], |row| Ok((row.get(0)?, row.get(1)?)))?
+        .collect::<Result<_, _>>()?;
+    for (id, created_at) in rows {
+        if let Some(normalized) = normalize(&created_at, timezone)
+            && normalized != created_at
+        {
+            conn.execute("UPDATE thoughts SET created_at = ?1 WHERE id = ?2", (&normalized, id))?;
+        }
+    }
+    conn.execute_batch(&format!(
+        "PRAGMA user_version = {NORMALIZED_VERSION}; RELEASE normalize_timestamps"
+    ))?;
+
+    Ok(())
+}
+
+/// Mark a database without thoughts as normalized, so thoughts added from now on are
+/// never mistaken for legacy ones; a database with thoughts is left for [`migrate`].
+pub fn mark_if_empty(conn: &Connection) -> Result<(), ThoughtError> {
+    let version: i64 = conn.query_row("PRAGMA user_version", [], |row| row.get(0))?;
+    let empty: bool = conn.query_row("SELECT NOT EXISTS (SELECT 1 FROM thoughts)", [], |row| row.get(0))?;
+    if version < NORMALIZED_VERSION && empty {
+        conn.execute_batch(&format!("PRAGMA user_version = {NORMALIZED_VERSION}"))?;
+    }
+    Ok(())
+}
+
+/// The normalized form of a stored timestamp, or `None` if it doesn't parse
+fn normalize(created_at: &str, timezone: Timezone) -> Option<String> {
+    let instant = match DateTime::parse_from_rfc3339(created_at) {
+        Ok(parsed) => {
+            // What `--date` used to write: exactly midnight, in UTC
+            if parsed.offset().local_minus_utc() == 0 && parsed.time() == NaiveTime::MIN {
+                timezone.start_of_day(parsed.date_naive())
+            } else {
+                parsed.with_timezone(&Utc)
+            }
+        }
+        // SQLite's own `datetime('now')` format, which is UTC
+        Err(_) => NaiveDateTime::parse_from_str(created_at, "%Y-%m-%d %H:%M:%S%.f")
+            .ok()?
+            .and_utc(),
+    };
+    Some(instant.to_rfc3339())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn setup(created_at: &[&str]) -> Connection {
+        let conn = Connection::open_in_memory().unwrap();
+        conn.execute_batch("CREATE TABLE thoughts (id INTEGER PRIMARY KEY, content TEXT, created_at TEXT);")
+            .unwrap();
+        for value in created_at {
+            conn.execute("INSERT INTO thoughts (content, created_at) VALUES ('x', ?1)", [value])
+                .unwrap();
+        }
+        conn
+    }
+
+    fn stored(conn: &Connection) -> Vec<String> {
+        conn.prepare("SELECT created_at FROM thoughts ORDER BY id")
+            .unwrap()
+            .query_map([], |row| row.get(0))
+            .unwrap()
+            .collect::<Result<_, _>>()
+            .unwrap()
+    }
+
+    #[test]
+    fn test_migration_reanchors_utc_midnight_and_normalizes_formats() {
+        let conn = setup(&[
+            "2026-03-10T00:00:00+00:00",
+            "2026-03-10T21:30:15.5+00:00",
+            "2026-03-10T23:30:00+02:00",
+            "2026-03-10 08:00:00",
+            "not a date",
+        ]);
+        let new_york = "America/New_York".parse().unwrap();
+
+        migrate(&conn, new_york).unwrap();
+
+        assert_eq!(
+            stored(&conn),
+            [
+                "2026-03-10T04:00:00+00:00",
+                "2026-03-10T21:30:15.500+00:00",
+                "2026-03-10T21:30:00+00:00",
+                "2026-03-10T08:00:00+00:00",
+                "not a date",
+            ]
+        );
+    }
+
+    #[test]
+    fn test_migration_runs_once() {
+        let conn = setup(&["2026-03-10T00:00:00+00:00"]);
+        let prague: Timezone = "Europe/Prague".parse().unwrap();
+
+        migrate(&conn, prague).unwrap();
+        conn.execute(
+            "INSERT INTO thoughts (content, created_at) VALUES ('x', '2026-03-11T00:00:00+00:00')",
+            [],
+        )
+        .unwrap();
+        migrate(&conn, prague).unwrap();
+
+        assert_eq!(
+            stored(&conn),
+            ["2026-03-09T23:00:00+00:00", "2026-03-11T00:00:00+00:00"]
+        );
+    }
+}
@@ -0,0 +1,58 @@
+/// Database migration for typed entity relations (works-at, located-in, ...)
+/// Creates table: entity_typed_relations
+///
+/// The parent/child hierarchy keeps its own `entity_relations` table; this one holds
+/// every other relation type, keyed by the type's name.
+use rusqlite::{Connection, Result};
+
+pub fn migrate(conn: &Connection) -> Result<()> {
+    conn.execute(
+        "CREATE TABLE IF NOT EXISTS entity_typed_relations (
+            source_id INTEGER NOT NULL,
+            target_id INTEGER NOT NULL,
+            relation_type TEXT NOT NULL CHECK (length(trim(relation_type)) > 0),
+            PRIMARY KEY (source_id, target_id, relation_type),
+            FOREIGN KEY (source_id) REFERENCES entities(id) ON DELETE CASCADE,
+            FOREIGN KEY (target_id) REFERENCES entities(id) ON DELETE CASCADE,
+            CHECK (source_id != target_id)
+        )",
+        [],
+    )?;
+
+    conn.execute(
+        "CREATE INDEX IF NOT EXISTS idx_entity_typed_relations_target ON entity_typed_relations(target_id)",
+        [],
+    )?;
+
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_migration_creates_table_and_index() {
+        let conn = Connection::open_in_memory().unwrap();
+        migrate(&conn).unwrap();
+
+        let names: Vec<String> = conn
+            .prepare("SELECT name FROM sqlite_master WHERE type IN ('table', 'index') ORDER BY name")
+            .unwrap()
+            .query_map([], |row| row.get(0))
+            .unwrap()
+            .collect::<Result<Vec<_>, _>>()
+            .unwrap();
+
+        assert!(names.contains(&"entity_typed_relations".to_string()));
+        assert!(names.contains(&"idx_entity_typed_relations_target".to_string()));
+    }
+
+    #[test]
+    fn test_migration_idempotent() {
+        let conn = Connection::open_in_memory().unwrap();
+
+        migrate(&conn).unwrap();
+        migrate(&conn).unwrap();
+    }
+}
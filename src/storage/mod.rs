@@ -2,14 +2,21 @@ pub mod connection;
 pub mod data_dir;
 pub mod entities_repository;
 pub mod entity_aliases_repository;
+pub mod entity_attributes_repository;
 pub mod entity_relations_repository;
 pub mod migrations;
+pub mod notebooks;
 pub mod thoughts_repository;
 
 pub use connection::{get_connection, get_memory_connection};
-pub use data_dir::{default_db_path_in, ensure_data_dir, resolve_data_dir};
+pub use data_dir::{DATA_DIR_ENV, DB_PATH_ENV, default_db_path_in, ensure_data_dir, path_from_env, resolve_data_dir};
 pub use entities_repository::EntitiesRepository;
 pub use entity_aliases_repository::EntityAliasesRepository;
+pub use entity_attributes_repository::EntityAttributesRepository;
 pub use entity_relations_repository::EntityRelationsRepository;
-pub use migrations::run_migrations;
+pub use migrations::{run_migrations, run_migrations_in};
+pub use notebooks::{
+    DEFAULT_NOTEBOOK, create_notebook, delete_notebook, list_notebooks, notebook_path, rename_notebook,
+    resolve_db_path, validate_notebook_name,
+};
 pub use thoughts_repository::ThoughtsRepository;
@@ -0,0 +1,210 @@
+/// Named notebooks: separate databases side by side in the data directory
+///
+/// A notebook called `work` is the file `<data_dir>/work.db`, so the `default`
+/// notebook is the `default.db` wetware has always used.
+use crate::errors::ThoughtError;
+use crate::storage::connection::get_connection;
+use crate::storage::migrations::run_migrations;
+use std::path::{Path, PathBuf};
+
+/// The notebook used when none is configured
+pub const DEFAULT_NOTEBOOK: &str = "default";
+
+const NOTEBOOK_EXTENSION: &str = "db";
+
+/// Check that `name` can name a notebook: letters, digits, `-` and `_`, not starting with `-`.
+///
+/// The name becomes a file name, so anything that could reach outside the data
+/// directory or be taken for a flag is refused.
+pub fn validate_notebook_name(name: &str) -> Result<(), String> {
+    let valid = !name.is_empty()
+        && !name.starts_with('-')
+        && name.chars().all(|c| c.is_ascii_alphanumeric() || c == '-' || c == '_');
+    if valid {
+        Ok(())
+    } else {
+        Err(format!(
+            "Invalid notebook name: '{name}'. Use letters, digits, '-' and '_', not starting with '-'"
+        ))
+    }
+}
+
+/// The database file of notebook `name`, whether or not it exists yet.
+pub fn notebook_path(data_dir: &Path, name: &str) -> Result<PathBuf, ThoughtError> {
+    validate_notebook_name(name).map_err(ThoughtError::InvalidInput)?;
+    Ok(data_dir.join(format!("{name}.{NOTEBOOK_EXTENSION}")))
+}
+
+/// The database commands use.
+///
+/// In order of precedence: the notebook picked with `--notebook`, the path in
+/// `WETWARE_DB`, then the `default` notebook from config. A picked notebook has to
+/// exist, so a typo doesn't quietly start a new one; the default notebook is created
+/// by the first command that uses it.
+pub fn resolve_db_path(
+    data_dir: &Path,
+    notebook: Option<&str>,
+    env_path: Option<PathBuf>,
+    default: &str,
+) -> Result<PathBuf, ThoughtError> {
+    match (notebook, env_path) {
+        (Some(name), _) => {
+            let path = notebook_path(data_dir, name)?;
+            if path.exists() || name == default {
+                Ok(path)
+            } else {
+                Err(ThoughtError::NotebookNotFound(name.to_string()))
+            }
+        }
+        (None, Some(path)) => Ok(path),
+        (None, None) => notebook_path(data_dir, default),
+    }
+}
+
+/// Names of the notebooks in the data directory, sorted.
+///
+/// Only `.db` files with a valid notebook name count; anything else in the
+/// directory is left alone.
+pub fn list_notebooks(data_dir: &Path) -> Result<Vec<String>, ThoughtError> {
+    let mut names = Vec::new();
+    for entry in std::fs::read_dir(data_dir)? {
+        let path = entry?.path();
+        if !path.is_file() || path.extension().and_then(|e| e.to_str()) != Some(NOTEBOOK_EXTENSION) {
+            continue;
+        }
+        if let Some(name) = path.file_stem().and_then(|s| s.to_str())
+            && validate_notebook_name(name).is_ok()
+        {
+            names.push(name.to_string());
+        }
+    }
+    names.sort();
+    Ok(names)
+}
+
+/// Create notebook `name` with an up-to-date, empty schema.
+///
+/// # Returns
+/// * `Ok(PathBuf)` - The new notebook's database file
+/// * `Err(ThoughtError::NotebookAlreadyExists)` - A notebook with that name exists
+pub fn create_notebook(data_dir: &Path, name: &str) -> Result<PathBuf, ThoughtError> {
+    let path = notebook_path(data_dir, name)?;
+    if path.exists() {
+        return Err(ThoughtError::NotebookAlreadyExists(name.to_string()));
+    }
+    let conn = get_connection(&path)?;
+    run_migrations(&conn)?;
+    Ok(path)
+}
+
+/// Rename notebook `old` to `new` by renaming its database file.
+///
+/// # Returns
+/// * `Err(ThoughtError::NotebookNotFound)` - `old` doesn't exist
+/// * `Err(ThoughtError::NotebookAlreadyExists)` - `new` already exists
+pub fn rename_notebook(data_dir: &Path, old: &str, new: &str) -> Result<(), ThoughtError> {
+    let from = notebook_path(data_dir, old)?;
+    let to = notebook_path(data_dir, new)?;
+    if !from.exists() {
+        return Err(ThoughtError::NotebookNotFound(old.to_string()));
+    }
+    if to.exists() {
+        return Err(ThoughtError::NotebookAlreadyExists(new.to_string()));
+    }
+    std::fs::rename(&from, &to)?;
+    Ok(())
+}
+
+/// Delete notebook `name` and everything in it.
+///
+/// # Returns
+/// * `Err(ThoughtError::NotebookNotFound)` - `name` doesn't exist
+pub fn delete_notebook(data_dir: &Path, name: &str) -> Result<(), ThoughtError> {
+    let path = notebook_path(data_dir, name)?;
+    if !path.exists() {
+        return Err(ThoughtError::NotebookNotFound(name.to_string()));
+    }
+    std::fs::remove_file(&path)?;
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::storage::data_dir::default_db_path_in;
+    use tempfile::TempDir;
+
+    #[test]
+    fn test_default_notebook_is_default_db() {
+        let dir = Path::new("/some/data/dir");
+        assert_eq!(notebook_path(dir, DEFAULT_NOTEBOOK).unwrap(), default_db_path_in(dir));
+    }
+
+    #[test]
+    fn test_validate_notebook_name() {
+        assert!(validate_notebook_name("work").is_ok());
+        assert!(validate_notebook_name("side_project-2").is_ok());
+        for bad in ["", "-work", "../work", "my notes", "work.db", "a/b"] {
+            let err = validate_notebook_name(bad).unwrap_err();
+            assert!(err.contains("Invalid notebook name"), "{bad}: {err}");
+        }
+    }
+
+    #[test]
+    fn test_resolve_db_path_precedence() {
+        let temp = TempDir::new().unwrap();
+        let dir = temp.path();
+        create_notebook(dir, "work").unwrap();
+        let env = || Some(PathBuf::from("/elsewhere/notes.db"));
+
+        assert_eq!(
+            resolve_db_path(dir, Some("work"), env(), "default").unwrap(),
+            dir.join("work.db")
+        );
+        assert_eq!(
+            resolve_db_path(dir, None, env(), "work").unwrap(),
+            PathBuf::from("/elsewhere/notes.db")
+        );
+        assert_eq!(resolve_db_path(dir, None, None, "work").unwrap(), dir.join("work.db"));
+        // The default notebook needn't exist yet; another picked one must
+        assert_eq!(
+            resolve_db_path(dir, Some("default"), None, "default").unwrap(),
+            dir.join("default.db")
+        );
+        assert!(matches!(
+            resolve_db_path(dir, Some("wrok"), None, "default"),
+            Err(ThoughtError::NotebookNotFound(_))
+        ));
+        assert!(resolve_db_path(dir, Some("../work"), None, "default").is_err());
+    }
+
+    #[test]
+    fn test_create_list_rename_delete() {
+        let temp = TempDir::new().unwrap();
+        std::fs::write(temp.path().join("config.toml"), "version = 1\n").unwrap();
+        std::fs::write(temp.path().join("not a notebook.db"), "").unwrap();
+
+        create_notebook(temp.path(), "work").unwrap();
+        create_notebook(temp.path(), "personal").unwrap();
+        assert!(matches!(
+            create_notebook(temp.path(), "work"),
+            Err(ThoughtError::NotebookAlreadyExists(_))
+        ));
+        assert_eq!(list_notebooks(temp.path()).unwrap(), ["personal", "work"]);
+
+        rename_notebook(temp.path(), "work", "job").unwrap();
+        assert!(matches!(
+            rename_notebook(temp.path(), "job", "personal"),
+            Err(ThoughtError::NotebookAlreadyExists(_))
+        ));
+        assert!(matches!(
+            rename_notebook(temp.path(), "work", "other"),
+            Err(ThoughtError::NotebookNotFound(_))
+        ));
+        assert_eq!(list_notebooks(temp.path()).unwrap(), ["job", "personal"]);
+
+        delete_notebook(temp.path(), "job").unwrap();
+        assert!(!notebook_path(temp.path(), "job").unwrap().exists());
+        assert_eq!(list_notebooks(temp.path()).unwrap(), ["personal"]);
+    }
+}
@@ -1,8 +1,11 @@
 /// Repository for thoughts persistence
 use crate::errors::ThoughtError;
 use crate::models::thought::Thought;
-use chrono::{DateTime, Utc};
+use crate::models::timezone::Timezone;
+use crate::services::thought_query::Query;
+use chrono::{DateTime, NaiveDate, Utc};
 use rusqlite::Connection;
+use rusqlite::types::Value;
 
 /// Thoughts repository for database operations
 pub struct ThoughtsRepository;
@@ -143,35 +146,122 @@ impl ThoughtsRepository {
         Ok(thoughts)
     }
 
-    /// List the most recent thoughts linked to an entity name or alias (case-insensitive),
-    /// newest first, including thoughts linked to any entity transitively reachable via
-    /// child relations (descendants). An unknown name/alias returns an empty list; an
-    /// alias registered to more than one entity returns `ThoughtError::AmbiguousAlias`.
-    pub fn list_latest_by_entity(
-        conn: &Connection,
-        entity_name: &str,
-        limit: usize,
-    ) -> Result<Vec<Thought>, ThoughtError> {
-        let Some(entity) = crate::storage::entities_repository::EntitiesRepository::resolve(conn, entity_name)? else {
-            return Ok(Vec::new());
-        };
+    /// List the thoughts linked directly to an entity (by ID) in chronological order,
+    /// without following relations to its descendants.
+    pub fn list_directly_linked(conn: &Connection, entity_id: i64) -> Result<Vec<Thought>, ThoughtError> {
+        let mut stmt = conn.prepare(
+            "SELECT t.id, t.content, t.created_at
+             FROM thoughts t
+             INNER JOIN thought_entities te ON t.id = te.thought_id
+             WHERE te.entity_id = ?1
+             ORDER BY t.created_at ASC",
+        )?;
+
+        let thoughts = stmt
+            .query_map([entity_id], |row| {
+                let created_at_str: String = row.get(2)?;
+                let created_at = DateTime::parse_from_rfc3339(&created_at_str)
+                    .map_err(|e| {
+                        rusqlite::Error::FromSqlConversionFailure(2, rusqlite::types::Type::Text, Box::new(e))
+                    })?
+                    .with_timezone(&Utc);
 
+                Ok(Thought {
+                    id: Some(row.get(0)?),
+                    content: row.get(1)?,
+                    created_at,
+                })
+            })?
+            .collect::<Result<Vec<_>, _>>()?;
+
+        Ok(thoughts)
+    }
+
+    /// List every thought linked to an entity, newest first, each paired with the IDs of
+    /// the entities it was reached through (ascending).
+    ///
+    /// With `include_descendants`, thoughts linked to any entity transitively reachable via
+    /// child relations are included too, and a thought's IDs may name those descendants
+    /// instead of (or as well as) the entity itself. `since` and `until` bound the thought's
+    /// date in `timezone` inclusively.
+    pub fn list_by_entity_with_sources(
+        conn: &Connection,
+        entity_id: i64,
+        include_descendants: bool,
+        since: Option<NaiveDate>,
+        until: Option<NaiveDate>,
+        timezone: Timezone,
+    ) -> Result<Vec<(Thought, Vec<i64>)>, ThoughtError> {
         let mut stmt = conn.prepare(
             "WITH RECURSIVE reachable(id) AS (
                  SELECT ?1
                  UNION
                  SELECT er.child_id FROM entity_relations er JOIN reachable r ON er.parent_id = r.id
+                 WHERE ?2
              )
-             SELECT DISTINCT t.id, t.content, t.created_at
+             SELECT t.id, t.content, t.created_at, te.entity_id
              FROM thoughts t
              INNER JOIN thought_entities te ON t.id = te.thought_id
              INNER JOIN reachable r ON te.entity_id = r.id
-             ORDER BY t.created_at DESC
-             LIMIT ?2",
+             WHERE (?3 IS NULL OR julianday(t.created_at) >= julianday(?3))
+               AND (?4 IS NULL OR julianday(t.created_at) < julianday(?4))
+             ORDER BY t.created_at DESC, t.id DESC, te.entity_id ASC",
         )?;
 
+        let start = |d: Option<NaiveDate>| d.map(|d| timezone.start_of_day(d).to_rfc3339());
+        let bounds = (start(since), start(until.and_then(|d| d.succ_opt())));
+        let rows = stmt
+            .query_map((entity_id, include_descendants, bounds.0, bounds.1), |row| {
+                let created_at_str: String = row.get(2)?;
+                let created_at = DateTime::parse_from_rfc3339(&created_at_str)
+                    .map_err(|e| {
+                        rusqlite::Error::FromSqlConversionFailure(2, rusqlite::types::Type::Text, Box::new(e))
+                    })?
+                    .with_timezone(&Utc);
+
+                let thought = Thought {
+                    id: Some(row.get(0)?),
+                    content: row.get(1)?,
+                    created_at,
+                };
+                Ok((thought, row.get::<_, i64>(3)?))
+            })?
+            .collect::<Result<Vec<_>, _>>()?;
+
+        let mut thoughts: Vec<(Thought, Vec<i64>)> = Vec::new();
+        for (thought, source_id) in rows {
+            match thoughts.last_mut() {
+                Some((last, sources)) if last.id == thought.id => sources.push(source_id),
+                _ => thoughts.push((thought, vec![source_id])),
+            }
+        }
+
+        Ok(thoughts)
+    }
+
+    /// List thoughts matching a `--where` query, chronological ascending.
+    ///
+    /// The query compiles to a single `WHERE` clause: each entity term becomes a
+    /// `t.id IN (...)` subquery over `thought_entities` and the same recursive descendant
+    /// CTE `list_by_entity` uses, and date terms compare against the start of the day in
+    /// `timezone`. Entity terms
+    /// resolve by name or alias; one that matches nothing is an error rather than an empty
+    /// result, so a typo under `NOT` can't silently match everything.
+    ///
+    /// # Returns
+    /// * `Err(ThoughtError::EntityNotFound)` - An entity term names no entity
+    /// * `Err(ThoughtError::AmbiguousAlias)` - An entity term is an alias of several entities
+    pub fn list_matching(conn: &Connection, query: &Query, timezone: Timezone) -> Result<Vec<Thought>, ThoughtError> {
+        let mut params = Vec::new();
+        let condition = compile_query(conn, query, timezone, &mut params)?;
+
+        let mut stmt = conn.prepare(&format!(
+            "SELECT t.id, t.content, t.created_at FROM thoughts t WHERE {} ORDER BY t.created_at ASC",
+            condition
+        ))?;
+
         let thoughts = stmt
-            .query_map((entity.id.unwrap(), limit as i64), |row| {
+            .query_map(rusqlite::params_from_iter(params), |row| {
                 let created_at_str: String = row.get(2)?;
                 let created_at = DateTime::parse_from_rfc3339(&created_at_str)
                     .map_err(|e| {
@@ -191,6 +281,53 @@ impl ThoughtsRepository {
     }
 }
 
+/// Compile a query to a SQL condition on `thoughts t`, appending its parameters to
+/// `params` (placeholders are numbered, so the order they're pushed in is the order bound).
+fn compile_query(
+    conn: &Connection,
+    query: &Query,
+    timezone: Timezone,
+    params: &mut Vec<Value>,
+) -> Result<String, ThoughtError> {
+    Ok(match query {
+        Query::Entity(name) => {
+            let entity = crate::storage::entities_repository::EntitiesRepository::resolve(conn, name)?
+                .ok_or_else(|| ThoughtError::EntityNotFound(name.clone()))?;
+            params.push(Value::Integer(entity.id.unwrap()));
+            format!(
+                "t.id IN (
+                     WITH RECURSIVE reachable(id) AS (
+                         SELECT ?{}
+                         UNION
+                         SELECT er.child_id FROM entity_relations er JOIN reachable r ON er.parent_id = r.id
+                     )
+                     SELECT te.thought_id FROM thought_entities te JOIN reachable r ON te.entity_id = r.id
+                 )",
+                params.len()
+            )
+        }
+        Query::After(day) => {
+            params.push(Value::Text(timezone.start_of_day(*day).to_rfc3339()));
+            format!("julianday(t.created_at) >= julianday(?{})", params.len())
+        }
+        Query::Before(day) => {
+            params.push(Value::Text(timezone.start_of_day(*day).to_rfc3339()));
+            format!("julianday(t.created_at) < julianday(?{})", params.len())
+        }
+        Query::Not(inner) => format!("NOT ({})", compile_query(conn, inner, timezone, params)?),
+        Query::And(a, b) => format!(
+            "({} AND {})",
+            compile_query(conn, a, timezone, params)?,
+            compile_query(conn, b, timezone, params)?
+        ),
+        Query::Or(a, b) => format!(
+            "({} OR {})",
+            compile_query(conn, a, timezone, params)?,
+            compile_query(conn, b, timezone, params)?
+        ),
+    })
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -377,68 +514,31 @@ mod tests {
         Utc::now() + Duration::days(offset)
     }
 
-    #[test]
-    fn test_list_latest_by_entity_orders_newest_first() {
-        let conn = get_memory_connection().unwrap();
-        run_migrations(&conn).unwrap();
-
-        save_linked_thought(&conn, "Oldest", "rust", day(-2));
-        save_linked_thought(&conn, "Middle", "rust", day(-1));
-        save_linked_thought(&conn, "Newest", "rust", day(0));
-
-        let thoughts = ThoughtsRepository::list_latest_by_entity(&conn, "rust", 5).unwrap();
-        assert_eq!(thoughts.len(), 3);
-        assert_eq!(thoughts[0].content, "Newest");
-        assert_eq!(thoughts[1].content, "Middle");
-        assert_eq!(thoughts[2].content, "Oldest");
+    fn utc() -> Timezone {
+        Timezone::Named(chrono_tz::Tz::UTC)
     }
 
     #[test]
-    fn test_list_latest_by_entity_respects_limit() {
-        let conn = get_memory_connection().unwrap();
-        run_migrations(&conn).unwrap();
-
-        for i in 0..7 {
-            save_linked_thought(&conn, &format!("Thought {i}"), "rust", day(i));
-        }
-
-        let thoughts = ThoughtsRepository::list_latest_by_entity(&conn, "rust", 5).unwrap();
-        assert_eq!(thoughts.len(), 5);
-        assert_eq!(thoughts[0].content, "Thought 6");
-        assert_eq!(thoughts[4].content, "Thought 2");
-    }
+    fn test_list_directly_linked_skips_descendants() {
+        use crate::models::entity::Entity;
+        use crate::storage::entities_repository::EntitiesRepository;
+        use crate::storage::entity_relations_repository::EntityRelationsRepository;
 
-    #[test]
-    fn test_list_latest_by_entity_fewer_than_limit_returns_all() {
         let conn = get_memory_connection().unwrap();
         run_migrations(&conn).unwrap();
 
-        save_linked_thought(&conn, "Only one", "rust", day(0));
+        let parent_id = EntitiesRepository::find_or_create(&conn, &Entity::new("Work".to_string())).unwrap();
+        let child_id = EntitiesRepository::find_or_create(&conn, &Entity::new("Acme".to_string())).unwrap();
+        EntityRelationsRepository::add_relation(&conn, child_id, parent_id).unwrap();
 
-        let thoughts = ThoughtsRepository::list_latest_by_entity(&conn, "rust", 5).unwrap();
-        assert_eq!(thoughts.len(), 1);
-        assert_eq!(thoughts[0].content, "Only one");
-    }
+        let direct = ThoughtsRepository::save(&conn, &Thought::new("[Work] stuff".to_string()).unwrap()).unwrap();
+        EntitiesRepository::link_to_thought(&conn, parent_id, direct).unwrap();
+        let via_child = ThoughtsRepository::save(&conn, &Thought::new("[Acme] stuff".to_string()).unwrap()).unwrap();
+        EntitiesRepository::link_to_thought(&conn, child_id, via_child).unwrap();
 
-    #[test]
-    fn test_list_latest_by_entity_no_thoughts_returns_empty() {
-        let conn = get_memory_connection().unwrap();
-        run_migrations(&conn).unwrap();
-
-        let thoughts = ThoughtsRepository::list_latest_by_entity(&conn, "nonexistent", 5).unwrap();
-        assert!(thoughts.is_empty());
-    }
-
-    #[test]
-    fn test_list_latest_by_entity_case_insensitive() {
-        let conn = get_memory_connection().unwrap();
-        run_migrations(&conn).unwrap();
-
-        save_linked_thought(&conn, "About Rust", "Rust", day(0));
-
-        let thoughts = ThoughtsRepository::list_latest_by_entity(&conn, "RUST", 5).unwrap();
+        let thoughts = ThoughtsRepository::list_directly_linked(&conn, parent_id).unwrap();
         assert_eq!(thoughts.len(), 1);
-        assert_eq!(thoughts[0].content, "About Rust");
+        assert_eq!(thoughts[0].id, Some(direct));
     }
 
     fn relate(conn: &Connection, child_name: &str, parent_name: &str) {
@@ -575,31 +675,116 @@ mod tests {
     }
 
     #[test]
-    fn test_list_latest_by_entity_includes_descendant_thoughts() {
+    fn test_list_by_entity_with_sources_names_the_descendant() {
+        use crate::storage::entities_repository::EntitiesRepository;
+
         let conn = get_memory_connection().unwrap();
         run_migrations(&conn).unwrap();
 
         save_linked_thought(&conn, "About Amazon", "Amazon", day(-1));
         save_linked_thought(&conn, "About AWS", "AWS", day(0));
         relate(&conn, "AWS", "Amazon");
+        let amazon = EntitiesRepository::find_by_name(&conn, "Amazon")
+            .unwrap()
+            .unwrap()
+            .id
+            .unwrap();
+        let aws = EntitiesRepository::find_by_name(&conn, "AWS")
+            .unwrap()
+            .unwrap()
+            .id
+            .unwrap();
 
-        let thoughts = ThoughtsRepository::list_latest_by_entity(&conn, "amazon", 5).unwrap();
-        assert_eq!(thoughts.len(), 2);
-        assert_eq!(thoughts[0].content, "About AWS");
-        assert_eq!(thoughts[1].content, "About Amazon");
+        let thoughts = ThoughtsRepository::list_by_entity_with_sources(&conn, amazon, true, None, None, utc()).unwrap();
+        let summary: Vec<_> = thoughts.iter().map(|(t, s)| (t.content.as_str(), s.clone())).collect();
+        assert_eq!(summary, vec![("About AWS", vec![aws]), ("About Amazon", vec![amazon])]);
+
+        let direct = ThoughtsRepository::list_by_entity_with_sources(&conn, amazon, false, None, None, utc()).unwrap();
+        assert_eq!(direct.len(), 1);
+        assert_eq!(direct[0].0.content, "About Amazon");
     }
 
     #[test]
-    fn test_list_latest_by_entity_limit_applies_across_descendants() {
+    fn test_list_by_entity_with_sources_filters_by_date() {
+        use crate::storage::entities_repository::EntitiesRepository;
+
         let conn = get_memory_connection().unwrap();
         run_migrations(&conn).unwrap();
 
-        save_linked_thought(&conn, "About Amazon", "Amazon", day(-1));
-        save_linked_thought(&conn, "About AWS", "AWS", day(0));
-        relate(&conn, "AWS", "Amazon");
+        save_linked_thought(&conn, "Old", "rust", day(-10));
+        save_linked_thought(&conn, "Recent", "rust", day(-1));
+        let rust = EntitiesRepository::find_by_name(&conn, "rust")
+            .unwrap()
+            .unwrap()
+            .id
+            .unwrap();
+
+        let since = Some(day(-5).date_naive());
+        let thoughts = ThoughtsRepository::list_by_entity_with_sources(&conn, rust, true, since, None, utc()).unwrap();
+        assert_eq!(thoughts.len(), 1);
+        assert_eq!(thoughts[0].0.content, "Recent");
 
-        let thoughts = ThoughtsRepository::list_latest_by_entity(&conn, "amazon", 1).unwrap();
+        let until = Some(day(-5).date_naive());
+        let thoughts = ThoughtsRepository::list_by_entity_with_sources(&conn, rust, true, None, until, utc()).unwrap();
         assert_eq!(thoughts.len(), 1);
-        assert_eq!(thoughts[0].content, "About AWS");
+        assert_eq!(thoughts[0].0.content, "Old");
+    }
+
+    #[test]
+    fn test_list_matching_combines_entities_descendants_and_dates() {
+        use crate::models::entity::Entity;
+        use crate::services::thought_query;
+        use crate::storage::entities_repository::EntitiesRepository;
+
+        let conn = get_memory_connection().unwrap();
+        run_migrations(&conn).unwrap();
+
+        let sarah = save_linked_thought(&conn, "Sarah on alpha", "Sarah", day(-3));
+        let draft = save_linked_thought(&conn, "Draft about alpha", "alpha", day(-2));
+        save_linked_thought(&conn, "Beta launch", "beta", day(-1));
+        save_linked_thought(&conn, "Gamma notes", "gamma", day(0));
+        relate(&conn, "gamma", "beta");
+        // Give the first two thoughts a second entity each
+        for (thought_id, name) in [(sarah, "alpha"), (draft, "draft")] {
+            let entity_id = EntitiesRepository::find_or_create(&conn, &Entity::new(name.to_string())).unwrap();
+            EntitiesRepository::link_to_thought(&conn, entity_id, thought_id).unwrap();
+        }
+
+        let contents = |input: &str| -> Vec<String> {
+            let query = thought_query::parse(input, Utc::now().date_naive()).unwrap();
+            ThoughtsRepository::list_matching(&conn, &query, utc())
+                .unwrap()
+                .into_iter()
+                .map(|t| t.content)
+                .collect()
+        };
+
+        assert_eq!(contents("alpha AND NOT draft"), ["Sarah on alpha"]);
+        assert_eq!(
+            contents("sarah OR beta"),
+            ["Sarah on alpha", "Beta launch", "Gamma notes"]
+        );
+        assert_eq!(
+            contents("(alpha OR beta) AND NOT draft"),
+            ["Sarah on alpha", "Beta launch", "Gamma notes"]
+        );
+        let since = day(-1).format("after:%Y-%m-%d").to_string();
+        assert_eq!(contents(&format!("beta AND {since}")), ["Beta launch", "Gamma notes"]);
+        let before = day(-1).format("before:%Y-%m-%d").to_string();
+        assert_eq!(contents(&before), ["Sarah on alpha", "Draft about alpha"]);
+    }
+
+    #[test]
+    fn test_list_matching_unknown_entity_is_an_error() {
+        use crate::services::thought_query;
+
+        let conn = get_memory_connection().unwrap();
+        run_migrations(&conn).unwrap();
+
+        save_linked_thought(&conn, "Sarah", "Sarah", day(0));
+
+        let query = thought_query::parse("NOT sarha", Utc::now().date_naive()).unwrap();
+        let result = ThoughtsRepository::list_matching(&conn, &query, utc());
+        assert!(matches!(result, Err(ThoughtError::EntityNotFound(name)) if name == "sarha"));
     }
 }
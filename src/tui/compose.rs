@@ -0,0 +1,462 @@
+//! Thought composer for `wet add -i`
+//!
+//! A one-line prompt drawn in an inline viewport under the shell prompt. Typing `[`
+//! opens a fuzzy list of entity names and aliases; accepting one writes the whole
+//! reference, so a misspelled name doesn't quietly become a new entity.
+
+use ratatui::crossterm::event::{self, Event, KeyCode, KeyEvent, KeyEventKind, KeyModifiers};
+use ratatui::layout::{Constraint, Layout};
+use ratatui::style::{Color, Modifier, Style};
+use ratatui::text::{Line, Span};
+use ratatui::widgets::Paragraph;
+use ratatui::{Frame, Terminal, backend::Backend};
+use tui_input::Input;
+use tui_input::backend::crossterm::EventHandler;
+
+use super::fuzzy;
+use crate::errors::ThoughtError;
+use crate::models::Entity;
+
+/// How many matches are listed under the prompt at once
+const VISIBLE_MATCHES: usize = 6;
+
+/// Lines the composer takes up: the prompt, the matches and a line of key help
+pub const HEIGHT: u16 = VISIBLE_MATCHES as u16 + 2;
+
+const PROMPT: &str = "add> ";
+
+/// A name the completion list offers
+#[derive(Debug, Clone, PartialEq)]
+pub struct NameChoice {
+    /// The text inserted: a canonical name or an alias
+    pub name: String,
+    /// Canonical name of the entity, when `name` is one of its aliases
+    pub alias_of: Option<String>,
+}
+
+/// The completion list, open while the cursor is inside an unfinished `[`
+#[derive(Debug, Clone, PartialEq)]
+pub struct Completion {
+    /// Character index of the `[` being completed
+    pub start: usize,
+    /// Indices into `Composer::names` matching the text typed after `[`, best first
+    pub matches: Vec<usize>,
+    /// Highlighted position in `matches`
+    pub selected: usize,
+}
+
+/// How the prompt was left
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Outcome {
+    /// Enter outside the completion list: add the thought
+    Submitted,
+    /// Esc outside the completion list, or Ctrl-C: add nothing
+    Cancelled,
+}
+
+/// State of the `wet add -i` prompt
+pub struct Composer {
+    /// The thought being typed
+    pub input: Input,
+    /// Canonical names, then aliases
+    pub names: Vec<NameChoice>,
+    /// Open completion list, if any
+    pub completion: Option<Completion>,
+    /// Set once the prompt is done
+    pub outcome: Option<Outcome>,
+}
+
+impl Composer {
+    /// A composer offering the canonical names of `entities`, then their `aliases`
+    /// (as `(entity_id, alias)` pairs, the shape `EntityAliasesRepository::list_all` returns)
+    pub fn new(entities: &[Entity], aliases: &[(i64, String)]) -> Self {
+        let mut names: Vec<NameChoice> = entities
+            .iter()
+            .map(|entity| NameChoice {
+                name: entity.canonical_name.clone(),
+                alias_of: None,
+            })
+            .collect();
+        for (entity_id, alias) in aliases {
+            if let Some(entity) = entities.iter().find(|entity| entity.id == Some(*entity_id)) {
+                names.push(NameChoice {
+                    name: alias.clone(),
+                    alias_of: Some(entity.canonical_name.clone()),
+                });
+            }
+        }
+
+        Self {
+            input: Input::default(),
+            names,
+            completion: None,
+            outcome: None,
+        }
+    }
+
+    /// Run the prompt until it is submitted or cancelled.
+    ///
+    /// Returns the typed thought, or `None` if the prompt was cancelled.
+    pub fn run(mut self, terminal: &mut Terminal<impl Backend>) -> Result<Option<String>, ThoughtError> {
+        loop {
+            terminal
+                .draw(|frame| self.render(frame))
+                .map_err(|e| ThoughtError::TuiError(e.to_string()))?;
+
+            let event = event::read().map_err(|e| ThoughtError::TuiError(e.to_string()))?;
+            if let Event::Key(key) = event
+                && key.kind == KeyEventKind::Press
+            {
+                self.handle_key(key);
+            }
+
+            if let Some(outcome) = self.outcome {
+                // Leave the shell as it was, without the match list
+                terminal.clear().map_err(|e| ThoughtError::TuiError(e.to_string()))?;
+                return Ok(match outcome {
+                    Outcome::Submitted => Some(self.input.value().to_string()),
+                    Outcome::Cancelled => None,
+                });
+            }
+        }
+    }
+
+    /// Update the prompt for one key press.
+    ///
+    /// With the completion list open, `↑`/`↓` move the highlight, `Enter` inserts
+    /// `[Name]`, `Tab` inserts `[typed](Name)` and `Esc` closes the list. Otherwise
+    /// `Enter` submits and `Esc` cancels. Everything else edits the text.
+    pub fn handle_key(&mut self, key: KeyEvent) {
+        if key.code == KeyCode::Char('c') && key.modifiers.contains(KeyModifiers::CONTROL) {
+            self.outcome = Some(Outcome::Cancelled);
+            return;
+        }
+
+        if let Some(completion) = self.completion.as_mut() {
+            match key.code {
+                KeyCode::Esc => {
+                    self.completion = None;
+                    return;
+                }
+                KeyCode::Up => {
+                    completion.selected = completion.selected.saturating_sub(1);
+                    return;
+                }
+                KeyCode::Down => {
+                    if completion.selected + 1 < completion.matches.len() {
+                        completion.selected += 1;
+                    }
+                    return;
+                }
+                KeyCode::Enter => {
+                    self.accept(false);
+                    return;
+                }
+                KeyCode::Tab => {
+                    self.accept(true);
+                    return;
+                }
+                _ => {}
+            }
+        } else {
+            match key.code {
+                KeyCode::Esc => {
+                    self.outcome = Some(Outcome::Cancelled);
+                    return;
+                }
+                KeyCode::Enter => {
+                    self.outcome = Some(Outcome::Submitted);
+                    return;
+                }
+                _ => {}
+            }
+        }
+
+        self.input.handle_event(&Event::Key(key));
+        if key.code == KeyCode::Char('[') && self.input.cursor() > 0 {
+            self.completion = Some(Completion {
+                start: self.input.cursor() - 1,
+                matches: Vec::new(),
+                selected: 0,
+            });
+        }
+        self.refresh_completion();
+    }
+
+    /// The text typed after the open `[`, up to the cursor
+    fn typed_name(&self, start: usize) -> String {
+        self.input
+            .value()
+            .chars()
+            .skip(start + 1)
+            .take(self.input.cursor().saturating_sub(start + 1))
+            .collect()
+    }
+
+    /// Re-rank the completion list for the current text, closing it once the cursor
+    /// leaves the reference (moved before the `[`, or past a typed `[` or `]`)
+    fn refresh_completion(&mut self) {
+        let Some(start) = self.completion.as_ref().map(|completion| completion.start) else {
+            return;
+        };
+        let typed = self.typed_name(start);
+        let still_open = self.input.value().chars().nth(start) == Some('[')
+            && self.input.cursor() > start
+            && !typed.contains(['[', ']']);
+        if !still_open {
+            self.completion = None;
+            return;
+        }
+
+        let names: Vec<&str> = self.names.iter().map(|choice| choice.name.as_str()).collect();
+        if let Some(completion) = self.completion.as_mut() {
+            completion.matches = fuzzy::rank(&typed, &names);
+            completion.selected = 0;
+        }
+    }
+
+    /// Replace the open `[` and the text typed after it with a finished reference.
+    ///
+    /// `keep_typed` writes `[typed](Name)`, keeping the wording; it falls back to
+    /// `[Name]` when nothing was typed or the text already is the name. With no match
+    /// highlighted the list just closes.
+    fn accept(&mut self, keep_typed: bool) {
+        let Some(completion) = self.completion.take() else {
+            return;
+        };
+        let Some(choice) = completion.matches.get(completion.selected).map(|&i| &self.names[i]) else {
+            return;
+        };
+
+        let typed = self.typed_name(completion.start);
+        let reference = if keep_typed && !typed.trim().is_empty() && !typed.eq_ignore_ascii_case(&choice.name) {
+            format!("[{}]({})", typed, choice.name)
+        } else {
+            format!("[{}]", choice.name)
+        };
+
+        let before: String = self.input.value().chars().take(completion.start).collect();
+        let after: String = self.input.value().chars().skip(self.input.cursor()).collect();
+        let cursor = before.chars().count() + reference.chars().count();
+        self.input = Input::new(format!("{before}{reference}{after}")).with_cursor(cursor);
+    }
+
+    /// Draw the prompt, the match list and the key help.
+    pub fn render(&self, frame: &mut Frame) {
+        let rows = Layout::vertical([
+            Constraint::Length(1),
+            Constraint::Length(VISIBLE_MATCHES as u16),
+            Constraint::Length(1),
+        ])
+        .split(frame.area());
+        let [prompt_area, input_area] =
+            Layout::horizontal([Constraint::Length(PROMPT.len() as u16), Constraint::Min(1)]).areas(rows[0]);
+
+        frame.render_widget(
+            Paragraph::new(PROMPT).style(Style::default().fg(Color::Cyan).add_modifier(Modifier::BOLD)),
+            prompt_area,
+        );
+        let scroll = self.input.visual_scroll(input_area.width as usize);
+        frame.render_widget(
+            Paragraph::new(self.input.value()).scroll((0, scroll as u16)),
+            input_area,
+        );
+        frame.set_cursor_position((
+            input_area.x + (self.input.visual_cursor().saturating_sub(scroll)) as u16,
+            input_area.y,
+        ));
+
+        let dim = Style::default().fg(Color::DarkGray);
+        let help = match &self.completion {
+            Some(completion) => {
+                frame.render_widget(Paragraph::new(self.match_lines(completion)), rows[1]);
+                "Enter [Name] · Tab [typed](Name) · ↑↓ choose · Esc close list"
+            }
+            None => "[ mention an entity · Enter add · Esc cancel",
+        };
+        frame.render_widget(Paragraph::new(help).style(dim), rows[2]);
+    }
+
+    /// The visible window of matches, scrolled to keep the highlight in view
+    fn match_lines(&self, completion: &Completion) -> Vec<Line<'_>> {
+        let dim = Style::default().fg(Color::DarkGray);
+        if completion.matches.is_empty() {
+            return vec![Line::styled(
+                "  No matching entity; finishing with ] creates a new one",
+                dim,
+            )];
+        }
+
+        let offset = completion.selected.saturating_sub(VISIBLE_MATCHES - 1);
+        completion
+            .matches
+            .iter()
+            .enumerate()
+            .skip(offset)
+            .take(VISIBLE_MATCHES)
+            .map(|(position, &i)| {
+                let choice = &self.names[i];
+                let style = if position == completion.selected {
+                    Style::default().add_modifier(Modifier::REVERSED)
+                } else {
+                    Style::default()
+                };
+                let mut spans = vec![Span::raw("  "), Span::styled(choice.name.as_str(), style)];
+                if let Some(entity) = &choice.alias_of {
+                    spans.push(Span::styled(format!(" → {entity}"), dim));
+                }
+                Line::from(spans)
+            })
+            .collect()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use ratatui::backend::TestBackend;
+
+    fn entity(id: i64, name: &str) -> Entity {
+        Entity {
+            id: Some(id),
+            ..Entity::new(name.to_string())
+        }
+    }
+
+    fn sample() -> Composer {
+        let entities = vec![entity(1, "Sarah"), entity(2, "Samuel"), entity(3, "project-alpha")];
+        Composer::new(&entities, &[(2, "Sammy".to_string())])
+    }
+
+    fn press(composer: &mut Composer, code: KeyCode) {
+        composer.handle_key(KeyEvent::from(code));
+    }
+
+    fn type_text(composer: &mut Composer, text: &str) {
+        for c in text.chars() {
+            press(composer, KeyCode::Char(c));
+        }
+    }
+
+    fn highlighted(composer: &Composer) -> &str {
+        let completion = composer.completion.as_ref().expect("completion list should be open");
+        &composer.names[completion.matches[completion.selected]].name
+    }
+
+    #[test]
+    fn test_new_lists_canonical_names_then_aliases() {
+        let composer = sample();
+        let names: Vec<&str> = composer.names.iter().map(|choice| choice.name.as_str()).collect();
+        assert_eq!(names, ["Sarah", "Samuel", "project-alpha", "Sammy"]);
+        assert_eq!(composer.names[3].alias_of.as_deref(), Some("Samuel"));
+    }
+
+    #[test]
+    fn test_bracket_opens_fuzzy_list_and_enter_inserts_name() {
+        let mut composer = sample();
+        type_text(&mut composer, "Met [");
+        assert_eq!(composer.completion.as_ref().unwrap().matches.len(), 4);
+
+        type_text(&mut composer, "pal");
+        assert_eq!(highlighted(&composer), "project-alpha");
+        press(&mut composer, KeyCode::Enter);
+        type_text(&mut composer, " today");
+
+        assert_eq!(composer.input.value(), "Met [project-alpha] today");
+        assert!(composer.completion.is_none());
+        assert!(composer.outcome.is_none());
+    }
+
+    #[test]
+    fn test_tab_keeps_the_typed_wording() {
+        let mut composer = sample();
+        type_text(&mut composer, "[smuel");
+        assert_eq!(highlighted(&composer), "Samuel");
+        press(&mut composer, KeyCode::Tab);
+        assert_eq!(composer.input.value(), "[smuel](Samuel)");
+
+        // Nothing typed, or the name itself: a plain reference
+        type_text(&mut composer, " and [");
+        press(&mut composer, KeyCode::Down);
+        press(&mut composer, KeyCode::Tab);
+        assert_eq!(composer.input.value(), "[smuel](Samuel) and [Samuel]");
+    }
+
+    #[test]
+    fn test_accepting_in_the_middle_keeps_the_rest() {
+        let mut composer = sample();
+        type_text(&mut composer, "Call  later");
+        for _ in 0.."later".len() + 1 {
+            press(&mut composer, KeyCode::Left);
+        }
+        type_text(&mut composer, "[sar");
+        press(&mut composer, KeyCode::Enter);
+        assert_eq!(composer.input.value(), "Call [Sarah] later");
+        assert_eq!(composer.input.cursor(), "Call [Sarah]".len());
+    }
+
+    #[test]
+    fn test_list_closes_on_closing_bracket_or_leaving_the_reference() {
+        let mut composer = sample();
+        type_text(&mut composer, "[newname]");
+        assert!(composer.completion.is_none());
+
+        type_text(&mut composer, " [s");
+        assert!(composer.completion.is_some());
+        press(&mut composer, KeyCode::Backspace);
+        press(&mut composer, KeyCode::Backspace);
+        assert!(composer.completion.is_none());
+        assert_eq!(composer.input.value(), "[newname] ");
+    }
+
+    #[test]
+    fn test_esc_closes_list_then_cancels_and_enter_submits() {
+        let mut composer = sample();
+        type_text(&mut composer, "[sa");
+        press(&mut composer, KeyCode::Esc);
+        assert!(composer.completion.is_none());
+        assert!(composer.outcome.is_none());
+        press(&mut composer, KeyCode::Enter);
+        assert_eq!(composer.outcome, Some(Outcome::Submitted));
+        assert_eq!(composer.input.value(), "[sa");
+
+        let mut composer = sample();
+        press(&mut composer, KeyCode::Esc);
+        assert_eq!(composer.outcome, Some(Outcome::Cancelled));
+
+        let mut composer = sample();
+        composer.handle_key(KeyEvent::new(KeyCode::Char('c'), KeyModifiers::CONTROL));
+        assert_eq!(composer.outcome, Some(Outcome::Cancelled));
+    }
+
+    #[test]
+    fn test_enter_without_a_match_just_closes_the_list() {
+        let mut composer = sample();
+        type_text(&mut composer, "[zzz");
+        assert!(composer.completion.as_ref().unwrap().matches.is_empty());
+        press(&mut composer, KeyCode::Enter);
+        assert!(composer.completion.is_none());
+        assert_eq!(composer.input.value(), "[zzz");
+        assert!(composer.outcome.is_none());
+    }
+
+    #[test]
+    fn test_render_shows_matches_with_alias_targets_and_help() {
+        let mut composer = sample();
+        type_text(&mut composer, "Lunch with [sam");
+        let mut terminal = Terminal::new(TestBackend::new(70, HEIGHT)).unwrap();
+        terminal.draw(|frame| composer.render(frame)).unwrap();
+        let output: String = terminal
+            .backend()
+            .buffer()
+            .content()
+            .chunks(70)
+            .map(|row| row.iter().map(|cell| cell.symbol()).collect::<String>() + "\n")
+            .collect();
+
+        assert!(output.contains("add> Lunch with [sam"), "{output}");
+        assert!(output.contains("Samuel"), "{output}");
+        assert!(output.contains("Sammy → Samuel"), "{output}");
+        assert!(output.contains("Tab [typed](Name)"), "{output}");
+    }
+}
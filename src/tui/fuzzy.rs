@@ -0,0 +1,47 @@
+//! Fuzzy name matching shared by the TUI's entity picker and the `wet add -i` prompt
+
+use nucleo_matcher::pattern::{AtomKind, CaseMatching, Normalization, Pattern};
+use nucleo_matcher::{Config, Matcher, Utf32Str};
+
+/// Indices of the `names` matching `query`, best match first.
+///
+/// Matching ignores case; equal scores keep the order of `names`. An empty query
+/// matches every name, in order.
+pub fn rank<S: AsRef<str>>(query: &str, names: &[S]) -> Vec<usize> {
+    if query.is_empty() {
+        return (0..names.len()).collect();
+    }
+
+    let mut matcher = Matcher::new(Config::DEFAULT);
+    let pattern = Pattern::new(query, CaseMatching::Ignore, Normalization::Smart, AtomKind::Fuzzy);
+    let mut buf = Vec::new();
+    let mut scored: Vec<(usize, u32)> = names
+        .iter()
+        .enumerate()
+        .filter_map(|(i, name)| {
+            let haystack = Utf32Str::new(name.as_ref(), &mut buf);
+            pattern.score(haystack, &mut matcher).map(|score| (i, score))
+        })
+        .collect();
+
+    scored.sort_by_key(|&(_, score)| std::cmp::Reverse(score));
+    scored.into_iter().map(|(i, _)| i).collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_rank_orders_by_score_ignoring_case() {
+        let names = ["project-alpha", "Sarah", "Samuel", "sarah-lee"];
+        let ranked = rank("sar", &names);
+        assert_eq!(ranked[..2], [1, 3]);
+        assert!(!ranked.contains(&0));
+    }
+
+    #[test]
+    fn test_empty_query_matches_everything_in_order() {
+        assert_eq!(rank("", &["b", "a"]), [0, 1]);
+    }
+}
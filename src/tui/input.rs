@@ -2,12 +2,15 @@
 //!
 //! Maps keyboard events to state mutations based on the current interaction mode.
 
-use nucleo_matcher::{Matcher, pattern::Pattern};
+use std::collections::HashSet;
+
 use ratatui::crossterm::event::{KeyCode, KeyEvent};
 use tui_input::backend::crossterm::EventHandler;
 
 use super::App;
+use super::fuzzy;
 use super::state::Mode;
+use crate::services::thought_query;
 
 /// Handle a key event and update app state.
 ///
@@ -17,6 +20,7 @@ pub fn handle_key_event(app: &mut App, key: KeyEvent) {
         Mode::Normal => handle_normal_mode(app, key),
         Mode::ConfirmDelete { .. } => handle_confirm_delete_mode(app, key),
         Mode::EntityPicker { .. } => handle_entity_picker_mode(app, key),
+        Mode::EntityTree { .. } => handle_entity_tree_mode(app, key),
         Mode::EntityDetail { .. } => handle_entity_detail_mode(app, key),
     }
 }
@@ -29,9 +33,7 @@ fn handle_normal_mode(app: &mut App, key: KeyEvent) {
         }
         KeyCode::Esc => {
             if app.active_filter.is_some() {
-                app.active_filter = None;
-                app.active_filter_reachable.clear();
-                app.recompute_displayed_thoughts();
+                app.clear_filter();
             } else {
                 app.should_quit = true;
             }
@@ -63,15 +65,11 @@ fn handle_normal_mode(app: &mut App, key: KeyEvent) {
                 app.list_state.select(Some(new_selected));
             }
         }
-        KeyCode::Home => {
-            if !app.displayed_thoughts.is_empty() {
-                app.list_state.select(Some(0));
-            }
+        KeyCode::Home if !app.displayed_thoughts.is_empty() => {
+            app.list_state.select(Some(0));
         }
-        KeyCode::End => {
-            if !app.displayed_thoughts.is_empty() {
-                app.list_state.select(Some(app.displayed_thoughts.len() - 1));
-            }
+        KeyCode::End if !app.displayed_thoughts.is_empty() => {
+            app.list_state.select(Some(app.displayed_thoughts.len() - 1));
         }
         KeyCode::Char('s') => {
             app.sort_order.toggle();
@@ -84,6 +82,13 @@ fn handle_normal_mode(app: &mut App, key: KeyEvent) {
                 input: tui_input::Input::default(),
                 matches: all_indices,
                 selected: 0,
+                error: None,
+            };
+        }
+        KeyCode::Char('t') if !app.entity_tree.is_empty() => {
+            app.mode = Mode::EntityTree {
+                selected: 0,
+                collapsed: HashSet::new(),
             };
         }
         KeyCode::Enter | KeyCode::Char('d') => {
@@ -123,12 +128,17 @@ fn handle_confirm_delete_mode(app: &mut App, key: KeyEvent) {
 }
 
 /// Handle key events in EntityPicker mode.
+///
+/// `Enter` on input that uses query syntax (see `thought_query::looks_like_query`)
+/// filters with the query, keeping the picker open with the error if it doesn't apply;
+/// otherwise it filters to the highlighted entity.
 fn handle_entity_picker_mode(app: &mut App, key: KeyEvent) {
     // We need to extract the mutable fields from Mode
     let Mode::EntityPicker {
         ref mut input,
         ref mut matches,
         ref mut selected,
+        ref mut error,
     } = app.mode
     else {
         return;
@@ -138,13 +148,21 @@ fn handle_entity_picker_mode(app: &mut App, key: KeyEvent) {
         KeyCode::Esc => {
             app.mode = Mode::Normal;
         }
+        KeyCode::Enter if thought_query::looks_like_query(input.value()) => {
+            let text = input.value().to_string();
+            match app.apply_query_filter(&text) {
+                Ok(()) => app.mode = Mode::Normal,
+                Err(e) => {
+                    if let Mode::EntityPicker { ref mut error, .. } = app.mode {
+                        *error = Some(e.to_string());
+                    }
+                }
+            }
+        }
         KeyCode::Enter => {
             if let Some(&entity_idx) = matches.get(*selected) {
-                let entity_name = app.entities[entity_idx].canonical_name.clone();
-                app.active_filter_reachable = app.reachable_names(entity_idx);
-                app.active_filter = Some(entity_name);
                 app.mode = Mode::Normal;
-                app.recompute_displayed_thoughts();
+                app.apply_entity_filter(entity_idx);
             } else {
                 app.mode = Mode::Normal;
             }
@@ -162,39 +180,81 @@ fn handle_entity_picker_mode(app: &mut App, key: KeyEvent) {
         _ => {
             // Forward to text input
             input.handle_event(&ratatui::crossterm::event::Event::Key(key));
+            *error = None;
 
             // Recompute fuzzy matches
-            let query = input.value();
-            if query.is_empty() {
-                *matches = (0..app.entities.len()).collect();
-            } else {
-                let mut matcher = Matcher::new(nucleo_matcher::Config::DEFAULT);
-                let pattern = Pattern::new(
-                    query,
-                    nucleo_matcher::pattern::CaseMatching::Ignore,
-                    nucleo_matcher::pattern::Normalization::Smart,
-                    nucleo_matcher::pattern::AtomKind::Fuzzy,
-                );
-
-                let mut scored: Vec<(usize, u32)> = app
-                    .entities
-                    .iter()
-                    .enumerate()
-                    .filter_map(|(i, entity)| {
-                        let mut buf = Vec::new();
-                        let haystack = nucleo_matcher::Utf32Str::new(&entity.canonical_name, &mut buf);
-                        pattern.score(haystack, &mut matcher).map(|score| (i, score))
-                    })
-                    .collect();
-
-                scored.sort_by(|a, b| b.1.cmp(&a.1));
-                *matches = scored.into_iter().map(|(i, _)| i).collect();
-            }
+            let names: Vec<&str> = app.entities.iter().map(|e| e.canonical_name.as_str()).collect();
+            *matches = fuzzy::rank(input.value(), &names);
             *selected = 0;
         }
     }
 }
 
+/// Handle key events in EntityTree mode.
+///
+/// Arrows move the highlight; `←`/`→` collapse/expand the highlighted entity and
+/// `Space` toggles it. `Enter` filters the thought list to the entity and its
+/// descendants.
+fn handle_entity_tree_mode(app: &mut App, key: KeyEvent) {
+    let Mode::EntityTree {
+        selected,
+        ref collapsed,
+    } = app.mode
+    else {
+        return;
+    };
+
+    let visible = app.visible_tree_rows(collapsed);
+    let Some(&row_idx) = visible.get(selected) else {
+        app.mode = Mode::Normal;
+        return;
+    };
+    let has_children = app.entity_tree[row_idx].has_children;
+    let entity_id = app.entity_tree[row_idx].entity_id;
+
+    match key.code {
+        KeyCode::Esc => {
+            app.mode = Mode::Normal;
+        }
+        KeyCode::Enter => {
+            app.mode = Mode::Normal;
+            if let Some(entity_idx) = app.entities.iter().position(|e| e.id == Some(entity_id)) {
+                app.apply_entity_filter(entity_idx);
+            }
+        }
+        _ => {
+            let Mode::EntityTree {
+                ref mut selected,
+                ref mut collapsed,
+            } = app.mode
+            else {
+                return;
+            };
+            match key.code {
+                KeyCode::Up => {
+                    *selected = selected.saturating_sub(1);
+                }
+                KeyCode::Down if *selected + 1 < visible.len() => {
+                    *selected += 1;
+                }
+                KeyCode::Left if has_children => {
+                    collapsed.insert(row_idx);
+                }
+                KeyCode::Right => {
+                    collapsed.remove(&row_idx);
+                }
+                KeyCode::Char(' ') if has_children && collapsed.contains(&row_idx) => {
+                    collapsed.remove(&row_idx);
+                }
+                KeyCode::Char(' ') if has_children => {
+                    collapsed.insert(row_idx);
+                }
+                _ => {}
+            }
+        }
+    }
+}
+
 /// Handle key events in EntityDetail mode.
 fn handle_entity_detail_mode(app: &mut App, key: KeyEvent) {
     let Mode::EntityDetail {
@@ -238,6 +298,7 @@ mod tests {
             name: name.to_lowercase(),
             canonical_name: name.to_string(),
             description: None,
+            kind: None,
         }
     }
 
@@ -389,6 +450,7 @@ mod tests {
             input: tui_input::Input::default(),
             matches: vec![0],
             selected: 0,
+            error: None,
         };
 
         handle_key_event(&mut app, key_event(KeyCode::Esc));
@@ -400,11 +462,12 @@ mod tests {
     fn test_entity_picker_enter_applies_filter() {
         let thoughts = vec![make_thought("[Sarah] hello", 0), make_thought("world", 1)];
         let entities = vec![make_entity("Sarah")];
-        let mut app = App::new(thoughts, entities, SortOrder::Ascending);
+        let mut app = App::new(thoughts, entities, SortOrder::Ascending).with_thought_links(vec![(0, 1)]);
         app.mode = Mode::EntityPicker {
             input: tui_input::Input::default(),
             matches: vec![0],
             selected: 0,
+            error: None,
         };
 
         handle_key_event(&mut app, key_event(KeyCode::Enter));
@@ -413,6 +476,52 @@ mod tests {
         assert_eq!(app.displayed_thoughts.len(), 1); // Only the thought with Sarah
     }
 
+    #[test]
+    fn test_entity_picker_enter_applies_query() {
+        let thoughts = vec![
+            make_thought("[Sarah] and [Bob]", 0),
+            make_thought("[Sarah] alone", 1),
+            make_thought("[Bob] alone", 2),
+        ];
+        let mut entities = vec![make_entity("Sarah"), make_entity("Bob")];
+        entities[1].id = Some(2);
+        let mut app =
+            App::new(thoughts, entities, SortOrder::Ascending).with_thought_links(vec![(0, 1), (0, 2), (1, 1), (2, 2)]);
+        app.mode = Mode::EntityPicker {
+            input: tui_input::Input::new("sarah AND NOT bob".to_string()),
+            matches: vec![],
+            selected: 0,
+            error: None,
+        };
+
+        handle_key_event(&mut app, key_event(KeyCode::Enter));
+        assert!(matches!(app.mode, Mode::Normal));
+        assert_eq!(app.active_filter.as_deref(), Some("sarah AND NOT bob"));
+        assert_eq!(app.displayed_thoughts, [1]);
+    }
+
+    #[test]
+    fn test_entity_picker_bad_query_stays_open_with_error() {
+        let mut app = App::new(vec![], vec![make_entity("Sarah")], SortOrder::Ascending);
+        app.mode = Mode::EntityPicker {
+            input: tui_input::Input::new("sarah AND".to_string()),
+            matches: vec![],
+            selected: 0,
+            error: None,
+        };
+
+        handle_key_event(&mut app, key_event(KeyCode::Enter));
+        let Mode::EntityPicker { error, .. } = &app.mode else {
+            panic!("picker should stay open");
+        };
+        assert!(error.as_deref().is_some_and(|e| e.contains("Invalid query")));
+        assert!(app.active_filter.is_none());
+
+        // Typing clears the error
+        handle_key_event(&mut app, key_event(KeyCode::Char(' ')));
+        assert!(matches!(app.mode, Mode::EntityPicker { error: None, .. }));
+    }
+
     #[test]
     fn test_entity_picker_up_down_navigation() {
         let entities = vec![make_entity("Alpha"), make_entity("Beta"), make_entity("Gamma")];
@@ -421,6 +530,7 @@ mod tests {
             input: tui_input::Input::default(),
             matches: vec![0, 1, 2],
             selected: 0,
+            error: None,
         };
 
         handle_key_event(&mut app, key_event(KeyCode::Down));
@@ -434,6 +544,66 @@ mod tests {
         }
     }
 
+    fn make_hierarchy_app() -> App {
+        let thoughts = vec![make_thought("[AWS] launch", 0), make_thought("[Other] news", 1)];
+        let mut amazon = make_entity("Amazon");
+        let mut aws = make_entity("AWS");
+        let mut other = make_entity("Other");
+        amazon.id = Some(1);
+        aws.id = Some(2);
+        other.id = Some(3);
+        App::new(thoughts, vec![amazon, aws, other], SortOrder::Ascending)
+            .with_relations(vec![(2, 1)])
+            .with_thought_links(vec![(0, 2), (1, 3)])
+    }
+
+    #[test]
+    fn test_normal_mode_t_opens_entity_tree() {
+        let mut app = make_hierarchy_app();
+        handle_key_event(&mut app, key_event(KeyCode::Char('t')));
+        assert!(matches!(app.mode, Mode::EntityTree { selected: 0, .. }));
+    }
+
+    #[test]
+    fn test_entity_tree_collapse_and_expand() {
+        let mut app = make_hierarchy_app();
+        handle_key_event(&mut app, key_event(KeyCode::Char('t')));
+
+        handle_key_event(&mut app, key_event(KeyCode::Left));
+        let Mode::EntityTree { ref collapsed, .. } = app.mode else {
+            panic!("Expected EntityTree mode");
+        };
+        assert_eq!(app.visible_tree_rows(collapsed).len(), 2); // Amazon (AWS hidden), Other
+
+        handle_key_event(&mut app, key_event(KeyCode::Right));
+        let Mode::EntityTree { ref collapsed, .. } = app.mode else {
+            panic!("Expected EntityTree mode");
+        };
+        assert_eq!(app.visible_tree_rows(collapsed).len(), 3);
+    }
+
+    #[test]
+    fn test_entity_tree_enter_applies_descendant_filter() {
+        let mut app = make_hierarchy_app();
+        handle_key_event(&mut app, key_event(KeyCode::Char('t')));
+
+        handle_key_event(&mut app, key_event(KeyCode::Enter));
+        assert!(matches!(app.mode, Mode::Normal));
+        assert_eq!(app.active_filter.as_deref(), Some("Amazon"));
+        assert_eq!(app.displayed_thoughts.len(), 1); // The AWS thought, via Amazon's descendants
+    }
+
+    #[test]
+    fn test_entity_tree_esc_returns_to_normal() {
+        let mut app = make_hierarchy_app();
+        handle_key_event(&mut app, key_event(KeyCode::Char('t')));
+
+        handle_key_event(&mut app, key_event(KeyCode::Down));
+        handle_key_event(&mut app, key_event(KeyCode::Esc));
+        assert!(matches!(app.mode, Mode::Normal));
+        assert!(app.active_filter.is_none());
+    }
+
     #[test]
     fn test_entity_detail_esc_returns_to_normal() {
         let mut app = App::new(vec![], vec![], SortOrder::Ascending);
@@ -3,19 +3,24 @@
 //! Provides an interactive terminal UI for browsing thoughts with entity
 //! highlighting, fuzzy entity filtering, sort toggling, and entity description popups.
 
+pub mod compose;
+pub mod fuzzy;
 pub mod input;
 pub mod state;
 pub mod ui;
 
-use std::collections::{HashMap, HashSet};
+use std::collections::{BTreeMap, HashMap, HashSet};
 use std::path::PathBuf;
 
 use ratatui::crossterm::event::{self, Event, KeyEventKind};
 use ratatui::{Terminal, backend::Backend};
 
 use crate::errors::ThoughtError;
-use crate::models::{Entity, SortOrder, Thought};
+use crate::models::{Entity, EntityKind, SortOrder, Thought, TimestampFormat};
 use crate::services::entity_parser;
+use crate::services::entity_tree::{self, TreeRow};
+use crate::services::related_entities::{self, RelatednessMetric};
+use crate::services::thought_query::{self, Query};
 use crate::storage::connection::get_connection;
 use crate::storage::migrations::run_migrations;
 use crate::storage::thoughts_repository::ThoughtsRepository;
@@ -39,15 +44,31 @@ pub struct App {
     pub mode: Mode,
     /// Current sort direction
     pub sort_order: SortOrder,
-    /// Entity name currently filtering by (None = show all), kept for display purposes
+    /// Zone thoughts are dated in, and whether their time of day is shown
+    pub timestamps: TimestampFormat,
+    /// Entity name or query text currently filtering by, kept for display purposes
     pub active_filter: Option<String>,
-    /// Lowercase names of the filter entity and all its transitive descendants (via
-    /// child relations); thoughts referencing any of these names pass the filter
-    pub active_filter_reachable: HashSet<String>,
+    /// Query the thought list is filtered with (None = show all); picking an entity
+    /// filters with a single-entity query
+    pub active_query: Option<Query>,
+    /// Lowercase entity term from the query -> ids of the entity it resolves to and all its
+    /// transitive descendants (via child relations); a thought linked to any of these
+    /// (see `thought_links`) matches the term, however its text spelled the reference
+    pub query_reachable: HashMap<String, HashSet<i64>>,
     /// Exit flag
     pub should_quit: bool,
     /// Path to the database for mutation operations
     pub db_path: Option<PathBuf>,
+    /// Lowercase entity names and aliases -> kind, used to color entities by kind
+    pub entity_kinds: HashMap<String, EntityKind>,
+    /// Entity id -> attributes (key -> value), shown in the entity detail popup
+    pub entity_attributes: HashMap<i64, BTreeMap<String, String>>,
+    /// `(thought_id, entity_id)` links, for ranking related entities in the detail popup
+    pub thought_links: Vec<(i64, i64)>,
+    /// `(entity_id, alias)` pairs, so query filters can name entities by alias
+    pub entity_aliases: Vec<(i64, String)>,
+    /// Entity hierarchy flattened into rows for the tree panel, built from loaded relation edges
+    pub entity_tree: Vec<TreeRow>,
     /// Parent entity id -> child entity ids, built from loaded relation edges
     entity_children: HashMap<i64, Vec<i64>>,
 }
@@ -55,6 +76,7 @@ pub struct App {
 impl App {
     /// Create a new App with loaded data and initial sort order.
     pub fn new(thoughts: Vec<Thought>, entities: Vec<Entity>, sort_order: SortOrder) -> Self {
+        let entity_tree = entity_tree::build(&entities, &[]);
         let mut app = Self {
             thoughts,
             entities,
@@ -62,10 +84,17 @@ impl App {
             list_state: ratatui::widgets::ListState::default(),
             mode: Mode::Normal,
             sort_order,
+            timestamps: TimestampFormat::default(),
             active_filter: None,
-            active_filter_reachable: HashSet::new(),
+            active_query: None,
+            query_reachable: HashMap::new(),
             should_quit: false,
             db_path: None,
+            entity_kinds: HashMap::new(),
+            entity_attributes: HashMap::new(),
+            thought_links: Vec::new(),
+            entity_aliases: Vec::new(),
+            entity_tree,
             entity_children: HashMap::new(),
         };
         app.recompute_displayed_thoughts();
@@ -81,9 +110,17 @@ impl App {
         self
     }
 
+    /// Set the zone thoughts are dated in and whether times are shown (see
+    /// `ThoughtsConfig::timestamp_format`).
+    pub fn with_timestamp_format(mut self, timestamps: TimestampFormat) -> Self {
+        self.timestamps = timestamps;
+        self
+    }
+
     /// Load the entity relation graph (child_id, parent_id edges) so entity-picker
-    /// filtering can include transitive descendants.
+    /// filtering can include transitive descendants, and build the tree panel's rows.
     pub fn with_relations(mut self, relations: Vec<(i64, i64)>) -> Self {
+        self.entity_tree = entity_tree::build(&self.entities, &relations);
         let mut entity_children: HashMap<i64, Vec<i64>> = HashMap::new();
         for (child_id, parent_id) in relations {
             entity_children.entry(parent_id).or_default().push(child_id);
@@ -92,9 +129,128 @@ impl App {
         self
     }
 
-    /// Lowercase names of `entities[root_idx]` and every entity transitively
-    /// reachable from it via child relations (descendants).
-    pub fn reachable_names(&self, root_idx: usize) -> HashSet<String> {
+    /// Load the name -> kind map (see `EntitiesRepository::kinds_by_name`) so entity
+    /// references are colored by their entity's kind.
+    pub fn with_entity_kinds(mut self, entity_kinds: HashMap<String, EntityKind>) -> Self {
+        self.entity_kinds = entity_kinds;
+        self
+    }
+
+    /// Load entity attributes (see `EntityAttributesRepository::list_all`) for the
+    /// entity detail popup.
+    pub fn with_entity_attributes(mut self, entity_attributes: HashMap<i64, BTreeMap<String, String>>) -> Self {
+        self.entity_attributes = entity_attributes;
+        self
+    }
+
+    /// Load thought/entity links (see `EntitiesRepository::thought_links`), which filters
+    /// match thoughts by and the entity detail popup ranks related entities with.
+    pub fn with_thought_links(mut self, thought_links: Vec<(i64, i64)>) -> Self {
+        self.thought_links = thought_links;
+        self
+    }
+
+    /// Load entity aliases (see `EntityAliasesRepository::list_all`) so query filters
+    /// resolve aliases as `wet thoughts --where` does.
+    pub fn with_entity_aliases(mut self, entity_aliases: Vec<(i64, String)>) -> Self {
+        self.entity_aliases = entity_aliases;
+        self
+    }
+
+    /// The entities most often mentioned alongside `entity_idx`, best first (Jaccard),
+    /// as indices into `entities` with the number of thoughts shared.
+    pub fn related_entities(&self, entity_idx: usize, limit: usize) -> Vec<(usize, usize)> {
+        let Some(entity_id) = self.entities[entity_idx].id else {
+            return Vec::new();
+        };
+        related_entities::rank(entity_id, &self.thought_links, RelatednessMetric::Jaccard)
+            .into_iter()
+            .filter_map(|related| {
+                self.entities
+                    .iter()
+                    .position(|e| e.id == Some(related.entity_id))
+                    .map(|idx| (idx, related.shared))
+            })
+            .take(limit)
+            .collect()
+    }
+
+    /// Indices into `entity_tree` of the rows currently shown in the tree panel: every
+    /// row except the descendants of a collapsed row.
+    pub fn visible_tree_rows(&self, collapsed: &HashSet<usize>) -> Vec<usize> {
+        let mut visible = Vec::new();
+        let mut hidden_below: Option<usize> = None;
+        for (i, row) in self.entity_tree.iter().enumerate() {
+            if let Some(depth) = hidden_below {
+                if row.depth > depth {
+                    continue;
+                }
+                hidden_below = None;
+            }
+            visible.push(i);
+            if collapsed.contains(&i) {
+                hidden_below = Some(row.depth);
+            }
+        }
+        visible
+    }
+
+    /// Filter the thought list to an entity and its transitive descendants, as picking
+    /// it in the entity picker does.
+    pub fn apply_entity_filter(&mut self, entity_idx: usize) {
+        let name = self.entities[entity_idx].canonical_name.clone();
+        self.query_reachable = HashMap::from([(name.to_lowercase(), self.reachable_ids(entity_idx))]);
+        self.active_query = Some(Query::Entity(name.clone()));
+        self.active_filter = Some(name);
+        self.recompute_displayed_thoughts();
+    }
+
+    /// Filter the thought list with a query (see `services::thought_query`), as typing one
+    /// into the entity picker does. Entity terms resolve by name or alias and include
+    /// descendants; on error the current filter is left alone.
+    ///
+    /// # Returns
+    /// * `Err(ThoughtError::InvalidQuery)` - The query doesn't parse
+    /// * `Err(ThoughtError::EntityNotFound)` - An entity term names no loaded entity
+    pub fn apply_query_filter(&mut self, text: &str) -> Result<(), ThoughtError> {
+        let query = thought_query::parse(text, self.timestamps.timezone.today())?;
+        let mut reachable = HashMap::new();
+        for name in query.entity_names() {
+            let entity_idx = self
+                .find_entity(name)
+                .ok_or_else(|| ThoughtError::EntityNotFound(name.to_string()))?;
+            reachable.insert(name.to_lowercase(), self.reachable_ids(entity_idx));
+        }
+        self.query_reachable = reachable;
+        self.active_query = Some(query);
+        self.active_filter = Some(text.trim().to_string());
+        self.recompute_displayed_thoughts();
+        Ok(())
+    }
+
+    /// Show every thought again.
+    pub fn clear_filter(&mut self) {
+        self.active_filter = None;
+        self.active_query = None;
+        self.query_reachable.clear();
+        self.recompute_displayed_thoughts();
+    }
+
+    /// Index into `entities` of the entity with this name, or failing that this alias
+    /// (case-insensitive).
+    fn find_entity(&self, name: &str) -> Option<usize> {
+        let lower = name.to_lowercase();
+        self.entities.iter().position(|e| e.name == lower).or_else(|| {
+            self.entity_aliases
+                .iter()
+                .find(|(_, alias)| alias.to_lowercase() == lower)
+                .and_then(|(entity_id, _)| self.entities.iter().position(|e| e.id == Some(*entity_id)))
+        })
+    }
+
+    /// Ids of `entities[root_idx]` and every entity transitively reachable from it via
+    /// child relations (descendants).
+    pub fn reachable_ids(&self, root_idx: usize) -> HashSet<i64> {
         let mut visited_ids = HashSet::new();
         let mut stack = vec![self.entities[root_idx].id.unwrap()];
 
@@ -106,12 +262,7 @@ impl App {
                 stack.extend(children.iter().copied());
             }
         }
-
-        self.entities
-            .iter()
-            .filter(|e| e.id.is_some_and(|id| visited_ids.contains(&id)))
-            .map(|e| e.name.clone())
-            .collect()
+        visited_ids
     }
 
     /// Delete the thought currently pending confirmation.
@@ -137,6 +288,8 @@ impl App {
         ThoughtsRepository::delete(&conn, thought_id)?;
 
         self.thoughts.remove(thought_index);
+        self.thought_links
+            .retain(|(linked_thought, _)| *linked_thought != thought_id);
         self.mode = Mode::Normal;
         self.recompute_displayed_thoughts();
 
@@ -144,16 +297,27 @@ impl App {
     }
 
     /// Recompute the displayed thoughts based on current filter and sort order.
+    ///
+    /// Entity terms match through `thought_links`, as `wet thoughts --where` matches
+    /// through the stored links, so a reference by alias counts.
     pub fn recompute_displayed_thoughts(&mut self) {
-        let mut indices: Vec<usize> = if self.active_filter.is_some() {
+        let mut indices: Vec<usize> = if let Some(query) = &self.active_query {
+            let mut linked: HashMap<i64, HashSet<i64>> = HashMap::new();
+            for &(thought_id, entity_id) in &self.thought_links {
+                linked.entry(thought_id).or_default().insert(entity_id);
+            }
             self.thoughts
                 .iter()
                 .enumerate()
                 .filter(|(_, thought)| {
-                    let entities = entity_parser::extract_entities(&thought.content);
-                    entities
-                        .iter()
-                        .any(|e| self.active_filter_reachable.contains(&e.to_lowercase()))
+                    let entity_ids = thought.id.and_then(|id| linked.get(&id));
+                    let has_entity = |name: &str| {
+                        self.query_reachable
+                            .get(&name.to_lowercase())
+                            .zip(entity_ids)
+                            .is_some_and(|(reachable, linked)| !reachable.is_disjoint(linked))
+                    };
+                    query.matches(self.timestamps.timezone.date_of(thought.created_at), &has_entity)
                 })
                 .map(|(i, _)| i)
                 .collect()
@@ -246,6 +410,7 @@ mod tests {
             name: name.to_lowercase(),
             canonical_name: name.to_string(),
             description: description.map(|s| s.to_string()),
+            kind: None,
         }
     }
 
@@ -321,10 +486,9 @@ mod tests {
             make_thought("No entities here", 1),
             make_thought("Called [Sarah] about [project]", 0),
         ];
-        let mut app = App::new(thoughts, vec![], SortOrder::Ascending);
-        app.active_filter = Some("Sarah".to_string());
-        app.active_filter_reachable = ["sarah".to_string()].into_iter().collect();
-        app.recompute_displayed_thoughts();
+        let mut app = App::new(thoughts, vec![make_entity("Sarah", None)], SortOrder::Ascending)
+            .with_thought_links(vec![(2, 1), (0, 1)]);
+        app.apply_entity_filter(0);
         assert_eq!(app.displayed_thoughts.len(), 2);
     }
 
@@ -334,18 +498,75 @@ mod tests {
             make_thought("Meeting with [Sarah]", 2),
             make_thought("No entities here", 1),
         ];
-        let mut app = App::new(thoughts, vec![], SortOrder::Ascending);
-        app.active_filter = Some("Sarah".to_string());
-        app.active_filter_reachable = ["sarah".to_string()].into_iter().collect();
-        app.recompute_displayed_thoughts();
+        let mut app =
+            App::new(thoughts, vec![make_entity("Sarah", None)], SortOrder::Ascending).with_thought_links(vec![(2, 1)]);
+        app.apply_entity_filter(0);
         assert_eq!(app.displayed_thoughts.len(), 1);
 
-        app.active_filter = None;
-        app.active_filter_reachable.clear();
-        app.recompute_displayed_thoughts();
+        app.clear_filter();
         assert_eq!(app.displayed_thoughts.len(), 2);
     }
 
+    #[test]
+    fn test_query_filter_combines_entities_aliases_and_dates() {
+        let thoughts = vec![
+            make_thought("[Sarah] on [alpha]", 3),
+            make_thought("[Sarah] on the [draft]", 2),
+            make_thought("[alpha] retro", 1),
+        ];
+        let mut entities = vec![
+            make_entity("Sarah", None),
+            make_entity("alpha", None),
+            make_entity("draft", None),
+        ];
+        for (id, entity) in (1..).zip(entities.iter_mut()) {
+            entity.id = Some(id);
+        }
+        let mut app = App::new(thoughts, entities, SortOrder::Ascending)
+            .with_thought_links(vec![(3, 1), (3, 2), (2, 1), (2, 3), (1, 2)])
+            .with_entity_aliases(vec![(1, "sar".to_string())]);
+
+        app.apply_query_filter("sar AND NOT draft").unwrap();
+        assert_eq!(app.displayed_thoughts, [0]);
+        assert_eq!(app.active_filter.as_deref(), Some("sar AND NOT draft"));
+
+        let yesterday = (app.timestamps.timezone.today() - chrono::Duration::days(1)).format("after:%Y-%m-%d");
+        app.apply_query_filter(&format!("alpha AND {yesterday}")).unwrap();
+        assert_eq!(app.displayed_thoughts, [2]);
+    }
+
+    #[test]
+    fn test_filters_match_references_by_alias() {
+        let thoughts = vec![make_thought("[sar] called", 1), make_thought("[Sarah] wrote", 0)];
+        let mut app = App::new(thoughts, vec![make_entity("Sarah", None)], SortOrder::Ascending)
+            .with_thought_links(vec![(1, 1), (0, 1)])
+            .with_entity_aliases(vec![(1, "sar".to_string())]);
+
+        app.apply_query_filter("sarah").unwrap();
+        assert_eq!(app.displayed_thoughts, [0, 1]);
+        app.apply_entity_filter(0);
+        assert_eq!(app.displayed_thoughts, [0, 1]);
+    }
+
+    #[test]
+    fn test_query_filter_errors_keep_the_current_filter() {
+        let thoughts = vec![make_thought("[Sarah] hello", 0), make_thought("plain", 1)];
+        let mut app =
+            App::new(thoughts, vec![make_entity("Sarah", None)], SortOrder::Ascending).with_thought_links(vec![(0, 1)]);
+        app.apply_entity_filter(0);
+
+        assert!(matches!(
+            app.apply_query_filter("sarah AND ("),
+            Err(ThoughtError::InvalidQuery(_))
+        ));
+        assert!(matches!(
+            app.apply_query_filter("NOT bob"),
+            Err(ThoughtError::EntityNotFound(_))
+        ));
+        assert_eq!(app.active_filter.as_deref(), Some("Sarah"));
+        assert_eq!(app.displayed_thoughts.len(), 1);
+    }
+
     #[test]
     fn test_selected_thought_entity_indices() {
         let thoughts = vec![make_thought("Meeting with [Sarah] about [Project]", 0)];
@@ -442,82 +663,108 @@ mod tests {
     }
 
     #[test]
-    fn test_reachable_names_no_relations_returns_only_self() {
+    fn test_related_entities_ranks_by_shared_thoughts() {
+        let mut entities = vec![
+            make_entity("Sarah", None),
+            make_entity("Acme", None),
+            make_entity("Berlin", None),
+            make_entity("Unrelated", None),
+        ];
+        for (i, entity) in entities.iter_mut().enumerate() {
+            entity.id = Some(i as i64 + 1);
+        }
+        // Sarah and Acme share thoughts 1 and 2, Sarah and Berlin only thought 2
+        let app = App::new(vec![], entities, SortOrder::Ascending).with_thought_links(vec![
+            (1, 1),
+            (1, 2),
+            (2, 1),
+            (2, 2),
+            (2, 3),
+            (3, 3),
+            (4, 4),
+        ]);
+
+        assert_eq!(app.related_entities(0, 5), vec![(1, 2), (2, 1)]);
+        assert_eq!(app.related_entities(0, 1), vec![(1, 2)]);
+        assert!(app.related_entities(3, 5).is_empty());
+    }
+
+    #[test]
+    fn test_reachable_ids_no_relations_returns_only_self() {
         let entities = vec![make_entity("Amazon", None)];
         let app = App::new(vec![], entities, SortOrder::Ascending);
 
-        let names = app.reachable_names(0);
-        assert_eq!(names, ["amazon".to_string()].into_iter().collect());
+        assert_eq!(app.reachable_ids(0), HashSet::from([1]));
     }
 
     #[test]
-    fn test_reachable_names_multi_level_chain() {
+    fn test_reachable_ids_multi_level_chain() {
         let entities = vec![
             Entity {
                 id: Some(1),
                 name: "amazon".to_string(),
                 canonical_name: "Amazon".to_string(),
                 description: None,
+                kind: None,
             },
             Entity {
                 id: Some(2),
                 name: "aws".to_string(),
                 canonical_name: "AWS".to_string(),
                 description: None,
+                kind: None,
             },
             Entity {
                 id: Some(3),
                 name: "ec2".to_string(),
                 canonical_name: "EC2".to_string(),
                 description: None,
+                kind: None,
             },
         ];
 
         // AWS child-of Amazon, EC2 child-of AWS
         let app = App::new(vec![], entities, SortOrder::Ascending).with_relations(vec![(2, 1), (3, 2)]);
 
-        let names = app.reachable_names(0);
-        assert_eq!(
-            names,
-            ["amazon".to_string(), "aws".to_string(), "ec2".to_string()]
-                .into_iter()
-                .collect()
-        );
+        assert_eq!(app.reachable_ids(0), HashSet::from([1, 2, 3]));
     }
 
     #[test]
-    fn test_reachable_names_diamond_shape() {
+    fn test_reachable_ids_diamond_shape() {
         let entities = vec![
             Entity {
                 id: Some(1),
                 name: "a".to_string(),
                 canonical_name: "A".to_string(),
                 description: None,
+                kind: None,
             },
             Entity {
                 id: Some(2),
                 name: "b".to_string(),
                 canonical_name: "B".to_string(),
                 description: None,
+                kind: None,
             },
             Entity {
                 id: Some(3),
                 name: "c".to_string(),
                 canonical_name: "C".to_string(),
                 description: None,
+                kind: None,
             },
             Entity {
                 id: Some(4),
                 name: "d".to_string(),
                 canonical_name: "D".to_string(),
                 description: None,
+                kind: None,
             },
         ];
         // B, C child-of A; D child-of both B and C
         let app = App::new(vec![], entities, SortOrder::Ascending).with_relations(vec![(2, 1), (3, 1), (4, 2), (4, 3)]);
 
-        let names = app.reachable_names(0);
-        assert_eq!(names, ["a", "b", "c", "d"].into_iter().map(String::from).collect());
+        assert_eq!(app.reachable_ids(0), HashSet::from([1, 2, 3, 4]));
     }
 
     #[test]
@@ -533,19 +780,21 @@ mod tests {
                 name: "amazon".to_string(),
                 canonical_name: "Amazon".to_string(),
                 description: None,
+                kind: None,
             },
             Entity {
                 id: Some(2),
                 name: "aws".to_string(),
                 canonical_name: "AWS".to_string(),
                 description: None,
+                kind: None,
             },
         ];
-        let mut app = App::new(thoughts, entities, SortOrder::Ascending).with_relations(vec![(2, 1)]);
+        let mut app = App::new(thoughts, entities, SortOrder::Ascending)
+            .with_relations(vec![(2, 1)])
+            .with_thought_links(vec![(1, 1), (0, 2)]);
 
-        app.active_filter = Some("Amazon".to_string());
-        app.active_filter_reachable = app.reachable_names(0);
-        app.recompute_displayed_thoughts();
+        app.apply_entity_filter(0);
 
         assert_eq!(app.displayed_thoughts.len(), 2);
     }
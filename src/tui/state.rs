@@ -2,13 +2,15 @@
 //!
 //! Defines the interaction modes used by the TUI.
 
+use std::collections::HashSet;
+
 /// Interaction mode of the TUI.
 ///
 /// Determines which key bindings are active and what overlays are shown.
 pub enum Mode {
     /// Browsing thought list with standard key bindings
     Normal,
-    /// Fuzzy entity picker overlay is open
+    /// Fuzzy entity picker overlay is open; also accepts a filter query
     EntityPicker {
         /// Text input state for the search field
         input: tui_input::Input,
@@ -16,12 +18,21 @@ pub enum Mode {
         matches: Vec<usize>,
         /// Currently highlighted match in the picker list
         selected: usize,
+        /// Why the typed query couldn't be applied, shown until the input changes
+        error: Option<String>,
     },
     /// Confirmation overlay for deleting a thought
     ConfirmDelete {
         /// Index into App::thoughts of the thought to delete
         thought_index: usize,
     },
+    /// Collapsible entity hierarchy panel is open
+    EntityTree {
+        /// Position of the highlighted row among the currently visible rows
+        selected: usize,
+        /// Indices into App::entity_tree of rows whose children are hidden
+        collapsed: HashSet<usize>,
+    },
     /// Entity description popup is showing
     EntityDetail {
         /// Indices into App::entities for entities referenced in the selected thought
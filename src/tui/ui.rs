@@ -3,6 +3,8 @@
 //! All rendering logic for the thought list, status bar, entity picker overlay,
 //! and entity description popup.
 
+use std::collections::HashMap;
+
 use owo_colors::AnsiColors;
 use ratatui::{
     Frame,
@@ -12,7 +14,10 @@ use ratatui::{
     widgets::{Block, Borders, Clear, List, ListItem, Paragraph, Wrap},
 };
 
+use crate::models::{Entity, EntityKind};
 use crate::services::entity_parser::ENTITY_PATTERN;
+use crate::services::entity_styler::kind_color;
+use crate::services::thought_query::Query;
 
 use super::App;
 use super::state::Mode;
@@ -33,6 +38,9 @@ const ENTITY_COLORS: [AnsiColors; 12] = [
     AnsiColors::BrightRed,
 ];
 
+/// Number of related entities listed in the entity detail popup.
+const RELATED_LIMIT: usize = 5;
+
 /// Map an owo-colors AnsiColor to a ratatui Color.
 ///
 /// Ensures visual consistency between CLI output and TUI display
@@ -70,13 +78,25 @@ fn entity_color_index(entity: &str) -> usize {
     hash % ENTITY_COLORS.len()
 }
 
-/// Get the ratatui Color for an entity name.
-fn entity_color(entity: &str) -> Color {
-    ansi_to_ratatui_color(ENTITY_COLORS[entity_color_index(entity)])
+/// Get the ratatui Color for an entity name: its kind's color when the entity has a
+/// kind, otherwise a hashed palette color.
+fn entity_color(entity: &str, kinds: &HashMap<String, EntityKind>) -> Color {
+    match kinds.get(&entity.to_lowercase()) {
+        Some(&kind) => ansi_to_ratatui_color(kind_color(kind)),
+        None => ansi_to_ratatui_color(ENTITY_COLORS[entity_color_index(entity)]),
+    }
+}
+
+/// An entity's display name, followed by its kind in parentheses if it has one.
+fn entity_label(entity: &Entity) -> String {
+    match entity.kind {
+        Some(kind) => format!("{} ({})", entity.canonical_name, kind),
+        None => entity.canonical_name.clone(),
+    }
 }
 
 /// Build a styled Line from thought content, highlighting entity references.
-fn styled_content_line(content: &str, max_width: usize) -> Line<'static> {
+fn styled_content_line(content: &str, max_width: usize, kinds: &HashMap<String, EntityKind>) -> Line<'static> {
     let mut spans: Vec<Span<'static>> = Vec::new();
     let mut last_end = 0;
 
@@ -91,7 +111,7 @@ fn styled_content_line(content: &str, max_width: usize) -> Line<'static> {
         }
 
         // Styled entity reference
-        let color = entity_color(target_entity);
+        let color = entity_color(target_entity, kinds);
         spans.push(Span::styled(
             display_text.to_string(),
             Style::default().fg(color).add_modifier(Modifier::BOLD),
@@ -125,7 +145,7 @@ fn styled_content_line(content: &str, max_width: usize) -> Line<'static> {
             if full_match.start() > last_end2 {
                 spans2.push(Span::raw(truncated[last_end2..full_match.start()].to_string()));
             }
-            let color = entity_color(target_entity);
+            let color = entity_color(target_entity, kinds);
             spans2.push(Span::styled(
                 display_text.to_string(),
                 Style::default().fg(color).add_modifier(Modifier::BOLD),
@@ -157,6 +177,7 @@ pub fn render(app: &App, frame: &mut Frame) {
     match &app.mode {
         Mode::ConfirmDelete { .. } => render_confirm_delete(app, frame, area),
         Mode::EntityPicker { .. } => render_entity_picker(app, frame, area),
+        Mode::EntityTree { .. } => render_entity_tree(app, frame, area),
         Mode::EntityDetail { .. } => render_entity_detail(app, frame, area),
         Mode::Normal => {}
     }
@@ -167,7 +188,11 @@ fn render_thought_list(app: &App, frame: &mut Frame, area: Rect) {
     if app.displayed_thoughts.is_empty() {
         let message = if app.active_filter.is_some() {
             let filter_name = app.active_filter.as_deref().unwrap_or("");
-            format!("No thoughts referencing \"{}\"", filter_name)
+            if matches!(app.active_query, None | Some(Query::Entity(_))) {
+                format!("No thoughts referencing \"{}\"", filter_name)
+            } else {
+                format!("No thoughts match \"{}\"", filter_name)
+            }
         } else {
             "No thoughts recorded yet".to_string()
         };
@@ -185,11 +210,11 @@ fn render_thought_list(app: &App, frame: &mut Frame, area: Rect) {
         .iter()
         .map(|&idx| {
             let thought = &app.thoughts[idx];
-            let date_str = thought.created_at.format("%Y-%m-%d").to_string();
+            let date_str = app.timestamps.format(thought.created_at);
             let date_span = Span::styled(format!("{} ", date_str), Style::default().fg(Color::DarkGray));
 
             let content_max = inner_width.saturating_sub(date_str.len() + 1);
-            let content_line = styled_content_line(&thought.content, content_max);
+            let content_line = styled_content_line(&thought.content, content_max, &app.entity_kinds);
 
             let mut spans = vec![date_span];
             spans.extend(content_line.spans);
@@ -214,7 +239,7 @@ fn render_thought_list(app: &App, frame: &mut Frame, area: Rect) {
 /// Render the status bar with sort order, active filter, and key hints.
 fn render_status_bar(app: &App, frame: &mut Frame, area: Rect) {
     let sort_label = format!("Sort: {}", app.sort_order.label());
-    let hints = "q:Quit  /:Filter  s:Sort  x:Delete  Enter:Details  ?:Help";
+    let hints = "q:Quit  /:Filter  t:Tree  s:Sort  x:Delete  Enter:Details  ?:Help";
 
     let mut spans = vec![
         Span::styled(format!(" {} ", sort_label), Style::default().fg(Color::Cyan)),
@@ -242,7 +267,7 @@ fn render_confirm_delete(app: &App, frame: &mut Frame, area: Rect) {
     };
 
     let thought = &app.thoughts[thought_index];
-    let date_str = thought.created_at.format("%Y-%m-%d").to_string();
+    let date_str = app.timestamps.format(thought.created_at);
 
     let popup_area = centered_rect(60, 30, area);
     frame.render_widget(Clear, popup_area);
@@ -285,6 +310,7 @@ fn render_entity_picker(app: &App, frame: &mut Frame, area: Rect) {
         ref input,
         ref matches,
         selected,
+        ref error,
     } = app.mode
     else {
         return;
@@ -295,7 +321,7 @@ fn render_entity_picker(app: &App, frame: &mut Frame, area: Rect) {
 
     let block = Block::default()
         .borders(Borders::ALL)
-        .title("Filter by Entity (type to search, Enter to select, Esc to cancel)");
+        .title("Filter by Entity or Query (type to search, Enter to select, Esc to cancel)");
 
     let inner = block.inner(popup_area);
     frame.render_widget(block, popup_area);
@@ -311,12 +337,12 @@ fn render_entity_picker(app: &App, frame: &mut Frame, area: Rect) {
     let input_line = Line::from(vec![Span::raw("> "), Span::raw(input.value().to_string())]);
     frame.render_widget(Paragraph::new(input_line), chunks[0]);
 
-    // Match count
-    let count_text = format!("{} matches", matches.len());
-    frame.render_widget(
-        Paragraph::new(count_text).style(Style::default().fg(Color::DarkGray)),
-        chunks[1],
-    );
+    // Match count, or why the typed query couldn't be applied
+    let status = match error {
+        Some(error) => Paragraph::new(error.as_str()).style(Style::default().fg(Color::Red)),
+        None => Paragraph::new(format!("{} matches", matches.len())).style(Style::default().fg(Color::DarkGray)),
+    };
+    frame.render_widget(status, chunks[1]);
 
     // Match list
     let items: Vec<ListItem> = matches
@@ -329,7 +355,7 @@ fn render_entity_picker(app: &App, frame: &mut Frame, area: Rect) {
             } else {
                 Style::default()
             };
-            ListItem::new(Span::styled(entity.canonical_name.clone(), style))
+            ListItem::new(Span::styled(entity_label(entity), style))
         })
         .collect();
 
@@ -342,6 +368,69 @@ fn render_entity_picker(app: &App, frame: &mut Frame, area: Rect) {
     frame.set_cursor_position((cursor_x, cursor_y));
 }
 
+/// Render the collapsible entity hierarchy panel.
+fn render_entity_tree(app: &App, frame: &mut Frame, area: Rect) {
+    let Mode::EntityTree {
+        selected,
+        ref collapsed,
+    } = app.mode
+    else {
+        return;
+    };
+
+    let popup_area = centered_rect(60, 70, area);
+    frame.render_widget(Clear, popup_area);
+
+    let block = Block::default()
+        .borders(Borders::ALL)
+        .title("Entity Tree (←→ collapse/expand, Enter to filter, Esc to close)");
+
+    let entities_by_id: HashMap<i64, &Entity> = app.entities.iter().filter_map(|e| e.id.map(|id| (id, e))).collect();
+
+    let items: Vec<ListItem> = app
+        .visible_tree_rows(collapsed)
+        .into_iter()
+        .filter_map(|row_idx| {
+            let row = &app.entity_tree[row_idx];
+            let entity = entities_by_id.get(&row.entity_id)?;
+            let marker = match (row.has_children, collapsed.contains(&row_idx)) {
+                (false, _) => "  ",
+                (true, true) => "▸ ",
+                (true, false) => "▾ ",
+            };
+
+            let mut spans = vec![
+                Span::styled(row.prefix.clone(), Style::default().fg(Color::DarkGray)),
+                Span::raw(marker),
+                Span::styled(
+                    entity_label(entity),
+                    Style::default()
+                        .fg(entity_color(&entity.name, &app.entity_kinds))
+                        .add_modifier(Modifier::BOLD),
+                ),
+            ];
+            if !row.other_parents.is_empty() {
+                let others: Vec<&str> = row
+                    .other_parents
+                    .iter()
+                    .filter_map(|id| entities_by_id.get(id).map(|e| e.canonical_name.as_str()))
+                    .collect();
+                spans.push(Span::styled(
+                    format!(" [also under: {}]", others.join(", ")),
+                    Style::default().fg(Color::DarkGray),
+                ));
+            }
+            Some(ListItem::new(Line::from(spans)))
+        })
+        .collect();
+
+    let list = List::new(items)
+        .block(block)
+        .highlight_style(Style::default().add_modifier(Modifier::REVERSED));
+    let mut state = ratatui::widgets::ListState::default().with_selected(Some(selected));
+    frame.render_stateful_widget(list, popup_area, &mut state);
+}
+
 /// Render the entity description modal popup.
 fn render_entity_detail(app: &App, frame: &mut Frame, area: Rect) {
     let Mode::EntityDetail {
@@ -368,19 +457,47 @@ fn render_entity_detail(app: &App, frame: &mut Frame, area: Rect) {
         let entity = &app.entities[idx];
 
         // Entity name header
-        let color = entity_color(&entity.name);
+        let color = entity_color(&entity.name, &app.entity_kinds);
         lines.push(Line::from(Span::styled(
-            entity.canonical_name.clone(),
+            entity_label(entity),
             Style::default()
                 .fg(color)
                 .add_modifier(Modifier::BOLD | Modifier::UNDERLINED),
         )));
 
+        // Attributes, one per line
+        if let Some(attributes) = entity.id.and_then(|id| app.entity_attributes.get(&id)) {
+            for (key, value) in attributes {
+                lines.push(Line::from(vec![
+                    Span::styled(format!("{}: ", key), Style::default().fg(Color::DarkGray)),
+                    Span::raw(value.clone()),
+                ]));
+            }
+        }
+
+        // Entities most often mentioned alongside this one
+        let related = app.related_entities(idx, RELATED_LIMIT);
+        if !related.is_empty() {
+            let mut spans = vec![Span::styled("Related: ", Style::default().fg(Color::DarkGray))];
+            for (n, (related_idx, shared)) in related.into_iter().enumerate() {
+                if n > 0 {
+                    spans.push(Span::raw(", "));
+                }
+                let other = &app.entities[related_idx];
+                spans.push(Span::styled(
+                    other.canonical_name.clone(),
+                    Style::default().fg(entity_color(&other.name, &app.entity_kinds)),
+                ));
+                spans.push(Span::raw(format!(" ({})", shared)));
+            }
+            lines.push(Line::from(spans));
+        }
+
         // Description or placeholder
         if let Some(ref desc) = entity.description {
             // Render description with entity highlighting
             for paragraph in desc.split("\n\n") {
-                let content_line = styled_content_line(paragraph, inner.width as usize);
+                let content_line = styled_content_line(paragraph, inner.width as usize, &app.entity_kinds);
                 lines.push(content_line);
                 lines.push(Line::raw(""));
             }
@@ -423,6 +540,7 @@ mod tests {
     use crate::models::{Entity, SortOrder, Thought};
     use chrono::Utc;
     use ratatui::{Terminal, backend::TestBackend};
+    use std::collections::BTreeMap;
 
     fn make_thought(content: &str, days_ago: i64) -> Thought {
         Thought {
@@ -438,6 +556,7 @@ mod tests {
             name: name.to_lowercase(),
             canonical_name: name.to_string(),
             description: description.map(|s| s.to_string()),
+            kind: None,
         }
     }
 
@@ -479,28 +598,34 @@ mod tests {
 
     #[test]
     fn test_entity_color_consistent() {
-        let color1 = entity_color("Sarah");
-        let color2 = entity_color("Sarah");
+        let color1 = entity_color("Sarah", &HashMap::new());
+        let color2 = entity_color("Sarah", &HashMap::new());
         assert_eq!(color1, color2);
     }
 
     #[test]
     fn test_entity_color_case_insensitive() {
-        let color1 = entity_color("Sarah");
-        let color2 = entity_color("sarah");
+        let color1 = entity_color("Sarah", &HashMap::new());
+        let color2 = entity_color("sarah", &HashMap::new());
         assert_eq!(color1, color2);
     }
 
+    #[test]
+    fn test_entity_color_uses_kind_color() {
+        let kinds = HashMap::from([("sarah".to_string(), EntityKind::Place)]);
+        assert_eq!(entity_color("Sarah", &kinds), Color::Yellow);
+    }
+
     #[test]
     fn test_styled_content_line_plain_text() {
-        let line = styled_content_line("plain text", 80);
+        let line = styled_content_line("plain text", 80, &HashMap::new());
         assert_eq!(line.spans.len(), 1);
         assert_eq!(line.spans[0].content, "plain text");
     }
 
     #[test]
     fn test_styled_content_line_with_entity() {
-        let line = styled_content_line("hello [Sarah] world", 80);
+        let line = styled_content_line("hello [Sarah] world", 80, &HashMap::new());
         assert_eq!(line.spans.len(), 3);
         assert_eq!(line.spans[0].content, "hello ");
         assert_eq!(line.spans[1].content, "Sarah");
@@ -511,7 +636,7 @@ mod tests {
 
     #[test]
     fn test_styled_content_line_with_aliased_entity() {
-        let line = styled_content_line("the [ML](machine-learning) course", 80);
+        let line = styled_content_line("the [ML](machine-learning) course", 80, &HashMap::new());
         assert_eq!(line.spans.len(), 3);
         assert_eq!(line.spans[0].content, "the ");
         assert_eq!(line.spans[1].content, "ML"); // Displays alias
@@ -537,6 +662,16 @@ mod tests {
         assert!(output.contains("Sarah"));
     }
 
+    #[test]
+    fn test_render_empty_query_result_says_no_match() {
+        let thoughts = vec![make_thought("[Sarah] hello", 0)];
+        let mut app =
+            App::new(thoughts, vec![make_entity("Sarah", None)], SortOrder::Ascending).with_thought_links(vec![(0, 1)]);
+        app.apply_query_filter("NOT sarah").unwrap();
+        let output = render_to_string(&app, 60, 10);
+        assert!(output.contains("No thoughts match"));
+    }
+
     #[test]
     fn test_render_thoughts_shows_content() {
         let thoughts = vec![make_thought("Meeting with team", 0)];
@@ -573,10 +708,9 @@ mod tests {
     #[test]
     fn test_render_filtered_title() {
         let thoughts = vec![make_thought("[Sarah] hello", 0)];
-        let mut app = App::new(thoughts, vec![], SortOrder::Ascending);
-        app.active_filter = Some("Sarah".to_string());
-        app.active_filter_reachable = ["sarah".to_string()].into_iter().collect();
-        app.recompute_displayed_thoughts();
+        let mut app =
+            App::new(thoughts, vec![make_entity("Sarah", None)], SortOrder::Ascending).with_thought_links(vec![(0, 1)]);
+        app.apply_entity_filter(0);
         let output = render_to_string(&app, 80, 10);
         assert!(output.contains("filtered: Sarah"));
     }
@@ -597,6 +731,7 @@ mod tests {
             input: tui_input::Input::default(),
             matches: vec![0, 1],
             selected: 0,
+            error: None,
         };
         let output = render_to_string(&app, 80, 24);
         assert!(output.contains("Filter by Entity"));
@@ -605,6 +740,20 @@ mod tests {
         assert!(output.contains("2 matches"));
     }
 
+    #[test]
+    fn test_render_entity_picker_shows_query_error() {
+        let mut app = App::new(vec![], vec![], SortOrder::Ascending);
+        app.mode = Mode::EntityPicker {
+            input: tui_input::Input::new("sarah AND".to_string()),
+            matches: vec![],
+            selected: 0,
+            error: Some("Invalid query: the query ends too early".to_string()),
+        };
+        let output = render_to_string(&app, 100, 24);
+        assert!(output.contains("Invalid query: the query ends too early"));
+        assert!(!output.contains("0 matches"));
+    }
+
     #[test]
     fn test_render_entity_detail_with_description() {
         let entities = vec![make_entity("Sarah", Some("A colleague from work"))];
@@ -619,6 +768,67 @@ mod tests {
         assert!(output.contains("A colleague from work"));
     }
 
+    #[test]
+    fn test_render_entity_detail_shows_kind() {
+        let mut entity = make_entity("Sarah", None);
+        entity.kind = Some(EntityKind::Person);
+        let mut app = App::new(vec![], vec![entity], SortOrder::Ascending);
+        app.mode = Mode::EntityDetail {
+            entity_indices: vec![0],
+            scroll_offset: 0,
+        };
+        let output = render_to_string(&app, 80, 24);
+        assert!(output.contains("Sarah (person)"));
+    }
+
+    #[test]
+    fn test_render_entity_detail_shows_attributes() {
+        let entities = vec![make_entity("Sarah", Some("A colleague"))];
+        let attributes = HashMap::from([(1, BTreeMap::from([("status".to_string(), "active".to_string())]))]);
+        let mut app = App::new(vec![], entities, SortOrder::Ascending).with_entity_attributes(attributes);
+        app.mode = Mode::EntityDetail {
+            entity_indices: vec![0],
+            scroll_offset: 0,
+        };
+        let output = render_to_string(&app, 80, 24);
+        assert!(output.contains("status: active"));
+        assert!(output.contains("A colleague"));
+    }
+
+    #[test]
+    fn test_render_entity_detail_shows_related_entities() {
+        let mut entities = vec![make_entity("Sarah", None), make_entity("Acme", None)];
+        entities[1].id = Some(2);
+        let mut app = App::new(vec![], entities, SortOrder::Ascending).with_thought_links(vec![(1, 1), (1, 2), (2, 2)]);
+        app.mode = Mode::EntityDetail {
+            entity_indices: vec![0],
+            scroll_offset: 0,
+        };
+        let output = render_to_string(&app, 80, 24);
+        assert!(output.contains("Related: Acme (1)"));
+    }
+
+    #[test]
+    fn test_render_entity_tree_marks_multiple_parents() {
+        let mut entities = vec![
+            make_entity("Amazon", None),
+            make_entity("Google", None),
+            make_entity("Kubernetes", None),
+        ];
+        for (i, entity) in entities.iter_mut().enumerate() {
+            entity.id = Some(i as i64 + 1);
+        }
+        let mut app = App::new(vec![], entities, SortOrder::Ascending).with_relations(vec![(3, 1), (3, 2)]);
+        app.mode = Mode::EntityTree {
+            selected: 0,
+            collapsed: std::collections::HashSet::new(),
+        };
+        let output = render_to_string(&app, 100, 24);
+        assert!(output.contains("Entity Tree"));
+        assert!(output.contains("└── "));
+        assert!(output.contains("Kubernetes [also under: Google]"));
+    }
+
     #[test]
     fn test_render_entity_detail_without_description() {
         let entities = vec![make_entity("Sarah", None)];
@@ -668,10 +878,24 @@ mod tests {
         let thoughts = vec![make_thought("dated thought", 0)];
         let app = App::new(thoughts, vec![], SortOrder::Ascending);
         let output = render_to_string(&app, 80, 10);
-        let today = Utc::now().format("%Y-%m-%d").to_string();
+        let today = app.timestamps.timezone.today().format("%Y-%m-%d").to_string();
         assert!(output.contains(&today));
     }
 
+    #[test]
+    fn test_render_thought_shows_time_in_the_configured_zone() {
+        use crate::models::TimestampFormat;
+
+        let mut thought = make_thought("timed thought", 0);
+        thought.created_at = "2026-06-10T21:30:00Z".parse().unwrap();
+        let app = App::new(vec![thought], vec![], SortOrder::Ascending).with_timestamp_format(TimestampFormat {
+            timezone: "Europe/Prague".parse().unwrap(),
+            show_time: true,
+        });
+        let output = render_to_string(&app, 80, 10);
+        assert!(output.contains("2026-06-10 23:30 timed thought"));
+    }
+
     #[test]
     fn test_centered_rect_produces_smaller_rect() {
         let area = Rect::new(0, 0, 100, 50);
@@ -721,6 +945,7 @@ mod tests {
             input: tui_input::Input::default(),
             matches: vec![0, 1, 2],
             selected: 1,
+            error: None,
         };
         // Should render without panic; the selected item gets REVERSED style
         let output = render_to_string(&app, 80, 24);
@@ -1,11 +1,25 @@
 /// Contract tests - test the CLI interface as a black box
 mod test_add_command;
+mod test_completions_command;
+mod test_config_command;
+mod test_db_merge_command;
 mod test_edit_command;
 mod test_entities_command;
 mod test_entity_alias_command;
+mod test_entity_attribute_command;
+mod test_entity_delete_command;
+mod test_entity_duplicates_command;
 mod test_entity_edit_command;
+mod test_entity_mentions_command;
 mod test_entity_merge_command;
 mod test_entity_relate_command;
+mod test_entity_related_command;
 mod test_entity_rename_command;
 mod test_entity_show_command;
+mod test_entity_split_command;
+mod test_entity_timeline_command;
+mod test_graph_command;
+mod test_notebook_command;
+mod test_stats_command;
+mod test_thought_show_command;
 mod test_thoughts_command;
@@ -1,5 +1,7 @@
 /// Contract tests for `wet add` command
-use crate::test_helpers::{run_wet_command, setup_temp_db};
+#[cfg(unix)]
+use crate::test_helpers::scripted_editor;
+use crate::test_helpers::{run_wet_command, run_wet_command_with_env, run_wet_command_with_stdin, setup_temp_db};
 
 #[test]
 fn test_add_command_success() {
@@ -73,6 +75,65 @@ fn test_add_command_with_date() {
     );
 }
 
+#[test]
+fn test_add_command_with_relative_date() {
+    let temp_db = setup_temp_db();
+    let result = run_wet_command(&["add", "Yesterday's meeting", "--date", "yesterday"], Some(&temp_db));
+    assert_eq!(result.status, 0, "Command should succeed. Stderr: {}", result.stderr);
+    let result = run_wet_command(&["add", "Two weeks back", "--date", "2 weeks ago"], Some(&temp_db));
+    assert_eq!(result.status, 0, "Command should succeed. Stderr: {}", result.stderr);
+
+    let today = chrono::Local::now().date_naive();
+    let yesterday = (today - chrono::Duration::days(1)).format("%Y-%m-%d").to_string();
+    let two_weeks_ago = (today - chrono::Duration::weeks(2)).format("%Y-%m-%d").to_string();
+    let list = run_wet_command(&["thoughts"], Some(&temp_db));
+    assert!(
+        list.stdout.contains(&format!("{} - Yesterday's meeting", yesterday)),
+        "Got: {}",
+        list.stdout
+    );
+    assert!(
+        list.stdout.contains(&format!("{} - Two weeks back", two_weeks_ago)),
+        "Got: {}",
+        list.stdout
+    );
+}
+
+#[test]
+fn test_add_command_date_is_a_day_in_the_configured_timezone() {
+    let temp_db = setup_temp_db();
+    let config = run_wet_command(&["config", "thoughts.timezone", "America/New_York"], Some(&temp_db));
+    assert_eq!(config.status, 0, "Stderr: {}", config.stderr);
+    let result = run_wet_command(
+        &["add", "Backdated in New York", "--date", "2026-03-10"],
+        Some(&temp_db),
+    );
+    assert_eq!(result.status, 0, "Stderr: {}", result.stderr);
+
+    let list = run_wet_command(&["thoughts"], Some(&temp_db));
+    assert!(
+        list.stdout.contains("2026-03-10 - Backdated in New York"),
+        "Got: {}",
+        list.stdout
+    );
+    let json = run_wet_command(&["thought", "show", "1", "--json"], Some(&temp_db));
+    assert!(
+        json.stdout.contains("\"created_at\": \"2026-03-10T00:00:00-04:00\""),
+        "Got: {}",
+        json.stdout
+    );
+
+    // The same instant, with the time of day, seen from Prague
+    run_wet_command(&["config", "thoughts.show_time", "true"], Some(&temp_db));
+    run_wet_command(&["config", "thoughts.timezone", "Europe/Prague"], Some(&temp_db));
+    let list = run_wet_command(&["thoughts"], Some(&temp_db));
+    assert!(
+        list.stdout.contains("2026-03-10 05:00 - Backdated in New York"),
+        "Got: {}",
+        list.stdout
+    );
+}
+
 #[test]
 fn test_add_command_with_invalid_date() {
     let temp_db = setup_temp_db();
@@ -84,4 +145,170 @@ fn test_add_command_with_invalid_date() {
         "Should report invalid date error. Got: {}",
         result.stderr
     );
+    assert!(
+        result.stderr.contains("last friday"),
+        "Should list the accepted forms. Got: {}",
+        result.stderr
+    );
+}
+
+#[test]
+fn test_add_command_reads_stdin() {
+    let temp_db = setup_temp_db();
+    let result = run_wet_command_with_stdin(&["add", "-"], Some(&temp_db), Some("Snippet about [Sarah]\n"));
+    assert_eq!(result.status, 0, "stderr: {}", result.stderr);
+    assert!(result.stdout.contains("1 entity reference"), "Got: {}", result.stdout);
+
+    // No argument at all also reads stdin when it's piped
+    let result = run_wet_command_with_stdin(&["add"], Some(&temp_db), Some("Second snippet\n"));
+    assert_eq!(result.status, 0, "stderr: {}", result.stderr);
+
+    let list = run_wet_command(&["thoughts"], Some(&temp_db));
+    assert!(list.stdout.contains("Snippet about Sarah"), "Got: {}", list.stdout);
+    assert!(list.stdout.contains("Second snippet"), "Got: {}", list.stdout);
+}
+
+#[test]
+fn test_add_command_batch_lines_and_blocks() {
+    let temp_db = setup_temp_db();
+    let result = run_wet_command_with_stdin(
+        &["add", "--batch", "--date", "2026-03-01"],
+        Some(&temp_db),
+        Some("2026-03-10: Met [Sarah]\n\nCall [John] about [Sarah]\n"),
+    );
+    assert_eq!(result.status, 0, "stderr: {}", result.stderr);
+    assert!(
+        result
+            .stdout
+            .contains("2 thoughts added successfully (IDs: 1-2, 3 entity references)"),
+        "Got: {}",
+        result.stdout
+    );
+
+    let result = run_wet_command_with_stdin(
+        &["add", "-", "--batch=blocks"],
+        Some(&temp_db),
+        Some("Retro notes\nwent well\n\n\nPlanning\n"),
+    );
+    assert_eq!(result.status, 0, "stderr: {}", result.stderr);
+    assert!(result.stdout.contains("IDs: 3-4"), "Got: {}", result.stdout);
+
+    let list = run_wet_command(&["thoughts"], Some(&temp_db));
+    assert!(list.stdout.contains("2026-03-10 - Met Sarah"), "Got: {}", list.stdout);
+    assert!(
+        list.stdout.contains("2026-03-01 - Call John about Sarah"),
+        "Got: {}",
+        list.stdout
+    );
+    let json = run_wet_command(&["thought", "show", "3", "--json"], Some(&temp_db));
+    assert!(json.stdout.contains("Retro notes\\nwent well"), "Got: {}", json.stdout);
+}
+
+#[test]
+fn test_add_command_batch_is_all_or_nothing() {
+    let temp_db = setup_temp_db();
+    let input = format!("Fine thought\n{}\n", "a".repeat(10_001));
+    let result = run_wet_command_with_stdin(&["add", "--batch"], Some(&temp_db), Some(&input));
+
+    assert_ne!(result.status, 0);
+    assert!(result.stderr.contains("line 2"), "Got: {}", result.stderr);
+    let list = run_wet_command(&["thoughts"], Some(&temp_db));
+    assert!(!list.stdout.contains("Fine thought"), "Got: {}", list.stdout);
+}
+
+#[test]
+fn test_add_command_batch_errors_count_leading_blank_lines() {
+    let temp_db = setup_temp_db();
+    let input = "\n\nFine thought\n2026-02-30: Not a day\n";
+    let result = run_wet_command_with_stdin(&["add", "--batch"], Some(&temp_db), Some(input));
+
+    assert_ne!(result.status, 0);
+    assert!(result.stderr.contains("line 4"), "Got: {}", result.stderr);
+}
+
+#[cfg(unix)]
+#[test]
+fn test_add_command_editor_reopens_on_empty_content() {
+    let temp_db = setup_temp_db();
+    run_wet_command(&["add", "Coffee with [Sarah]"], Some(&temp_db));
+    let editor_dir = tempfile::TempDir::new().unwrap();
+    let editor = scripted_editor(editor_dir.path(), &["# only a comment\n", "Drafted for [Sarah]\n"]);
+
+    let result = run_wet_command_with_env(
+        &["add", "--editor", "--date", "2026-03-10"],
+        Some(&temp_db),
+        None,
+        &[("EDITOR", editor.to_str().unwrap())],
+    );
+    assert_eq!(result.status, 0, "stderr: {}", result.stderr);
+    assert!(
+        result.stdout.contains("Thought added successfully (ID: 2"),
+        "Got: {}",
+        result.stdout
+    );
+
+    let first = std::fs::read_to_string(editor_dir.path().join("buffer1")).unwrap();
+    assert!(first.contains("# Recently mentioned: Sarah"), "Got: {first}");
+    assert!(!first.contains("Error"), "Got: {first}");
+    let second = std::fs::read_to_string(editor_dir.path().join("buffer2")).unwrap();
+    assert!(
+        second.contains("# Error: Thought content cannot be empty"),
+        "Got: {second}"
+    );
+
+    let list = run_wet_command(&["thoughts"], Some(&temp_db));
+    assert!(
+        list.stdout.contains("2026-03-10 - Drafted for Sarah"),
+        "Got: {}",
+        list.stdout
+    );
+}
+
+#[cfg(unix)]
+#[test]
+fn test_add_command_editor_keeps_too_long_content_and_can_be_cancelled() {
+    let temp_db = setup_temp_db();
+    let editor_dir = tempfile::TempDir::new().unwrap();
+    let long = "a".repeat(10_001);
+    // Too long, then nothing, then the empty-content template saved unchanged
+    let editor = scripted_editor(editor_dir.path(), &[&long, ""]);
+
+    let result = run_wet_command_with_env(
+        &["add", "--editor"],
+        Some(&temp_db),
+        None,
+        &[("EDITOR", editor.to_str().unwrap())],
+    );
+    assert_eq!(result.status, 0, "stderr: {}", result.stderr);
+    assert!(result.stdout.contains("No thought added"), "Got: {}", result.stdout);
+
+    let second = std::fs::read_to_string(editor_dir.path().join("buffer2")).unwrap();
+    assert!(second.starts_with(&long), "The rejected text should be kept");
+    assert!(
+        second.contains("# Error: Thought exceeds maximum length"),
+        "Got: {second}"
+    );
+    assert_eq!(
+        std::fs::read_to_string(editor_dir.path().join("calls")).unwrap().trim(),
+        "3"
+    );
+
+    let list = run_wet_command(&["thoughts"], Some(&temp_db));
+    assert!(!list.stdout.contains("aaaa"), "Got: {}", list.stdout);
+}
+
+#[test]
+fn test_add_command_interactive_needs_a_terminal() {
+    let temp_db = setup_temp_db();
+    let result = run_wet_command_with_stdin(&["add", "-i"], Some(&temp_db), Some("Piped [Sarah]"));
+    assert_ne!(result.status, 0);
+    assert!(result.stderr.contains("-i needs a terminal"), "Got: {}", result.stderr);
+
+    let conflict = run_wet_command(&["add", "-i", "Inline"], Some(&temp_db));
+    assert_ne!(conflict.status, 0);
+    assert!(
+        conflict.stderr.contains("cannot be used with"),
+        "Got: {}",
+        conflict.stderr
+    );
 }
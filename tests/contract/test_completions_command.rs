@@ -0,0 +1,90 @@
+/// Contract tests for `wet completions` and the completion requests its scripts make
+use crate::test_helpers::{CommandResult, run_wet_command, run_wet_command_with_env, setup_temp_db};
+use tempfile::TempDir;
+
+/// Ask `wet` for completions the way the generated fish script does
+fn complete(words: &[&str], temp_db: &TempDir) -> CommandResult {
+    let mut args = vec!["--", "wet"];
+    args.extend_from_slice(words);
+    run_wet_command_with_env(&args, Some(temp_db), None, &[("COMPLETE", "fish")])
+}
+
+#[test]
+fn test_completions_command_prints_scripts_that_call_back_into_wet() {
+    let temp_db = setup_temp_db();
+    for (shell, registration, callback) in [
+        ("bash", "complete -o nospace", "COMPLETE=\"bash\""),
+        ("zsh", "#compdef wet", "COMPLETE=\"zsh\""),
+        ("fish", "complete --keep-order", "COMPLETE=fish wet"),
+    ] {
+        let result = run_wet_command(&["completions", shell], Some(&temp_db));
+        assert_eq!(result.status, 0, "stderr: {}", result.stderr);
+        assert!(
+            result.stdout.contains(registration),
+            "{shell} script: {}",
+            result.stdout
+        );
+        assert!(result.stdout.contains(callback), "{shell} script: {}", result.stdout);
+    }
+
+    let result = run_wet_command(&["completions", "tcsh"], Some(&temp_db));
+    assert_ne!(result.status, 0);
+}
+
+#[test]
+fn test_completion_offers_entity_names_and_aliases_ignoring_case() {
+    let temp_db = setup_temp_db();
+    run_wet_command(&["add", "Lunch with [Sarah] about [project-alpha]"], Some(&temp_db));
+    run_wet_command(&["entity", "alias", "Sarah", "--alias", "sally"], Some(&temp_db));
+
+    let result = complete(&["entity", "show", "SA"], &temp_db);
+    assert_eq!(result.status, 0, "stderr: {}", result.stderr);
+    assert_eq!(result.stdout, "Sarah\nsally\talias of Sarah\n");
+
+    let result = complete(&["thoughts", "--on", "proj"], &temp_db);
+    assert_eq!(result.stdout, "project-alpha\n");
+    let result = complete(&["entity", "merge", "Sarah", "--into", "p"], &temp_db);
+    assert_eq!(result.stdout, "project-alpha\n");
+}
+
+#[test]
+fn test_completion_offers_thought_ids_with_a_preview() {
+    let temp_db = setup_temp_db();
+    run_wet_command(&["add", "First thought"], Some(&temp_db));
+    run_wet_command(&["add", "Second thought about [Sarah]"], Some(&temp_db));
+
+    let result = complete(&["delete", ""], &temp_db);
+    assert_eq!(result.status, 0, "stderr: {}", result.stderr);
+    assert!(
+        result
+            .stdout
+            .starts_with("2\tSecond thought about [Sarah]\n1\tFirst thought\n"),
+        "Got: {}",
+        result.stdout
+    );
+}
+
+#[test]
+fn test_completion_reads_the_notebook_being_completed_for() {
+    let temp_db = setup_temp_db();
+    run_wet_command(&["add", "Lunch with [Sarah]"], Some(&temp_db));
+    run_wet_command(&["notebook", "create", "work"], Some(&temp_db));
+    run_wet_command(&["--notebook", "work", "add", "Standup with [Sam]"], Some(&temp_db));
+
+    let result = complete(&["--notebook", "work", "entity", "show", "S"], &temp_db);
+    assert_eq!(result.status, 0, "stderr: {}", result.stderr);
+    assert_eq!(result.stdout, "Sam\n");
+    let result = complete(&["entity", "show", "--notebook=work", "S"], &temp_db);
+    assert_eq!(result.stdout, "Sam\n");
+    let result = complete(&["entity", "show", "S"], &temp_db);
+    assert_eq!(result.stdout, "Sarah\n");
+}
+
+#[test]
+fn test_completion_without_a_database_offers_nothing() {
+    let temp_db = setup_temp_db();
+    let result = complete(&["entity", "show", "S"], &temp_db);
+    assert_eq!(result.status, 0, "stderr: {}", result.stderr);
+    assert_eq!(result.stdout, "");
+    assert!(!temp_db.path().join("test.db").exists());
+}
@@ -0,0 +1,266 @@
+/// Contract tests for `wet config` command
+#[cfg(unix)]
+use crate::test_helpers::scripted_editor;
+use crate::test_helpers::{run_wet_command, run_wet_command_with_env, setup_temp_db};
+
+fn config_file(temp_db: &tempfile::TempDir) -> String {
+    std::fs::read_to_string(temp_db.path().join("config.toml")).unwrap()
+}
+
+fn listed_line<'a>(stdout: &'a str, key: &str) -> &'a str {
+    stdout
+        .lines()
+        .find(|line| line.starts_with(key))
+        .unwrap_or_else(|| panic!("{key} not listed in: {stdout}"))
+}
+
+#[test]
+fn test_config_list_shows_value_and_source() {
+    let temp_db = setup_temp_db();
+    let result = run_wet_command(&["config", "--list"], Some(&temp_db));
+    assert_eq!(result.status, 0, "stderr: {}", result.stderr);
+    let order = listed_line(&result.stdout, "thoughts.order");
+    assert!(
+        order.contains("descending") && order.ends_with("(default)"),
+        "Got: {order}"
+    );
+    assert_eq!(config_file(&temp_db).trim(), "version = 1");
+
+    run_wet_command(&["config", "thoughts.order", "ascending"], Some(&temp_db));
+    let result = run_wet_command_with_env(
+        &["config", "--list"],
+        Some(&temp_db),
+        None,
+        &[("WETWARE_THOUGHTS_SHOW_TIME", "true")],
+    );
+    let order = listed_line(&result.stdout, "thoughts.order");
+    assert!(order.contains("ascending") && order.ends_with("(file)"), "Got: {order}");
+    let show_time = listed_line(&result.stdout, "thoughts.show_time");
+    assert!(
+        show_time.contains("true") && show_time.ends_with("(env)"),
+        "Got: {show_time}"
+    );
+    let timezone = listed_line(&result.stdout, "thoughts.timezone");
+    assert!(timezone.ends_with("(default)"), "Got: {timezone}");
+}
+
+#[test]
+fn test_config_env_override_applies_to_commands() {
+    let temp_db = setup_temp_db();
+    run_wet_command(&["add", "First"], Some(&temp_db));
+    run_wet_command(&["add", "Second"], Some(&temp_db));
+
+    let result = run_wet_command_with_env(
+        &["thoughts"],
+        Some(&temp_db),
+        None,
+        &[("WETWARE_THOUGHTS_ORDER", "ascending")],
+    );
+    assert!(
+        result.stdout.find("First").unwrap() < result.stdout.find("Second").unwrap(),
+        "Got: {}",
+        result.stdout
+    );
+
+    let result = run_wet_command_with_env(
+        &["thoughts"],
+        Some(&temp_db),
+        None,
+        &[("WETWARE_THOUGHTS_ORDER", "sideways")],
+    );
+    assert_ne!(result.status, 0);
+    assert!(
+        result
+            .stderr
+            .contains("WETWARE_THOUGHTS_ORDER: Invalid value 'sideways'"),
+        "Got: {}",
+        result.stderr
+    );
+}
+
+#[test]
+fn test_config_unset_restores_default() {
+    let temp_db = setup_temp_db();
+    run_wet_command(&["config", "thoughts.show_time", "true"], Some(&temp_db));
+    assert!(config_file(&temp_db).contains("show_time = true"));
+
+    let result = run_wet_command(&["config", "--unset", "thoughts.show_time"], Some(&temp_db));
+    assert_eq!(result.status, 0, "stderr: {}", result.stderr);
+    assert!(
+        result.stdout.contains("now false, the default"),
+        "Got: {}",
+        result.stdout
+    );
+    assert_eq!(config_file(&temp_db).trim(), "version = 1");
+
+    let again = run_wet_command(&["config", "--unset", "thoughts.show_time"], Some(&temp_db));
+    assert!(
+        again.stdout.contains("not set in the config file"),
+        "Got: {}",
+        again.stdout
+    );
+
+    let unknown = run_wet_command(&["config", "--unset", "thoughts.ordr"], Some(&temp_db));
+    assert_ne!(unknown.status, 0);
+    assert!(unknown.stderr.contains("Unknown config key: thoughts.ordr"));
+}
+
+#[test]
+fn test_config_set_rejects_invalid_value_and_keeps_file() {
+    let temp_db = setup_temp_db();
+    run_wet_command(&["config", "thoughts.timezone", "Europe/Prague"], Some(&temp_db));
+    let before = config_file(&temp_db);
+
+    let result = run_wet_command(&["config", "thoughts.order", "sideways"], Some(&temp_db));
+    assert_ne!(result.status, 0);
+    assert!(
+        result.stderr.contains("Valid values: ascending, descending"),
+        "Got: {}",
+        result.stderr
+    );
+    assert_eq!(config_file(&temp_db), before);
+
+    let get = run_wet_command(&["config", "thoughts.timezone"], Some(&temp_db));
+    assert_eq!(get.stdout.trim(), "Europe/Prague");
+}
+
+#[cfg(unix)]
+#[test]
+fn test_config_edit_reopens_on_invalid_file_then_saves() {
+    let temp_db = setup_temp_db();
+    run_wet_command(&["config", "--list"], Some(&temp_db));
+    let editor_dir = tempfile::TempDir::new().unwrap();
+    let editor = scripted_editor(
+        editor_dir.path(),
+        &[
+            "version = 1\n[thoughts]\nordr = \"ascending\"\n",
+            "version = 1\n# newest last\n[thoughts]\norder = \"ascending\"\n",
+        ],
+    );
+
+    let result = run_wet_command_with_env(
+        &["config", "edit"],
+        Some(&temp_db),
+        None,
+        &[("EDITOR", editor.to_str().unwrap())],
+    );
+    assert_eq!(result.status, 0, "stderr: {}", result.stderr);
+    assert!(
+        result.stderr.contains("Unknown config key: thoughts.ordr"),
+        "Got: {}",
+        result.stderr
+    );
+
+    let second = std::fs::read_to_string(editor_dir.path().join("buffer2")).unwrap();
+    assert!(
+        second.starts_with("# wet: Error: Invalid input: Unknown config key: thoughts.ordr\n"),
+        "Got: {second}"
+    );
+    assert!(
+        second.contains("ordr = \"ascending\""),
+        "The rejected text should be kept"
+    );
+    assert_eq!(
+        config_file(&temp_db),
+        "version = 1\n# newest last\n[thoughts]\norder = \"ascending\"\n"
+    );
+}
+
+#[cfg(unix)]
+#[test]
+fn test_config_edit_can_be_abandoned() {
+    let temp_db = setup_temp_db();
+    run_wet_command(&["config", "thoughts.show_time", "true"], Some(&temp_db));
+    let before = config_file(&temp_db);
+    let editor_dir = tempfile::TempDir::new().unwrap();
+    // Invalid TOML, then the error buffer saved unchanged
+    let editor = scripted_editor(editor_dir.path(), &["version = 1\n[thoughts\n"]);
+
+    let result = run_wet_command_with_env(
+        &["config", "edit"],
+        Some(&temp_db),
+        None,
+        &[("EDITOR", editor.to_str().unwrap())],
+    );
+    assert_eq!(result.status, 0, "stderr: {}", result.stderr);
+    assert!(result.stdout.contains("Config not changed"), "Got: {}", result.stdout);
+    assert!(
+        result.stderr.contains("Malformed config file"),
+        "Got: {}",
+        result.stderr
+    );
+    assert_eq!(config_file(&temp_db), before);
+}
+
+#[cfg(unix)]
+#[test]
+fn test_config_edit_and_unset_repair_a_malformed_file() {
+    let temp_db = setup_temp_db();
+    std::fs::write(temp_db.path().join("config.toml"), "version = 1\nthoughts = \"x\"\n").unwrap();
+    let broken = run_wet_command(&["thoughts"], Some(&temp_db));
+    assert_ne!(broken.status, 0);
+
+    let result = run_wet_command(&["config", "thoughts", "--unset"], Some(&temp_db));
+    assert_eq!(result.status, 0, "stderr: {}", result.stderr);
+    assert!(
+        result.stderr.contains("Using the default settings"),
+        "Got: {}",
+        result.stderr
+    );
+    assert_eq!(config_file(&temp_db), "version = 1\n");
+
+    std::fs::write(temp_db.path().join("config.toml"), "version = 1\nthoughts = \"x\"\n").unwrap();
+    let editor_dir = tempfile::TempDir::new().unwrap();
+    let editor = scripted_editor(editor_dir.path(), &["version = 1\n[thoughts]\norder = \"ascending\"\n"]);
+    let result = run_wet_command_with_env(
+        &["config", "edit"],
+        Some(&temp_db),
+        None,
+        &[("EDITOR", editor.to_str().unwrap())],
+    );
+    assert_eq!(result.status, 0, "stderr: {}", result.stderr);
+    assert_eq!(
+        config_file(&temp_db),
+        "version = 1\n[thoughts]\norder = \"ascending\"\n"
+    );
+    let fixed = run_wet_command(&["thoughts"], Some(&temp_db));
+    assert_eq!(fixed.status, 0, "stderr: {}", fixed.stderr);
+}
+
+#[test]
+fn test_config_warns_about_unknown_keys_and_keeps_comments() {
+    let temp_db = setup_temp_db();
+    std::fs::write(
+        temp_db.path().join("config.toml"),
+        "# my settings\nversion = 1\n\n[thoughts]\nordr = \"ascending\" # typo\n",
+    )
+    .unwrap();
+
+    let result = run_wet_command(&["config", "thoughts.show_time", "true"], Some(&temp_db));
+    assert_eq!(result.status, 0, "stderr: {}", result.stderr);
+    assert!(
+        result
+            .stderr
+            .contains("Unknown key 'thoughts.ordr' in config.toml is ignored"),
+        "Got: {}",
+        result.stderr
+    );
+    assert_eq!(
+        config_file(&temp_db),
+        "# my settings\nversion = 1\n\n[thoughts]\nordr = \"ascending\" # typo\nshow_time = true\n"
+    );
+}
+
+#[test]
+fn test_config_from_a_newer_wet_is_refused() {
+    let temp_db = setup_temp_db();
+    std::fs::write(temp_db.path().join("config.toml"), "version = 99\n").unwrap();
+    let result = run_wet_command(&["thoughts"], Some(&temp_db));
+    assert_ne!(result.status, 0);
+    assert!(
+        result.stderr.contains("config.toml is version 99"),
+        "Got: {}",
+        result.stderr
+    );
+    assert_eq!(config_file(&temp_db), "version = 99\n");
+}
@@ -0,0 +1,149 @@
+/// Contract tests for `wet db merge` command
+use crate::test_helpers::{run_wet_command, run_wet_command_with_env, setup_temp_db};
+
+/// Fill a `laptop` notebook next to the test database and return its path
+fn laptop_db(temp_db: &tempfile::TempDir) -> String {
+    run_wet_command(&["notebook", "create", "laptop"], Some(temp_db));
+    let laptop = |args: &[&str]| {
+        let mut full = vec!["--notebook", "laptop"];
+        full.extend_from_slice(args);
+        let result = run_wet_command(&full, Some(temp_db));
+        assert_eq!(result.status, 0, "stderr: {}", result.stderr);
+    };
+    laptop(&["add", "Coffee with [Sarah]", "--date", "2025-03-01"]);
+    laptop(&["add", "[Bob] moved to [Acme]", "--date", "2025-03-02"]);
+    laptop(&["entity", "relate", "bob", "--as", "works-at", "acme"]);
+    laptop(&["entity", "edit", "sarah", "--description", "Engineer"]);
+    temp_db.path().join("laptop.db").to_str().unwrap().to_string()
+}
+
+#[test]
+fn test_db_merge_imports_and_dedupes() {
+    let temp_db = setup_temp_db();
+    run_wet_command(&["add", "Coffee with [Sarah]", "--date", "2025-03-01"], Some(&temp_db));
+    run_wet_command(&["add", "Lunch with [Robert]"], Some(&temp_db));
+    run_wet_command(&["entity", "alias", "robert", "--alias", "Bob"], Some(&temp_db));
+    let other = laptop_db(&temp_db);
+
+    let result = run_wet_command(&["db", "merge", &other], Some(&temp_db));
+    assert_eq!(result.status, 0, "stderr: {}", result.stderr);
+    assert!(
+        result.stdout.contains("Imported 1 thought(s); skipped 1 duplicate(s)."),
+        "Got: {}",
+        result.stdout
+    );
+
+    let robert = run_wet_command(&["thoughts", "--on", "robert"], Some(&temp_db));
+    assert!(robert.stdout.contains("Bob moved to Acme"), "Got: {}", robert.stdout);
+    let show = run_wet_command(&["entity", "show", "robert"], Some(&temp_db));
+    assert!(show.stdout.contains("works-at"), "Got: {}", show.stdout);
+    let sarah = run_wet_command(&["entity", "show", "sarah"], Some(&temp_db));
+    assert!(sarah.stdout.contains("Engineer"), "Got: {}", sarah.stdout);
+
+    let again = run_wet_command(&["db", "merge", &other], Some(&temp_db));
+    assert!(
+        again.stdout.contains("Imported 0 thought(s); skipped 2 duplicate(s)."),
+        "Got: {}",
+        again.stdout
+    );
+}
+
+#[test]
+fn test_db_merge_description_conflicts() {
+    let temp_db = setup_temp_db();
+    run_wet_command(&["add", "Met [Sarah]"], Some(&temp_db));
+    run_wet_command(
+        &["entity", "edit", "sarah", "--description", "Designer"],
+        Some(&temp_db),
+    );
+    let other = laptop_db(&temp_db);
+
+    let result = run_wet_command(&["db", "merge", &other], Some(&temp_db));
+    assert_ne!(result.status, 0);
+    assert!(
+        result
+            .stderr
+            .contains("Descriptions differ between the databases for Sarah"),
+        "Got: {}",
+        result.stderr
+    );
+    let thoughts = run_wet_command(&["thoughts"], Some(&temp_db));
+    assert!(
+        !thoughts.stdout.contains("Coffee"),
+        "Nothing imported. Got: {}",
+        thoughts.stdout
+    );
+
+    let result = run_wet_command(
+        &["db", "merge", &other, "--descriptions", "append", "--dry-run"],
+        Some(&temp_db),
+    );
+    assert_eq!(result.status, 0, "stderr: {}", result.stderr);
+    assert!(result.stdout.contains("Dry run"), "Got: {}", result.stdout);
+    let thoughts = run_wet_command(&["thoughts"], Some(&temp_db));
+    assert!(!thoughts.stdout.contains("Coffee"), "Got: {}", thoughts.stdout);
+
+    let result = run_wet_command(&["db", "merge", &other, "--descriptions", "append"], Some(&temp_db));
+    assert!(
+        result
+            .stdout
+            .contains("Descriptions differed for Sarah (appended the other database's)"),
+        "Got: {}",
+        result.stdout
+    );
+    let sarah = run_wet_command(&["entity", "show", "sarah"], Some(&temp_db));
+    assert!(
+        sarah.stdout.contains("Designer") && sarah.stdout.contains("Engineer"),
+        "Got: {}",
+        sarah.stdout
+    );
+}
+
+#[test]
+fn test_db_merge_rejects_missing_and_same_database() {
+    let temp_db = setup_temp_db();
+    run_wet_command(&["add", "Something"], Some(&temp_db));
+
+    let result = run_wet_command(&["db", "merge", "/no/such/file.db"], Some(&temp_db));
+    assert_ne!(result.status, 0);
+    assert!(result.stderr.contains("does not exist"), "Got: {}", result.stderr);
+
+    let own = temp_db.path().join("test.db");
+    let result = run_wet_command(&["db", "merge", own.to_str().unwrap()], Some(&temp_db));
+    assert_ne!(result.status, 0);
+    assert!(result.stderr.contains("into itself"), "Got: {}", result.stderr);
+}
+
+#[test]
+fn test_db_merge_never_writes_the_other_database() {
+    let temp_db = setup_temp_db();
+    run_wet_command(&["add", "Something"], Some(&temp_db));
+    let other = laptop_db(&temp_db);
+    // A legacy backdated thought the other database has not been migrated for yet
+    let conn = rusqlite::Connection::open(&other).unwrap();
+    conn.execute_batch(
+        "INSERT INTO thoughts (content, created_at) VALUES ('Old backdated', '2025-03-10T00:00:00+00:00');
+         PRAGMA user_version = 0;",
+    )
+    .unwrap();
+    drop(conn);
+    let before = std::fs::read(&other).unwrap();
+
+    let env = [("WETWARE_THOUGHTS_TIMEZONE", "America/Los_Angeles")];
+    for args in [vec!["db", "merge", &other, "--dry-run"], vec!["db", "merge", &other]] {
+        let result = run_wet_command_with_env(&args, Some(&temp_db), None, &env);
+        assert_eq!(result.status, 0, "stderr: {}", result.stderr);
+        assert_eq!(std::fs::read(&other).unwrap(), before, "{args:?} changed the file");
+        let conn = rusqlite::Connection::open_with_flags(&other, rusqlite::OpenFlags::SQLITE_OPEN_READ_ONLY).unwrap();
+        let version: i64 = conn.query_row("PRAGMA user_version", [], |row| row.get(0)).unwrap();
+        assert_eq!(version, 0, "{args:?} migrated the file");
+    }
+
+    // The copy was migrated in the configured zone
+    let result = run_wet_command_with_env(&["thoughts"], Some(&temp_db), None, &env);
+    assert!(
+        result.stdout.contains("2025-03-10 - Old backdated"),
+        "Got: {}",
+        result.stdout
+    );
+}
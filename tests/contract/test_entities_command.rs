@@ -280,3 +280,55 @@ fn test_entities_preview_entity_references_as_plain_text() {
         rust_line
     );
 }
+
+#[test]
+fn test_entities_filter_by_kind() {
+    let temp_db = setup_temp_db();
+    run_wet_command(&["add", "[Sarah] moved to [Lisbon]"], Some(&temp_db));
+    run_wet_command(&["entity", "edit", "sarah", "--kind", "person"], Some(&temp_db));
+    run_wet_command(&["entity", "edit", "lisbon", "--kind", "place"], Some(&temp_db));
+
+    let result = run_wet_command(&["entities", "--kind", "person"], Some(&temp_db));
+    assert_eq!(result.status, 0, "stderr: {}", result.stderr);
+    assert!(result.stdout.contains("Sarah (person)"), "Got: {}", result.stdout);
+    assert!(!result.stdout.contains("Lisbon"), "Got: {}", result.stdout);
+
+    let none = run_wet_command(&["entities", "--kind", "event"], Some(&temp_db));
+    assert!(none.stdout.contains("No matching entities found."));
+}
+
+#[test]
+fn test_entities_tree_shows_hierarchy_counts_and_multiple_parents() {
+    let temp_db = setup_temp_db();
+    run_wet_command(
+        &["add", "[Amazon] bought [AWS] and [Google] uses [Kubernetes]"],
+        Some(&temp_db),
+    );
+    run_wet_command(&["add", "[AWS] outage"], Some(&temp_db));
+    run_wet_command(&["entity", "relate", "aws", "--parent", "amazon"], Some(&temp_db));
+    run_wet_command(&["entity", "relate", "kubernetes", "--parent", "aws"], Some(&temp_db));
+    run_wet_command(
+        &["entity", "relate", "kubernetes", "--parent", "google"],
+        Some(&temp_db),
+    );
+
+    let result = run_wet_command(&["entities", "--tree"], Some(&temp_db));
+    assert_eq!(result.status, 0, "stderr: {}", result.stderr);
+
+    let expected = "\
+Amazon (1 thought)
+└── AWS (2 thoughts)
+    └── Kubernetes (1 thought) [also under: Google]
+Google (1 thought)
+└── Kubernetes (1 thought) [also under: AWS]
+";
+    assert_eq!(result.stdout, expected);
+}
+
+#[test]
+fn test_entities_tree_conflicts_with_filters() {
+    let temp_db = setup_temp_db();
+
+    let result = run_wet_command(&["entities", "--tree", "--kind", "person"], Some(&temp_db));
+    assert_ne!(result.status, 0);
+}
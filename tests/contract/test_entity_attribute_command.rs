@@ -0,0 +1,81 @@
+/// Contract tests for `wet entity set` / `wet entity unset` commands
+use crate::test_helpers::{run_wet_command, setup_temp_db};
+
+#[test]
+fn test_entity_set_shows_in_entity_show_and_json() {
+    let temp_db = setup_temp_db();
+    run_wet_command(&["add", "Coffee with [Sarah]"], Some(&temp_db));
+
+    let result = run_wet_command(
+        &["entity", "set", "sarah", "email=sarah@example.com", "Status=active"],
+        Some(&temp_db),
+    );
+    assert_eq!(result.status, 0, "Command should succeed. stderr: {}", result.stderr);
+    assert!(
+        result.stdout.contains("Set status = active on 'Sarah'."),
+        "Got: {}",
+        result.stdout
+    );
+
+    let show = run_wet_command(&["entity", "show", "sarah"], Some(&temp_db));
+    assert!(show.stdout.contains("Attributes:"), "Got: {}", show.stdout);
+    assert!(
+        show.stdout.contains("  email: sarah@example.com"),
+        "Got: {}",
+        show.stdout
+    );
+    assert!(show.stdout.contains("  status: active"), "Got: {}", show.stdout);
+
+    let json = run_wet_command(&["thought", "show", "1", "--json"], Some(&temp_db));
+    assert!(json.stdout.contains("\"status\": \"active\""), "Got: {}", json.stdout);
+}
+
+#[test]
+fn test_entity_unset_removes_attribute() {
+    let temp_db = setup_temp_db();
+    run_wet_command(&["add", "Coffee with [Sarah]"], Some(&temp_db));
+    run_wet_command(&["entity", "set", "sarah", "status=active"], Some(&temp_db));
+
+    let result = run_wet_command(&["entity", "unset", "sarah", "status"], Some(&temp_db));
+    assert_eq!(result.status, 0, "stderr: {}", result.stderr);
+    assert!(result.stdout.contains("Removed status from 'Sarah'."));
+
+    let show = run_wet_command(&["entity", "show", "sarah"], Some(&temp_db));
+    assert!(!show.stdout.contains("Attributes:"), "Got: {}", show.stdout);
+}
+
+#[test]
+fn test_entity_set_malformed_assignment_changes_nothing() {
+    let temp_db = setup_temp_db();
+    run_wet_command(&["add", "Coffee with [Sarah]"], Some(&temp_db));
+
+    let result = run_wet_command(
+        &["entity", "set", "sarah", "email=s@example.com", "status"],
+        Some(&temp_db),
+    );
+    assert_ne!(result.status, 0);
+    assert!(result.stderr.contains("Expected key=value"), "Got: {}", result.stderr);
+
+    let show = run_wet_command(&["entity", "show", "sarah"], Some(&temp_db));
+    assert!(!show.stdout.contains("email"), "Got: {}", show.stdout);
+}
+
+#[test]
+fn test_entities_filter_by_kind_and_attribute() {
+    let temp_db = setup_temp_db();
+    run_wet_command(&["add", "[Sarah], [John] and [Acme]"], Some(&temp_db));
+    run_wet_command(&["entity", "edit", "sarah", "--kind", "person"], Some(&temp_db));
+    run_wet_command(&["entity", "edit", "john", "--kind", "person"], Some(&temp_db));
+    run_wet_command(&["entity", "set", "sarah", "status=active"], Some(&temp_db));
+    run_wet_command(&["entity", "set", "john", "status=retired"], Some(&temp_db));
+    run_wet_command(&["entity", "set", "acme", "status=active"], Some(&temp_db));
+
+    let result = run_wet_command(
+        &["entities", "--kind", "person", "--attr", "status=active"],
+        Some(&temp_db),
+    );
+    assert_eq!(result.status, 0, "stderr: {}", result.stderr);
+    assert!(result.stdout.contains("Sarah"), "Got: {}", result.stdout);
+    assert!(!result.stdout.contains("John"), "Got: {}", result.stdout);
+    assert!(!result.stdout.contains("Acme"), "Got: {}", result.stdout);
+}
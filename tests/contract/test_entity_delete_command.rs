@@ -0,0 +1,105 @@
+/// Contract tests for `wet entity delete` command
+use crate::test_helpers::{run_wet_command, run_wet_command_with_stdin, setup_temp_db};
+
+#[test]
+fn test_entity_delete_not_found() {
+    let temp_db = setup_temp_db();
+
+    let result = run_wet_command(&["entity", "delete", "nonexistent", "--yes"], Some(&temp_db));
+
+    assert_ne!(result.status, 0, "Command should fail for nonexistent entity");
+    assert!(result.stderr.contains("not found"), "Got: {}", result.stderr);
+}
+
+#[test]
+fn test_entity_delete_refuses_referenced_entity_by_default() {
+    let temp_db = setup_temp_db();
+
+    run_wet_command(&["add", "Coffee with [Sara]"], Some(&temp_db));
+
+    let result = run_wet_command(&["entity", "delete", "sara", "--yes"], Some(&temp_db));
+
+    assert_ne!(result.status, 0, "Delete should be refused");
+    assert!(
+        result.stderr.contains("still referenced by 1 thought(s)"),
+        "Got: {}",
+        result.stderr
+    );
+
+    let entities = run_wet_command(&["entities"], Some(&temp_db));
+    assert!(
+        entities.stdout.contains("Sara"),
+        "Entity should remain. Got: {}",
+        entities.stdout
+    );
+}
+
+#[test]
+fn test_entity_delete_dry_run_changes_nothing() {
+    let temp_db = setup_temp_db();
+
+    run_wet_command(&["add", "Coffee with [Sara]"], Some(&temp_db));
+
+    let result = run_wet_command(
+        &["entity", "delete", "sara", "--references", "strip", "--dry-run"],
+        Some(&temp_db),
+    );
+
+    assert_eq!(result.status, 0, "Dry run should succeed. Stderr: {}", result.stderr);
+    assert!(result.stdout.contains("1 linked thought(s)"), "Got: {}", result.stdout);
+    assert!(
+        result.stdout.contains("stripped to plain text"),
+        "Got: {}",
+        result.stdout
+    );
+    assert!(result.stdout.contains("Dry run"), "Got: {}", result.stdout);
+
+    let thoughts = run_wet_command(&["thoughts", "--on", "sara"], Some(&temp_db));
+    assert!(thoughts.stdout.contains("Coffee with Sara"), "Got: {}", thoughts.stdout);
+}
+
+#[test]
+fn test_entity_delete_strip_removes_entity_and_markup() {
+    let temp_db = setup_temp_db();
+
+    run_wet_command(&["add", "Coffee with [Sara] and [Bob]"], Some(&temp_db));
+
+    let result = run_wet_command(
+        &["entity", "delete", "sara", "--references", "strip", "--yes"],
+        Some(&temp_db),
+    );
+    assert_eq!(result.status, 0, "Delete should succeed. Stderr: {}", result.stderr);
+    assert!(
+        result.stdout.contains("Entity 'Sara' deleted."),
+        "Got: {}",
+        result.stdout
+    );
+
+    let show = run_wet_command(&["thought", "show", "1", "--json"], Some(&temp_db));
+    assert!(
+        show.stdout.contains("\"content\": \"Coffee with Sara and [Bob]\""),
+        "Got: {}",
+        show.stdout
+    );
+
+    let entities = run_wet_command(&["entities"], Some(&temp_db));
+    assert!(!entities.stdout.contains("Sara"), "Got: {}", entities.stdout);
+}
+
+#[test]
+fn test_entity_delete_prompt_declined_keeps_entity() {
+    let temp_db = setup_temp_db();
+
+    run_wet_command(&["add", "Coffee with [Sara]"], Some(&temp_db));
+
+    let result = run_wet_command_with_stdin(
+        &["entity", "delete", "sara", "--references", "dangle"],
+        Some(&temp_db),
+        Some("n\n"),
+    );
+    assert_eq!(result.status, 0, "Declining should succeed. Stderr: {}", result.stderr);
+    assert!(result.stdout.contains("Aborted"), "Got: {}", result.stdout);
+
+    let entities = run_wet_command(&["entities"], Some(&temp_db));
+    assert!(entities.stdout.contains("Sara"), "Got: {}", entities.stdout);
+}
@@ -0,0 +1,69 @@
+/// Contract tests for `wet entity duplicates` command and the `wet add` near-duplicate warning
+use crate::test_helpers::{run_wet_command, setup_temp_db};
+
+#[test]
+fn test_entity_duplicates_none_found() {
+    let temp_db = setup_temp_db();
+
+    run_wet_command(&["add", "[rust] and [python]"], Some(&temp_db));
+
+    let result = run_wet_command(&["entity", "duplicates"], Some(&temp_db));
+
+    assert_eq!(result.status, 0, "Command should succeed");
+    assert!(
+        result.stdout.contains("No likely duplicate entities found."),
+        "Got: {}",
+        result.stdout
+    );
+}
+
+#[test]
+fn test_entity_duplicates_lists_pair_with_counts_and_suggestions() {
+    let temp_db = setup_temp_db();
+
+    run_wet_command(&["add", "Lunch with [Sarah]"], Some(&temp_db));
+    run_wet_command(&["add", "Call with [Sarah]"], Some(&temp_db));
+    run_wet_command(&["add", "Coffee with [Sara]"], Some(&temp_db));
+
+    let result = run_wet_command(&["entity", "duplicates"], Some(&temp_db));
+
+    assert_eq!(result.status, 0, "Command should succeed");
+    assert!(
+        result.stdout.contains("Sara (1 thought) ~ Sarah (2 thoughts)"),
+        "Got: {}",
+        result.stdout
+    );
+    assert!(
+        result.stdout.contains("wet entity merge \"Sara\" --into \"Sarah\""),
+        "Got: {}",
+        result.stdout
+    );
+    assert!(
+        result
+            .stdout
+            .contains("  then, to keep \"Sara\" resolving: wet entity alias \"Sarah\" --alias \"Sara\""),
+        "Got: {}",
+        result.stdout
+    );
+}
+
+#[test]
+fn test_add_warns_about_near_duplicate_new_entity() {
+    let temp_db = setup_temp_db();
+
+    run_wet_command(&["add", "Kicked off [project-alpha]"], Some(&temp_db));
+    let result = run_wet_command(&["add", "Planning [project alpha]"], Some(&temp_db));
+
+    assert_eq!(result.status, 0, "Add should still succeed");
+    assert!(
+        result
+            .stderr
+            .contains("Warning: new entity 'project alpha' looks similar to existing 'project-alpha'"),
+        "Got: {}",
+        result.stderr
+    );
+
+    // Referencing the existing entity again does not warn
+    let again = run_wet_command(&["add", "More on [Project-Alpha]"], Some(&temp_db));
+    assert!(!again.stderr.contains("Warning"), "Got: {}", again.stderr);
+}
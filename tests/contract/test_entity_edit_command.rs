@@ -297,3 +297,32 @@ fn test_entity_edit_update_existing_description() {
         "Should show success message"
     );
 }
+
+#[test]
+fn test_entity_edit_kind_sets_and_clears_without_editor() {
+    let temp_db = setup_temp_db();
+    run_wet_command(&["add", "Coffee with [Sarah]"], Some(&temp_db));
+
+    // No description flags: only the kind changes, and no editor is launched
+    let result = run_wet_command(&["entity", "edit", "sarah", "--kind", "Person"], Some(&temp_db));
+    assert_eq!(result.status, 0, "Command should succeed. stderr: {}", result.stderr);
+    assert!(result.stdout.contains("Kind set to 'person' for entity 'sarah'"));
+
+    let show = run_wet_command(&["entity", "show", "sarah"], Some(&temp_db));
+    assert!(show.stdout.starts_with("Sarah (person)"), "Got: {}", show.stdout);
+
+    let cleared = run_wet_command(&["entity", "edit", "sarah", "--kind", "none"], Some(&temp_db));
+    assert_eq!(cleared.status, 0, "stderr: {}", cleared.stderr);
+    let show = run_wet_command(&["entity", "show", "sarah"], Some(&temp_db));
+    assert!(!show.stdout.contains("(person)"), "Got: {}", show.stdout);
+}
+
+#[test]
+fn test_entity_edit_invalid_kind_fails() {
+    let temp_db = setup_temp_db();
+    run_wet_command(&["add", "Walked the [dog]"], Some(&temp_db));
+
+    let result = run_wet_command(&["entity", "edit", "dog", "--kind", "animal"], Some(&temp_db));
+    assert_ne!(result.status, 0);
+    assert!(result.stderr.contains("Invalid entity kind"), "Got: {}", result.stderr);
+}
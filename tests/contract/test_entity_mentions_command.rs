@@ -0,0 +1,93 @@
+/// Contract tests for `wet entity mentions` command
+use crate::test_helpers::{run_wet_command, run_wet_command_with_stdin, setup_temp_db};
+
+#[test]
+fn test_entity_mentions_entity_not_found() {
+    let temp_db = setup_temp_db();
+
+    let result = run_wet_command(&["entity", "mentions", "nonexistent"], Some(&temp_db));
+
+    assert_ne!(result.status, 0, "Command should fail for nonexistent entity");
+    assert!(result.stderr.contains("not found"), "Got: {}", result.stderr);
+}
+
+#[test]
+fn test_entity_mentions_lists_plain_mentions_only() {
+    let temp_db = setup_temp_db();
+
+    run_wet_command(&["add", "Started with [Rust]"], Some(&temp_db));
+    run_wet_command(&["add", "More rust practice today"], Some(&temp_db));
+    run_wet_command(&["add", "Nothing to see"], Some(&temp_db));
+
+    let result = run_wet_command(&["entity", "mentions", "rust"], Some(&temp_db));
+
+    assert_eq!(result.status, 0, "Command should succeed. Stderr: {}", result.stderr);
+    assert!(
+        result.stdout.contains("[2] "),
+        "Should list the unlinked thought. Got: {}",
+        result.stdout
+    );
+    assert!(
+        !result.stdout.contains("[1] ") && !result.stdout.contains("[3] "),
+        "Should not list linked or unrelated thoughts. Got: {}",
+        result.stdout
+    );
+
+    // Listing alone changes nothing
+    let on = run_wet_command(&["thoughts", "--on", "rust"], Some(&temp_db));
+    assert!(!on.stdout.contains("More rust practice"), "Got: {}", on.stdout);
+}
+
+#[test]
+fn test_entity_mentions_link_yes_links_alias_mentions() {
+    let temp_db = setup_temp_db();
+
+    run_wet_command(&["add", "Reading about [machine-learning]"], Some(&temp_db));
+    run_wet_command(
+        &["entity", "alias", "machine-learning", "--alias", "ML"],
+        Some(&temp_db),
+    );
+    run_wet_command(&["add", "An ML paper"], Some(&temp_db));
+
+    let result = run_wet_command(&["entity", "mentions", "ml", "--link", "--yes"], Some(&temp_db));
+    assert_eq!(result.status, 0, "Command should succeed. Stderr: {}", result.stderr);
+    assert!(result.stdout.contains("1 thought(s)"), "Got: {}", result.stdout);
+
+    let on = run_wet_command(&["thoughts", "--on", "machine-learning"], Some(&temp_db));
+    assert!(
+        on.stdout.contains("An ML paper"),
+        "Should now be linked. Got: {}",
+        on.stdout
+    );
+
+    let again = run_wet_command(&["entity", "mentions", "machine-learning"], Some(&temp_db));
+    assert!(again.stdout.contains("No unlinked mentions"), "Got: {}", again.stdout);
+}
+
+#[test]
+fn test_entity_mentions_link_prompts_per_thought() {
+    let temp_db = setup_temp_db();
+
+    run_wet_command(&["add", "[Sarah] joined"], Some(&temp_db));
+    run_wet_command(&["add", "sarah called"], Some(&temp_db));
+    run_wet_command(&["add", "emailed Sarah"], Some(&temp_db));
+
+    let result = run_wet_command_with_stdin(
+        &["entity", "mentions", "sarah", "--link"],
+        Some(&temp_db),
+        Some("n\ny\n"),
+    );
+    assert_eq!(result.status, 0, "Command should succeed. Stderr: {}", result.stderr);
+
+    let on = run_wet_command(&["thoughts", "--on", "sarah"], Some(&temp_db));
+    assert!(
+        !on.stdout.contains("sarah called"),
+        "Declined thought stays unlinked. Got: {}",
+        on.stdout
+    );
+    assert!(
+        on.stdout.contains("emailed Sarah"),
+        "Accepted thought is linked. Got: {}",
+        on.stdout
+    );
+}
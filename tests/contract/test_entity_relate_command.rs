@@ -123,3 +123,128 @@ fn test_entity_show_lists_parents_and_children() {
         aws_result.stdout
     );
 }
+
+#[test]
+fn test_entity_relate_as_typed_relation_shown_from_both_ends() {
+    let temp_db = setup_temp_db();
+
+    run_wet_command(&["add", "[Alice] joined [Acme]"], Some(&temp_db));
+
+    let result = run_wet_command(
+        &["entity", "relate", "alice", "--as", "works-at", "acme"],
+        Some(&temp_db),
+    );
+    assert_eq!(result.status, 0, "Command should succeed. stderr: {}", result.stderr);
+    assert!(
+        result.stdout.contains("Recorded: Alice works-at Acme."),
+        "Should confirm the relation. Got: {}",
+        result.stdout
+    );
+
+    let alice = run_wet_command(&["entity", "show", "Alice"], Some(&temp_db));
+    assert!(
+        alice.stdout.contains("works-at: Acme"),
+        "Source side should read with the type name. Got: {}",
+        alice.stdout
+    );
+
+    let acme = run_wet_command(&["entity", "show", "Acme"], Some(&temp_db));
+    assert!(
+        acme.stdout.contains("employs: Alice"),
+        "Target side should read with the inverse. Got: {}",
+        acme.stdout
+    );
+    assert!(
+        !acme.stdout.contains("Children:"),
+        "Typed relations are not part of the hierarchy. Got: {}",
+        acme.stdout
+    );
+}
+
+#[test]
+fn test_entity_relate_as_existing_relation_says_so() {
+    let temp_db = setup_temp_db();
+
+    run_wet_command(&["add", "[Alice] met [Bob] at [Acme]"], Some(&temp_db));
+    run_wet_command(
+        &["entity", "relate", "alice", "--as", "works-at", "acme"],
+        Some(&temp_db),
+    );
+    run_wet_command(
+        &["entity", "relate", "alice", "--as", "related-to", "bob"],
+        Some(&temp_db),
+    );
+
+    for args in [
+        ["entity", "relate", "alice", "--as", "works-at", "acme"],
+        // Undirected, and already stored the other way round
+        ["entity", "relate", "bob", "--as", "related-to", "alice"],
+    ] {
+        let result = run_wet_command(&args, Some(&temp_db));
+        assert_eq!(result.status, 0, "Command should succeed. stderr: {}", result.stderr);
+        assert!(
+            result.stdout.starts_with("Already exists: "),
+            "Should not claim to record it again. Got: {}",
+            result.stdout
+        );
+    }
+}
+
+#[test]
+fn test_entity_relate_as_unknown_type_errors() {
+    let temp_db = setup_temp_db();
+
+    run_wet_command(&["add", "[Alice] and [Bob]"], Some(&temp_db));
+
+    let result = run_wet_command(&["entity", "relate", "alice", "--as", "mentors", "bob"], Some(&temp_db));
+
+    assert_ne!(result.status, 0, "Command should fail");
+    assert!(
+        result.stderr.contains("Unknown relation type 'mentors'"),
+        "Should name the unknown type. Got: {}",
+        result.stderr
+    );
+}
+
+#[test]
+fn test_entity_relate_as_configured_type() {
+    let temp_db = setup_temp_db();
+    std::fs::write(
+        temp_db.path().join("config.toml"),
+        "version = 1\n\n[[relations.types]]\nname = \"mentors\"\ninverse = \"mentored-by\"\n",
+    )
+    .unwrap();
+
+    run_wet_command(&["add", "[Alice] and [Bob]"], Some(&temp_db));
+
+    let result = run_wet_command(&["entity", "relate", "alice", "--as", "mentors", "bob"], Some(&temp_db));
+    assert_eq!(result.status, 0, "Command should succeed. stderr: {}", result.stderr);
+
+    let bob = run_wet_command(&["entity", "show", "Bob"], Some(&temp_db));
+    assert!(
+        bob.stdout.contains("mentored-by: Alice"),
+        "Configured inverse should label the target side. Got: {}",
+        bob.stdout
+    );
+
+    let result = run_wet_command(
+        &["entity", "unrelate", "alice", "--as", "mentors", "bob"],
+        Some(&temp_db),
+    );
+    assert_eq!(result.status, 0, "Unrelate should succeed. stderr: {}", result.stderr);
+    let bob = run_wet_command(&["entity", "show", "Bob"], Some(&temp_db));
+    assert!(
+        !bob.stdout.contains("Relations:"),
+        "Relation should be gone. Got: {}",
+        bob.stdout
+    );
+}
+
+#[test]
+fn test_entity_relate_requires_parent_or_type() {
+    let temp_db = setup_temp_db();
+
+    let result = run_wet_command(&["entity", "relate", "alice"], Some(&temp_db));
+
+    assert_ne!(result.status, 0, "Command should fail without --parent or --as");
+}
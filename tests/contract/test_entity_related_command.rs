@@ -0,0 +1,80 @@
+/// Contract tests for `wet entity related` command
+use crate::test_helpers::{run_wet_command, setup_temp_db};
+
+fn setup_meetings() -> tempfile::TempDir {
+    let temp_db = setup_temp_db();
+    run_wet_command(
+        &["add", "[Alice] and [Bob] planned [Apollo]", "--date", "2026-01-10"],
+        Some(&temp_db),
+    );
+    run_wet_command(
+        &["add", "[Alice] reviewed [Apollo]", "--date", "2026-02-10"],
+        Some(&temp_db),
+    );
+    run_wet_command(&["add", "[Bob] on holiday", "--date", "2026-02-11"], Some(&temp_db));
+    run_wet_command(&["add", "[Carol] met [Bob]", "--date", "2026-03-01"], Some(&temp_db));
+    temp_db
+}
+
+#[test]
+fn test_entity_related_ranks_by_jaccard() {
+    let temp_db = setup_meetings();
+
+    let result = run_wet_command(&["entity", "related", "apollo"], Some(&temp_db));
+    assert_eq!(result.status, 0, "stderr: {}", result.stderr);
+    assert_eq!(
+        result.stdout,
+        "Related to Apollo (jaccard):\n  Alice (2 shared thoughts, score 1.00)\n  Bob (1 shared thought, score 0.25)\n"
+    );
+}
+
+#[test]
+fn test_entity_related_time_window_and_pmi() {
+    let temp_db = setup_meetings();
+
+    let result = run_wet_command(
+        &["entity", "related", "bob", "--metric", "pmi", "--since", "2026-02-01"],
+        Some(&temp_db),
+    );
+    assert_eq!(result.status, 0, "stderr: {}", result.stderr);
+    // Three thoughts in the window; Bob is in two of them, Carol in one, together in one
+    assert_eq!(
+        result.stdout,
+        "Related to Bob (pmi):\n  Carol (1 shared thought, score 0.58)\n"
+    );
+}
+
+#[test]
+fn test_entity_related_nothing_shared() {
+    let temp_db = setup_meetings();
+
+    let result = run_wet_command(&["entity", "related", "carol", "--until", "2026-02-28"], Some(&temp_db));
+    assert_eq!(result.status, 0, "stderr: {}", result.stderr);
+    assert!(
+        result.stdout.contains("No entities share a thought with 'Carol'."),
+        "Got: {}",
+        result.stdout
+    );
+}
+
+#[test]
+fn test_entity_related_invalid_date_fails() {
+    let temp_db = setup_meetings();
+
+    let result = run_wet_command(&["entity", "related", "bob", "--since", "last week"], Some(&temp_db));
+    assert_ne!(result.status, 0);
+    assert!(result.stderr.contains("Invalid --since date"), "Got: {}", result.stderr);
+}
+
+#[test]
+fn test_entity_show_lists_related_entities() {
+    let temp_db = setup_meetings();
+
+    let result = run_wet_command(&["entity", "show", "apollo"], Some(&temp_db));
+    assert_eq!(result.status, 0, "stderr: {}", result.stderr);
+    assert!(
+        result.stdout.contains("Related: Alice (2), Bob (1)"),
+        "Got: {}",
+        result.stdout
+    );
+}
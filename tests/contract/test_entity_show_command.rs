@@ -171,3 +171,115 @@ fn test_entity_show_color_always_styles_description_and_thoughts_consistently()
         result.stdout
     );
 }
+
+/// Amazon with child AWS, which has child S3; one thought each, a month apart
+fn setup_hierarchy() -> tempfile::TempDir {
+    let temp_db = setup_temp_db();
+    run_wet_command(
+        &["add", "Thinking about [Amazon]", "--date", "2026-01-01"],
+        Some(&temp_db),
+    );
+    run_wet_command(&["add", "[AWS] bill went up", "--date", "2026-02-01"], Some(&temp_db));
+    run_wet_command(
+        &["add", "[S3] and [AWS] outage", "--date", "2026-03-01"],
+        Some(&temp_db),
+    );
+    run_wet_command(&["entity", "relate", "AWS", "--parent", "Amazon"], Some(&temp_db));
+    run_wet_command(&["entity", "relate", "S3", "--parent", "AWS"], Some(&temp_db));
+    temp_db
+}
+
+#[test]
+fn test_entity_show_marks_thoughts_reached_through_descendants() {
+    let temp_db = setup_hierarchy();
+
+    let result = run_wet_command(&["entity", "show", "amazon"], Some(&temp_db));
+
+    assert_eq!(result.status, 0, "Command should succeed. Stderr: {}", result.stderr);
+    assert!(
+        result.stdout.contains("S3 and AWS outage (via AWS, S3)"),
+        "Got: {}",
+        result.stdout
+    );
+    assert!(
+        result.stdout.contains("AWS bill went up (via AWS)"),
+        "Got: {}",
+        result.stdout
+    );
+    assert!(
+        result.stdout.contains("Thinking about Amazon\n"),
+        "Direct thoughts carry no via marker. Got: {}",
+        result.stdout
+    );
+}
+
+#[test]
+fn test_entity_show_limit_reports_hidden_thoughts() {
+    let temp_db = setup_hierarchy();
+
+    let result = run_wet_command(&["entity", "show", "amazon", "--limit", "1"], Some(&temp_db));
+
+    assert_eq!(result.status, 0, "Command should succeed. Stderr: {}", result.stderr);
+    assert!(result.stdout.contains("S3 and AWS outage"), "Got: {}", result.stdout);
+    assert!(!result.stdout.contains("AWS bill went up"), "Got: {}", result.stdout);
+    assert!(
+        result.stdout.contains("Showing 1 of 3 thoughts"),
+        "Got: {}",
+        result.stdout
+    );
+
+    let result = run_wet_command(&["entity", "show", "amazon", "--all"], Some(&temp_db));
+    assert!(result.stdout.contains("Thoughts:"), "Got: {}", result.stdout);
+    assert!(
+        result.stdout.contains("Thinking about Amazon"),
+        "Got: {}",
+        result.stdout
+    );
+    assert!(!result.stdout.contains("Showing"), "Got: {}", result.stdout);
+}
+
+#[test]
+fn test_entity_show_date_range_and_no_descendants() {
+    let temp_db = setup_hierarchy();
+
+    let result = run_wet_command(
+        &[
+            "entity",
+            "show",
+            "amazon",
+            "--since",
+            "2026-02-01",
+            "--until",
+            "2026-02-28",
+        ],
+        Some(&temp_db),
+    );
+    assert_eq!(result.status, 0, "Command should succeed. Stderr: {}", result.stderr);
+    assert!(result.stdout.contains("AWS bill went up"), "Got: {}", result.stdout);
+    assert!(!result.stdout.contains("outage"), "Got: {}", result.stdout);
+    assert!(
+        !result.stdout.contains("Thinking about Amazon"),
+        "Got: {}",
+        result.stdout
+    );
+
+    let result = run_wet_command(&["entity", "show", "amazon", "--no-descendants"], Some(&temp_db));
+    assert!(
+        result.stdout.contains("Thinking about Amazon"),
+        "Got: {}",
+        result.stdout
+    );
+    assert!(!result.stdout.contains("AWS bill"), "Got: {}", result.stdout);
+}
+
+#[test]
+fn test_entity_show_rejects_invalid_date_and_all_with_limit() {
+    let temp_db = setup_hierarchy();
+
+    let result = run_wet_command(&["entity", "show", "amazon", "--since", "soon"], Some(&temp_db));
+    assert_ne!(result.status, 0);
+    assert!(result.stderr.contains("--since"), "Got: {}", result.stderr);
+
+    let result = run_wet_command(&["entity", "show", "amazon", "--all", "--limit", "3"], Some(&temp_db));
+    assert_ne!(result.status, 0, "--all and --limit should conflict");
+}
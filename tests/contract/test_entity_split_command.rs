@@ -0,0 +1,166 @@
+/// Contract tests for `wet entity split` command
+use crate::test_helpers::{run_wet_command, run_wet_command_with_stdin, setup_temp_db};
+
+#[test]
+fn test_entity_split_by_co_mentioned_entity() {
+    let temp_db = setup_temp_db();
+
+    run_wet_command(&["add", "[Alex] shipped the [Acme] release"], Some(&temp_db));
+    run_wet_command(&["add", "[Alex] is my neighbour"], Some(&temp_db));
+
+    let result = run_wet_command(
+        &["entity", "split", "alex", "--into", "Alex Kim", "--with", "acme"],
+        Some(&temp_db),
+    );
+
+    assert_eq!(result.status, 0, "Command should succeed. stderr: {}", result.stderr);
+    assert!(
+        result
+            .stdout
+            .contains("Moved 1 thought(s) from 'Alex' to new entity 'Alex Kim'"),
+        "Should report the move. Got: {}",
+        result.stdout
+    );
+
+    let moved = run_wet_command(&["entity", "show", "Alex Kim"], Some(&temp_db));
+    assert!(
+        moved.stdout.contains("shipped") && !moved.stdout.contains("neighbour"),
+        "Only the co-mentioning thought should move to the new entity. Got: {}",
+        moved.stdout
+    );
+
+    let json = run_wet_command(&["thought", "show", "1", "--json"], Some(&temp_db));
+    assert!(
+        json.stdout.contains("[Alex](Alex Kim) shipped"),
+        "Moved thought should keep its wording but target the new entity. Got: {}",
+        json.stdout
+    );
+}
+
+#[test]
+fn test_entity_split_by_date_range() {
+    let temp_db = setup_temp_db();
+
+    run_wet_command(&["add", "Old job at [Mercury]", "--date", "2024-01-10"], Some(&temp_db));
+    run_wet_command(
+        &["add", "New [Mercury] planet notes", "--date", "2025-06-01"],
+        Some(&temp_db),
+    );
+
+    let result = run_wet_command(
+        &[
+            "entity",
+            "split",
+            "Mercury",
+            "--into",
+            "Mercury Planet",
+            "--since",
+            "2025-01-01",
+        ],
+        Some(&temp_db),
+    );
+
+    assert_eq!(result.status, 0, "Command should succeed. stderr: {}", result.stderr);
+
+    let show = run_wet_command(&["entity", "show", "Mercury Planet"], Some(&temp_db));
+    assert!(
+        show.stdout.contains("planet notes") && !show.stdout.contains("Old job"),
+        "Only the thought inside the range should move. Got: {}",
+        show.stdout
+    );
+}
+
+#[test]
+fn test_entity_split_interactive_moves_accepted_thoughts() {
+    let temp_db = setup_temp_db();
+
+    run_wet_command(&["add", "First [Jordan] note"], Some(&temp_db));
+    run_wet_command(&["add", "Second [Jordan] note"], Some(&temp_db));
+
+    let result = run_wet_command_with_stdin(
+        &["entity", "split", "Jordan", "--into", "Jordan River", "--interactive"],
+        Some(&temp_db),
+        Some("n\ny\n"),
+    );
+
+    assert_eq!(result.status, 0, "Command should succeed. stderr: {}", result.stderr);
+    assert!(
+        result.stdout.contains("Moved 1 thought(s)"),
+        "Only the accepted thought should move. Got: {}",
+        result.stdout
+    );
+
+    let show = run_wet_command(&["entity", "show", "Jordan River"], Some(&temp_db));
+    assert!(
+        show.stdout.contains("Second") && !show.stdout.contains("First"),
+        "The second thought should belong to the new entity. Got: {}",
+        show.stdout
+    );
+}
+
+#[test]
+fn test_entity_split_move_aliases_leaves_them_unambiguous() {
+    let temp_db = setup_temp_db();
+
+    run_wet_command(&["add", "[Alex] shipped the [Acme] release"], Some(&temp_db));
+    run_wet_command(&["add", "[Alex] is my neighbour"], Some(&temp_db));
+    run_wet_command(&["entity", "alias", "alex", "--alias", "AK"], Some(&temp_db));
+
+    let result = run_wet_command(
+        &[
+            "entity",
+            "split",
+            "alex",
+            "--into",
+            "Alex Kim",
+            "--with",
+            "acme",
+            "--move-aliases",
+        ],
+        Some(&temp_db),
+    );
+    assert_eq!(result.status, 0, "Command should succeed. stderr: {}", result.stderr);
+    assert!(result.stdout.contains("Moved 1 alias(es)."), "Got: {}", result.stdout);
+
+    let result = run_wet_command(&["add", "[AK] again"], Some(&temp_db));
+    assert_eq!(
+        result.status, 0,
+        "Alias should not be ambiguous. stderr: {}",
+        result.stderr
+    );
+    let moved = run_wet_command(&["entity", "show", "Alex Kim"], Some(&temp_db));
+    assert!(moved.stdout.contains("again"), "Got: {}", moved.stdout);
+}
+
+#[test]
+fn test_entity_split_requires_a_selector() {
+    let temp_db = setup_temp_db();
+
+    run_wet_command(&["add", "[Alex] note"], Some(&temp_db));
+
+    let result = run_wet_command(&["entity", "split", "alex", "--into", "Alex Kim"], Some(&temp_db));
+
+    assert_ne!(result.status, 0, "Command should fail without a selector");
+    assert!(
+        result.stderr.contains("--since/--until, --with or --interactive"),
+        "Should explain how to choose thoughts. Got: {}",
+        result.stderr
+    );
+}
+
+#[test]
+fn test_entity_split_nonexistent_entity() {
+    let temp_db = setup_temp_db();
+
+    let result = run_wet_command(
+        &["entity", "split", "Nobody", "--into", "Somebody", "--interactive"],
+        Some(&temp_db),
+    );
+
+    assert_ne!(result.status, 0, "Command should fail for unknown entity");
+    assert!(
+        result.stderr.contains("not found"),
+        "Should report the missing entity. Got: {}",
+        result.stderr
+    );
+}
@@ -0,0 +1,89 @@
+/// Contract tests for `wet entity timeline` command
+use crate::test_helpers::{run_wet_command, setup_temp_db};
+
+#[test]
+fn test_entity_timeline_groups_by_month_with_descendants() {
+    let temp_db = setup_temp_db();
+    run_wet_command(&["add", "Planning [Apollo]", "--date", "2026-01-10"], Some(&temp_db));
+    run_wet_command(&["add", "[Apollo] kickoff", "--date", "2026-01-20"], Some(&temp_db));
+    run_wet_command(
+        &["add", "[Apollo Rocket] launched", "--date", "2026-03-02"],
+        Some(&temp_db),
+    );
+    run_wet_command(
+        &["add", "Unrelated [Berlin] trip", "--date", "2026-02-01"],
+        Some(&temp_db),
+    );
+    run_wet_command(
+        &["entity", "relate", "apollo rocket", "--parent", "apollo"],
+        Some(&temp_db),
+    );
+
+    let result = run_wet_command(&["entity", "timeline", "apollo"], Some(&temp_db));
+    assert_eq!(result.status, 0, "stderr: {}", result.stderr);
+    assert_eq!(
+        result.stdout,
+        "Apollo
+3 thoughts, first 2026-01-10, last 2026-03-02
+
+2026-01 █ ▄ 2026-03
+
+2026-01 (2 thoughts)
+  [1] 2026-01-10 - Planning Apollo
+  [2] 2026-01-20 - Apollo kickoff
+
+2026-03 (1 thought)
+  [3] 2026-03-02 - Apollo Rocket launched
+"
+    );
+}
+
+#[test]
+fn test_entity_timeline_by_week() {
+    let temp_db = setup_temp_db();
+    run_wet_command(&["add", "[Apollo] monday", "--date", "2026-03-09"], Some(&temp_db));
+    run_wet_command(&["add", "[Apollo] sunday", "--date", "2026-03-15"], Some(&temp_db));
+    run_wet_command(&["add", "[Apollo] next monday", "--date", "2026-03-16"], Some(&temp_db));
+
+    let result = run_wet_command(&["entity", "timeline", "apollo", "--by", "week"], Some(&temp_db));
+    assert_eq!(result.status, 0, "stderr: {}", result.stderr);
+    assert!(
+        result.stdout.contains("\n2026-03-09 █▄ 2026-03-16\n"),
+        "Got: {}",
+        result.stdout
+    );
+    assert!(
+        result.stdout.contains("\n2026-03-09 (2 thoughts)\n"),
+        "Got: {}",
+        result.stdout
+    );
+    assert!(
+        result.stdout.contains("\n2026-03-16 (1 thought)\n"),
+        "Got: {}",
+        result.stdout
+    );
+}
+
+#[test]
+fn test_entity_timeline_without_thoughts() {
+    let temp_db = setup_temp_db();
+    run_wet_command(&["add", "About [Apollo]"], Some(&temp_db));
+    run_wet_command(&["delete", "1"], Some(&temp_db));
+
+    let result = run_wet_command(&["entity", "timeline", "apollo"], Some(&temp_db));
+    assert_eq!(result.status, 0, "stderr: {}", result.stderr);
+    assert!(
+        result.stdout.contains("No thoughts found for entity: Apollo"),
+        "Got: {}",
+        result.stdout
+    );
+}
+
+#[test]
+fn test_entity_timeline_unknown_entity_fails() {
+    let temp_db = setup_temp_db();
+
+    let result = run_wet_command(&["entity", "timeline", "nobody"], Some(&temp_db));
+    assert_ne!(result.status, 0);
+    assert!(result.stderr.contains("nobody"), "Got: {}", result.stderr);
+}
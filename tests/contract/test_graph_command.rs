@@ -0,0 +1,111 @@
+/// Contract tests for `wet graph` command
+use crate::test_helpers::{run_wet_command, setup_temp_db};
+
+fn setup_network() -> tempfile::TempDir {
+    let temp_db = setup_temp_db();
+    run_wet_command(&["add", "[Alice] met [Bob] about [AWS]"], Some(&temp_db));
+    run_wet_command(&["add", "[Alice] and [Bob] again"], Some(&temp_db));
+    run_wet_command(&["add", "Reading about [Amazon]"], Some(&temp_db));
+    run_wet_command(&["entity", "relate", "aws", "--parent", "amazon"], Some(&temp_db));
+    run_wet_command(
+        &["entity", "relate", "alice", "--as", "works-at", "amazon"],
+        Some(&temp_db),
+    );
+    temp_db
+}
+
+#[test]
+fn test_graph_dot_has_relation_and_weighted_co_occurrence_edges() {
+    let temp_db = setup_network();
+
+    let result = run_wet_command(&["graph"], Some(&temp_db));
+    assert_eq!(result.status, 0, "stderr: {}", result.stderr);
+
+    let expected = "digraph wetware {
+    node [shape=box];
+    e1 [label=\"Alice\"];
+    e4 [label=\"Amazon\"];
+    e3 [label=\"AWS\"];
+    e2 [label=\"Bob\"];
+    e4 -> e3 [label=\"parent-of\"];
+    e1 -> e4 [label=\"works-at\"];
+    e1 -> e3 [label=\"1\", dir=none, style=dashed, weight=1];
+    e1 -> e2 [label=\"2\", dir=none, style=dashed, weight=2];
+    e3 -> e2 [label=\"1\", dir=none, style=dashed, weight=1];
+}
+";
+    assert_eq!(result.stdout, expected);
+}
+
+#[test]
+fn test_graph_root_and_depth_limit_the_export() {
+    let temp_db = setup_network();
+
+    let result = run_wet_command(
+        &[
+            "graph",
+            "--root",
+            "amazon",
+            "--depth",
+            "1",
+            "--no-co-occurrence",
+            "--format",
+            "mermaid",
+        ],
+        Some(&temp_db),
+    );
+    assert_eq!(result.status, 0, "stderr: {}", result.stderr);
+
+    let expected = "flowchart TD
+    e1[\"Alice\"]
+    e4[\"Amazon\"]
+    e3[\"AWS\"]
+    e4 -->|\"parent-of\"| e3
+    e1 -->|\"works-at\"| e4
+";
+    assert_eq!(result.stdout, expected);
+}
+
+#[test]
+fn test_graph_graphml_includes_alias_nodes() {
+    let temp_db = setup_network();
+    run_wet_command(
+        &["entity", "alias", "aws", "--alias", "Amazon Web Services"],
+        Some(&temp_db),
+    );
+
+    let result = run_wet_command(&["graph", "--format", "graphml", "--aliases"], Some(&temp_db));
+    assert_eq!(result.status, 0, "stderr: {}", result.stderr);
+    assert!(result.stdout.starts_with("<?xml"), "Got: {}", result.stdout);
+    assert!(
+        result
+            .stdout
+            .contains("<data key=\"label\">Amazon Web Services</data>\n      <data key=\"type\">alias</data>"),
+        "Got: {}",
+        result.stdout
+    );
+    assert!(
+        result
+            .stdout
+            .contains("<edge source=\"a1\" target=\"e3\" directed=\"false\">"),
+        "Got: {}",
+        result.stdout
+    );
+}
+
+#[test]
+fn test_graph_unknown_root_fails() {
+    let temp_db = setup_network();
+
+    let result = run_wet_command(&["graph", "--root", "nobody"], Some(&temp_db));
+    assert_ne!(result.status, 0);
+    assert!(result.stderr.contains("nobody"), "Got: {}", result.stderr);
+}
+
+#[test]
+fn test_graph_depth_requires_root() {
+    let temp_db = setup_temp_db();
+
+    let result = run_wet_command(&["graph", "--depth", "2"], Some(&temp_db));
+    assert_ne!(result.status, 0);
+}
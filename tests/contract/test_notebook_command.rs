@@ -0,0 +1,120 @@
+/// Contract tests for `wet notebook` commands and the global `--notebook` flag
+use crate::test_helpers::{run_wet_command, run_wet_command_with_env, run_wet_command_with_stdin, setup_temp_db};
+
+/// `WETWARE_DB` set to nothing, so the notebooks in the data directory decide
+const NO_DB_OVERRIDE: &[(&str, &str)] = &[("WETWARE_DB", "")];
+
+#[test]
+fn test_notebooks_keep_thoughts_apart() {
+    let temp_db = setup_temp_db();
+
+    let result = run_wet_command(&["notebook", "create", "work"], Some(&temp_db));
+    assert_eq!(result.status, 0, "stderr: {}", result.stderr);
+    assert!(temp_db.path().join("work.db").exists());
+
+    run_wet_command(&["--notebook", "work", "add", "Standup with [Sarah]"], Some(&temp_db));
+    run_wet_command_with_env(&["add", "Call [Mum]"], Some(&temp_db), None, NO_DB_OVERRIDE);
+
+    let work = run_wet_command(&["thoughts", "--notebook", "work"], Some(&temp_db));
+    assert!(work.stdout.contains("Standup with Sarah"), "Got: {}", work.stdout);
+    assert!(!work.stdout.contains("Mum"), "Got: {}", work.stdout);
+
+    let personal = run_wet_command_with_env(&["thoughts"], Some(&temp_db), None, NO_DB_OVERRIDE);
+    assert!(personal.stdout.contains("Call Mum"), "Got: {}", personal.stdout);
+    assert!(!personal.stdout.contains("Sarah"), "Got: {}", personal.stdout);
+
+    let list = run_wet_command(&["notebook", "list"], Some(&temp_db));
+    assert_eq!(list.stdout, "* default\n  work\n");
+}
+
+#[test]
+fn test_unknown_notebook_is_an_error() {
+    let temp_db = setup_temp_db();
+
+    let result = run_wet_command(&["--notebook", "wrok", "add", "Lost thought"], Some(&temp_db));
+    assert_ne!(result.status, 0);
+    assert!(
+        result.stderr.contains("Notebook 'wrok' not found"),
+        "Got: {}",
+        result.stderr
+    );
+    assert!(!temp_db.path().join("wrok.db").exists());
+
+    let result = run_wet_command(&["notebook", "create", "../escape"], Some(&temp_db));
+    assert_ne!(result.status, 0);
+    assert!(
+        result.stderr.contains("Invalid notebook name"),
+        "Got: {}",
+        result.stderr
+    );
+}
+
+#[test]
+fn test_default_notebook_from_config() {
+    let temp_db = setup_temp_db();
+    run_wet_command(&["notebook", "create", "work"], Some(&temp_db));
+    run_wet_command(&["--notebook", "work", "add", "Quarterly plan"], Some(&temp_db));
+
+    let result = run_wet_command(&["config", "notebooks.default", "work"], Some(&temp_db));
+    assert_eq!(result.status, 0, "stderr: {}", result.stderr);
+
+    let thoughts = run_wet_command_with_env(&["thoughts"], Some(&temp_db), None, NO_DB_OVERRIDE);
+    assert!(thoughts.stdout.contains("Quarterly plan"), "Got: {}", thoughts.stdout);
+    let list = run_wet_command(&["notebook", "list"], Some(&temp_db));
+    assert_eq!(list.stdout, "* work\n");
+
+    let result = run_wet_command(&["config", "notebooks.default", "no/such"], Some(&temp_db));
+    assert_ne!(result.status, 0);
+    assert!(
+        result.stderr.contains("Invalid notebook name"),
+        "Got: {}",
+        result.stderr
+    );
+}
+
+#[test]
+fn test_rename_default_notebook_follows_in_config() {
+    let temp_db = setup_temp_db();
+    run_wet_command_with_env(&["add", "Old note"], Some(&temp_db), None, NO_DB_OVERRIDE);
+
+    let result = run_wet_command(&["notebook", "rename", "default", "personal"], Some(&temp_db));
+    assert_eq!(result.status, 0, "stderr: {}", result.stderr);
+    assert!(
+        result.stdout.contains("notebooks.default is now 'personal'"),
+        "Got: {}",
+        result.stdout
+    );
+
+    let thoughts = run_wet_command_with_env(&["thoughts"], Some(&temp_db), None, NO_DB_OVERRIDE);
+    assert!(thoughts.stdout.contains("Old note"), "Got: {}", thoughts.stdout);
+    assert!(!temp_db.path().join("default.db").exists());
+}
+
+#[test]
+fn test_delete_notebook() {
+    let temp_db = setup_temp_db();
+    run_wet_command(&["notebook", "create", "scratch"], Some(&temp_db));
+    run_wet_command(&["--notebook", "scratch", "add", "Temporary"], Some(&temp_db));
+
+    let result = run_wet_command(&["notebook", "delete", "default", "--yes"], Some(&temp_db));
+    assert_ne!(result.status, 0);
+    assert!(
+        result.stderr.contains("is the default notebook"),
+        "Got: {}",
+        result.stderr
+    );
+
+    let result = run_wet_command_with_stdin(&["notebook", "delete", "scratch"], Some(&temp_db), Some("n\n"));
+    assert!(
+        result
+            .stdout
+            .contains("Delete notebook 'scratch' and its 1 thought(s)?"),
+        "Got: {}",
+        result.stdout
+    );
+    assert!(temp_db.path().join("scratch.db").exists());
+
+    let result = run_wet_command(&["notebook", "delete", "scratch", "--yes"], Some(&temp_db));
+    assert_eq!(result.status, 0, "stderr: {}", result.stderr);
+    assert!(!temp_db.path().join("scratch.db").exists());
+}
@@ -0,0 +1,69 @@
+/// Contract tests for `wet stats` command
+use crate::test_helpers::{run_wet_command, setup_temp_db};
+
+#[test]
+fn test_stats_reports_totals_streak_and_entities() {
+    let temp_db = setup_temp_db();
+    run_wet_command(&["add", "Trip to [Berlin]", "--date", "2020-01-01"], Some(&temp_db));
+    run_wet_command(&["add", "Call with [Alice] about [Apollo]"], Some(&temp_db));
+    run_wet_command(&["add", "[Alice] shipped it"], Some(&temp_db));
+
+    let result = run_wet_command(&["stats"], Some(&temp_db));
+    assert_eq!(result.status, 0, "stderr: {}", result.stderr);
+
+    let out = &result.stdout;
+    assert!(out.starts_with("Thoughts: 3\nEntities: 3\n"), "Got: {}", out);
+    assert!(out.contains("Thoughts per week:\n"), "Got: {}", out);
+    assert!(out.contains("Activity (last year):\n"), "Got: {}", out);
+    assert!(out.contains("\n  Mon "), "Got: {}", out);
+    assert!(out.contains("Streak: 1 day (longest 1 day)"), "Got: {}", out);
+    assert!(
+        out.contains("Most mentioned:\n  Alice (2 thoughts)\n  Berlin (1 thought)\n  Apollo (1 thought)\n"),
+        "Got: {}",
+        out
+    );
+    assert!(
+        out.contains("Fastest growing (last 30 days vs the 30 before):\n  Alice (+2: 2, was 0)\n"),
+        "Got: {}",
+        out
+    );
+    assert!(
+        out.contains("Not mentioned in 90 days:\n  Berlin (last 2020-01-01)\n"),
+        "Got: {}",
+        out
+    );
+}
+
+#[test]
+fn test_stats_by_month_shows_requested_periods() {
+    let temp_db = setup_temp_db();
+    run_wet_command(&["add", "A thought"], Some(&temp_db));
+
+    let result = run_wet_command(&["stats", "--by", "month", "--periods", "3"], Some(&temp_db));
+    assert_eq!(result.status, 0, "stderr: {}", result.stderr);
+
+    let section: Vec<&str> = result
+        .stdout
+        .lines()
+        .skip_while(|line| *line != "Thoughts per month:")
+        .skip(1)
+        .take_while(|line| !line.is_empty())
+        .collect();
+    assert_eq!(section.len(), 3, "Got: {}", result.stdout);
+    assert!(
+        section[2].ends_with(" 1 ██████████████████████████████"),
+        "Got: {:?}",
+        section
+    );
+}
+
+#[test]
+fn test_stats_empty_database() {
+    let temp_db = setup_temp_db();
+
+    let result = run_wet_command(&["stats"], Some(&temp_db));
+    assert_eq!(result.status, 0, "stderr: {}", result.stderr);
+    assert!(result.stdout.starts_with("Thoughts: 0\nEntities: 0\n"));
+    assert!(result.stdout.contains("Streak: 0 days (longest 0 days)"));
+    assert!(!result.stdout.contains("Most mentioned:"));
+}
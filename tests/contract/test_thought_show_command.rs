@@ -0,0 +1,67 @@
+/// Contract tests for `wet thought show` command
+use crate::test_helpers::{run_wet_command, setup_temp_db};
+
+#[test]
+fn test_thought_show_not_found() {
+    let temp_db = setup_temp_db();
+
+    let result = run_wet_command(&["thought", "show", "999"], Some(&temp_db));
+
+    assert_ne!(result.status, 0, "Command should fail for nonexistent thought");
+    assert!(
+        result.stderr.contains("not found"),
+        "Should report thought not found. Got: {}",
+        result.stderr
+    );
+}
+
+#[test]
+fn test_thought_show_lists_entities_wording_and_parents() {
+    let temp_db = setup_temp_db();
+
+    run_wet_command(&["add", "Joined [Acme] with [Sarah]"], Some(&temp_db));
+    run_wet_command(&["entity", "relate", "sarah", "--parent", "acme"], Some(&temp_db));
+    run_wet_command(&["add", "Lunch with [S](sarah) today"], Some(&temp_db));
+
+    let result = run_wet_command(&["thought", "show", "2"], Some(&temp_db));
+
+    assert_eq!(result.status, 0, "Command should succeed. Stderr: {}", result.stderr);
+    assert!(
+        result.stdout.contains("[2] "),
+        "Should show ID header. Got: {}",
+        result.stdout
+    );
+    assert!(
+        result.stdout.contains("Lunch with S today"),
+        "Should show rendered content. Got: {}",
+        result.stdout
+    );
+    assert!(
+        result.stdout.contains("Sarah (written as: S) - parents: Acme"),
+        "Should show entity with wording and parents. Got: {}",
+        result.stdout
+    );
+}
+
+#[test]
+fn test_thought_show_json() {
+    let temp_db = setup_temp_db();
+
+    run_wet_command(&["add", "Reading about [rust]", "--date", "2026-01-15"], Some(&temp_db));
+
+    let result = run_wet_command(&["thought", "show", "1", "--json"], Some(&temp_db));
+
+    assert_eq!(result.status, 0, "Command should succeed. Stderr: {}", result.stderr);
+    assert!(result.stdout.contains("\"id\": 1"), "Got: {}", result.stdout);
+    assert!(
+        result.stdout.contains("\"created_at\": \"2026-01-15"),
+        "Got: {}",
+        result.stdout
+    );
+    assert!(
+        result.stdout.contains("\"content\": \"Reading about [rust]\""),
+        "JSON keeps raw markup. Got: {}",
+        result.stdout
+    );
+    assert!(result.stdout.contains("\"name\": \"rust\""), "Got: {}", result.stdout);
+}
@@ -1,5 +1,5 @@
 /// Contract tests for `wet thoughts` command
-use crate::test_helpers::{run_wet_command, setup_temp_db};
+use crate::test_helpers::{run_wet_command, run_wet_command_with_env, setup_temp_db};
 
 #[test]
 fn test_notes_command_lists_all_notes() {
@@ -292,3 +292,127 @@ fn test_notes_command_entity_with_special_characters() {
         "Should not contain bracketed entity"
     );
 }
+
+#[test]
+fn test_thoughts_where_combines_entities_and_dates() {
+    let temp_db = setup_temp_db();
+
+    run_wet_command(
+        &["add", "[Sarah] reviewed [alpha]", "--date", "2024-12-20"],
+        Some(&temp_db),
+    );
+    run_wet_command(
+        &["add", "[Sarah] shipped [beta]", "--date", "2025-01-10"],
+        Some(&temp_db),
+    );
+    run_wet_command(
+        &["add", "[Sarah] wrote a [draft] of [alpha]", "--date", "2025-01-12"],
+        Some(&temp_db),
+    );
+    run_wet_command(&["add", "[alpha] retro", "--date", "2025-01-15"], Some(&temp_db));
+
+    let result = run_wet_command(
+        &[
+            "thoughts",
+            "--where",
+            "sarah AND (alpha OR beta) AND NOT draft AND after:2025-01-01",
+        ],
+        Some(&temp_db),
+    );
+
+    assert_eq!(result.status, 0, "Command should succeed. Stderr: {}", result.stderr);
+    assert!(result.stdout.contains("Sarah shipped beta"), "Got: {}", result.stdout);
+    assert!(!result.stdout.contains("reviewed"), "Got: {}", result.stdout);
+    assert!(!result.stdout.contains("draft"), "Got: {}", result.stdout);
+    assert!(!result.stdout.contains("retro"), "Got: {}", result.stdout);
+}
+
+#[test]
+fn test_thoughts_where_includes_descendants() {
+    let temp_db = setup_temp_db();
+
+    run_wet_command(&["add", "[AWS] bill went up"], Some(&temp_db));
+    run_wet_command(&["add", "Thinking about [Amazon]"], Some(&temp_db));
+    run_wet_command(&["entity", "relate", "AWS", "--parent", "Amazon"], Some(&temp_db));
+
+    let result = run_wet_command(&["thoughts", "--where", "amazon AND NOT \"Amazon\""], Some(&temp_db));
+
+    assert_eq!(result.status, 0, "Command should succeed. Stderr: {}", result.stderr);
+    assert!(
+        result.stdout.contains("No thoughts match: amazon AND NOT \"Amazon\""),
+        "Got: {}",
+        result.stdout
+    );
+
+    let result = run_wet_command(&["thoughts", "--where", "amazon"], Some(&temp_db));
+    assert!(result.stdout.contains("AWS bill went up"), "Got: {}", result.stdout);
+    assert!(
+        result.stdout.contains("Thinking about Amazon"),
+        "Got: {}",
+        result.stdout
+    );
+}
+
+#[test]
+fn test_thoughts_where_reports_bad_queries_and_unknown_entities() {
+    let temp_db = setup_temp_db();
+    run_wet_command(&["add", "Meeting with [Sarah]"], Some(&temp_db));
+
+    let result = run_wet_command(&["thoughts", "--where", "(sarah AND"], Some(&temp_db));
+    assert_ne!(result.status, 0);
+    assert!(result.stderr.contains("Invalid query"), "Got: {}", result.stderr);
+
+    let result = run_wet_command(&["thoughts", "--where", "NOT sarha"], Some(&temp_db));
+    assert_ne!(result.status, 0);
+    assert!(result.stderr.contains("'sarha' not found"), "Got: {}", result.stderr);
+
+    let result = run_wet_command(&["thoughts", "--on", "sarah", "--where", "sarah"], Some(&temp_db));
+    assert_ne!(result.status, 0, "--on and --where should conflict");
+}
+
+#[test]
+fn test_thoughts_where_accepts_relative_dates() {
+    let temp_db = setup_temp_db();
+
+    run_wet_command(&["add", "Old [Sarah] note", "--date", "-30d"], Some(&temp_db));
+    run_wet_command(&["add", "Recent [Sarah] note", "--date", "yesterday"], Some(&temp_db));
+
+    let result = run_wet_command(
+        &["thoughts", "--where", "sarah AND after:\"2 weeks ago\""],
+        Some(&temp_db),
+    );
+    assert_eq!(result.status, 0, "Command should succeed. Stderr: {}", result.stderr);
+    assert!(result.stdout.contains("Recent Sarah note"), "Got: {}", result.stdout);
+    assert!(!result.stdout.contains("Old Sarah note"), "Got: {}", result.stdout);
+
+    let result = run_wet_command(&["thoughts", "--where", "before:-1w"], Some(&temp_db));
+    assert!(result.stdout.contains("Old Sarah note"), "Got: {}", result.stdout);
+    assert!(!result.stdout.contains("Recent Sarah note"), "Got: {}", result.stdout);
+}
+
+#[test]
+fn test_legacy_backdated_thoughts_are_dated_in_the_configured_zone() {
+    let temp_db = setup_temp_db();
+    run_wet_command(&["thoughts"], Some(&temp_db));
+    // What `--date 2025-03-10` stored before thoughts were dated in a zone
+    let conn = rusqlite::Connection::open(temp_db.path().join("test.db")).unwrap();
+    conn.execute_batch(
+        "INSERT INTO thoughts (content, created_at) VALUES ('Old backdated', '2025-03-10T00:00:00+00:00');
+         PRAGMA user_version = 0;",
+    )
+    .unwrap();
+    drop(conn);
+
+    // The system zone is a day ahead of the configured one
+    let env = [
+        ("TZ", "Pacific/Auckland"),
+        ("WETWARE_THOUGHTS_TIMEZONE", "America/Los_Angeles"),
+    ];
+    let result = run_wet_command_with_env(&["thoughts"], Some(&temp_db), None, &env);
+    assert_eq!(result.status, 0, "Stderr: {}", result.stderr);
+    assert!(
+        result.stdout.contains("2025-03-10 - Old backdated"),
+        "Got: {}",
+        result.stdout
+    );
+}
@@ -11,6 +11,7 @@ use wetware::models::thought::Thought;
 use wetware::storage::connection::get_memory_connection;
 use wetware::storage::entities_repository::EntitiesRepository;
 use wetware::storage::entity_aliases_repository::EntityAliasesRepository;
+use wetware::storage::entity_attributes_repository::EntityAttributesRepository;
 use wetware::storage::entity_relations_repository::EntityRelationsRepository;
 use wetware::storage::migrations::run_migrations;
 use wetware::storage::thoughts_repository::ThoughtsRepository;
@@ -270,6 +271,32 @@ fn test_merge_transfers_parent_and_child_relations() {
     );
 }
 
+#[test]
+fn test_merge_transfers_typed_relations_in_both_directions() {
+    let mut conn = get_memory_connection().unwrap();
+    run_migrations(&conn).unwrap();
+
+    let alice = entity(&conn, "Alice");
+    let bob = entity(&conn, "Bob");
+    let acme = entity(&conn, "Acme");
+    let carol = entity(&conn, "Carol");
+    EntityRelationsRepository::add_typed_relation(&conn, alice, "works-at", acme).unwrap();
+    EntityRelationsRepository::add_typed_relation(&conn, carol, "related-to", alice).unwrap();
+    EntityRelationsRepository::add_typed_relation(&conn, alice, "related-to", bob).unwrap();
+
+    let summary = merge(&mut conn, "alice", "bob").unwrap();
+
+    assert_eq!(summary.relations_dropped, 1);
+    assert!(EntityRelationsRepository::has_typed_relation(&conn, bob, "works-at", acme).unwrap());
+    assert!(EntityRelationsRepository::has_typed_relation(&conn, carol, "related-to", bob).unwrap());
+    assert_eq!(
+        EntityRelationsRepository::list_typed_relations(&conn, bob)
+            .unwrap()
+            .len(),
+        2
+    );
+}
+
 #[test]
 fn test_merge_drops_relation_that_would_become_self_relation() {
     let mut conn = get_memory_connection().unwrap();
@@ -350,3 +377,21 @@ fn test_merge_missing_entity_leaves_database_untouched() {
     let unchanged = ThoughtsRepository::get_by_id(&conn, thought_id).unwrap();
     assert_eq!(unchanged.content, "Lunch with [Alice]");
 }
+
+#[test]
+fn test_merge_transfers_attributes_keeping_target_values() {
+    let mut conn = get_memory_connection().unwrap();
+    run_migrations(&conn).unwrap();
+
+    let alice = entity(&conn, "Alice");
+    let bob = entity(&conn, "Bob");
+    EntityAttributesRepository::set(&conn, alice, "email", "alice@example.com").unwrap();
+    EntityAttributesRepository::set(&conn, alice, "status", "active").unwrap();
+    EntityAttributesRepository::set(&conn, bob, "email", "bob@example.com").unwrap();
+
+    merge(&mut conn, "alice", "bob").unwrap();
+
+    let attributes = EntityAttributesRepository::list_for_entity(&conn, bob).unwrap();
+    assert_eq!(attributes["email"], "bob@example.com");
+    assert_eq!(attributes["status"], "active");
+}
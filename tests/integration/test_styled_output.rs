@@ -1,7 +1,7 @@
 /// Integration tests for styled entity output (T030)
 use tempfile::TempDir;
 use wetware::cli::{add, thoughts};
-use wetware::models::SortOrder;
+use wetware::models::{SortOrder, TimestampFormat, Timezone};
 use wetware::services::color_mode::ColorMode;
 
 #[test]
@@ -10,10 +10,17 @@ fn test_styled_output_with_color_always() {
     let db_path = temp_dir.path().join("test.db");
 
     // Add thought with entity
-    add::execute("Meeting with [Sarah]".to_string(), None, &db_path).unwrap();
+    add::execute("Meeting with [Sarah]".to_string(), None, Timezone::Local, &db_path).unwrap();
 
     // Execute with colors always on (even though we're not in a TTY)
-    let result = thoughts::execute(&db_path, None, ColorMode::Always, SortOrder::Descending);
+    let result = thoughts::execute(
+        &db_path,
+        None,
+        None,
+        ColorMode::Always,
+        SortOrder::Descending,
+        TimestampFormat::default(),
+    );
     assert!(result.is_ok());
 }
 
@@ -23,10 +30,17 @@ fn test_styled_output_with_color_never() {
     let db_path = temp_dir.path().join("test.db");
 
     // Add thought with entity
-    add::execute("Meeting with [Sarah]".to_string(), None, &db_path).unwrap();
+    add::execute("Meeting with [Sarah]".to_string(), None, Timezone::Local, &db_path).unwrap();
 
     // Execute with colors disabled
-    let result = thoughts::execute(&db_path, None, ColorMode::Never, SortOrder::Descending);
+    let result = thoughts::execute(
+        &db_path,
+        None,
+        None,
+        ColorMode::Never,
+        SortOrder::Descending,
+        TimestampFormat::default(),
+    );
     assert!(result.is_ok());
 }
 
@@ -36,10 +50,17 @@ fn test_styled_output_with_color_auto() {
     let db_path = temp_dir.path().join("test.db");
 
     // Add thought with entity
-    add::execute("Meeting with [Sarah]".to_string(), None, &db_path).unwrap();
+    add::execute("Meeting with [Sarah]".to_string(), None, Timezone::Local, &db_path).unwrap();
 
     // Execute with auto-detection (will be plain since tests aren't TTY)
-    let result = thoughts::execute(&db_path, None, ColorMode::Auto, SortOrder::Descending);
+    let result = thoughts::execute(
+        &db_path,
+        None,
+        None,
+        ColorMode::Auto,
+        SortOrder::Descending,
+        TimestampFormat::default(),
+    );
     assert!(result.is_ok());
 }
 
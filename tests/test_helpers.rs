@@ -1,5 +1,6 @@
 /// Test helpers for contract and integration tests
-use std::process::Command;
+use std::io::Write;
+use std::process::{Command, Stdio};
 use tempfile::TempDir;
 
 /// Result of running a CLI command
@@ -12,6 +13,21 @@ pub struct CommandResult {
 
 /// Run a wet CLI command with arguments
 pub fn run_wet_command(args: &[&str], db_dir: Option<&TempDir>) -> CommandResult {
+    run_wet_command_with_stdin(args, db_dir, None)
+}
+
+/// Run a wet CLI command with arguments, feeding `stdin` to the process if given
+pub fn run_wet_command_with_stdin(args: &[&str], db_dir: Option<&TempDir>, stdin: Option<&str>) -> CommandResult {
+    run_wet_command_with_env(args, db_dir, stdin, &[])
+}
+
+/// Run a wet CLI command with extra environment variables, feeding `stdin` if given
+pub fn run_wet_command_with_env(
+    args: &[&str],
+    db_dir: Option<&TempDir>,
+    stdin: Option<&str>,
+    env: &[(&str, &str)],
+) -> CommandResult {
     // Build the binary first (in a separate command to avoid stderr pollution)
     let build_result = Command::new("cargo")
         .arg("build")
@@ -39,8 +55,28 @@ pub fn run_wet_command(args: &[&str], db_dir: Option<&TempDir>) -> CommandResult
     for arg in args {
         cmd.arg(arg);
     }
+    for (key, value) in env {
+        cmd.env(key, value);
+    }
 
-    let output = cmd.output().expect("Failed to execute command");
+    let output = match stdin {
+        Some(input) => {
+            let mut child = cmd
+                .stdin(Stdio::piped())
+                .stdout(Stdio::piped())
+                .stderr(Stdio::piped())
+                .spawn()
+                .expect("Failed to spawn command");
+            child
+                .stdin
+                .take()
+                .unwrap()
+                .write_all(input.as_bytes())
+                .expect("Failed to write stdin");
+            child.wait_with_output().expect("Failed to execute command")
+        }
+        None => cmd.output().expect("Failed to execute command"),
+    };
 
     CommandResult {
         status: output.status.code().unwrap_or(-1),
@@ -53,3 +89,23 @@ pub fn run_wet_command(args: &[&str], db_dir: Option<&TempDir>) -> CommandResult
 pub fn setup_temp_db() -> TempDir {
     TempDir::new().expect("Failed to create temp directory")
 }
+
+/// Write an executable `$EDITOR` stand-in that records each buffer it is given as
+/// `bufferN` next to itself and replaces it with `replies[N - 1]`
+#[cfg(unix)]
+pub fn scripted_editor(dir: &std::path::Path, replies: &[&str]) -> std::path::PathBuf {
+    use std::os::unix::fs::PermissionsExt;
+
+    let mut script = String::from(
+        "#!/bin/sh\ndir=$(dirname \"$0\")\nn=$(($(cat \"$dir/calls\" 2>/dev/null || echo 0) + 1))\n\
+         echo $n > \"$dir/calls\"\ncp \"$1\" \"$dir/buffer$n\"\n",
+    );
+    for (i, reply) in replies.iter().enumerate() {
+        script.push_str(&format!("[ $n = {} ] && printf '%s' '{}' > \"$1\"\n", i + 1, reply));
+    }
+    script.push_str("exit 0\n");
+    let path = dir.join("editor.sh");
+    std::fs::write(&path, script).unwrap();
+    std::fs::set_permissions(&path, std::fs::Permissions::from_mode(0o755)).unwrap();
+    path
+}